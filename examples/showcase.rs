@@ -10,7 +10,7 @@ use applib::{
     NavigationSplitView, Picker, ProgressStyle, ProgressView, RadioGroup, ScrollView,
     Section, Sheet, SidebarItem, Slider, Spacer, Stepper, Tab, TabView, Table, TableColumn,
     Text, TextAlign, TextStyle, TitleBar, Toggle, ToggleStyle, VStack,
-    WindowFrame, ZStack, ZStackAlignment,
+    WindowChrome, WindowFrame, ZStack, ZStackAlignment,
 };
 use gpui::*;
 
@@ -944,9 +944,12 @@ impl ShowcaseView {
 }
 
 impl Render for ShowcaseView {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let chrome = WindowChrome::from_window(window);
+
         // Title bar
         let title_bar = TitleBar::new("Component Showcase")
+            .chrome(chrome)
             .on_close(cx.listener(|_this, _event, _window, cx| {
                 cx.quit();
             }))
@@ -998,7 +1001,10 @@ impl Render for ShowcaseView {
             window_content = window_content.child(alert);
         }
 
-        WindowFrame::new().child(window_content).into_element()
+        WindowFrame::new()
+            .chrome(chrome)
+            .child(window_content)
+            .into_element()
     }
 }
 
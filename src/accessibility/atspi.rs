@@ -0,0 +1,1605 @@
+//! Publishes accessibility trees on the AT-SPI accessibility bus.
+//!
+//! Every node becomes an object under `/org/a11y/atspi/accessible/` that
+//! implements `org.a11y.atspi.Accessible` and `Component`, plus `Action`,
+//! `Value` and `Text` when the node has actions, a value range or text
+//! content. The application
+//! root implements `org.a11y.atspi.Application` and is embedded into the
+//! registry's desktop, which is how screen readers discover it.
+
+use std::collections::{HashMap, HashSet};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use futures::stream::{Stream, StreamExt};
+use gpui::{App, AppContext as _, Bounds, Pixels, SharedString, Task, Window};
+use zbus::connection::Builder;
+use zbus::names::BusName;
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, Structure, Value};
+use zbus::{fdo, interface, Connection};
+
+use super::proxy::{BusProxy, SocketProxy, NULL_PATH, ROOT_PATH};
+use super::{AccessNode, AccessState, Role, ValueRange};
+
+const OBJECT_PREFIX: &str = "/org/a11y/atspi/accessible/";
+const EVENT_INTERFACE: &str = "org.a11y.atspi.Event.Object";
+
+/// AT-SPI state names, indexed by `AtspiStateType`.
+const STATE_NAMES: [&str; 44] = [
+    "invalid",
+    "active",
+    "armed",
+    "busy",
+    "checked",
+    "collapsed",
+    "defunct",
+    "editable",
+    "enabled",
+    "expandable",
+    "expanded",
+    "focusable",
+    "focused",
+    "has-tooltip",
+    "horizontal",
+    "iconified",
+    "modal",
+    "multi-line",
+    "multiselectable",
+    "opaque",
+    "pressed",
+    "resizable",
+    "selectable",
+    "selected",
+    "sensitive",
+    "showing",
+    "single-line",
+    "stale",
+    "transient",
+    "vertical",
+    "visible",
+    "manages-descendants",
+    "indeterminate",
+    "required",
+    "truncated",
+    "animated",
+    "invalid-entry",
+    "supports-autocompletion",
+    "selectable-text",
+    "is-default",
+    "visited",
+    "checkable",
+    "has-popup",
+    "read-only",
+];
+
+const STATE_COLLAPSED: u32 = 5;
+const STATE_DEFUNCT: usize = 6;
+const STATE_ENABLED: u32 = 8;
+const STATE_FOCUSABLE: u32 = 11;
+const STATE_SENSITIVE: u32 = 24;
+const STATE_SHOWING: u32 = 25;
+const STATE_SINGLE_LINE: u32 = 26;
+const STATE_VISIBLE: u32 = 30;
+
+/// Returns the `AtspiStateType` of a state, or `None` for states that
+/// only remove others.
+fn atspi_state(state: AccessState) -> Option<u32> {
+    Some(match state {
+        AccessState::Disabled => return None,
+        AccessState::Busy => 3,
+        AccessState::Checked => 4,
+        AccessState::Editable => 7,
+        AccessState::Expandable => 9,
+        AccessState::Expanded => 10,
+        AccessState::Focusable => 11,
+        AccessState::Focused => 12,
+        AccessState::Modal => 16,
+        AccessState::MultiLine => 17,
+        AccessState::MultiSelectable => 18,
+        AccessState::Pressed => 20,
+        AccessState::Selectable => 22,
+        AccessState::Selected => 23,
+        AccessState::Mixed => 32,
+        AccessState::Required => 33,
+        AccessState::Invalid => 36,
+        AccessState::Default => 39,
+        AccessState::Checkable => 41,
+        AccessState::HasPopup => 42,
+        AccessState::ReadOnly => 43,
+    })
+}
+
+/// Returns the AT-SPI state set of a node, including the states AT-SPI
+/// expects on every object that is enabled and on screen.
+fn atspi_states(node: &AccessNode) -> u64 {
+    let mut bits = node
+        .states()
+        .filter_map(atspi_state)
+        .fold(0u64, |bits, state| bits | 1 << state);
+    bits |= 1 << STATE_VISIBLE | 1 << STATE_SHOWING;
+    if !node.has_state(AccessState::Disabled) {
+        bits |= 1 << STATE_ENABLED | 1 << STATE_SENSITIVE;
+    }
+    if node.has_state(AccessState::Expandable) && !node.has_state(AccessState::Expanded) {
+        bits |= 1 << STATE_COLLAPSED;
+    }
+    if matches!(node.role, Role::Text | Role::PasswordText)
+        && !node.has_state(AccessState::MultiLine)
+    {
+        bits |= 1 << STATE_SINGLE_LINE;
+    }
+    bits
+}
+
+/// A request from assistive technology, to be carried out by the application.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccessibilityRequest {
+    /// An action of a node was invoked, e.g. a screen reader pressed a button.
+    Action {
+        /// The window's node id
+        window: SharedString,
+        /// The node's id, or its position when it has none
+        node: SharedString,
+        /// The action name, such as `"click"`
+        action: SharedString,
+    },
+    /// The value of a slider or stepper was set.
+    SetValue {
+        /// The window's node id
+        window: SharedString,
+        /// The node's id, or its position when it has none
+        node: SharedString,
+        /// The requested value
+        value: f64,
+    },
+    /// Keyboard focus should move to a focusable node.
+    Focus {
+        /// The window's node id
+        window: SharedString,
+        /// The node's id, or its position when it has none
+        node: SharedString,
+    },
+}
+
+/// A stream of [`AccessibilityRequest`]s.
+pub type AccessibilityRequests = Pin<Box<dyn Stream<Item = AccessibilityRequest> + Send>>;
+
+/// A rectangle as `(x, y, width, height)` in whole pixels.
+type Extents = (i32, i32, i32, i32);
+
+fn extents(bounds: Bounds<Pixels>) -> Extents {
+    (
+        f32::from(bounds.origin.x).round() as i32,
+        f32::from(bounds.origin.y).round() as i32,
+        f32::from(bounds.size.width).round() as i32,
+        f32::from(bounds.size.height).round() as i32,
+    )
+}
+
+/// A node as published, without the resolvers and children of [`AccessNode`].
+#[derive(Debug, Clone, PartialEq)]
+struct FlatNode {
+    key: String,
+    role: Role,
+    name: String,
+    description: String,
+    states: u64,
+    value: Option<ValueRange>,
+    text: Option<String>,
+    actions: Vec<String>,
+    shortcut: Option<String>,
+    bounds: Option<Extents>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+}
+
+/// Flattens a window's tree in depth-first order.
+///
+/// Nodes are keyed by their id. Nodes without one, or with an id already
+/// used in the window, are keyed by their position below the parent.
+fn flatten(window: &AccessNode) -> Vec<FlatNode> {
+    fn visit(
+        node: &AccessNode,
+        parent: Option<usize>,
+        fallback: String,
+        keys: &mut HashSet<String>,
+        nodes: &mut Vec<FlatNode>,
+    ) -> usize {
+        let key = match &node.id {
+            Some(id) if keys.insert(id.to_string()) => id.to_string(),
+            _ => {
+                keys.insert(fallback.clone());
+                fallback
+            }
+        };
+        let index = nodes.len();
+        nodes.push(FlatNode {
+            key: key.clone(),
+            role: node.role,
+            name: node.name.to_string(),
+            description: node.description.to_string(),
+            states: atspi_states(node),
+            value: node.value,
+            text: node.text.as_ref().map(|text| text.to_string()),
+            actions: node
+                .actions
+                .iter()
+                .map(|action| action.to_string())
+                .collect(),
+            shortcut: node.shortcut.as_ref().map(|shortcut| shortcut.to_string()),
+            bounds: node.bounds.map(extents),
+            parent,
+            children: Vec::new(),
+        });
+        for (position, child) in node.children.iter().enumerate() {
+            let child = visit(child, Some(index), format!("{key}/{position}"), keys, nodes);
+            nodes[index].children.push(child);
+        }
+        index
+    }
+
+    let mut nodes = Vec::new();
+    visit(
+        window,
+        None,
+        window_key(window),
+        &mut HashSet::new(),
+        &mut nodes,
+    );
+    nodes
+}
+
+fn window_key(window: &AccessNode) -> String {
+    window
+        .id
+        .as_ref()
+        .map_or_else(|| "window".to_string(), |id| id.to_string())
+}
+
+fn object_path(number: u32) -> OwnedObjectPath {
+    let path = if number == 0 {
+        ROOT_PATH.to_string()
+    } else {
+        format!("{OBJECT_PREFIX}{number}")
+    };
+    OwnedObjectPath::try_from(path).expect("object paths are valid")
+}
+
+/// The D-Bus interfaces an object implements besides `Accessible`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct Interfaces {
+    accessible: bool,
+    action: bool,
+    value: bool,
+    text: bool,
+}
+
+impl Interfaces {
+    fn of(node: &FlatNode) -> Self {
+        Self {
+            accessible: true,
+            action: !node.actions.is_empty(),
+            value: node.value.is_some(),
+            text: node.text.is_some(),
+        }
+    }
+
+    fn names(&self, root: bool) -> Vec<String> {
+        let mut names = vec!["org.a11y.atspi.Accessible".to_string()];
+        if root {
+            names.push("org.a11y.atspi.Application".to_string());
+        } else {
+            names.push("org.a11y.atspi.Component".to_string());
+        }
+        for (implemented, name) in [
+            (self.action, "org.a11y.atspi.Action"),
+            (self.value, "org.a11y.atspi.Value"),
+            (self.text, "org.a11y.atspi.Text"),
+        ] {
+            if implemented {
+                names.push(name.to_string());
+            }
+        }
+        names
+    }
+}
+
+/// A published node and where it sits in the tree.
+struct Object {
+    node: FlatNode,
+    window: String,
+    parent: u32,
+    children: Vec<u32>,
+}
+
+/// A value reported with a property change event.
+#[derive(Debug, Clone, PartialEq)]
+enum PropertyValue {
+    Text(String),
+    Number(f64),
+}
+
+/// An event reporting a change to assistive technology.
+#[derive(Debug, Clone, PartialEq)]
+enum Event {
+    State {
+        object: u32,
+        state: &'static str,
+        enabled: bool,
+    },
+    Property {
+        object: u32,
+        property: &'static str,
+        value: PropertyValue,
+    },
+    Children {
+        object: u32,
+        added: bool,
+        index: usize,
+        child: u32,
+    },
+}
+
+/// What publishing an update changed, applied to the bus after the tree
+/// lock is released.
+#[derive(Default)]
+struct Changes {
+    interfaces: Vec<(u32, Interfaces, Interfaces)>,
+    events: Vec<Event>,
+}
+
+enum Update {
+    Publish {
+        window: String,
+        origin: (i32, i32),
+        nodes: Vec<FlatNode>,
+    },
+    Remove {
+        window: String,
+    },
+}
+
+impl Update {
+    fn window(&self) -> &str {
+        match self {
+            Update::Publish { window, .. } | Update::Remove { window } => window,
+        }
+    }
+}
+
+/// The published tree, shared with the D-Bus objects that answer queries.
+struct TreeState {
+    bus_name: String,
+    parent: (String, OwnedObjectPath),
+    app_id: i32,
+    objects: HashMap<u32, Object>,
+    numbers: HashMap<(String, String), u32>,
+    next_number: u32,
+    windows: Vec<(String, u32)>,
+    origins: HashMap<String, (i32, i32)>,
+    subscribers: Vec<UnboundedSender<AccessibilityRequest>>,
+}
+
+impl TreeState {
+    fn new(app_name: &str, bus_name: String) -> Self {
+        let root = Object {
+            node: FlatNode {
+                key: String::new(),
+                role: Role::Application,
+                name: app_name.to_string(),
+                description: String::new(),
+                states: atspi_states(&AccessNode::new(Role::Application)),
+                value: None,
+                text: None,
+                actions: Vec::new(),
+                shortcut: None,
+                bounds: None,
+                parent: None,
+                children: Vec::new(),
+            },
+            window: String::new(),
+            parent: 0,
+            children: Vec::new(),
+        };
+        Self {
+            bus_name,
+            parent: (String::new(), OwnedObjectPath::try_from(NULL_PATH).unwrap()),
+            app_id: 0,
+            objects: HashMap::from([(0, root)]),
+            numbers: HashMap::new(),
+            next_number: 1,
+            windows: Vec::new(),
+            origins: HashMap::new(),
+            subscribers: Vec::new(),
+        }
+    }
+
+    fn reference(&self, number: u32) -> (String, OwnedObjectPath) {
+        (self.bus_name.clone(), object_path(number))
+    }
+
+    fn number(&mut self, window: &str, key: &str) -> u32 {
+        let next = &mut self.next_number;
+        *self
+            .numbers
+            .entry((window.to_string(), key.to_string()))
+            .or_insert_with(|| {
+                *next += 1;
+                *next - 1
+            })
+    }
+
+    fn send(&mut self, request: AccessibilityRequest) {
+        self.subscribers
+            .retain(|subscriber| subscriber.unbounded_send(request.clone()).is_ok());
+    }
+
+    /// Returns an object's bounds in window coordinates, or those of its
+    /// nearest ancestor that has bounds.
+    fn bounds(&self, mut number: u32) -> Extents {
+        while let Some(object) = self.objects.get(&number) {
+            if let Some(bounds) = object.node.bounds {
+                return bounds;
+            }
+            if number == 0 {
+                break;
+            }
+            number = object.parent;
+        }
+        (0, 0, 0, 0)
+    }
+
+    /// Returns what to add to window coordinates of an object to get the
+    /// given `AtspiCoordType`: 0 for the screen, 1 for the window and 2
+    /// for the parent.
+    fn offset(&self, number: u32, coord_type: u32) -> (i32, i32) {
+        let Some(object) = self.objects.get(&number) else {
+            return (0, 0);
+        };
+        match coord_type {
+            0 => self
+                .origins
+                .get(&object.window)
+                .copied()
+                .unwrap_or_default(),
+            2 => {
+                let (x, y, _, _) = self.bounds(object.parent);
+                (-x, -y)
+            }
+            _ => (0, 0),
+        }
+    }
+
+    /// Returns an object's bounds in the given `AtspiCoordType`.
+    fn extents(&self, number: u32, coord_type: u32) -> Extents {
+        let (x, y, width, height) = self.bounds(number);
+        let (dx, dy) = self.offset(number, coord_type);
+        (x + dx, y + dy, width, height)
+    }
+
+    /// Returns the deepest descendant with bounds containing a point in
+    /// window coordinates.
+    fn descendant_at(&self, number: u32, x: i32, y: i32) -> Option<u32> {
+        let object = self.objects.get(&number)?;
+        object
+            .children
+            .iter()
+            .rev()
+            .find_map(|child| match self.objects.get(child)?.node.bounds {
+                Some(bounds) if contains(bounds, x, y) => {
+                    Some(self.descendant_at(*child, x, y).unwrap_or(*child))
+                }
+                Some(_) => None,
+                None => self.descendant_at(*child, x, y),
+            })
+    }
+
+    /// Replaces or removes a window's nodes and returns what changed.
+    fn apply(&mut self, update: Update) -> Changes {
+        let window = update.window().to_string();
+        let old_objects: HashMap<u32, Object> = {
+            let numbers: Vec<u32> = self
+                .objects
+                .iter()
+                .filter(|(number, object)| **number != 0 && object.window == window)
+                .map(|(number, _)| *number)
+                .collect();
+            numbers
+                .into_iter()
+                .filter_map(|number| Some((number, self.objects.remove(&number)?)))
+                .collect()
+        };
+        let old_windows = self.objects[&0].children.clone();
+
+        let mut new_numbers = HashSet::new();
+        match update {
+            Update::Publish { nodes, origin, .. } => {
+                self.origins.insert(window.clone(), origin);
+                let numbers: Vec<u32> = nodes
+                    .iter()
+                    .map(|node| self.number(&window, &node.key))
+                    .collect();
+                for (node, number) in nodes.into_iter().zip(&numbers) {
+                    let parent = node.parent.map_or(0, |parent| numbers[parent]);
+                    let children = node.children.iter().map(|child| numbers[*child]).collect();
+                    self.objects.insert(
+                        *number,
+                        Object {
+                            node,
+                            window: window.clone(),
+                            parent,
+                            children,
+                        },
+                    );
+                    new_numbers.insert(*number);
+                }
+                match self.windows.iter_mut().find(|(key, _)| *key == window) {
+                    Some((_, root)) => *root = numbers[0],
+                    None => self.windows.push((window.clone(), numbers[0])),
+                }
+            }
+            Update::Remove { .. } => {
+                self.windows.retain(|(key, _)| *key != window);
+                self.origins.remove(&window);
+            }
+        }
+        self.numbers
+            .retain(|(key, _), number| *key != window || new_numbers.contains(number));
+        let windows: Vec<u32> = self.windows.iter().map(|(_, root)| *root).collect();
+        self.objects.get_mut(&0).unwrap().children = windows.clone();
+
+        let mut changes = Changes::default();
+        diff_children(0, &old_windows, &windows, &mut changes.events);
+        for (number, old) in &old_objects {
+            if !self.objects.contains_key(number) {
+                changes.interfaces.push((
+                    *number,
+                    Interfaces::of(&old.node),
+                    Interfaces::default(),
+                ));
+                changes.events.push(Event::State {
+                    object: *number,
+                    state: STATE_NAMES[STATE_DEFUNCT],
+                    enabled: true,
+                });
+            }
+        }
+        let mut numbers: Vec<u32> = new_numbers.into_iter().collect();
+        numbers.sort_unstable();
+        for number in numbers {
+            let new = &self.objects[&number];
+            let Some(old) = old_objects.get(&number) else {
+                changes
+                    .interfaces
+                    .push((number, Interfaces::default(), Interfaces::of(&new.node)));
+                continue;
+            };
+            let (old_interfaces, new_interfaces) =
+                (Interfaces::of(&old.node), Interfaces::of(&new.node));
+            if old_interfaces != new_interfaces {
+                changes
+                    .interfaces
+                    .push((number, old_interfaces, new_interfaces));
+            }
+            diff_node(number, &old.node, &new.node, &mut changes.events);
+            diff_children(number, &old.children, &new.children, &mut changes.events);
+        }
+        changes
+    }
+}
+
+fn contains((left, top, width, height): Extents, x: i32, y: i32) -> bool {
+    (left..left + width).contains(&x) && (top..top + height).contains(&y)
+}
+
+fn diff_node(object: u32, old: &FlatNode, new: &FlatNode, events: &mut Vec<Event>) {
+    let changed = old.states ^ new.states;
+    for (bit, state) in STATE_NAMES.iter().enumerate() {
+        if changed & (1 << bit) != 0 {
+            events.push(Event::State {
+                object,
+                state,
+                enabled: new.states & (1 << bit) != 0,
+            });
+        }
+    }
+    if old.name != new.name {
+        events.push(Event::Property {
+            object,
+            property: "accessible-name",
+            value: PropertyValue::Text(new.name.clone()),
+        });
+    }
+    if old.description != new.description {
+        events.push(Event::Property {
+            object,
+            property: "accessible-description",
+            value: PropertyValue::Text(new.description.clone()),
+        });
+    }
+    if let Some(value) = new.value {
+        if old.value.map(|old| old.current) != Some(value.current) {
+            events.push(Event::Property {
+                object,
+                property: "accessible-value",
+                value: PropertyValue::Number(value.current),
+            });
+        }
+    }
+}
+
+fn diff_children(object: u32, old: &[u32], new: &[u32], events: &mut Vec<Event>) {
+    for (index, child) in old.iter().enumerate() {
+        if !new.contains(child) {
+            events.push(Event::Children {
+                object,
+                added: false,
+                index,
+                child: *child,
+            });
+        }
+    }
+    for (index, child) in new.iter().enumerate() {
+        if !old.contains(child) {
+            events.push(Event::Children {
+                object,
+                added: true,
+                index,
+                child: *child,
+            });
+        }
+    }
+}
+
+/// Registers objects and emits events on the accessibility bus.
+#[derive(Clone)]
+struct Publisher {
+    connection: Connection,
+    tree: Arc<Mutex<TreeState>>,
+}
+
+impl Publisher {
+    async fn apply(&self, update: Update) -> Result<()> {
+        let changes = self.tree.lock().unwrap().apply(update);
+        for (number, old, new) in changes.interfaces {
+            self.update_interfaces(number, old, new).await?;
+        }
+        for event in changes.events {
+            self.emit(event).await?;
+        }
+        Ok(())
+    }
+
+    async fn update_interfaces(&self, number: u32, old: Interfaces, new: Interfaces) -> Result<()> {
+        let server = self.connection.object_server();
+        let path = object_path(number);
+        let object = NodeObject {
+            tree: self.tree.clone(),
+            number,
+        };
+        macro_rules! update {
+            ($field:ident, $interface:ident) => {
+                if old.$field != new.$field {
+                    if new.$field {
+                        server.at(&path, $interface(object.clone())).await?;
+                    } else {
+                        server.remove::<$interface, _>(&path).await?;
+                    }
+                }
+            };
+        }
+        update!(accessible, AccessibleInterface);
+        update!(accessible, ComponentInterface);
+        update!(action, ActionInterface);
+        update!(value, ValueInterface);
+        update!(text, TextInterface);
+        Ok(())
+    }
+
+    async fn emit(&self, event: Event) -> Result<()> {
+        let (object, member, detail, detail1, data) = {
+            let tree = self.tree.lock().unwrap();
+            match event {
+                Event::State {
+                    object,
+                    state,
+                    enabled,
+                } => (
+                    object,
+                    "StateChanged",
+                    state,
+                    enabled as i32,
+                    Value::from(0i32),
+                ),
+                Event::Property {
+                    object,
+                    property,
+                    value,
+                } => {
+                    let value = match value {
+                        PropertyValue::Text(text) => Value::from(text),
+                        PropertyValue::Number(number) => Value::from(number),
+                    };
+                    (object, "PropertyChange", property, 0, value)
+                }
+                Event::Children {
+                    object,
+                    added,
+                    index,
+                    child,
+                } => {
+                    let (bus_name, path) = tree.reference(child);
+                    let child = Structure::from((bus_name, path.into_inner()));
+                    let detail = if added { "add" } else { "remove" };
+                    (
+                        object,
+                        "ChildrenChanged",
+                        detail,
+                        index as i32,
+                        Value::from(child),
+                    )
+                }
+            }
+        };
+        let properties: HashMap<&str, Value<'_>> = HashMap::new();
+        self.connection
+            .emit_signal(
+                None::<BusName<'_>>,
+                object_path(object),
+                EVENT_INTERFACE,
+                member,
+                &(detail, detail1, 0i32, data, properties),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+/// A connection that publishes the application's accessibility tree.
+///
+/// The bridge adds the application to the AT-SPI registry when it connects.
+/// Publish each window's tree whenever it may have changed, typically at
+/// the end of `render`; the bridge compares it with the previous tree and
+/// reports only the differences. Actions invoked by assistive technology
+/// arrive as [`AccessibilityRequest`]s.
+///
+/// # Example
+///
+/// ```ignore
+/// let task = cx.spawn(async move |this, cx| {
+///     let bridge = AccessibilityBridge::connect("Settings").await?;
+///     this.update(cx, |this, cx| {
+///         this.requests = Some(bridge.subscribe(cx, |request, _cx| {
+///             println!("{:?}", request);
+///         }));
+///         this.bridge = Some(bridge);
+///         cx.notify();
+///     })
+/// });
+/// ```
+#[derive(Clone)]
+pub struct AccessibilityBridge {
+    publisher: Publisher,
+    last_error: Arc<Mutex<Option<SharedString>>>,
+    updates: UnboundedSender<Update>,
+    pending: Arc<Mutex<Option<UnboundedReceiver<Update>>>>,
+}
+
+impl AccessibilityBridge {
+    /// Connects to the accessibility bus and registers the application.
+    ///
+    /// The bus address is taken from `AT_SPI_BUS_ADDRESS`, or asked from
+    /// the `org.a11y.Bus` service on the session bus.
+    pub async fn connect(app_name: impl Into<SharedString>) -> Result<Self> {
+        let address = match std::env::var("AT_SPI_BUS_ADDRESS") {
+            Ok(address) => address,
+            Err(_) => {
+                let session = Connection::session().await?;
+                BusProxy::new(&session).await?.get_address().await?
+            }
+        };
+        let connection = Builder::address(address.as_str())?.build().await?;
+        let bridge = Self::with_connection(&connection, app_name).await?;
+        bridge.embed().await?;
+        Ok(bridge)
+    }
+
+    /// Serves the tree on an existing connection, e.g. a private bus in tests.
+    ///
+    /// Unlike [`connect`](Self::connect), this doesn't register the
+    /// application with the AT-SPI registry.
+    pub async fn with_connection(
+        connection: &Connection,
+        app_name: impl Into<SharedString>,
+    ) -> Result<Self> {
+        let bus_name = connection
+            .unique_name()
+            .map(|name| name.to_string())
+            .unwrap_or_default();
+        let tree = Arc::new(Mutex::new(TreeState::new(&app_name.into(), bus_name)));
+        let root = NodeObject {
+            tree: tree.clone(),
+            number: 0,
+        };
+        let server = connection.object_server();
+        server
+            .at(ROOT_PATH, AccessibleInterface(root.clone()))
+            .await?;
+        server.at(ROOT_PATH, ApplicationInterface(root)).await?;
+
+        let (updates, pending) = mpsc::unbounded();
+        Ok(Self {
+            publisher: Publisher {
+                connection: connection.clone(),
+                tree,
+            },
+            last_error: Arc::new(Mutex::new(None)),
+            updates,
+            pending: Arc::new(Mutex::new(Some(pending))),
+        })
+    }
+
+    async fn embed(&self) -> Result<()> {
+        let socket = SocketProxy::new(&self.publisher.connection).await?;
+        let bus_name = self.publisher.tree.lock().unwrap().bus_name.clone();
+        let parent = socket
+            .embed(&(bus_name.as_str(), ObjectPath::try_from(ROOT_PATH)?))
+            .await?;
+        self.publisher.tree.lock().unwrap().parent = parent;
+        Ok(())
+    }
+
+    /// Publishes or updates a window's tree.
+    ///
+    /// The tree is identified by the root node's id, so give each window
+    /// a distinct one. Bindings, views and focus handles in the tree are
+    /// read now; the changes are sent on a background task. The root node
+    /// covers the whole window unless it has bounds.
+    pub fn publish(&self, tree: &AccessNode, window: &Window, cx: &App) {
+        let mut tree = tree.resolve(cx);
+        tree.resolve_focus(window);
+        let bounds = window.bounds();
+        if tree.bounds.is_none() {
+            tree.bounds = Some(Bounds::new(Default::default(), bounds.size));
+        }
+        let (x, y, _, _) = extents(bounds);
+        self.send(
+            Update::Publish {
+                window: window_key(&tree),
+                origin: (x, y),
+                nodes: flatten(&tree),
+            },
+            cx,
+        );
+    }
+
+    /// Removes a window's tree, e.g. when the window closes.
+    pub fn remove(&self, window_id: impl Into<SharedString>, cx: &App) {
+        self.send(
+            Update::Remove {
+                window: window_id.into().to_string(),
+            },
+            cx,
+        );
+    }
+
+    fn send(&self, update: Update, cx: &App) {
+        if let Some(mut pending) = self.pending.lock().unwrap().take() {
+            let publisher = self.publisher.clone();
+            let last_error = self.last_error.clone();
+            cx.background_spawn(async move {
+                while let Some(update) = pending.next().await {
+                    // Only the latest tree of each window needs publishing.
+                    let mut updates = vec![update];
+                    while let Ok(Some(update)) = pending.try_next() {
+                        updates.retain(|queued| queued.window() != update.window());
+                        updates.push(update);
+                    }
+                    for update in updates {
+                        let result = publisher.apply(update).await;
+                        *last_error.lock().unwrap() =
+                            result.err().map(|error| error.to_string().into());
+                    }
+                }
+            })
+            .detach();
+        }
+        self.updates.unbounded_send(update).ok();
+    }
+
+    /// Returns why the most recent update failed to reach the bus, or
+    /// `None` if it was published.
+    ///
+    /// Updates are sent on a background task, so an error shows up here
+    /// after `publish` returns. Queries still see the tree of a failed
+    /// update, but some of its objects or change events may be missing.
+    pub fn last_error(&self) -> Option<SharedString> {
+        self.last_error.lock().unwrap().clone()
+    }
+
+    /// Returns a stream of requests from assistive technology.
+    pub fn requests(&self) -> AccessibilityRequests {
+        let (sender, receiver) = mpsc::unbounded();
+        self.publisher.tree.lock().unwrap().subscribers.push(sender);
+        Box::pin(receiver)
+    }
+
+    /// Calls `handler` on the main thread for each request until the returned task is dropped.
+    pub fn subscribe(
+        &self,
+        cx: &mut App,
+        handler: impl Fn(AccessibilityRequest, &mut App) + 'static,
+    ) -> Task<()> {
+        let mut requests = self.requests();
+        cx.spawn(async move |cx| {
+            while let Some(request) = requests.next().await {
+                if cx.update(|cx| handler(request, cx)).is_err() {
+                    break;
+                }
+            }
+        })
+    }
+}
+
+/// A published object, shared by the interfaces registered at its path.
+#[derive(Clone)]
+struct NodeObject {
+    tree: Arc<Mutex<TreeState>>,
+    number: u32,
+}
+
+impl NodeObject {
+    fn with<R>(&self, f: impl FnOnce(&Object, &TreeState) -> R) -> fdo::Result<R> {
+        let tree = self.tree.lock().unwrap();
+        let object = tree
+            .objects
+            .get(&self.number)
+            .ok_or_else(|| fdo::Error::UnknownObject(object_path(self.number).to_string()))?;
+        Ok(f(object, &tree))
+    }
+
+    fn request(&self, request: impl FnOnce(SharedString, SharedString) -> AccessibilityRequest) {
+        let mut tree = self.tree.lock().unwrap();
+        let Some(object) = tree.objects.get(&self.number) else {
+            return;
+        };
+        let request = request(object.window.clone().into(), object.node.key.clone().into());
+        tree.send(request);
+    }
+}
+
+struct AccessibleInterface(NodeObject);
+
+#[interface(name = "org.a11y.atspi.Accessible")]
+impl AccessibleInterface {
+    #[zbus(property)]
+    fn name(&self) -> fdo::Result<String> {
+        self.0.with(|object, _| object.node.name.clone())
+    }
+
+    #[zbus(property)]
+    fn description(&self) -> fdo::Result<String> {
+        self.0.with(|object, _| object.node.description.clone())
+    }
+
+    #[zbus(property)]
+    fn parent(&self) -> fdo::Result<(String, OwnedObjectPath)> {
+        self.0.with(|object, tree| {
+            if self.0.number == 0 {
+                tree.parent.clone()
+            } else {
+                tree.reference(object.parent)
+            }
+        })
+    }
+
+    #[zbus(property)]
+    fn child_count(&self) -> fdo::Result<i32> {
+        self.0.with(|object, _| object.children.len() as i32)
+    }
+
+    #[zbus(property)]
+    fn locale(&self) -> String {
+        String::new()
+    }
+
+    #[zbus(property)]
+    fn accessible_id(&self) -> fdo::Result<String> {
+        self.0.with(|object, _| object.node.key.clone())
+    }
+
+    fn get_child_at_index(&self, index: i32) -> fdo::Result<(String, OwnedObjectPath)> {
+        self.0.with(|object, tree| {
+            usize::try_from(index)
+                .ok()
+                .and_then(|index| object.children.get(index))
+                .map_or_else(
+                    || (String::new(), OwnedObjectPath::try_from(NULL_PATH).unwrap()),
+                    |child| tree.reference(*child),
+                )
+        })
+    }
+
+    fn get_children(&self) -> fdo::Result<Vec<(String, OwnedObjectPath)>> {
+        self.0.with(|object, tree| {
+            object
+                .children
+                .iter()
+                .map(|child| tree.reference(*child))
+                .collect()
+        })
+    }
+
+    fn get_index_in_parent(&self) -> fdo::Result<i32> {
+        self.0.with(|object, tree| {
+            if self.0.number == 0 {
+                return -1;
+            }
+            tree.objects[&object.parent]
+                .children
+                .iter()
+                .position(|child| *child == self.0.number)
+                .map_or(-1, |index| index as i32)
+        })
+    }
+
+    fn get_relation_set(&self) -> Vec<(u32, Vec<(String, OwnedObjectPath)>)> {
+        Vec::new()
+    }
+
+    fn get_role(&self) -> fdo::Result<u32> {
+        self.0.with(|object, _| object.node.role.atspi_role())
+    }
+
+    fn get_role_name(&self) -> fdo::Result<String> {
+        self.0.with(|object, _| object.node.role.name().to_string())
+    }
+
+    fn get_localized_role_name(&self) -> fdo::Result<String> {
+        self.get_role_name()
+    }
+
+    fn get_state(&self) -> fdo::Result<Vec<u32>> {
+        self.0.with(|object, _| {
+            let states = object.node.states;
+            vec![states as u32, (states >> 32) as u32]
+        })
+    }
+
+    fn get_attributes(&self) -> HashMap<String, String> {
+        HashMap::from([("toolkit".to_string(), "applib".to_string())])
+    }
+
+    fn get_application(&self) -> fdo::Result<(String, OwnedObjectPath)> {
+        self.0.with(|_, tree| tree.reference(0))
+    }
+
+    fn get_interfaces(&self) -> fdo::Result<Vec<String>> {
+        self.0
+            .with(|object, _| Interfaces::of(&object.node).names(self.0.number == 0))
+    }
+}
+
+struct ComponentInterface(NodeObject);
+
+#[interface(name = "org.a11y.atspi.Component")]
+impl ComponentInterface {
+    fn contains(&self, x: i32, y: i32, coord_type: u32) -> fdo::Result<bool> {
+        self.0
+            .with(|_, tree| contains(tree.extents(self.0.number, coord_type), x, y))
+    }
+
+    fn get_accessible_at_point(
+        &self,
+        x: i32,
+        y: i32,
+        coord_type: u32,
+    ) -> fdo::Result<(String, OwnedObjectPath)> {
+        self.0.with(|_, tree| {
+            let (dx, dy) = tree.offset(self.0.number, coord_type);
+            tree.descendant_at(self.0.number, x - dx, y - dy)
+                .map_or_else(
+                    || (String::new(), OwnedObjectPath::try_from(NULL_PATH).unwrap()),
+                    |number| tree.reference(number),
+                )
+        })
+    }
+
+    fn get_extents(&self, coord_type: u32) -> fdo::Result<Extents> {
+        self.0
+            .with(|_, tree| tree.extents(self.0.number, coord_type))
+    }
+
+    fn get_position(&self, coord_type: u32) -> fdo::Result<(i32, i32)> {
+        let (x, y, _, _) = self.get_extents(coord_type)?;
+        Ok((x, y))
+    }
+
+    fn get_size(&self) -> fdo::Result<(i32, i32)> {
+        let (_, _, width, height) = self.get_extents(1)?;
+        Ok((width, height))
+    }
+
+    /// Returns the `AtspiComponentLayer`: window for windows and dialogs,
+    /// widget for everything inside them.
+    fn get_layer(&self) -> fdo::Result<u32> {
+        self.0.with(|object, _| match object.node.role {
+            Role::Window | Role::Dialog => 7,
+            _ => 3,
+        })
+    }
+
+    #[zbus(name = "GetMDIZOrder")]
+    fn get_mdi_z_order(&self) -> i16 {
+        0
+    }
+
+    /// Asks the application to focus the node, if it is focusable.
+    fn grab_focus(&self) -> fdo::Result<bool> {
+        let focusable = self
+            .0
+            .with(|object, _| object.node.states & 1 << STATE_FOCUSABLE != 0)?;
+        if focusable {
+            self.0
+                .request(|window, node| AccessibilityRequest::Focus { window, node });
+        }
+        Ok(focusable)
+    }
+
+    fn get_alpha(&self) -> f64 {
+        1.0
+    }
+}
+
+struct ApplicationInterface(NodeObject);
+
+#[interface(name = "org.a11y.atspi.Application")]
+impl ApplicationInterface {
+    #[zbus(property)]
+    fn toolkit_name(&self) -> String {
+        "applib".to_string()
+    }
+
+    #[zbus(property)]
+    fn version(&self) -> String {
+        env!("CARGO_PKG_VERSION").to_string()
+    }
+
+    #[zbus(property)]
+    fn atspi_version(&self) -> String {
+        "2.1".to_string()
+    }
+
+    #[zbus(property)]
+    fn id(&self) -> i32 {
+        self.0.tree.lock().unwrap().app_id
+    }
+
+    #[zbus(property)]
+    fn set_id(&mut self, id: i32) {
+        self.0.tree.lock().unwrap().app_id = id;
+    }
+
+    fn get_locale(&self, _lctype: u32) -> String {
+        String::new()
+    }
+}
+
+struct ActionInterface(NodeObject);
+
+#[interface(name = "org.a11y.atspi.Action")]
+impl ActionInterface {
+    #[zbus(property)]
+    fn n_actions(&self) -> fdo::Result<i32> {
+        self.0.with(|object, _| object.node.actions.len() as i32)
+    }
+
+    fn get_description(&self, _index: i32) -> String {
+        String::new()
+    }
+
+    fn get_name(&self, index: i32) -> fdo::Result<String> {
+        self.0.with(|object, _| {
+            usize::try_from(index)
+                .ok()
+                .and_then(|index| object.node.actions.get(index))
+                .cloned()
+                .unwrap_or_default()
+        })
+    }
+
+    fn get_localized_name(&self, index: i32) -> fdo::Result<String> {
+        self.get_name(index)
+    }
+
+    fn get_key_binding(&self, index: i32) -> fdo::Result<String> {
+        self.0.with(|object, _| match index {
+            0 => object.node.shortcut.clone().unwrap_or_default(),
+            _ => String::new(),
+        })
+    }
+
+    fn get_actions(&self) -> fdo::Result<Vec<(String, String, String)>> {
+        self.0.with(|object, _| {
+            object
+                .node
+                .actions
+                .iter()
+                .enumerate()
+                .map(|(index, action)| {
+                    let key_binding = match index {
+                        0 => object.node.shortcut.clone().unwrap_or_default(),
+                        _ => String::new(),
+                    };
+                    (action.clone(), String::new(), key_binding)
+                })
+                .collect()
+        })
+    }
+
+    fn do_action(&self, index: i32) -> fdo::Result<bool> {
+        let action = self.get_name(index)?;
+        if action.is_empty() {
+            return Ok(false);
+        }
+        self.0.request(|window, node| AccessibilityRequest::Action {
+            window,
+            node,
+            action: action.into(),
+        });
+        Ok(true)
+    }
+}
+
+struct ValueInterface(NodeObject);
+
+impl ValueInterface {
+    fn range(&self) -> fdo::Result<ValueRange> {
+        self.0
+            .with(|object, _| object.node.value.unwrap_or(ValueRange::new(0.0, 0.0..=0.0)))
+    }
+}
+
+#[interface(name = "org.a11y.atspi.Value")]
+impl ValueInterface {
+    #[zbus(property)]
+    fn minimum_value(&self) -> fdo::Result<f64> {
+        Ok(self.range()?.min)
+    }
+
+    #[zbus(property)]
+    fn maximum_value(&self) -> fdo::Result<f64> {
+        Ok(self.range()?.max)
+    }
+
+    #[zbus(property)]
+    fn minimum_increment(&self) -> fdo::Result<f64> {
+        Ok(self.range()?.step)
+    }
+
+    #[zbus(property)]
+    fn current_value(&self) -> fdo::Result<f64> {
+        Ok(self.range()?.current)
+    }
+
+    #[zbus(property)]
+    fn set_current_value(&mut self, value: f64) {
+        self.0
+            .request(|window, node| AccessibilityRequest::SetValue {
+                window,
+                node,
+                value,
+            });
+    }
+
+    #[zbus(property)]
+    fn text(&self) -> fdo::Result<String> {
+        Ok(self.range()?.current.to_string())
+    }
+}
+
+struct TextInterface(NodeObject);
+
+impl TextInterface {
+    fn content(&self) -> fdo::Result<String> {
+        self.0
+            .with(|object, _| object.node.text.clone().unwrap_or_default())
+    }
+}
+
+#[interface(name = "org.a11y.atspi.Text")]
+impl TextInterface {
+    #[zbus(property)]
+    fn character_count(&self) -> fdo::Result<i32> {
+        Ok(self.content()?.chars().count() as i32)
+    }
+
+    #[zbus(property)]
+    fn caret_offset(&self) -> fdo::Result<i32> {
+        self.character_count()
+    }
+
+    /// Returns the characters from `start_offset` up to `end_offset`, or
+    /// to the end when `end_offset` is -1.
+    fn get_text(&self, start_offset: i32, end_offset: i32) -> fdo::Result<String> {
+        let content = self.content()?;
+        let start = start_offset.max(0) as usize;
+        let end = if end_offset < 0 {
+            usize::MAX
+        } else {
+            end_offset as usize
+        };
+        Ok(content
+            .chars()
+            .skip(start)
+            .take(end.saturating_sub(start))
+            .collect())
+    }
+
+    fn get_character_at_offset(&self, offset: i32) -> fdo::Result<i32> {
+        let content = self.content()?;
+        Ok(usize::try_from(offset)
+            .ok()
+            .and_then(|offset| content.chars().nth(offset))
+            .map_or(0, |character| character as i32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::proxy::{AccessibleProxy, ActionProxy, ComponentProxy, ValueProxy};
+    use super::*;
+    use gpui::{point, px, size};
+    use std::os::unix::net::UnixStream;
+    use zbus::Guid;
+
+    /// Connects a bridge to a client over a private socket pair.
+    async fn connect_bridge() -> (AccessibilityBridge, Connection) {
+        let (server_stream, client_stream) = UnixStream::pair().unwrap();
+        let guid = Guid::generate();
+        let server = Builder::unix_stream(server_stream)
+            .server(guid)
+            .unwrap()
+            .p2p()
+            .build();
+        let client = Builder::unix_stream(client_stream).p2p().build();
+        let (server, client) = futures::join!(server, client);
+        let (server, client) = (server.unwrap(), client.unwrap());
+
+        let bridge = AccessibilityBridge::with_connection(&server, "Settings")
+            .await
+            .unwrap();
+        (bridge, client)
+    }
+
+    async fn publish(bridge: &AccessibilityBridge, window: &AccessNode) {
+        bridge
+            .publisher
+            .apply(Update::Publish {
+                window: window_key(window),
+                origin: (100, 50),
+                nodes: flatten(window),
+            })
+            .await
+            .unwrap();
+    }
+
+    async fn proxy<'a>(client: &Connection, path: OwnedObjectPath) -> AccessibleProxy<'a> {
+        AccessibleProxy::builder(client)
+            .path(path)
+            .unwrap()
+            .build()
+            .await
+            .unwrap()
+    }
+
+    fn window(subscribed: bool) -> AccessNode {
+        AccessNode::new(Role::Window)
+            .id("settings")
+            .name("Settings")
+            .bounds(Bounds::new(
+                point(px(0.0), px(0.0)),
+                size(px(400.0), px(300.0)),
+            ))
+            .child(
+                AccessNode::new(Role::CheckBox)
+                    .id("subscribe")
+                    .name("Subscribe")
+                    .bounds(Bounds::new(
+                        point(px(20.0), px(40.0)),
+                        size(px(120.0), px(20.0)),
+                    ))
+                    .state(AccessState::Checkable)
+                    .state(AccessState::Focusable)
+                    .state_if(AccessState::Checked, subscribed)
+                    .action("click"),
+            )
+            .child(
+                AccessNode::new(Role::Slider)
+                    .name("Volume")
+                    .value(ValueRange::new(0.25, 0.0..=1.0)),
+            )
+    }
+
+    #[test]
+    fn test_atspi_states() {
+        let checkbox = AccessNode::new(Role::CheckBox)
+            .state(AccessState::Checkable)
+            .state(AccessState::Checked);
+        let states = atspi_states(&checkbox);
+        assert_ne!(states & 1 << 4, 0, "checked");
+        assert_ne!(states & 1 << 41, 0, "checkable");
+        assert_ne!(states & 1 << STATE_ENABLED, 0);
+
+        let disabled = atspi_states(&checkbox.state(AccessState::Disabled));
+        assert_eq!(disabled & 1 << STATE_ENABLED, 0);
+        assert_eq!(disabled & 1 << STATE_SENSITIVE, 0);
+
+        let field = atspi_states(&AccessNode::new(Role::Text));
+        assert_ne!(field & 1 << STATE_SINGLE_LINE, 0);
+
+        let group = atspi_states(&AccessNode::new(Role::TreeItem).state(AccessState::Expandable));
+        assert_ne!(group & 1 << STATE_COLLAPSED, 0);
+    }
+
+    #[test]
+    fn test_flatten_keys() {
+        let nodes = flatten(
+            &AccessNode::new(Role::Window)
+                .id("main")
+                .child(AccessNode::new(Role::Button).id("ok"))
+                .child(AccessNode::new(Role::Button).id("ok"))
+                .child(AccessNode::new(Role::Group).child(AccessNode::new(Role::Label))),
+        );
+        let keys: Vec<&str> = nodes.iter().map(|node| node.key.as_str()).collect();
+        assert_eq!(keys, vec!["main", "ok", "main/1", "main/2", "main/2/0"]);
+        assert_eq!(nodes[0].children, vec![1, 2, 3]);
+        assert_eq!(nodes[4].parent, Some(3));
+    }
+
+    #[test]
+    fn test_update_events() {
+        let mut tree = TreeState::new("Settings", String::new());
+        let changes = tree.apply(Update::Publish {
+            window: "settings".into(),
+            origin: (0, 0),
+            nodes: flatten(&window(false)),
+        });
+        assert_eq!(changes.interfaces.len(), 3);
+        assert_eq!(
+            changes.events,
+            vec![Event::Children {
+                object: 0,
+                added: true,
+                index: 0,
+                child: 1
+            }]
+        );
+
+        let changes = tree.apply(Update::Publish {
+            window: "settings".into(),
+            origin: (0, 0),
+            nodes: flatten(&window(true)),
+        });
+        assert!(changes.interfaces.is_empty());
+        assert_eq!(
+            changes.events,
+            vec![Event::State {
+                object: 2,
+                state: "checked",
+                enabled: true
+            }]
+        );
+
+        let changes = tree.apply(Update::Publish {
+            window: "settings".into(),
+            origin: (0, 0),
+            nodes: flatten(
+                &window(true).child(AccessNode::new(Role::Button).state(AccessState::Focused)),
+            ),
+        });
+        assert!(changes.events.contains(&Event::State {
+            object: 4,
+            state: "focused",
+            enabled: true
+        }));
+
+        let changes = tree.apply(Update::Remove {
+            window: "settings".into(),
+        });
+        assert_eq!(changes.interfaces.len(), 3);
+        assert!(tree.objects[&0].children.is_empty());
+        assert!(tree.numbers.is_empty());
+    }
+
+    #[test]
+    fn test_extents() {
+        let mut tree = TreeState::new("Settings", String::new());
+        tree.apply(Update::Publish {
+            window: "settings".into(),
+            origin: (100, 50),
+            nodes: flatten(&window(false)),
+        });
+        // Window, checkbox and slider, which has no bounds of its own
+        assert_eq!(tree.extents(2, 0), (120, 90, 120, 20));
+        assert_eq!(tree.extents(2, 1), (20, 40, 120, 20));
+        assert_eq!(tree.extents(3, 1), (0, 0, 400, 300));
+        assert_eq!(tree.descendant_at(1, 30, 50), Some(2));
+        assert_eq!(tree.descendant_at(1, 300, 200), None);
+    }
+
+    #[test]
+    fn test_publish_over_dbus() {
+        zbus::block_on(async {
+            let (bridge, client) = connect_bridge().await;
+            let mut requests = bridge.requests();
+            publish(&bridge, &window(true)).await;
+
+            let root = proxy(&client, object_path(0)).await;
+            assert_eq!(root.name().await.unwrap(), "Settings");
+            assert_eq!(root.get_role_name().await.unwrap(), "application");
+            assert_eq!(root.child_count().await.unwrap(), 1);
+
+            let (_, window_path) = root.get_child_at_index(0).await.unwrap();
+            let window = proxy(&client, window_path).await;
+            assert_eq!(window.get_role().await.unwrap(), 23);
+            assert_eq!(window.child_count().await.unwrap(), 2);
+
+            let (_, checkbox_path) = window.get_child_at_index(0).await.unwrap();
+            let checkbox = proxy(&client, checkbox_path.clone()).await;
+            assert_eq!(checkbox.get_role_name().await.unwrap(), "check box");
+            let states = checkbox.get_state().await.unwrap();
+            assert_ne!(states[0] & 1 << 4, 0, "checked");
+            assert!(checkbox
+                .get_interfaces()
+                .await
+                .unwrap()
+                .contains(&"org.a11y.atspi.Action".to_string()));
+
+            let action = ActionProxy::builder(&client)
+                .path(checkbox_path.clone())
+                .unwrap()
+                .build()
+                .await
+                .unwrap();
+            let component = ComponentProxy::builder(&client)
+                .path(checkbox_path)
+                .unwrap()
+                .build()
+                .await
+                .unwrap();
+            assert_eq!(component.get_extents(0).await.unwrap(), (120, 90, 120, 20));
+            assert!(component.grab_focus().await.unwrap());
+            assert_eq!(
+                requests.next().await,
+                Some(AccessibilityRequest::Focus {
+                    window: "settings".into(),
+                    node: "subscribe".into(),
+                })
+            );
+
+            assert_eq!(action.get_name(0).await.unwrap(), "click");
+            assert!(action.do_action(0).await.unwrap());
+            assert_eq!(
+                requests.next().await,
+                Some(AccessibilityRequest::Action {
+                    window: "settings".into(),
+                    node: "subscribe".into(),
+                    action: "click".into(),
+                })
+            );
+
+            let (_, slider_path) = window.get_child_at_index(1).await.unwrap();
+            let value = ValueProxy::builder(&client)
+                .path(slider_path)
+                .unwrap()
+                .build()
+                .await
+                .unwrap();
+            assert_eq!(value.current_value().await.unwrap(), 0.25);
+            assert_eq!(value.maximum_value().await.unwrap(), 1.0);
+        });
+    }
+}
@@ -0,0 +1,743 @@
+//! Accessibility tree exposed to assistive technology through AT-SPI.
+//!
+//! Components describe themselves with an [`AccessNode`]: a role, a name,
+//! states such as checked or selected, an optional value range, and the
+//! actions a screen reader may invoke. Containers such as [`Form`](crate::Form),
+//! [`List`](crate::List), [`Table`](crate::Table) and [`Menu`](crate::Menu)
+//! include their rows and items, so a window's tree is built by asking its
+//! top-level components for their nodes.
+//!
+//! An [`AccessibilityBridge`] publishes the tree on the accessibility bus,
+//! where screen readers such as Orca find it through the AT-SPI registry.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use applib::accessibility::{AccessNode, Accessible, AccessibilityBridge, Role};
+//!
+//! impl Render for SettingsView {
+//!     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+//!         let form = Form::new().section("Account", |section| {
+//!             section.row(FormRow::new("Name").field(self.name_field.clone()))
+//!         });
+//!
+//!         if let Some(bridge) = &self.bridge {
+//!             let tree = AccessNode::new(Role::Window)
+//!                 .id("settings")
+//!                 .name("Settings")
+//!                 .child(form.accessibility_node());
+//!             bridge.publish(&tree, window, cx);
+//!         }
+//!         form
+//!     }
+//! }
+//! ```
+
+mod atspi;
+mod proxy;
+
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::rc::Rc;
+
+use gpui::{App, Bounds, Entity, FocusHandle, Pixels, SharedString, Window};
+
+pub use atspi::{AccessibilityBridge, AccessibilityRequest, AccessibilityRequests};
+
+/// What kind of object a node represents.
+///
+/// Screen readers announce the role together with the name, e.g.
+/// "Subscribe, check box, checked".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Role {
+    /// The application, the root of the tree
+    Application,
+    /// A top-level window
+    Window,
+    /// A dialog or sheet
+    Dialog,
+    /// An alert that needs the user's attention
+    Alert,
+    /// A generic container
+    #[default]
+    Panel,
+    /// A titled group of related controls
+    Group,
+    /// A form of labelled fields
+    Form,
+    /// Static text
+    Label,
+    /// A section heading
+    Heading,
+    /// A hyperlink
+    Link,
+    /// An image
+    Image,
+    /// A push button
+    Button,
+    /// A button that stays on or off, such as a switch
+    ToggleButton,
+    /// A checkbox
+    CheckBox,
+    /// One option of a radio group
+    RadioButton,
+    /// A slider with a value range
+    Slider,
+    /// A numeric field with increment and decrement buttons
+    SpinButton,
+    /// A progress indicator
+    ProgressBar,
+    /// An editable text field
+    Text,
+    /// A text field whose content is hidden
+    PasswordText,
+    /// A button that opens a list of options
+    ComboBox,
+    /// A list of items
+    List,
+    /// An item of a list
+    ListItem,
+    /// A table of rows and columns
+    Table,
+    /// A row of a table
+    TableRow,
+    /// A cell of a table row
+    TableCell,
+    /// The header of a table column
+    ColumnHeader,
+    /// A tree of expandable items
+    Tree,
+    /// An item of a tree
+    TreeItem,
+    /// A menu or submenu
+    Menu,
+    /// An item of a menu
+    MenuItem,
+    /// A divider between groups of items
+    Separator,
+    /// The tab strip of a tab view
+    PageTabList,
+    /// A tab of a tab view
+    PageTab,
+    /// A tooltip
+    ToolTip,
+}
+
+impl Role {
+    /// Returns the role name used by AT-SPI, such as `"push button"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Role::Application => "application",
+            Role::Window => "frame",
+            Role::Dialog => "dialog",
+            Role::Alert => "alert",
+            Role::Panel => "panel",
+            Role::Group => "grouping",
+            Role::Form => "form",
+            Role::Label => "label",
+            Role::Heading => "heading",
+            Role::Link => "link",
+            Role::Image => "image",
+            Role::Button => "push button",
+            Role::ToggleButton => "toggle button",
+            Role::CheckBox => "check box",
+            Role::RadioButton => "radio button",
+            Role::Slider => "slider",
+            Role::SpinButton => "spin button",
+            Role::ProgressBar => "progress bar",
+            Role::Text => "text",
+            Role::PasswordText => "password text",
+            Role::ComboBox => "combo box",
+            Role::List => "list",
+            Role::ListItem => "list item",
+            Role::Table => "table",
+            Role::TableRow => "table row",
+            Role::TableCell => "table cell",
+            Role::ColumnHeader => "table column header",
+            Role::Tree => "tree",
+            Role::TreeItem => "tree item",
+            Role::Menu => "menu",
+            Role::MenuItem => "menu item",
+            Role::Separator => "separator",
+            Role::PageTabList => "page tab list",
+            Role::PageTab => "page tab",
+            Role::ToolTip => "tool tip",
+        }
+    }
+
+    /// Returns the `AtspiRole` number of the role.
+    fn atspi_role(&self) -> u32 {
+        match self {
+            Role::Application => 75,
+            Role::Window => 23,
+            Role::Dialog => 16,
+            Role::Alert => 2,
+            Role::Panel => 39,
+            Role::Group => 99,
+            Role::Form => 87,
+            Role::Label => 29,
+            Role::Heading => 83,
+            Role::Link => 88,
+            Role::Image => 27,
+            Role::Button => 43,
+            Role::ToggleButton => 62,
+            Role::CheckBox => 7,
+            Role::RadioButton => 44,
+            Role::Slider => 51,
+            Role::SpinButton => 52,
+            Role::ProgressBar => 42,
+            Role::Text => 61,
+            Role::PasswordText => 40,
+            Role::ComboBox => 11,
+            Role::List => 31,
+            Role::ListItem => 32,
+            Role::Table => 55,
+            Role::TableRow => 90,
+            Role::TableCell => 56,
+            Role::ColumnHeader => 57,
+            Role::Tree => 65,
+            Role::TreeItem => 91,
+            Role::Menu => 33,
+            Role::MenuItem => 35,
+            Role::Separator => 50,
+            Role::PageTabList => 38,
+            Role::PageTab => 37,
+            Role::ToolTip => 64,
+        }
+    }
+}
+
+/// A state of an accessible node.
+///
+/// Nodes are enabled, visible and showing unless marked [`Disabled`](AccessState::Disabled).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccessState {
+    /// The control can't be used
+    Disabled,
+    /// The control can receive keyboard focus
+    Focusable,
+    /// The control has keyboard focus
+    Focused,
+    /// The item can be selected
+    Selectable,
+    /// The item is selected
+    Selected,
+    /// More than one item can be selected
+    MultiSelectable,
+    /// The control can be checked
+    Checkable,
+    /// The control is checked or on
+    Checked,
+    /// The control is partially checked
+    Mixed,
+    /// The item can be expanded
+    Expandable,
+    /// The item is expanded
+    Expanded,
+    /// The text can be edited
+    Editable,
+    /// The value can be read but not changed
+    ReadOnly,
+    /// The field must be filled in
+    Required,
+    /// The field's value failed validation
+    Invalid,
+    /// The text spans several lines
+    MultiLine,
+    /// The control opens a menu or popup
+    HasPopup,
+    /// The button is pressed
+    Pressed,
+    /// The object is loading or working
+    Busy,
+    /// The window blocks the rest of the application
+    Modal,
+    /// The button is activated by Enter
+    Default,
+}
+
+impl AccessState {
+    const ALL: [AccessState; 21] = [
+        AccessState::Disabled,
+        AccessState::Focusable,
+        AccessState::Focused,
+        AccessState::Selectable,
+        AccessState::Selected,
+        AccessState::MultiSelectable,
+        AccessState::Checkable,
+        AccessState::Checked,
+        AccessState::Mixed,
+        AccessState::Expandable,
+        AccessState::Expanded,
+        AccessState::Editable,
+        AccessState::ReadOnly,
+        AccessState::Required,
+        AccessState::Invalid,
+        AccessState::MultiLine,
+        AccessState::HasPopup,
+        AccessState::Pressed,
+        AccessState::Busy,
+        AccessState::Modal,
+        AccessState::Default,
+    ];
+
+    fn bit(self) -> u32 {
+        1 << self as u32
+    }
+
+    /// Returns the state name, such as `"checked"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AccessState::Disabled => "disabled",
+            AccessState::Focusable => "focusable",
+            AccessState::Focused => "focused",
+            AccessState::Selectable => "selectable",
+            AccessState::Selected => "selected",
+            AccessState::MultiSelectable => "multiselectable",
+            AccessState::Checkable => "checkable",
+            AccessState::Checked => "checked",
+            AccessState::Mixed => "mixed",
+            AccessState::Expandable => "expandable",
+            AccessState::Expanded => "expanded",
+            AccessState::Editable => "editable",
+            AccessState::ReadOnly => "read-only",
+            AccessState::Required => "required",
+            AccessState::Invalid => "invalid",
+            AccessState::MultiLine => "multi-line",
+            AccessState::HasPopup => "has-popup",
+            AccessState::Pressed => "pressed",
+            AccessState::Busy => "busy",
+            AccessState::Modal => "modal",
+            AccessState::Default => "default",
+        }
+    }
+}
+
+/// The current value and range of a slider, stepper or progress bar.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValueRange {
+    /// The current value
+    pub current: f64,
+    /// The smallest allowed value
+    pub min: f64,
+    /// The largest allowed value
+    pub max: f64,
+    /// The smallest change, or 0 for a continuous range
+    pub step: f64,
+}
+
+impl ValueRange {
+    /// Creates a continuous value range.
+    pub fn new(current: f64, range: RangeInclusive<f64>) -> Self {
+        Self {
+            current,
+            min: *range.start(),
+            max: *range.end(),
+            step: 0.0,
+        }
+    }
+
+    /// Sets the smallest change.
+    pub fn step(mut self, step: f64) -> Self {
+        self.step = step;
+        self
+    }
+}
+
+type Resolver = Rc<dyn Fn(AccessNode, &App) -> AccessNode>;
+
+/// A node of the accessibility tree.
+///
+/// Nodes are plain descriptions built with the builder methods below.
+/// Values that live in bindings or entities are read when the tree is
+/// published; see [`resolve_with`](Self::resolve_with).
+///
+/// # Example
+///
+/// ```ignore
+/// AccessNode::new(Role::CheckBox)
+///     .id("subscribe")
+///     .name("Subscribe to newsletter")
+///     .state(AccessState::Checkable)
+///     .state_if(AccessState::Checked, is_subscribed)
+///     .action("click")
+/// ```
+#[derive(Clone, Default)]
+pub struct AccessNode {
+    /// Stable identifier, if set
+    pub id: Option<SharedString>,
+    /// What kind of object the node represents
+    pub role: Role,
+    /// The name a screen reader announces
+    pub name: SharedString,
+    /// A longer description, such as a validation error
+    pub description: SharedString,
+    /// Value and range of a slider, stepper or progress bar
+    pub value: Option<ValueRange>,
+    /// Text content of a text field
+    pub text: Option<SharedString>,
+    /// Actions assistive technology may invoke
+    pub actions: Vec<SharedString>,
+    /// Keyboard shortcut of the first action
+    pub shortcut: Option<SharedString>,
+    /// Position and size within the window, if known
+    pub bounds: Option<Bounds<Pixels>>,
+    /// Child nodes
+    pub children: Vec<AccessNode>,
+    states: u32,
+    focus: Option<FocusHandle>,
+    resolver: Option<Resolver>,
+}
+
+impl AccessNode {
+    /// Creates a node with the given role.
+    pub fn new(role: Role) -> Self {
+        Self {
+            role,
+            ..Default::default()
+        }
+    }
+
+    /// Sets a stable identifier.
+    ///
+    /// Assistive technology keeps track of objects across updates by their
+    /// identifier. Nodes without one are identified by their position.
+    pub fn id(mut self, id: impl Into<SharedString>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Sets the name a screen reader announces, usually the visible label.
+    pub fn name(mut self, name: impl Into<SharedString>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Sets a longer description, such as a validation error or hint.
+    pub fn description(mut self, description: impl Into<SharedString>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Adds a state.
+    pub fn state(mut self, state: AccessState) -> Self {
+        self.states |= state.bit();
+        self
+    }
+
+    /// Adds a state when `condition` is true.
+    pub fn state_if(self, state: AccessState, condition: bool) -> Self {
+        if condition {
+            self.state(state)
+        } else {
+            self
+        }
+    }
+
+    /// Sets the value and range of a slider, stepper or progress bar.
+    pub fn value(mut self, value: ValueRange) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    /// Sets the text content of a text field.
+    pub fn text(mut self, text: impl Into<SharedString>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Adds an action, such as `"click"` or `"activate"`.
+    ///
+    /// Invoked actions are reported as [`AccessibilityRequest::Action`].
+    pub fn action(mut self, action: impl Into<SharedString>) -> Self {
+        self.actions.push(action.into());
+        self
+    }
+
+    /// Sets the keyboard shortcut of the first action, such as `"Ctrl+S"`.
+    pub fn shortcut(mut self, shortcut: impl Into<SharedString>) -> Self {
+        self.shortcut = Some(shortcut.into());
+        self
+    }
+
+    /// Tracks keyboard focus with `handle`.
+    ///
+    /// The node is focusable, and focused whenever the handle is focused
+    /// in the window the tree is published for.
+    pub fn focus_handle(mut self, handle: &FocusHandle) -> Self {
+        self.focus = Some(handle.clone());
+        self.state(AccessState::Focusable)
+    }
+
+    /// Sets the position and size within the window.
+    ///
+    /// Screen readers and magnifiers use it to highlight the object. Nodes
+    /// without bounds report those of their parent.
+    pub fn bounds(mut self, bounds: Bounds<Pixels>) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// Adds a child node.
+    pub fn child(mut self, child: AccessNode) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Adds child nodes.
+    pub fn children(mut self, children: impl IntoIterator<Item = AccessNode>) -> Self {
+        self.children.extend(children);
+        self
+    }
+
+    /// Updates the node from application state when the tree is published.
+    ///
+    /// Components use this to read bindings, which need the app context.
+    /// The function receives the node as built and returns the node to publish.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// AccessNode::new(Role::CheckBox)
+    ///     .name("Subscribe")
+    ///     .resolve_with(move |node, cx| {
+    ///         node.state_if(AccessState::Checked, binding.get(cx))
+    ///     })
+    /// ```
+    pub fn resolve_with(
+        mut self,
+        resolve: impl Fn(AccessNode, &App) -> AccessNode + 'static,
+    ) -> Self {
+        self.resolver = Some(Rc::new(resolve));
+        self
+    }
+
+    /// Returns a copy of the tree with every value read from application state.
+    pub fn resolve(&self, cx: &App) -> AccessNode {
+        let mut node = self.clone();
+        if let Some(resolver) = node.resolver.take() {
+            node = resolver(node, cx);
+        }
+        node.children = node
+            .children
+            .iter()
+            .map(|child| child.resolve(cx))
+            .collect();
+        node
+    }
+
+    /// Marks the nodes whose focus handle is focused in `window`.
+    fn resolve_focus(&mut self, window: &Window) {
+        if self
+            .focus
+            .as_ref()
+            .is_some_and(|handle| handle.is_focused(window))
+        {
+            self.states |= AccessState::Focused.bit();
+        }
+        for child in &mut self.children {
+            child.resolve_focus(window);
+        }
+    }
+
+    /// Fills in what this node leaves unset from `outer`.
+    ///
+    /// Used when a node stands in for one that is only known when the tree
+    /// is resolved, so names given by a container are kept.
+    fn inherit(mut self, outer: &AccessNode) -> Self {
+        if self.id.is_none() {
+            self.id = outer.id.clone();
+        }
+        if self.name.is_empty() {
+            self.name = outer.name.clone();
+        }
+        if self.description.is_empty() {
+            self.description = outer.description.clone();
+        }
+        if self.bounds.is_none() {
+            self.bounds = outer.bounds;
+        }
+        if self.focus.is_none() {
+            self.focus = outer.focus.clone();
+        }
+        self.states |= outer.states;
+        self
+    }
+
+    /// Names the node unless it already has a name.
+    pub(crate) fn or_name(mut self, name: &SharedString) -> Self {
+        if self.name.is_empty() {
+            self.name = name.clone();
+        }
+        self
+    }
+
+    /// Returns whether the node has the given state.
+    pub fn has_state(&self, state: AccessState) -> bool {
+        self.states & state.bit() != 0
+    }
+
+    /// Returns the states of the node.
+    pub fn states(&self) -> impl Iterator<Item = AccessState> + '_ {
+        AccessState::ALL
+            .into_iter()
+            .filter(|state| self.has_state(*state))
+    }
+
+    fn write_outline(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(f, "{:indent$}{}", "", self.role.name(), indent = depth * 2)?;
+        if !self.name.is_empty() {
+            write!(f, " {:?}", self.name.as_ref())?;
+        }
+        if let Some(text) = self.text.as_ref().filter(|text| !text.is_empty()) {
+            write!(f, " text={:?}", text.as_ref())?;
+        }
+        if let Some(value) = self.value {
+            write!(
+                f,
+                " value={} range={}..={}",
+                value.current, value.min, value.max
+            )?;
+        }
+        if !self.description.is_empty() {
+            write!(f, " description={:?}", self.description.as_ref())?;
+        }
+        let states: Vec<&str> = self.states().map(|state| state.name()).collect();
+        if !states.is_empty() {
+            write!(f, " [{}]", states.join(", "))?;
+        }
+        for child in &self.children {
+            writeln!(f)?;
+            child.write_outline(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the tree as an indented outline, one node per line.
+///
+/// ```text
+/// form
+///   grouping "Account"
+///     check box "Subscribe" [checkable, checked]
+/// ```
+impl fmt::Display for AccessNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_outline(f, 0)
+    }
+}
+
+impl fmt::Debug for AccessNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AccessNode")
+            .field("id", &self.id)
+            .field("role", &self.role)
+            .field("name", &self.name)
+            .field("states", &self.states().collect::<Vec<_>>())
+            .field("value", &self.value)
+            .field("children", &self.children)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A component that can describe itself to assistive technology.
+///
+/// # Example
+///
+/// ```ignore
+/// let save = Button::new("save", "Save").primary();
+/// let node = save.accessibility_node();
+/// assert_eq!(node.role, Role::Button);
+/// ```
+pub trait Accessible {
+    /// Returns the node describing this component and its children.
+    fn accessibility_node(&self) -> AccessNode;
+}
+
+/// Views such as `TextFieldState` are described when the tree is published.
+impl<T: Accessible + 'static> Accessible for Entity<T> {
+    fn accessibility_node(&self) -> AccessNode {
+        let entity = self.clone();
+        AccessNode::default().resolve_with(move |outer, cx| {
+            entity
+                .read(cx)
+                .accessibility_node()
+                .resolve(cx)
+                .inherit(&outer)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_builder() {
+        let node = AccessNode::new(Role::CheckBox)
+            .id("subscribe")
+            .name("Subscribe")
+            .state(AccessState::Checkable)
+            .state_if(AccessState::Checked, true)
+            .state_if(AccessState::Disabled, false)
+            .action("click");
+
+        assert_eq!(node.role, Role::CheckBox);
+        assert_eq!(node.id, Some("subscribe".into()));
+        assert!(node.has_state(AccessState::Checked));
+        assert!(!node.has_state(AccessState::Disabled));
+        assert_eq!(
+            node.states().collect::<Vec<_>>(),
+            vec![AccessState::Checkable, AccessState::Checked]
+        );
+        assert_eq!(node.actions, vec!["click"]);
+    }
+
+    #[test]
+    fn test_outline() {
+        let node = AccessNode::new(Role::Form).child(
+            AccessNode::new(Role::Group)
+                .name("Audio")
+                .child(
+                    AccessNode::new(Role::Slider)
+                        .name("Volume")
+                        .value(ValueRange::new(0.5, 0.0..=1.0)),
+                )
+                .child(
+                    AccessNode::new(Role::Text)
+                        .name("Device")
+                        .text("Speakers")
+                        .description("Not connected")
+                        .state(AccessState::Invalid),
+                ),
+        );
+
+        assert_eq!(
+            node.to_string(),
+            "form\n\
+             \x20 grouping \"Audio\"\n\
+             \x20   slider \"Volume\" value=0.5 range=0..=1\n\
+             \x20   text \"Device\" text=\"Speakers\" description=\"Not connected\" [invalid]"
+        );
+    }
+
+    #[test]
+    fn test_inherit_keeps_own_values() {
+        let outer = AccessNode::default()
+            .id("email")
+            .name("Email")
+            .state(AccessState::Invalid);
+        let inner = AccessNode::new(Role::Text).state(AccessState::Editable);
+        let node = inner.inherit(&outer);
+
+        assert_eq!(node.role, Role::Text);
+        assert_eq!(node.name, "Email");
+        assert!(node.has_state(AccessState::Editable));
+        assert!(node.has_state(AccessState::Invalid));
+
+        let named = AccessNode::new(Role::Text)
+            .name("Work email")
+            .inherit(&outer);
+        assert_eq!(named.name, "Work email");
+    }
+}
@@ -0,0 +1,106 @@
+//! D-Bus proxies for the accessibility bus and the AT-SPI registry.
+
+use zbus::proxy;
+use zbus::zvariant::{ObjectPath, OwnedObjectPath};
+
+/// Path of the application's root object, and of the registry's desktop.
+pub(crate) const ROOT_PATH: &str = "/org/a11y/atspi/accessible/root";
+
+/// Path used by AT-SPI for a missing object reference.
+pub(crate) const NULL_PATH: &str = "/org/a11y/atspi/null";
+
+#[proxy(
+    interface = "org.a11y.Bus",
+    default_service = "org.a11y.Bus",
+    default_path = "/org/a11y/bus"
+)]
+pub(crate) trait Bus {
+    /// Returns the address of the accessibility bus.
+    fn get_address(&self) -> zbus::Result<String>;
+}
+
+#[proxy(
+    interface = "org.a11y.atspi.Socket",
+    default_service = "org.a11y.atspi.Registry",
+    default_path = "/org/a11y/atspi/accessible/root"
+)]
+pub(crate) trait Socket {
+    /// Adds the application's root to the desktop and returns its parent.
+    fn embed(&self, plug: &(&str, ObjectPath<'_>)) -> zbus::Result<(String, OwnedObjectPath)>;
+}
+
+#[cfg(test)]
+#[proxy(
+    interface = "org.a11y.atspi.Accessible",
+    default_service = "org.a11y.atspi.Registry",
+    default_path = "/org/a11y/atspi/accessible/root"
+)]
+pub(crate) trait Accessible {
+    /// The object's name.
+    #[zbus(property)]
+    fn name(&self) -> zbus::Result<String>;
+
+    /// The object's description.
+    #[zbus(property)]
+    fn description(&self) -> zbus::Result<String>;
+
+    /// The number of children.
+    #[zbus(property)]
+    fn child_count(&self) -> zbus::Result<i32>;
+
+    /// Returns the child at the given index.
+    fn get_child_at_index(&self, index: i32) -> zbus::Result<(String, OwnedObjectPath)>;
+
+    /// Returns the `AtspiRole` number.
+    fn get_role(&self) -> zbus::Result<u32>;
+
+    /// Returns the role name.
+    fn get_role_name(&self) -> zbus::Result<String>;
+
+    /// Returns the state set as two 32-bit words.
+    fn get_state(&self) -> zbus::Result<Vec<u32>>;
+
+    /// Returns the names of the interfaces the object implements.
+    fn get_interfaces(&self) -> zbus::Result<Vec<String>>;
+}
+
+#[cfg(test)]
+#[proxy(
+    interface = "org.a11y.atspi.Action",
+    default_service = "org.a11y.atspi.Registry"
+)]
+pub(crate) trait Action {
+    /// Returns the name of the action at the given index.
+    fn get_name(&self, index: i32) -> zbus::Result<String>;
+
+    /// Invokes the action at the given index.
+    fn do_action(&self, index: i32) -> zbus::Result<bool>;
+}
+
+#[cfg(test)]
+#[proxy(
+    interface = "org.a11y.atspi.Component",
+    default_service = "org.a11y.atspi.Registry"
+)]
+pub(crate) trait Component {
+    /// Returns the position and size in the given coordinate type.
+    fn get_extents(&self, coord_type: u32) -> zbus::Result<(i32, i32, i32, i32)>;
+
+    /// Asks the application to focus the object.
+    fn grab_focus(&self) -> zbus::Result<bool>;
+}
+
+#[cfg(test)]
+#[proxy(
+    interface = "org.a11y.atspi.Value",
+    default_service = "org.a11y.atspi.Registry"
+)]
+pub(crate) trait Value {
+    /// The current value.
+    #[zbus(property)]
+    fn current_value(&self) -> zbus::Result<f64>;
+
+    /// The largest value.
+    #[zbus(property)]
+    fn maximum_value(&self) -> zbus::Result<f64>;
+}
@@ -0,0 +1,553 @@
+//! Document-based windows with tabs and a "Save changes?" alert.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use gpui::prelude::*;
+use gpui::*;
+
+use super::{AppWindows, CloseWindow, NewTab, Save};
+use crate::components::{Alert, AlertButton, AlertIcon, Tab, TabView};
+
+/// A document that can be opened from and saved to a file.
+///
+/// Implement this on the view that edits the document. The document keeps
+/// track of its own unsaved changes and clears them when it is saved.
+///
+/// # Example
+///
+/// ```ignore
+/// struct TextDocument {
+///     text: String,
+///     dirty: bool,
+/// }
+///
+/// impl Document for TextDocument {
+///     fn untitled(_window: &mut Window, _cx: &mut App) -> Self {
+///         Self { text: String::new(), dirty: false }
+///     }
+///
+///     fn open(path: &Path, _cx: &mut App) -> Result<Self> {
+///         Ok(Self { text: std::fs::read_to_string(path)?, dirty: false })
+///     }
+///
+///     fn save(&mut self, path: &Path, _window: &mut Window, _cx: &mut Context<Self>) -> Result<()> {
+///         std::fs::write(path, &self.text)?;
+///         self.dirty = false;
+///         Ok(())
+///     }
+///
+///     fn is_dirty(&self) -> bool {
+///         self.dirty
+///     }
+/// }
+/// ```
+pub trait Document: Render + Sized {
+    /// Creates a new, empty document.
+    fn untitled(window: &mut Window, cx: &mut App) -> Self;
+
+    /// Reads the document at `path`.
+    ///
+    /// This runs before the document's window opens, so a file that can't
+    /// be read never shows an empty window.
+    fn open(path: &Path, cx: &mut App) -> Result<Self>;
+
+    /// Writes the document to `path` and clears its unsaved changes.
+    fn save(&mut self, path: &Path, window: &mut Window, cx: &mut Context<Self>) -> Result<()>;
+
+    /// Returns true if the document has unsaved changes.
+    fn is_dirty(&self) -> bool;
+}
+
+struct DocumentTab<D: Document> {
+    document: Entity<D>,
+    path: Option<PathBuf>,
+}
+
+impl<D: Document> DocumentTab<D> {
+    fn name(&self) -> SharedString {
+        display_name(self.path.as_deref())
+    }
+}
+
+/// Returns the file name of a document, or "Untitled" if it has no path.
+fn display_name(path: Option<&Path>) -> SharedString {
+    path.and_then(Path::file_name)
+        .map(|name| name.to_string_lossy().into_owned().into())
+        .unwrap_or_else(|| "Untitled".into())
+}
+
+/// A window that shows one or more documents as tabs.
+///
+/// Ctrl+T opens an untitled document in a new tab, Ctrl+S saves the active
+/// document, and Ctrl+W closes the active tab, or the window if it has a
+/// single tab. Closing a document with unsaved changes shows a
+/// "Save changes?" alert first. The window title shows the active
+/// document's name and whether it has been edited.
+///
+/// # Example
+///
+/// ```ignore
+/// AppWindows::init(cx);
+///
+/// // A window with an untitled document
+/// DocumentWindow::<TextDocument>::open_untitled(cx)?;
+///
+/// // A window with a document from disk
+/// let window = DocumentWindow::<TextDocument>::open_path("notes.txt".into(), cx)?;
+///
+/// // Another document in a tab of the same window
+/// window.update(cx, |this, _window, cx| this.add_path("todo.txt".into(), cx))??;
+/// ```
+pub struct DocumentWindow<D: Document> {
+    tabs: Vec<DocumentTab<D>>,
+    active: usize,
+    /// The tab waiting for an answer to the "Save changes?" alert
+    confirming: Option<usize>,
+    /// Whether the whole window closes once the confirmed tab is closed
+    closing_window: bool,
+    error: Option<SharedString>,
+    focus_handle: FocusHandle,
+}
+
+impl<D: Document + 'static> DocumentWindow<D> {
+    /// Opens a window with a new, untitled document.
+    pub fn open_untitled(cx: &mut App) -> Result<WindowHandle<Self>> {
+        Self::open(cx, |window, cx| {
+            let document = D::untitled(window, cx);
+            DocumentTab {
+                document: cx.new(|_| document),
+                path: None,
+            }
+        })
+    }
+
+    /// Opens a window with the document at `path`.
+    ///
+    /// The document is read first; if that fails, no window opens.
+    pub fn open_path(path: PathBuf, cx: &mut App) -> Result<WindowHandle<Self>> {
+        let document = D::open(&path, cx)?;
+        let tab = DocumentTab {
+            document: cx.new(|_| document),
+            path: Some(path),
+        };
+        Self::open(cx, move |_window, _cx| tab)
+    }
+
+    fn open(
+        cx: &mut App,
+        first_tab: impl FnOnce(&mut Window, &mut App) -> DocumentTab<D>,
+    ) -> Result<WindowHandle<Self>> {
+        let options = WindowOptions {
+            focus: true,
+            ..Default::default()
+        };
+        let handle = AppWindows::open_window(options, cx, |window, cx| {
+            let tab = first_tab(window, cx);
+            cx.new(|cx| {
+                let focus_handle = cx.focus_handle();
+                focus_handle.focus(window);
+                Self {
+                    tabs: vec![tab],
+                    active: 0,
+                    confirming: None,
+                    closing_window: false,
+                    error: None,
+                    focus_handle,
+                }
+            })
+        })?;
+
+        let entity = handle.entity(cx)?;
+        AppWindows::set_close_handler(handle.into(), cx, move |window, cx| {
+            entity.update(cx, |this, cx| this.request_close_window(window, cx))
+        });
+        Ok(handle)
+    }
+
+    /// Adds a new, untitled document as a tab and selects it.
+    pub fn add_untitled(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let document = D::untitled(window, cx);
+        self.push_tab(
+            DocumentTab {
+                document: cx.new(|_| document),
+                path: None,
+            },
+            cx,
+        );
+    }
+
+    /// Adds the document at `path` as a tab and selects it.
+    ///
+    /// If the document is already open in this window, its tab is selected instead.
+    pub fn add_path(&mut self, path: PathBuf, cx: &mut Context<Self>) -> Result<()> {
+        if let Some(index) = self
+            .tabs
+            .iter()
+            .position(|tab| tab.path.as_deref() == Some(path.as_path()))
+        {
+            self.select(index, cx);
+            return Ok(());
+        }
+
+        let document = D::open(&path, cx)?;
+        self.push_tab(
+            DocumentTab {
+                document: cx.new(|_| document),
+                path: Some(path),
+            },
+            cx,
+        );
+        Ok(())
+    }
+
+    /// Returns the active document.
+    pub fn active_document(&self) -> Option<&Entity<D>> {
+        self.tabs.get(self.active).map(|tab| &tab.document)
+    }
+
+    /// Returns the path of the active document, or `None` if it is untitled.
+    pub fn active_path(&self) -> Option<&Path> {
+        self.tabs.get(self.active)?.path.as_deref()
+    }
+
+    /// Returns the number of open documents.
+    pub fn tab_count(&self) -> usize {
+        self.tabs.len()
+    }
+
+    /// Selects the tab at `index`.
+    pub fn select(&mut self, index: usize, cx: &mut Context<Self>) {
+        if index < self.tabs.len() {
+            self.active = index;
+            cx.notify();
+        }
+    }
+
+    /// Saves the active document.
+    ///
+    /// Untitled documents ask for a location with the chooser set by
+    /// [`AppWindows::set_save_path_chooser`]. Resolves to `false` if the
+    /// user cancels the chooser.
+    pub fn save(&mut self, window: &mut Window, cx: &mut Context<Self>) -> Task<Result<bool>> {
+        self.save_tab(self.active, window, cx)
+    }
+
+    /// Saves the active document to a new location chosen by the user.
+    ///
+    /// Resolves to `false` if the user cancels the chooser.
+    pub fn save_as(&mut self, window: &mut Window, cx: &mut Context<Self>) -> Task<Result<bool>> {
+        let Some(tab) = self.tabs.get(self.active) else {
+            return Task::ready(Ok(false));
+        };
+        let document = tab.document.clone();
+        self.choose_path_and_save(document, tab.name(), window, cx)
+    }
+
+    /// Saves a document to `path` and makes it the document's location.
+    pub fn save_to(
+        &mut self,
+        index: usize,
+        path: PathBuf,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        let Some(tab) = self.tabs.get_mut(index) else {
+            return Ok(());
+        };
+        tab.document
+            .update(cx, |document, cx| document.save(&path, window, cx))?;
+        tab.path = Some(path);
+        cx.notify();
+        Ok(())
+    }
+
+    fn save_tab(
+        &mut self,
+        index: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<bool>> {
+        let Some(tab) = self.tabs.get(index) else {
+            return Task::ready(Ok(false));
+        };
+        match tab.path.clone() {
+            Some(path) => Task::ready(self.save_to(index, path, window, cx).map(|()| true)),
+            None => {
+                let document = tab.document.clone();
+                self.choose_path_and_save(document, tab.name(), window, cx)
+            }
+        }
+    }
+
+    fn choose_path_and_save(
+        &mut self,
+        document: Entity<D>,
+        name: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<bool>> {
+        let Some(choose) = AppWindows::choose_save_path(name, window, cx) else {
+            return Task::ready(Err(anyhow!("no save location chooser is set")));
+        };
+        cx.spawn_in(window, async move |this, cx| {
+            let Some(path) = choose.await else {
+                return Ok(false);
+            };
+            this.update_in(cx, |this, window, cx| {
+                // The tab may have moved while the chooser was open
+                let index = this
+                    .tabs
+                    .iter()
+                    .position(|tab| tab.document == document)
+                    .ok_or_else(|| anyhow!("document was closed"))?;
+                this.save_to(index, path, window, cx).map(|()| true)
+            })?
+        })
+    }
+
+    fn push_tab(&mut self, tab: DocumentTab<D>, cx: &mut Context<Self>) {
+        self.tabs.push(tab);
+        self.active = self.tabs.len() - 1;
+        cx.notify();
+    }
+
+    fn is_dirty(&self, index: usize, cx: &App) -> bool {
+        self.tabs
+            .get(index)
+            .is_some_and(|tab| tab.document.read(cx).is_dirty())
+    }
+
+    /// Closes the active tab, asking to save changes first.
+    fn close_active_tab(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.tabs.len() <= 1 {
+            if self.request_close_window(window, cx) {
+                window.remove_window();
+            }
+            return;
+        }
+        if self.is_dirty(self.active, cx) {
+            self.confirming = Some(self.active);
+            cx.notify();
+        } else {
+            self.remove_tab(self.active, cx);
+        }
+    }
+
+    /// Returns true if the window can close now, or starts asking to save
+    /// changes and returns false.
+    fn request_close_window(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        self.closing_window = true;
+        self.confirm_next(window, cx)
+    }
+
+    /// Asks about the next document with unsaved changes, closing the window
+    /// once none are left. Returns true if the window can close now.
+    fn confirm_next(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> bool {
+        match (0..self.tabs.len()).find(|index| self.is_dirty(*index, cx)) {
+            Some(index) => {
+                self.active = index;
+                self.confirming = Some(index);
+                cx.notify();
+                false
+            }
+            None => true,
+        }
+    }
+
+    fn remove_tab(&mut self, index: usize, cx: &mut Context<Self>) {
+        if index < self.tabs.len() {
+            self.tabs.remove(index);
+        }
+        if self.active >= self.tabs.len() {
+            self.active = self.tabs.len().saturating_sub(1);
+        }
+        cx.notify();
+    }
+
+    /// Continues closing after the confirmed tab was saved or discarded.
+    fn finish_confirmed(&mut self, discard: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(index) = self.confirming.take() else {
+            return;
+        };
+        if self.closing_window {
+            if discard {
+                // Closed documents are dropped along with the window
+                self.remove_tab(index, cx);
+            }
+            if self.confirm_next(window, cx) {
+                window.remove_window();
+            }
+        } else {
+            self.remove_tab(index, cx);
+        }
+    }
+
+    fn save_confirmed(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(index) = self.confirming else {
+            return;
+        };
+        let save = self.save_tab(index, window, cx);
+        cx.spawn_in(window, async move |this, cx| {
+            let result = save.await;
+            this.update_in(cx, |this, window, cx| match result {
+                Ok(true) => this.finish_confirmed(false, window, cx),
+                Ok(false) => this.cancel_confirmed(cx),
+                Err(err) => {
+                    this.error = Some(err.to_string().into());
+                    this.cancel_confirmed(cx);
+                }
+            })
+        })
+        .detach();
+    }
+
+    fn cancel_confirmed(&mut self, cx: &mut Context<Self>) {
+        self.confirming = None;
+        self.closing_window = false;
+        AppWindows::cancel_quit(cx);
+        cx.notify();
+    }
+
+    fn render_alert(&self, index: usize, cx: &mut Context<Self>) -> Alert {
+        let name = self.tabs[index].name();
+        let this = cx.entity().downgrade();
+        let on_save = this.clone();
+        let on_discard = this.clone();
+        let on_dismiss = this;
+
+        Alert::new(format!("Do you want to save the changes to \"{}\"?", name))
+            .id("document-save-alert")
+            .message("Your changes will be lost if you don't save them.")
+            .icon(AlertIcon::Warning)
+            .button(AlertButton::destructive(
+                "Don't Save",
+                move |_, window, cx| {
+                    let _ =
+                        on_discard.update(cx, |this, cx| this.finish_confirmed(true, window, cx));
+                },
+            ))
+            .button(AlertButton::cancel("Cancel").on_click({
+                let this = on_dismiss.clone();
+                move |_, _window, cx| {
+                    let _ = this.update(cx, |this, cx| this.cancel_confirmed(cx));
+                }
+            }))
+            .button(AlertButton::new("Save").on_click(move |_, window, cx| {
+                let _ = on_save.update(cx, |this, cx| this.save_confirmed(window, cx));
+            }))
+            .on_dismiss(move |_, _window, cx| {
+                let _ = on_dismiss.update(cx, |this, cx| this.cancel_confirmed(cx));
+            })
+    }
+
+    fn render_error(&self, error: SharedString, cx: &mut Context<Self>) -> Alert {
+        let this = cx.entity().downgrade();
+        let on_dismiss = this.clone();
+
+        Alert::new("The document could not be saved.")
+            .id("document-error-alert")
+            .message(error)
+            .icon(AlertIcon::Error)
+            .button(AlertButton::new("OK").on_click(move |_, _window, cx| {
+                let _ = this.update(cx, |this, cx| {
+                    this.error = None;
+                    cx.notify();
+                });
+            }))
+            .on_dismiss(move |_, _window, cx| {
+                let _ = on_dismiss.update(cx, |this, cx| {
+                    this.error = None;
+                    cx.notify();
+                });
+            })
+    }
+}
+
+impl<D: Document + 'static> Focusable for DocumentWindow<D> {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl<D: Document + 'static> Render for DocumentWindow<D> {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if let Some(tab) = self.tabs.get(self.active) {
+            let edited = if tab.document.read(cx).is_dirty() {
+                " — Edited"
+            } else {
+                ""
+            };
+            window.set_window_title(&format!("{}{}", tab.name(), edited));
+        }
+
+        let content = if self.tabs.len() > 1 {
+            let this = cx.entity().downgrade();
+            let mut tab_view = TabView::new("document-tabs", self.active).on_selection_change(
+                move |index, _window, cx| {
+                    let _ = this.update(cx, |this, cx| this.select(index, cx));
+                },
+            );
+            for tab in &self.tabs {
+                let mut label = tab.name();
+                if tab.document.read(cx).is_dirty() {
+                    label = format!("{} •", label).into();
+                }
+                tab_view = tab_view.tab(Tab::new(label, tab.document.clone()));
+            }
+            tab_view.into_any_element()
+        } else if let Some(tab) = self.tabs.first() {
+            tab.document.clone().into_any_element()
+        } else {
+            div().into_any_element()
+        };
+
+        let mut root = div()
+            .id("document-window")
+            .key_context("DocumentWindow")
+            .track_focus(&self.focus_handle)
+            .relative()
+            .size_full()
+            .flex()
+            .flex_col()
+            .on_action(cx.listener(|this, _: &NewTab, window, cx| this.add_untitled(window, cx)))
+            .on_action(cx.listener(|this, _: &Save, window, cx| {
+                let save = this.save(window, cx);
+                cx.spawn_in(window, async move |this, cx| {
+                    if let Err(err) = save.await {
+                        let _ = this.update(cx, |this, cx| {
+                            this.error = Some(err.to_string().into());
+                            cx.notify();
+                        });
+                    }
+                })
+                .detach();
+            }))
+            .on_action(
+                cx.listener(|this, _: &CloseWindow, window, cx| this.close_active_tab(window, cx)),
+            )
+            .child(content);
+
+        if let Some(index) = self.confirming.filter(|index| *index < self.tabs.len()) {
+            root = root.child(self.render_alert(index, cx));
+        } else if let Some(error) = self.error.clone() {
+            root = root.child(self.render_error(error, cx));
+        }
+
+        root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_name() {
+        assert_eq!(display_name(None), "Untitled");
+        assert_eq!(
+            display_name(Some(Path::new("/home/user/notes.txt"))),
+            "notes.txt"
+        );
+    }
+}
@@ -0,0 +1,270 @@
+//! Application lifecycle for multi-window apps.
+//!
+//! [`AppWindows`] tracks the application's windows, routes the
+//! [`NewWindow`], [`CloseWindow`] and [`Quit`] commands, and quits when the
+//! last window closes. [`DocumentWindow`] builds on it to show [`Document`]s
+//! in tabbed windows that ask to save unsaved changes before closing.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use applib::application::{AppWindows, DocumentWindow};
+//!
+//! Application::new().run(|cx| {
+//!     AppWindows::init(cx);
+//!     AppWindows::set_new_window_handler(cx, |cx| {
+//!         DocumentWindow::<TextDocument>::open_untitled(cx).ok();
+//!     });
+//!
+//!     DocumentWindow::<TextDocument>::open_untitled(cx).unwrap();
+//! });
+//! ```
+
+mod document;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use anyhow::Result;
+use gpui::{
+    actions, AnyWindowHandle, App, BorrowAppContext, Entity, Global, KeyBinding, Render,
+    SharedString, Subscription, Task, Window, WindowHandle, WindowId, WindowOptions,
+};
+
+pub use document::{Document, DocumentWindow};
+
+actions!(
+    applib,
+    [
+        /// Opens a new window.
+        NewWindow,
+        /// Opens a new tab in the active document window.
+        NewTab,
+        /// Closes the active tab, or the window if it has a single tab.
+        CloseWindow,
+        /// Saves the active document.
+        Save,
+        /// Closes all windows and quits.
+        Quit,
+    ]
+);
+
+type NewWindowHandler = Rc<dyn Fn(&mut App) + 'static>;
+type CloseHandler = Rc<dyn Fn(&mut Window, &mut App) -> bool + 'static>;
+type SavePathChooser =
+    Rc<dyn Fn(SharedString, &mut Window, &mut App) -> Task<Option<PathBuf>> + 'static>;
+
+/// The application's open windows, stored in the `AppWindows` global.
+///
+/// Call [`AppWindows::init`] once at startup to bind the default shortcuts
+/// (Ctrl+N, Ctrl+T, Ctrl+W, Ctrl+S and Ctrl+Q) and start tracking windows.
+///
+/// # Example
+///
+/// ```ignore
+/// AppWindows::init(cx);
+/// AppWindows::set_quit_when_last_window_closes(false, cx);
+/// AppWindows::set_new_window_handler(cx, |cx| {
+///     AppWindows::open_window(WindowOptions::default(), cx, |_window, cx| {
+///         cx.new(|cx| BrowserView::new(cx))
+///     })
+///     .ok();
+/// });
+/// ```
+pub struct AppWindows {
+    windows: Vec<AnyWindowHandle>,
+    close_handlers: HashMap<WindowId, CloseHandler>,
+    new_window: Option<NewWindowHandler>,
+    choose_save_path: Option<SavePathChooser>,
+    quit_when_last_window_closes: bool,
+    quitting: bool,
+    _window_closed: Option<Subscription>,
+}
+
+impl Default for AppWindows {
+    fn default() -> Self {
+        Self {
+            windows: Vec::new(),
+            close_handlers: HashMap::new(),
+            new_window: None,
+            choose_save_path: None,
+            quit_when_last_window_closes: true,
+            quitting: false,
+            _window_closed: None,
+        }
+    }
+}
+
+impl Global for AppWindows {}
+
+impl AppWindows {
+    /// Binds the default shortcuts and registers the application-wide command handlers.
+    pub fn init(cx: &mut App) {
+        cx.bind_keys([
+            KeyBinding::new("ctrl-n", NewWindow, None),
+            KeyBinding::new("ctrl-t", NewTab, None),
+            KeyBinding::new("ctrl-w", CloseWindow, None),
+            KeyBinding::new("ctrl-s", Save, None),
+            KeyBinding::new("ctrl-q", Quit, None),
+        ]);
+
+        cx.on_action(|_: &NewWindow, cx| Self::new_window(cx));
+        // Windows without tabs treat a new tab as a new window
+        cx.on_action(|_: &NewTab, cx| Self::new_window(cx));
+        cx.on_action(|_: &CloseWindow, cx| {
+            if let Some(window) = cx.active_window() {
+                Self::close_window(window, cx);
+            }
+        });
+        cx.on_action(|_: &Quit, cx| Self::quit(cx));
+
+        let subscription = cx.on_window_closed(Self::window_closed);
+        cx.update_default_global(|windows: &mut Self, _cx| {
+            windows._window_closed = Some(subscription);
+        });
+    }
+
+    /// Sets the function that opens a window for the [`NewWindow`] command.
+    pub fn set_new_window_handler(cx: &mut App, handler: impl Fn(&mut App) + 'static) {
+        cx.update_default_global(|windows: &mut Self, _cx| {
+            windows.new_window = Some(Rc::new(handler));
+        });
+    }
+
+    /// Sets whether the application quits when its last window closes (default: true).
+    pub fn set_quit_when_last_window_closes(quit: bool, cx: &mut App) {
+        cx.update_default_global(|windows: &mut Self, _cx| {
+            windows.quit_when_last_window_closes = quit;
+        });
+    }
+
+    /// Sets the function that asks where to save an untitled document.
+    ///
+    /// The function receives the document's display name and resolves to
+    /// `None` if the user cancels. Without one, untitled documents can't be saved.
+    pub fn set_save_path_chooser(
+        cx: &mut App,
+        chooser: impl Fn(SharedString, &mut Window, &mut App) -> Task<Option<PathBuf>> + 'static,
+    ) {
+        cx.update_default_global(|windows: &mut Self, _cx| {
+            windows.choose_save_path = Some(Rc::new(chooser));
+        });
+    }
+
+    /// Opens a window and tracks it.
+    pub fn open_window<V: Render + 'static>(
+        options: WindowOptions,
+        cx: &mut App,
+        build_root_view: impl FnOnce(&mut Window, &mut App) -> Entity<V>,
+    ) -> Result<WindowHandle<V>> {
+        let handle = cx.open_window(options, |window, cx| {
+            let handle = window.window_handle();
+            window.on_window_should_close(cx, move |window, cx| {
+                Self::should_close(handle, window, cx)
+            });
+            build_root_view(window, cx)
+        })?;
+        cx.update_default_global(|windows: &mut Self, _cx| windows.windows.push(handle.into()));
+        Ok(handle)
+    }
+
+    /// Returns the tracked windows in the order they were opened.
+    pub fn windows(cx: &App) -> Vec<AnyWindowHandle> {
+        cx.try_global::<Self>()
+            .map(|windows| windows.windows.clone())
+            .unwrap_or_default()
+    }
+
+    /// Sets the function asked before a window closes.
+    ///
+    /// The handler returns `true` if the window can close now. It returns
+    /// `false` to keep the window open, e.g. while asking to save changes, and
+    /// then closes the window itself with [`Window::remove_window`] or calls
+    /// [`AppWindows::cancel_quit`].
+    pub fn set_close_handler(
+        window: AnyWindowHandle,
+        cx: &mut App,
+        handler: impl Fn(&mut Window, &mut App) -> bool + 'static,
+    ) {
+        cx.update_default_global(|windows: &mut Self, _cx| {
+            windows
+                .close_handlers
+                .insert(window.window_id(), Rc::new(handler));
+        });
+    }
+
+    /// Opens a new window with the handler set by [`AppWindows::set_new_window_handler`].
+    pub fn new_window(cx: &mut App) {
+        let handler = cx
+            .try_global::<Self>()
+            .and_then(|windows| windows.new_window.clone());
+        if let Some(handler) = handler {
+            handler(cx);
+        }
+    }
+
+    /// Asks a window to close, running its close handler first.
+    pub fn close_window(window: AnyWindowHandle, cx: &mut App) {
+        let _ = window.update(cx, |_, window, cx| {
+            if Self::should_close(window.window_handle(), window, cx) {
+                window.remove_window();
+            }
+        });
+    }
+
+    /// Closes all windows, then quits.
+    ///
+    /// Quitting stops if a window keeps itself open and calls [`AppWindows::cancel_quit`].
+    pub fn quit(cx: &mut App) {
+        cx.update_default_global(|windows: &mut Self, _cx| windows.quitting = true);
+        Self::close_next_for_quit(cx);
+    }
+
+    /// Stops a quit in progress, e.g. when the user cancels a "Save changes?" alert.
+    pub fn cancel_quit(cx: &mut App) {
+        cx.update_default_global(|windows: &mut Self, _cx| windows.quitting = false);
+    }
+
+    pub(crate) fn choose_save_path(
+        name: SharedString,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<Task<Option<PathBuf>>> {
+        let chooser = cx.try_global::<Self>()?.choose_save_path.clone()?;
+        Some(chooser(name, window, cx))
+    }
+
+    fn should_close(handle: AnyWindowHandle, window: &mut Window, cx: &mut App) -> bool {
+        let handler = cx
+            .try_global::<Self>()
+            .and_then(|windows| windows.close_handlers.get(&handle.window_id()).cloned());
+        handler.is_none_or(|handler| handler(window, cx))
+    }
+
+    fn close_next_for_quit(cx: &mut App) {
+        let Some(window) = cx.windows().into_iter().last() else {
+            cx.quit();
+            return;
+        };
+        Self::close_window(window, cx);
+    }
+
+    fn window_closed(cx: &mut App) {
+        let open = cx.windows();
+        let (quitting, quit_when_empty) = cx.update_default_global(|windows: &mut Self, _cx| {
+            windows.windows.retain(|window| open.contains(window));
+            windows
+                .close_handlers
+                .retain(|id, _| open.iter().any(|window| window.window_id() == *id));
+            (windows.quitting, windows.quit_when_last_window_closes)
+        });
+
+        if quitting {
+            // Continue with the next window once this close has finished
+            cx.defer(Self::close_next_for_quit);
+        } else if open.is_empty() && quit_when_empty {
+            cx.quit();
+        }
+    }
+}
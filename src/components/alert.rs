@@ -2,15 +2,22 @@
 //!
 //! A modal dialog for displaying important messages with action buttons.
 
+use std::rc::Rc;
+
 use gpui::prelude::*;
 use gpui::*;
 
+type ClickHandler = Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>;
+type DismissHandler = Rc<dyn Fn(&mut Window, &mut App) + 'static>;
+
 /// Icon types for alert dialogs.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum AlertIcon {
     /// Informational alert
     #[default]
     Info,
+    /// Success confirmation
+    Success,
     /// Warning alert
     Warning,
     /// Error/critical alert
@@ -19,25 +26,30 @@ pub enum AlertIcon {
 
 impl AlertIcon {
     /// Returns the Unicode character for this icon.
-    fn as_str(&self) -> &'static str {
+    pub(crate) fn as_str(&self) -> &'static str {
         match self {
             AlertIcon::Info => "ℹ",
+            AlertIcon::Success => "✓",
             AlertIcon::Warning => "⚠",
             AlertIcon::Error => "⊘",
         }
     }
 
     /// Returns the color for this icon type.
-    fn color(&self) -> Hsla {
+    pub(crate) fn color(&self) -> Hsla {
         match self {
             AlertIcon::Info => hsla(211.0 / 360.0, 0.95, 0.53, 1.0), // Blue
+            AlertIcon::Success => hsla(135.0 / 360.0, 0.60, 0.45, 1.0), // Green
             AlertIcon::Warning => hsla(45.0 / 360.0, 0.95, 0.50, 1.0), // Yellow/Orange
-            AlertIcon::Error => hsla(0.0, 0.85, 0.55, 1.0),           // Red
+            AlertIcon::Error => hsla(0.0, 0.85, 0.55, 1.0),          // Red
         }
     }
 }
 
 /// Role/style for alert buttons.
+///
+/// Alerts presented with [`ModalPresenter::present_alert`](super::ModalPresenter::present_alert)
+/// activate the first `Default` button on Enter and the first `Cancel` button on Escape.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum AlertButtonRole {
     /// Standard button
@@ -53,7 +65,7 @@ pub enum AlertButtonRole {
 pub struct AlertButton {
     label: SharedString,
     role: AlertButtonRole,
-    on_click: Option<Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>>,
+    on_click: Option<ClickHandler>,
 }
 
 impl AlertButton {
@@ -195,7 +207,7 @@ pub struct Alert {
     message: Option<SharedString>,
     icon: Option<AlertIcon>,
     buttons: Vec<AlertButton>,
-    on_dismiss: Option<Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>>,
+    on_dismiss: Option<ClickHandler>,
 }
 
 impl Alert {
@@ -248,12 +260,48 @@ impl Alert {
     }
 }
 
-impl IntoElement for Alert {
-    type Element = Stateful<Div>;
+impl Alert {
+    /// Takes the dismiss handler, leaving the alert without one.
+    pub(crate) fn take_dismiss_handler(
+        &mut self,
+    ) -> Option<ClickHandler> {
+        self.on_dismiss.take()
+    }
 
-    fn into_element(self) -> Self::Element {
+    /// Returns whether the alert has a button with the given role.
+    pub(crate) fn has_button(&self, role: AlertButtonRole) -> bool {
+        self.buttons.iter().any(|button| button.role == role)
+    }
+
+    /// Takes the click handler of the first button with the given role.
+    pub(crate) fn take_button_handler(
+        &mut self,
+        role: AlertButtonRole,
+    ) -> Option<ClickHandler> {
+        self.buttons
+            .iter_mut()
+            .find(|button| button.role == role)
+            .and_then(|button| button.on_click.take())
+    }
+
+    /// Makes every button call `dismiss` after its own click handler.
+    pub(crate) fn dismiss_on_click(mut self, dismiss: DismissHandler) -> Self {
+        for button in &mut self.buttons {
+            let handler = button.on_click.take();
+            let dismiss = dismiss.clone();
+            button.on_click = Some(Box::new(move |event, window, cx| {
+                if let Some(handler) = &handler {
+                    handler(event, window, cx);
+                }
+                dismiss(window, cx);
+            }));
+        }
+        self
+    }
+
+    /// Builds the dialog panel without the backdrop.
+    pub(crate) fn into_panel(self) -> Div {
         // Colors
-        let backdrop_color = hsla(0.0, 0.0, 0.0, 0.4);
         let panel_bg = hsla(0.0, 0.0, 0.97, 1.0);
         let title_color = hsla(0.0, 0.0, 0.15, 1.0);
         let message_color = hsla(0.0, 0.0, 0.40, 1.0);
@@ -370,9 +418,22 @@ impl IntoElement for Alert {
             panel = panel.child(buttons);
         }
 
+        panel
+    }
+}
+
+impl IntoElement for Alert {
+    type Element = Stateful<Div>;
+
+    fn into_element(mut self) -> Self::Element {
+        let backdrop_color = hsla(0.0, 0.0, 0.0, 0.4);
+        let id = self.id.clone();
+        let on_dismiss = self.take_dismiss_handler();
+        let panel = self.into_panel();
+
         // Build the backdrop with click-to-dismiss
         let mut backdrop = div()
-            .id(self.id)
+            .id(id)
             .absolute()
             .inset_0()
             .flex()
@@ -381,7 +442,7 @@ impl IntoElement for Alert {
             .bg(backdrop_color);
 
         // Add dismiss handler to backdrop
-        if let Some(handler) = self.on_dismiss {
+        if let Some(handler) = on_dismiss {
             backdrop = backdrop.on_click(move |event, window, cx| {
                 handler(event, window, cx);
             });
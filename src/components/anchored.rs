@@ -0,0 +1,579 @@
+//! Anchored positioning for floating panels.
+//!
+//! [`AnchoredOverlay`] places a popover, menu or dropdown panel next to its
+//! anchor element and keeps it inside the window. The panel is measured and
+//! positioned during every prepaint, so it follows the anchor when the window
+//! is resized or the anchor scrolls.
+//!
+//! Positioning works in three steps:
+//! 1. Try the preferred [`Placement`], then its opposite side (flip), then any
+//!    fallbacks, and use the first one with enough room.
+//! 2. Shift the panel along both axes so it stays within the window margins.
+//! 3. Realign the optional arrow so it keeps pointing at the anchor.
+
+use gpui::prelude::*;
+use gpui::*;
+
+/// Default distance kept between a panel and the window edges.
+const DEFAULT_WINDOW_MARGIN: f32 = 4.0;
+
+/// Minimum distance between the arrow center and the panel corners.
+const ARROW_INSET: f32 = 12.0;
+
+/// The side of the anchor a panel is attached to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Side {
+    /// Above the anchor.
+    Top,
+    /// Below the anchor (default).
+    #[default]
+    Bottom,
+    /// Left of the anchor.
+    Left,
+    /// Right of the anchor.
+    Right,
+}
+
+impl Side {
+    /// Returns the side across the anchor from this one.
+    pub fn opposite(self) -> Self {
+        match self {
+            Side::Top => Side::Bottom,
+            Side::Bottom => Side::Top,
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+
+    fn is_vertical(self) -> bool {
+        matches!(self, Side::Top | Side::Bottom)
+    }
+}
+
+/// How a panel lines up with the anchor along the attached side.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Align {
+    /// Aligns the leading (left or top) edges (default).
+    #[default]
+    Start,
+    /// Centers the panel on the anchor.
+    Center,
+    /// Aligns the trailing (right or bottom) edges.
+    End,
+}
+
+/// Where a panel is placed relative to its anchor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Placement {
+    /// The side of the anchor the panel is attached to.
+    pub side: Side,
+    /// The alignment along that side.
+    pub align: Align,
+}
+
+impl Placement {
+    /// Creates a placement on the given side with the given alignment.
+    pub const fn new(side: Side, align: Align) -> Self {
+        Self { side, align }
+    }
+
+    /// Returns the same placement on the opposite side.
+    pub fn flipped(self) -> Self {
+        Self::new(self.side.opposite(), self.align)
+    }
+}
+
+/// Positioning preferences for an [`AnchoredOverlay`].
+#[derive(Debug, Clone, PartialEq)]
+struct PositionOptions {
+    placement: Placement,
+    fallbacks: Vec<Placement>,
+    offset: Pixels,
+    window_margin: Pixels,
+    flip: bool,
+    shift: bool,
+    match_anchor_width: bool,
+}
+
+impl Default for PositionOptions {
+    fn default() -> Self {
+        Self {
+            placement: Placement::default(),
+            fallbacks: Vec::new(),
+            offset: px(0.0),
+            window_margin: px(DEFAULT_WINDOW_MARGIN),
+            flip: true,
+            shift: true,
+            match_anchor_width: false,
+        }
+    }
+}
+
+/// The outcome of positioning a panel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ResolvedPosition {
+    /// Window-space origin of the panel.
+    origin: Point<Pixels>,
+    /// The placement actually used after flipping or falling back.
+    placement: Placement,
+    /// Distance of the arrow center from the panel's leading edge.
+    arrow_offset: Pixels,
+}
+
+/// Positions a panel of `size` next to `anchor` within a window of `viewport`.
+fn resolve_position(
+    anchor: Bounds<Pixels>,
+    size: Size<Pixels>,
+    viewport: Size<Pixels>,
+    options: &PositionOptions,
+) -> ResolvedPosition {
+    let margin = options.window_margin;
+    let limits = Bounds::new(
+        point(margin, margin),
+        Size::new(
+            viewport.width - margin * 2.0,
+            viewport.height - margin * 2.0,
+        ),
+    );
+
+    let origin_for = |placement: Placement| {
+        let (main, cross) = if placement.side.is_vertical() {
+            (anchor.origin.y, anchor.origin.x)
+        } else {
+            (anchor.origin.x, anchor.origin.y)
+        };
+        let (anchor_main, anchor_cross, panel_main, panel_cross) = if placement.side.is_vertical() {
+            (
+                anchor.size.height,
+                anchor.size.width,
+                size.height,
+                size.width,
+            )
+        } else {
+            (
+                anchor.size.width,
+                anchor.size.height,
+                size.width,
+                size.height,
+            )
+        };
+        let main = match placement.side {
+            Side::Top | Side::Left => main - options.offset - panel_main,
+            Side::Bottom | Side::Right => main + anchor_main + options.offset,
+        };
+        let cross = match placement.align {
+            Align::Start => cross,
+            Align::Center => cross + (anchor_cross - panel_cross) / 2.0,
+            Align::End => cross + anchor_cross - panel_cross,
+        };
+        if placement.side.is_vertical() {
+            point(cross, main)
+        } else {
+            point(main, cross)
+        }
+    };
+
+    // How far a placement sticks out of the window; zero means it fits
+    let overflow = |placement: Placement| {
+        let bounds = Bounds::new(origin_for(placement), size);
+        let vertical = (limits.top() - bounds.top()).max(px(0.0))
+            + (bounds.bottom() - limits.bottom()).max(px(0.0));
+        let horizontal = (limits.left() - bounds.left()).max(px(0.0))
+            + (bounds.right() - limits.right()).max(px(0.0));
+        match (placement.side.is_vertical(), options.shift) {
+            // Shifting fixes overflow along the attached side
+            (true, true) => vertical,
+            (false, true) => horizontal,
+            _ => vertical + horizontal,
+        }
+    };
+
+    let mut candidates = vec![options.placement];
+    if options.flip {
+        candidates.push(options.placement.flipped());
+    }
+    for fallback in &options.fallbacks {
+        candidates.push(*fallback);
+        if options.flip {
+            candidates.push(fallback.flipped());
+        }
+    }
+
+    let placement = candidates
+        .iter()
+        .copied()
+        .find(|placement| overflow(*placement) == px(0.0))
+        .unwrap_or_else(|| {
+            // Nothing fits: use the placement that overflows the least
+            candidates
+                .iter()
+                .copied()
+                .fold(options.placement, |best, placement| {
+                    if overflow(placement) < overflow(best) {
+                        placement
+                    } else {
+                        best
+                    }
+                })
+        });
+
+    let mut origin = origin_for(placement);
+    if options.shift {
+        // Favor the top-left edge when the panel is larger than the window
+        origin.x = origin.x.min(limits.right() - size.width).max(limits.left());
+        origin.y = origin
+            .y
+            .min(limits.bottom() - size.height)
+            .max(limits.top());
+    }
+
+    let center = anchor.center();
+    let (arrow_offset, length) = if placement.side.is_vertical() {
+        (center.x - origin.x, size.width)
+    } else {
+        (center.y - origin.y, size.height)
+    };
+    let inset = px(ARROW_INSET).min(length / 2.0);
+    let arrow_offset = arrow_offset.max(inset).min(length - inset);
+
+    ResolvedPosition {
+        origin,
+        placement,
+        arrow_offset,
+    }
+}
+
+/// Builder for an arrow pointing from a panel to its anchor.
+type ArrowBuilder = Box<dyn Fn(Side) -> AnyElement>;
+
+/// Positions a floating panel next to the element that contains it.
+///
+/// Add the overlay as a direct child of the anchor's container; it covers
+/// the container and places its child panel outside of it. Combine it with
+/// `deferred` to draw the panel above the rest of the window.
+///
+/// # Example
+///
+/// ```ignore
+/// div()
+///     .relative()
+///     .child(trigger)
+///     .child(deferred(
+///         AnchoredOverlay::new(panel)
+///             .placement(Placement::new(Side::Bottom, Align::Start))
+///             .offset(px(4.0))
+///             .match_anchor_width(true),
+///     ))
+/// ```
+pub struct AnchoredOverlay {
+    child: AnyElement,
+    options: PositionOptions,
+    anchor_point: Option<Point<Pixels>>,
+    arrow: Option<ArrowBuilder>,
+}
+
+impl AnchoredOverlay {
+    /// Creates an overlay that positions the given panel.
+    pub fn new(child: impl IntoElement) -> Self {
+        Self {
+            child: child.into_any_element(),
+            options: PositionOptions::default(),
+            anchor_point: None,
+            arrow: None,
+        }
+    }
+
+    /// Sets the preferred placement (default: below, aligned to the start).
+    pub fn placement(mut self, placement: Placement) -> Self {
+        self.options.placement = placement;
+        self
+    }
+
+    /// Adds a placement to try when the preferred one (and its flip) does
+    /// not fit. Fallbacks are tried in the order they were added.
+    pub fn fallback(mut self, placement: Placement) -> Self {
+        self.options.fallbacks.push(placement);
+        self
+    }
+
+    /// Sets the gap between the anchor and the panel.
+    pub fn offset(mut self, offset: Pixels) -> Self {
+        self.options.offset = offset;
+        self
+    }
+
+    /// Sets the minimum distance kept from the window edges (default: 4px).
+    pub fn window_margin(mut self, margin: Pixels) -> Self {
+        self.options.window_margin = margin;
+        self
+    }
+
+    /// Sets whether the panel may flip to the opposite side (default: true).
+    pub fn flip(mut self, flip: bool) -> Self {
+        self.options.flip = flip;
+        self
+    }
+
+    /// Sets whether the panel shifts to stay inside the window (default: true).
+    pub fn shift(mut self, shift: bool) -> Self {
+        self.options.shift = shift;
+        self
+    }
+
+    /// Makes the panel at least as wide as the anchor, as for dropdowns.
+    pub fn match_anchor_width(mut self, match_width: bool) -> Self {
+        self.options.match_anchor_width = match_width;
+        self
+    }
+
+    /// Anchors the panel to a window-space point instead of the container,
+    /// such as the cursor position for a context menu.
+    pub fn anchor_point(mut self, point: Point<Pixels>) -> Self {
+        self.anchor_point = Some(point);
+        self
+    }
+
+    /// Draws an arrow on the panel edge facing the anchor.
+    ///
+    /// The builder receives the side the panel ended up on and returns an
+    /// unpositioned element; the overlay centers it on the panel edge and
+    /// slides it along that edge to keep pointing at the anchor.
+    pub fn arrow<E: IntoElement>(mut self, build: impl Fn(Side) -> E + 'static) -> Self {
+        self.arrow = Some(Box::new(move |side| build(side).into_any_element()));
+        self
+    }
+}
+
+impl IntoElement for AnchoredOverlay {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for AnchoredOverlay {
+    type RequestLayoutState = LayoutId;
+    type PrepaintState = Option<AnyElement>;
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn source_location(&self) -> Option<&'static core::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let child = self.child.request_layout(window, cx);
+
+        // The panel is laid out at its natural size, optionally widened to
+        // the anchor, inside a node that covers the anchor.
+        let mut panel_style = Style {
+            position: Position::Absolute,
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            ..Style::default()
+        };
+        if self.options.match_anchor_width {
+            panel_style.min_size.width = relative(1.0).into();
+        }
+        let panel = window.request_layout(panel_style, [child], cx);
+
+        let mut style = Style {
+            position: Position::Absolute,
+            ..Style::default()
+        };
+        style.inset.top = px(0.0).into();
+        style.inset.left = px(0.0).into();
+        style.size.width = relative(1.0).into();
+        style.size.height = relative(1.0).into();
+        let layout_id = window.request_layout(style, [panel], cx);
+
+        (layout_id, panel)
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        panel: &mut Self::RequestLayoutState,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self::PrepaintState {
+        let panel_bounds = window.layout_bounds(*panel);
+        let anchor = match self.anchor_point {
+            Some(point) => Bounds::new(point, Size::default()),
+            None => bounds,
+        };
+        let position = resolve_position(
+            anchor,
+            panel_bounds.size,
+            window.viewport_size(),
+            &self.options,
+        );
+
+        let offset = position.origin - panel_bounds.origin;
+        let offset = point(offset.x.round(), offset.y.round());
+        window.with_element_offset(offset, |window| {
+            self.child.prepaint(window, cx);
+        });
+
+        let build_arrow = self.arrow.as_ref()?;
+        let side = position.placement.side;
+        let mut arrow = build_arrow(side);
+        let arrow_size = arrow.layout_as_root(AvailableSpace::min_size(), window, cx);
+
+        // Center the arrow on the panel edge that faces the anchor
+        let origin = position.origin;
+        let panel_size = panel_bounds.size;
+        let arrow_origin = match side {
+            Side::Top => point(
+                origin.x + position.arrow_offset - arrow_size.width / 2.0,
+                origin.y + panel_size.height - arrow_size.height / 2.0,
+            ),
+            Side::Bottom => point(
+                origin.x + position.arrow_offset - arrow_size.width / 2.0,
+                origin.y - arrow_size.height / 2.0,
+            ),
+            Side::Left => point(
+                origin.x + panel_size.width - arrow_size.width / 2.0,
+                origin.y + position.arrow_offset - arrow_size.height / 2.0,
+            ),
+            Side::Right => point(
+                origin.x - arrow_size.width / 2.0,
+                origin.y + position.arrow_offset - arrow_size.height / 2.0,
+            ),
+        };
+        arrow.prepaint_at(
+            point(arrow_origin.x.round(), arrow_origin.y.round()),
+            window,
+            cx,
+        );
+        Some(arrow)
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        _bounds: Bounds<Pixels>,
+        _panel: &mut Self::RequestLayoutState,
+        arrow: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        self.child.paint(window, cx);
+        if let Some(arrow) = arrow {
+            arrow.paint(window, cx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anchor(x: f32, y: f32, width: f32, height: f32) -> Bounds<Pixels> {
+        Bounds::new(point(px(x), px(y)), size(px(width), px(height)))
+    }
+
+    #[test]
+    fn test_preferred_placement() {
+        let viewport = size(px(400.0), px(300.0));
+        let panel = size(px(60.0), px(20.0));
+        let options = PositionOptions {
+            placement: Placement::new(Side::Top, Align::Center),
+            offset: px(4.0),
+            ..PositionOptions::default()
+        };
+
+        let position =
+            resolve_position(anchor(170.0, 100.0, 60.0, 24.0), panel, viewport, &options);
+        assert_eq!(position.origin, point(px(170.0), px(76.0)));
+        assert_eq!(position.placement.side, Side::Top);
+        assert_eq!(position.arrow_offset, px(30.0));
+
+        let options = PositionOptions {
+            placement: Placement::new(Side::Bottom, Align::End),
+            ..options
+        };
+        let position =
+            resolve_position(anchor(170.0, 100.0, 100.0, 24.0), panel, viewport, &options);
+        assert_eq!(position.origin, point(px(210.0), px(128.0)));
+    }
+
+    #[test]
+    fn test_flip_and_fallback() {
+        let viewport = size(px(400.0), px(300.0));
+        let panel = size(px(60.0), px(20.0));
+        let options = PositionOptions {
+            placement: Placement::new(Side::Top, Align::Center),
+            offset: px(4.0),
+            ..PositionOptions::default()
+        };
+
+        // No room above: flips below
+        let position = resolve_position(anchor(170.0, 0.0, 60.0, 24.0), panel, viewport, &options);
+        assert_eq!(position.placement.side, Side::Bottom);
+        assert_eq!(position.origin, point(px(170.0), px(28.0)));
+
+        // Without flipping, the panel is shifted back inside instead
+        let no_flip = PositionOptions {
+            flip: false,
+            ..options.clone()
+        };
+        let position = resolve_position(anchor(170.0, 0.0, 60.0, 24.0), panel, viewport, &no_flip);
+        assert_eq!(position.placement.side, Side::Top);
+        assert_eq!(position.origin.y, px(4.0));
+
+        // A tall panel fits neither above nor below: uses the fallback
+        let tall = size(px(60.0), px(200.0));
+        let with_fallback = PositionOptions {
+            fallbacks: vec![Placement::new(Side::Right, Align::Center)],
+            ..options
+        };
+        let position = resolve_position(
+            anchor(20.0, 120.0, 60.0, 24.0),
+            tall,
+            viewport,
+            &with_fallback,
+        );
+        assert_eq!(position.placement.side, Side::Right);
+        assert_eq!(position.origin.x, px(84.0));
+    }
+
+    #[test]
+    fn test_shift_and_arrow() {
+        let viewport = size(px(400.0), px(300.0));
+        let panel = size(px(60.0), px(20.0));
+        let options = PositionOptions {
+            placement: Placement::new(Side::Bottom, Align::Center),
+            offset: px(4.0),
+            ..PositionOptions::default()
+        };
+
+        // Anchor at the right edge: shifts left, arrow keeps pointing at the anchor
+        let position =
+            resolve_position(anchor(380.0, 100.0, 20.0, 24.0), panel, viewport, &options);
+        assert_eq!(position.origin, point(px(336.0), px(128.0)));
+        assert_eq!(position.arrow_offset, px(48.0));
+
+        // Submenu at the right edge opens to the left
+        let submenu = PositionOptions {
+            placement: Placement::new(Side::Right, Align::Start),
+            ..options
+        };
+        let position =
+            resolve_position(anchor(340.0, 100.0, 50.0, 24.0), panel, viewport, &submenu);
+        assert_eq!(position.placement.side, Side::Left);
+        assert_eq!(position.origin, point(px(276.0), px(100.0)));
+    }
+}
@@ -4,6 +4,9 @@
 
 use gpui::prelude::*;
 use gpui::*;
+use std::rc::Rc;
+
+type RemoveHandler = Rc<dyn Fn(&mut Window, &mut App) + 'static>;
 
 /// A badge component for displaying counts or short labels.
 ///
@@ -13,9 +16,16 @@ use gpui::*;
 ///
 /// ```ignore
 /// Badge::new("3")
+///
+/// // A removable chip, as used by TokenField
+/// Badge::new("rust")
+///     .selected(true)
+///     .removable(|_window, _cx| println!("Removed"))
 /// ```
 pub struct Badge {
     label: SharedString,
+    selected: bool,
+    on_remove: Option<RemoveHandler>,
 }
 
 impl Badge {
@@ -23,29 +33,67 @@ impl Badge {
     pub fn new(label: impl Into<SharedString>) -> Self {
         Self {
             label: label.into(),
+            selected: false,
+            on_remove: None,
         }
     }
+
+    /// Sets whether the badge is drawn with the accent color.
+    pub fn selected(mut self, selected: bool) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// Shows a remove button that calls `handler` when pressed.
+    pub fn removable(mut self, handler: impl Fn(&mut Window, &mut App) + 'static) -> Self {
+        self.on_remove = Some(Rc::new(handler));
+        self
+    }
 }
 
 impl IntoElement for Badge {
     type Element = Div;
 
     fn into_element(self) -> Self::Element {
-        div()
+        let bg_color = if self.selected {
+            hsla(211.0 / 360.0, 0.95, 0.53, 1.0) // Accent badge
+        } else {
+            hsla(0.0, 0.0, 0.55, 1.0) // Gray badge
+        };
+
+        let mut badge = div()
             .flex()
             .items_center()
             .justify_center()
+            .gap(px(4.0))
             .px(px(6.0))
             .h(px(16.0))
             .min_w(px(20.0))
             .rounded(px(8.0))
-            .bg(hsla(0.0, 0.0, 0.55, 1.0)) // Gray badge
+            .bg(bg_color)
             .child(
                 div()
                     .text_xs()
                     .font_weight(FontWeight::MEDIUM)
                     .text_color(hsla(0.0, 0.0, 1.0, 1.0)) // White text
                     .child(self.label),
-            )
+            );
+
+        if let Some(handler) = self.on_remove {
+            badge = badge.child(
+                div()
+                    .text_xs()
+                    .text_color(hsla(0.0, 0.0, 1.0, 0.75))
+                    .cursor_pointer()
+                    .hover(|style| style.text_color(hsla(0.0, 0.0, 1.0, 1.0)))
+                    .on_mouse_down(MouseButton::Left, move |_event, window, cx| {
+                        cx.stop_propagation();
+                        handler(window, cx);
+                    })
+                    .child("✕"),
+            );
+        }
+
+        badge
     }
 }
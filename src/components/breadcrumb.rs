@@ -0,0 +1,702 @@
+//! Breadcrumb component for GPUI.
+//!
+//! A trail of clickable segments, such as the folders of a path or the pages
+//! of a settings hierarchy. Middle segments collapse into an overflow menu
+//! when the trail doesn't fit, each segment can offer a dropdown of its
+//! siblings, and clicking the empty part of the bar switches to a text field
+//! for typing a location.
+
+use gpui::prelude::*;
+use gpui::*;
+use std::cell::Cell;
+use std::ops::Range;
+use std::path::Path;
+use std::rc::Rc;
+
+use super::label::Icon;
+use super::menu::{Menu, MenuItem};
+use super::text_field::{TextField, TextFieldState};
+
+type NavigateHandler = Rc<dyn Fn(&SharedString, &mut Window, &mut App) + 'static>;
+type SiblingProvider = Rc<dyn Fn(usize, &mut App) -> Task<Vec<BreadcrumbItem>> + 'static>;
+type DismissHandler = Rc<dyn Fn(&mut Window, &mut App) + 'static>;
+
+/// Font size of the segment labels.
+const TEXT_SIZE: f32 = 13.0;
+/// Horizontal padding inside a segment.
+const SEGMENT_PADDING: f32 = 4.0;
+/// Width of a segment's sibling dropdown button.
+const DROPDOWN_WIDTH: f32 = 14.0;
+/// Gap between segments and separators.
+const GAP: f32 = 4.0;
+
+/// A segment of a breadcrumb trail.
+///
+/// The target identifies the location the segment stands for, such as a
+/// path, and is passed to the navigate handler when the segment is clicked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreadcrumbItem {
+    label: SharedString,
+    target: SharedString,
+    icon: Option<Icon>,
+}
+
+impl BreadcrumbItem {
+    /// Creates a segment with a label and the target it navigates to.
+    pub fn new(label: impl Into<SharedString>, target: impl Into<SharedString>) -> Self {
+        Self {
+            label: label.into(),
+            target: target.into(),
+            icon: None,
+        }
+    }
+
+    /// Sets an icon shown before the label.
+    pub fn icon(mut self, icon: Icon) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Returns the label.
+    pub fn label(&self) -> &SharedString {
+        &self.label
+    }
+
+    /// Returns the target passed to the navigate handler.
+    pub fn target(&self) -> &SharedString {
+        &self.target
+    }
+
+    /// Returns one segment per folder of a path, from the root to the path itself.
+    ///
+    /// Each segment's target is the folder's full path.
+    pub fn path_trail(path: &Path) -> Vec<BreadcrumbItem> {
+        let mut trail: Vec<BreadcrumbItem> = path
+            .ancestors()
+            .map(|ancestor| {
+                let label = ancestor
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| ancestor.display().to_string());
+                BreadcrumbItem::new(label, ancestor.display().to_string())
+            })
+            .collect();
+        trail.reverse();
+        trail
+    }
+}
+
+/// Returns the range of segments to collapse into the overflow menu so the
+/// trail fits, or `None` if it fits as it is.
+///
+/// The first and last segments always stay visible. Segments are collapsed
+/// from the second one onwards, so the ones nearest the end stay readable.
+fn collapsed_range(
+    widths: &[Pixels],
+    available: Pixels,
+    separator: Pixels,
+    overflow: Pixels,
+) -> Option<Range<usize>> {
+    let total = |widths: &mut dyn Iterator<Item = Pixels>| {
+        let mut count: usize = 0;
+        let sum = widths.fold(px(0.0), |sum, width| {
+            count += 1;
+            sum + width
+        });
+        sum + separator * count.saturating_sub(1) as f32
+    };
+
+    if total(&mut widths.iter().copied()) <= available || widths.len() < 3 {
+        return None;
+    }
+
+    let last = widths.len() - 1;
+    for end in 2..=last {
+        let visible = std::iter::once(widths[0])
+            .chain(std::iter::once(overflow))
+            .chain(widths[end..].iter().copied());
+        if total(&mut visible.into_iter()) <= available || end == last {
+            return Some(1..end);
+        }
+    }
+    None
+}
+
+/// A breadcrumb trail of clickable segments.
+///
+/// This is a builder; convert it into a `BreadcrumbState` view with
+/// `BreadcrumbState::new`.
+///
+/// # Example
+///
+/// ```ignore
+/// let breadcrumb = cx.new(|cx| BreadcrumbState::new(
+///     Breadcrumb::new("path", cx)
+///         .items(BreadcrumbItem::path_trail(&current_dir))
+///         .siblings(|index, cx| {
+///             cx.background_executor()
+///                 .spawn(async move { sibling_folders(index) })
+///         })
+///         .on_navigate(|target, _window, cx| open_folder(target, cx)),
+///     window,
+///     cx,
+/// ));
+/// ```
+pub struct Breadcrumb {
+    id: ElementId,
+    items: Vec<BreadcrumbItem>,
+    separator: SharedString,
+    editable: bool,
+    text_field: TextField,
+    on_navigate: Option<NavigateHandler>,
+    siblings: Option<SiblingProvider>,
+}
+
+impl Breadcrumb {
+    /// Creates an empty breadcrumb with the given id.
+    pub fn new(id: impl Into<ElementId>, cx: &mut App) -> Self {
+        let id = id.into();
+        Self {
+            text_field: TextField::new(id.clone(), cx),
+            id,
+            items: Vec::new(),
+            separator: "›".into(),
+            editable: true,
+            on_navigate: None,
+            siblings: None,
+        }
+    }
+
+    /// Sets the segments, from the root to the current location.
+    pub fn items(mut self, items: impl IntoIterator<Item = BreadcrumbItem>) -> Self {
+        self.items = items.into_iter().collect();
+        self
+    }
+
+    /// Adds a segment at the end of the trail.
+    pub fn item(mut self, item: BreadcrumbItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    /// Sets the separator shown between segments (default: "›").
+    pub fn separator(mut self, separator: impl Into<SharedString>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Sets whether clicking the empty part of the bar shows a text field
+    /// for typing a target (default: true).
+    pub fn editable(mut self, editable: bool) -> Self {
+        self.editable = editable;
+        self
+    }
+
+    /// Sets the placeholder of the text field shown while editing.
+    pub fn placeholder(mut self, placeholder: impl Into<SharedString>) -> Self {
+        self.text_field = self.text_field.placeholder(placeholder);
+        self
+    }
+
+    /// Sets the handler called with the target of a clicked segment, a
+    /// chosen sibling or overflow entry, or the text typed while editing.
+    pub fn on_navigate(
+        mut self,
+        handler: impl Fn(&SharedString, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_navigate = Some(Rc::new(handler));
+        self
+    }
+
+    /// Sets a provider for the siblings of the segment at an index.
+    ///
+    /// Each segment then shows a dropdown button. The provider is called
+    /// when the dropdown opens, and the menu lists the items once its task
+    /// finishes, so slow sources such as the file system can be read on the
+    /// background executor. Use `Task::ready` for items already at hand.
+    pub fn siblings(
+        mut self,
+        provider: impl Fn(usize, &mut App) -> Task<Vec<BreadcrumbItem>> + 'static,
+    ) -> Self {
+        self.siblings = Some(Rc::new(provider));
+        self
+    }
+}
+
+/// Which popup menu of the breadcrumb is open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OpenMenu {
+    /// The menu of collapsed segments
+    Overflow,
+    /// The sibling dropdown of the segment at an index
+    Siblings(usize),
+}
+
+/// Internal state for a Breadcrumb rendered as a view.
+///
+/// # Example
+///
+/// ```ignore
+/// // Update the trail after navigating
+/// self.breadcrumb.update(cx, |breadcrumb, cx| {
+///     breadcrumb.set_items(BreadcrumbItem::path_trail(&new_dir), cx);
+/// });
+/// ```
+pub struct BreadcrumbState {
+    id: ElementId,
+    items: Vec<BreadcrumbItem>,
+    separator: SharedString,
+    editable: bool,
+    on_navigate: Option<NavigateHandler>,
+    siblings: Option<SiblingProvider>,
+    open_menu: Option<OpenMenu>,
+    /// Siblings loaded when the sibling dropdown opened
+    sibling_items: Vec<BreadcrumbItem>,
+    _loading_siblings: Option<Task<()>>,
+    text_field: Entity<TextFieldState>,
+    editing: bool,
+    /// Width of the bar at the last paint, used to collapse segments
+    available_width: Rc<Cell<Option<Pixels>>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl BreadcrumbState {
+    /// Creates the breadcrumb view from a builder.
+    pub fn new(builder: Breadcrumb, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let text_field = cx.new(|_| TextFieldState::from(builder.text_field));
+        let focus_handle = text_field.focus_handle(cx);
+        let subscriptions = vec![cx.on_blur(&focus_handle, window, |this, _window, cx| {
+            this.stop_editing(cx);
+        })];
+
+        Self {
+            id: builder.id,
+            items: builder.items,
+            separator: builder.separator,
+            editable: builder.editable,
+            on_navigate: builder.on_navigate,
+            siblings: builder.siblings,
+            open_menu: None,
+            sibling_items: Vec::new(),
+            _loading_siblings: None,
+            text_field,
+            editing: false,
+            available_width: Rc::new(Cell::new(None)),
+            _subscriptions: subscriptions,
+        }
+    }
+
+    /// Returns the segments.
+    pub fn items(&self) -> &[BreadcrumbItem] {
+        &self.items
+    }
+
+    /// Replaces the segments, e.g. after navigating.
+    pub fn set_items(&mut self, items: Vec<BreadcrumbItem>, cx: &mut Context<Self>) {
+        self.items = items;
+        self.open_menu = None;
+        cx.notify();
+    }
+
+    /// Returns true while the text field is shown.
+    pub fn is_editing(&self) -> bool {
+        self.editing
+    }
+
+    /// Shows the text field, prefilled with the last segment's target.
+    pub fn start_editing(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let text = self
+            .items
+            .last()
+            .map(|item| item.target.to_string())
+            .unwrap_or_default();
+        self.text_field.update(cx, |field, cx| {
+            field.set_value(text);
+            field.focus(window);
+            cx.notify();
+        });
+        self.editing = true;
+        self.open_menu = None;
+        cx.notify();
+    }
+
+    /// Hides the text field without navigating.
+    pub fn stop_editing(&mut self, cx: &mut Context<Self>) {
+        if self.editing {
+            self.editing = false;
+            cx.notify();
+        }
+    }
+
+    fn navigate(&mut self, target: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        self.open_menu = None;
+        cx.notify();
+        if let Some(handler) = self.on_navigate.clone() {
+            handler(&target, window, cx);
+        }
+    }
+
+    fn toggle_menu(&mut self, menu: OpenMenu, cx: &mut Context<Self>) {
+        if self.open_menu == Some(menu) {
+            self.open_menu = None;
+        } else {
+            if let (OpenMenu::Siblings(index), Some(provider)) = (menu, self.siblings.clone()) {
+                self.sibling_items.clear();
+                let siblings = provider(index, cx);
+                self._loading_siblings = Some(cx.spawn(async move |this, cx| {
+                    let items = siblings.await;
+                    this.update(cx, |this, cx| {
+                        this.sibling_items = items;
+                        cx.notify();
+                    })
+                    .ok();
+                }));
+            }
+            self.open_menu = Some(menu);
+        }
+        cx.notify();
+    }
+
+    fn handle_key_down(
+        &mut self,
+        event: &KeyDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event.keystroke.key.as_str() {
+            "enter" => {
+                let text = self.text_field.read(cx).value().trim().to_string();
+                self.stop_editing(cx);
+                if !text.is_empty() {
+                    self.navigate(text.into(), window, cx);
+                }
+                cx.stop_propagation();
+            }
+            "escape" => {
+                self.stop_editing(cx);
+                cx.stop_propagation();
+            }
+            _ => {}
+        }
+    }
+
+    /// Measures the rendered width of each segment.
+    fn segment_widths(&self, window: &mut Window) -> Vec<Pixels> {
+        let font = window.text_style().font();
+        let dropdown = if self.siblings.is_some() {
+            px(DROPDOWN_WIDTH)
+        } else {
+            px(0.0)
+        };
+        self.items
+            .iter()
+            .map(|item| {
+                let mut label = item.label.to_string();
+                if let Some(icon) = item.icon {
+                    label = format!("{} {}", icon.as_str(), label);
+                }
+                text_width(&label, font.clone(), window) + px(SEGMENT_PADDING * 2.0) + dropdown
+            })
+            .collect()
+    }
+
+    fn render_segment(
+        &self,
+        index: usize,
+        item: &BreadcrumbItem,
+        is_last: bool,
+        cx: &mut Context<Self>,
+    ) -> Div {
+        let text_color = hsla(0.0, 0.0, 0.25, 1.0);
+        let target = item.target.clone();
+        let is_open = self.open_menu == Some(OpenMenu::Siblings(index));
+
+        let label = div()
+            .id(("breadcrumb-segment", index))
+            .flex()
+            .items_center()
+            .gap(px(4.0))
+            .px(px(SEGMENT_PADDING))
+            .rounded(px(3.0))
+            .text_color(text_color)
+            .when(is_last, |label| label.font_weight(FontWeight::SEMIBOLD))
+            .cursor_pointer()
+            .hover(|style| style.bg(hsla(0.0, 0.0, 0.0, 0.06)))
+            .when_some(item.icon, |label, icon| label.child(icon.as_str()))
+            .child(item.label.clone())
+            .on_click(cx.listener(move |this, _event, window, cx| {
+                this.navigate(target.clone(), window, cx);
+            }));
+
+        let dropdown = self.siblings.is_some().then(|| {
+            let button = div()
+                .id(("breadcrumb-siblings", index))
+                .w(px(DROPDOWN_WIDTH))
+                .flex()
+                .justify_center()
+                .rounded(px(3.0))
+                .text_xs()
+                .text_color(hsla(0.0, 0.0, 0.5, 1.0))
+                .cursor_pointer()
+                .hover(|style| style.bg(hsla(0.0, 0.0, 0.0, 0.06)))
+                .child("▾")
+                .on_click(cx.listener(move |this, _event, _window, cx| {
+                    this.toggle_menu(OpenMenu::Siblings(index), cx);
+                }));
+            let items = self.sibling_items.clone();
+            div()
+                .relative()
+                .child(button)
+                .when(is_open, |anchor| anchor.child(self.render_menu(items, cx)))
+        });
+
+        div().flex().items_center().child(label).children(dropdown)
+    }
+
+    fn render_overflow(&self, range: Range<usize>, cx: &mut Context<Self>) -> Div {
+        let is_open = self.open_menu == Some(OpenMenu::Overflow);
+        let items = self.items[range].to_vec();
+        div()
+            .relative()
+            .child(
+                div()
+                    .id("breadcrumb-overflow")
+                    .px(px(SEGMENT_PADDING))
+                    .rounded(px(3.0))
+                    .text_color(hsla(0.0, 0.0, 0.4, 1.0))
+                    .cursor_pointer()
+                    .hover(|style| style.bg(hsla(0.0, 0.0, 0.0, 0.06)))
+                    .child("…")
+                    .on_click(cx.listener(|this, _event, _window, cx| {
+                        this.toggle_menu(OpenMenu::Overflow, cx);
+                    })),
+            )
+            .when(is_open, |anchor| anchor.child(self.render_menu(items, cx)))
+    }
+
+    /// Renders a popup menu of segments below its anchor.
+    fn render_menu(&self, items: Vec<BreadcrumbItem>, cx: &mut Context<Self>) -> AnyElement {
+        let view = cx.entity().downgrade();
+        let dismiss: DismissHandler = Rc::new(move |_window, cx| {
+            view.update(cx, |this, cx| {
+                this.open_menu = None;
+                cx.notify();
+            })
+            .ok();
+        });
+
+        let mut panel = div()
+            .id("breadcrumb-menu")
+            .absolute()
+            .top_full()
+            .left(px(0.0))
+            .mt(px(4.0))
+            .min_w(px(160.0))
+            .py(px(4.0))
+            .bg(hsla(0.0, 0.0, 1.0, 1.0))
+            .rounded(px(6.0))
+            .border_1()
+            .border_color(hsla(0.0, 0.0, 0.78, 1.0))
+            .shadow(vec![BoxShadow {
+                color: hsla(0.0, 0.0, 0.0, 0.15),
+                offset: point(px(0.0), px(4.0)),
+                blur_radius: px(12.0),
+                spread_radius: px(0.0),
+            }])
+            // Keep clicks inside the menu from reaching the backdrop
+            .on_mouse_down(MouseButton::Left, |_event, _window, cx| {
+                cx.stop_propagation()
+            });
+
+        if items.is_empty() {
+            panel = panel.child(Menu::build_menu_item(
+                MenuItem::new("breadcrumb-menu-empty", "No Items").disabled(true),
+                |_window, _cx| {},
+            ));
+        }
+        for (index, item) in items.into_iter().enumerate() {
+            let view = cx.entity().downgrade();
+            let target = item.target.clone();
+            let mut menu_item = MenuItem::new(("breadcrumb-menu-item", index), item.label)
+                .on_select(move |window, cx| {
+                    view.update(cx, |this, cx| this.navigate(target.clone(), window, cx))
+                        .ok();
+                });
+            if let Some(icon) = item.icon {
+                menu_item = menu_item.icon(icon);
+            }
+            let dismiss = dismiss.clone();
+            panel = panel.child(Menu::build_menu_item(menu_item, move |window, cx| {
+                dismiss(window, cx)
+            }));
+        }
+
+        let backdrop = div()
+            .absolute()
+            .top(px(0.0))
+            .left(px(0.0))
+            .w(px(10000.0))
+            .h(px(10000.0))
+            .on_mouse_down(MouseButton::Left, move |_event, window, cx| {
+                dismiss(window, cx)
+            });
+
+        div()
+            .child(deferred(backdrop).with_priority(999))
+            .child(deferred(panel).with_priority(1000))
+            .into_any_element()
+    }
+}
+
+/// Measures a single line of segment text.
+fn text_width(text: &str, font: Font, window: &mut Window) -> Pixels {
+    let run = TextRun {
+        len: text.len(),
+        font,
+        color: hsla(0.0, 0.0, 0.0, 1.0),
+        background_color: None,
+        underline: None,
+        strikethrough: None,
+    };
+    window
+        .text_system()
+        .layout_line(text, px(TEXT_SIZE), &[run], None)
+        .width
+}
+
+impl Render for BreadcrumbState {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.editing {
+            return div()
+                .id(self.id.clone())
+                .flex_1()
+                .on_key_down(cx.listener(Self::handle_key_down))
+                .child(self.text_field.clone());
+        }
+
+        // Collapse middle segments when the measured width is too small
+        let font = window.text_style().font();
+        let separator_width = text_width(&self.separator, font.clone(), window) + px(GAP * 2.0);
+        let overflow_width = text_width("…", font, window) + px(SEGMENT_PADDING * 2.0);
+        let widths = self.segment_widths(window);
+        let collapsed = self.available_width.get().and_then(|available| {
+            collapsed_range(&widths, available, separator_width, overflow_width)
+        });
+
+        let separator_color = hsla(0.0, 0.0, 0.55, 1.0);
+        let last = self.items.len().saturating_sub(1);
+        let mut trail = div().flex().items_center().gap(px(GAP)).flex_shrink_0();
+        for (index, item) in self.items.iter().enumerate() {
+            let hidden = collapsed
+                .as_ref()
+                .is_some_and(|range| range.contains(&index));
+            let first_hidden = collapsed.as_ref().is_some_and(|range| range.start == index);
+            if hidden && !first_hidden {
+                continue;
+            }
+            if index > 0 {
+                trail = trail.child(
+                    div()
+                        .text_color(separator_color)
+                        .child(self.separator.clone()),
+                );
+            }
+            trail = if first_hidden {
+                trail.child(self.render_overflow(collapsed.clone().unwrap(), cx))
+            } else {
+                trail.child(self.render_segment(index, item, index == last, cx))
+            };
+        }
+
+        // Track the bar's width and render again when it changes
+        let available_width = self.available_width.clone();
+        let width_tracker = canvas(
+            |bounds, _window, _cx| bounds,
+            move |bounds, _, window, cx| {
+                if available_width.get() != Some(bounds.size.width) {
+                    available_width.set(Some(bounds.size.width));
+                    window.defer(cx, |window, _cx| window.refresh());
+                }
+            },
+        )
+        .absolute()
+        .size_full();
+
+        div()
+            .id(self.id.clone())
+            .relative()
+            .flex()
+            .flex_1()
+            .items_center()
+            .min_w(px(0.0))
+            .overflow_hidden()
+            .text_size(px(TEXT_SIZE))
+            .child(width_tracker)
+            .child(trail)
+            .child(
+                // The empty part of the bar switches to editing
+                div()
+                    .id("breadcrumb-edit-area")
+                    .flex_1()
+                    .h_full()
+                    .min_h(px(20.0))
+                    .when(self.editable, |area| {
+                        area.cursor_text()
+                            .on_click(cx.listener(|this, _event, window, cx| {
+                                this.start_editing(window, cx);
+                            }))
+                    }),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn widths(values: &[f32]) -> Vec<Pixels> {
+        values.iter().map(|&value| px(value)).collect()
+    }
+
+    #[test]
+    fn test_collapsed_range() {
+        let segments = widths(&[20.0, 50.0, 50.0, 50.0, 40.0]);
+        // 210 for the segments plus 4 separators of 10
+        assert_eq!(
+            collapsed_range(&segments, px(250.0), px(10.0), px(15.0)),
+            None
+        );
+        // Hiding segment 1 gives 20 + 15 + 50 + 50 + 40 + 40 = 215
+        assert_eq!(
+            collapsed_range(&segments, px(220.0), px(10.0), px(15.0)),
+            Some(1..2)
+        );
+        assert_eq!(
+            collapsed_range(&segments, px(150.0), px(10.0), px(15.0)),
+            Some(1..4)
+        );
+        // First and last always stay, even if they don't fit
+        assert_eq!(
+            collapsed_range(&segments, px(10.0), px(10.0), px(15.0)),
+            Some(1..4)
+        );
+        assert_eq!(
+            collapsed_range(&widths(&[100.0, 100.0]), px(50.0), px(10.0), px(15.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_path_trail() {
+        let trail = BreadcrumbItem::path_trail(Path::new("/home/ada/Documents"));
+        assert_eq!(
+            trail,
+            vec![
+                BreadcrumbItem::new("/", "/"),
+                BreadcrumbItem::new("home", "/home"),
+                BreadcrumbItem::new("ada", "/home/ada"),
+                BreadcrumbItem::new("Documents", "/home/ada/Documents"),
+            ]
+        );
+    }
+}
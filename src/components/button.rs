@@ -5,6 +5,9 @@
 use gpui::prelude::*;
 use gpui::*;
 
+use super::tooltip::{Tooltip, TooltipContent};
+use crate::accessibility::{AccessNode, AccessState, Accessible, Role};
+
 /// Button style variants.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum ButtonStyle {
@@ -22,6 +25,7 @@ pub enum ButtonStyle {
 /// ```ignore
 /// Button::new("my-button", "Click Me")
 ///     .style(ButtonStyle::Primary)
+///     .tooltip("Apply the changes")
 ///     .on_click(cx.listener(|this, _event, _window, cx| {
 ///         // Handle click
 ///     }))
@@ -31,6 +35,8 @@ pub struct Button {
     label: SharedString,
     style: ButtonStyle,
     disabled: bool,
+    tooltip: Option<TooltipContent>,
+    focus_handle: Option<FocusHandle>,
     on_click: Option<Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>>,
 }
 
@@ -42,6 +48,8 @@ impl Button {
             label: label.into(),
             style: ButtonStyle::default(),
             disabled: false,
+            tooltip: None,
+            focus_handle: None,
             on_click: None,
         }
     }
@@ -70,6 +78,22 @@ impl Button {
         self
     }
 
+    /// Sets the tooltip shown after hovering the button.
+    ///
+    /// Accepts text or rich [`TooltipContent`].
+    pub fn tooltip(mut self, tooltip: impl Into<TooltipContent>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Makes the button focusable, so Enter and Space click it.
+    ///
+    /// Screen readers are told when the button has focus.
+    pub fn focusable(mut self, focus_handle: FocusHandle) -> Self {
+        self.focus_handle = Some(focus_handle);
+        self
+    }
+
     /// Sets the click handler for this button.
     pub fn on_click(
         mut self,
@@ -154,7 +178,16 @@ impl IntoElement for Button {
                 })
         };
 
-        let with_id = styled.child(self.label).id(self.id);
+        // When wrapped in a tooltip, the wrapper takes the button's id
+        let (button_id, tooltip) = match self.tooltip {
+            Some(tooltip) => (ElementId::from("button-inner"), Some((self.id, tooltip))),
+            None => (self.id, None),
+        };
+        let focus_handle = self.focus_handle.filter(|_| !disabled);
+        let mut with_id = styled.child(self.label).id(button_id);
+        if let Some(handle) = &focus_handle {
+            with_id = with_id.track_focus(handle);
+        }
 
         let with_active = if disabled {
             with_id.active(|style| style)
@@ -170,7 +203,7 @@ impl IntoElement for Button {
         };
 
         // Add click handler if provided and not disabled
-        if let Some(handler) = self.on_click {
+        let with_click = if let Some(handler) = self.on_click {
             if !disabled {
                 with_active.on_click(move |event, window, cx| {
                     handler(event, window, cx);
@@ -180,13 +213,59 @@ impl IntoElement for Button {
             }
         } else {
             with_active
+        };
+
+        if let Some((id, content)) = tooltip {
+            let mut tooltip = Tooltip::new("tooltip", with_click, content);
+            if let Some(handle) = focus_handle {
+                tooltip = tooltip.focus_handle(handle);
+            }
+            div().id(id).child(tooltip)
+        } else {
+            with_click
         }
     }
 }
 
+impl Accessible for Button {
+    fn accessibility_node(&self) -> AccessNode {
+        let mut node = AccessNode::new(Role::Button)
+            .id(self.id.to_string())
+            .name(self.label.clone())
+            .state(AccessState::Focusable)
+            .state_if(AccessState::Default, self.style == ButtonStyle::Primary)
+            .state_if(AccessState::Disabled, self.disabled)
+            .action("click");
+        if let Some(text) = self.tooltip.as_ref().and_then(|tooltip| tooltip.text()) {
+            node = node.description(text.clone());
+        }
+        if let Some(handle) = &self.focus_handle {
+            node = node.focus_handle(handle);
+        }
+        node
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use gpui::TestAppContext;
+    use std::time::Duration;
+    // `use gpui::*` brings in gpui's `test` attribute, which `#[gpui::test]`
+    // expands into; keep `#[test]` the built-in one
+    use std::prelude::v1::test;
+
+    struct FocusableButton {
+        focus_handle: FocusHandle,
+    }
+
+    impl Render for FocusableButton {
+        fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+            Button::new("save", "Save")
+                .tooltip("Save the document")
+                .focusable(self.focus_handle.clone())
+        }
+    }
 
     #[test]
     fn test_button_creation() {
@@ -200,4 +279,31 @@ mod tests {
         let button = Button::new("test", "Test").primary();
         assert_eq!(button.style, ButtonStyle::Primary);
     }
+
+    #[test]
+    fn test_button_tooltip() {
+        let button = Button::new("test", "Test");
+        assert!(button.tooltip.is_none());
+
+        let button = button.tooltip("Hint");
+        assert!(matches!(button.tooltip, Some(TooltipContent::Text(ref text)) if text == "Hint"));
+    }
+
+    #[gpui::test]
+    fn test_button_focus_shows_tooltip(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_window, cx| FocusableButton {
+            focus_handle: cx.focus_handle(),
+        });
+        assert!(cx.debug_bounds("tooltip").is_none());
+
+        cx.update(|window, cx| view.read(cx).focus_handle.focus(window));
+        cx.run_until_parked();
+        cx.executor().advance_clock(Duration::from_secs(1));
+        cx.run_until_parked();
+        assert!(cx.debug_bounds("tooltip").is_some());
+
+        cx.update(|window, _cx| window.blur());
+        cx.run_until_parked();
+        assert!(cx.debug_bounds("tooltip").is_none());
+    }
 }
@@ -5,6 +5,9 @@
 use gpui::prelude::*;
 use gpui::*;
 
+use crate::accessibility::{AccessNode, AccessState, Accessible, Role};
+use crate::state::Binding;
+
 /// A checkbox component.
 ///
 /// # Example
@@ -16,13 +19,18 @@ use gpui::*;
 ///         this.checkout_after_create = *checked;
 ///         cx.notify();
 ///     }))
+///
+/// // Bound to a `State<bool>`
+/// Checkbox::new("remember", "Remember me").is_checked(State::binding(&self.remember, cx))
 /// ```
+#[derive(IntoElement)]
 pub struct Checkbox {
     id: ElementId,
     label: SharedString,
     checked: bool,
     disabled: bool,
     on_change: Option<Box<dyn Fn(&bool, &mut Window, &mut App) + 'static>>,
+    checked_binding: Option<Binding<bool>>,
 }
 
 impl Checkbox {
@@ -34,6 +42,7 @@ impl Checkbox {
             checked: false,
             disabled: false,
             on_change: None,
+            checked_binding: None,
         }
     }
 
@@ -49,6 +58,15 @@ impl Checkbox {
         self
     }
 
+    /// Sets a two-way binding for the checked state.
+    ///
+    /// The bound value replaces `checked`, and clicking the checkbox writes
+    /// the new state back.
+    pub fn is_checked(mut self, binding: Binding<bool>) -> Self {
+        self.checked_binding = Some(binding);
+        self
+    }
+
     /// Sets the change handler for this checkbox.
     ///
     /// The handler receives a reference to the new checked state.
@@ -61,10 +79,11 @@ impl Checkbox {
     }
 }
 
-impl IntoElement for Checkbox {
-    type Element = Stateful<Div>;
-
-    fn into_element(self) -> Self::Element {
+impl RenderOnce for Checkbox {
+    fn render(mut self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        if let Some(ref binding) = self.checked_binding {
+            self.checked = binding.get(cx);
+        }
         let checked = self.checked;
         let disabled = self.disabled;
 
@@ -167,17 +186,35 @@ impl IntoElement for Checkbox {
         let container = container.child(checkbox_box).child(label);
 
         // Add click handler if provided and not disabled
-        if let Some(handler) = self.on_change {
-            if !disabled {
-                let new_checked = !checked;
-                container.on_click(move |_event, window, cx| {
-                    handler(&new_checked, window, cx);
-                })
-            } else {
-                container
+        if disabled || (self.on_change.is_none() && self.checked_binding.is_none()) {
+            return container;
+        }
+        let (handler, binding) = (self.on_change, self.checked_binding);
+        let new_checked = !checked;
+        container.on_click(move |_event, window, cx| {
+            if let Some(ref binding) = binding {
+                binding.set_if_changed(new_checked, cx);
             }
-        } else {
-            container
+            if let Some(ref handler) = handler {
+                handler(&new_checked, window, cx);
+            }
+        })
+    }
+}
+
+impl Accessible for Checkbox {
+    fn accessibility_node(&self) -> AccessNode {
+        let node = AccessNode::new(Role::CheckBox)
+            .id(self.id.to_string())
+            .name(self.label.clone())
+            .state(AccessState::Focusable)
+            .state(AccessState::Checkable)
+            .state_if(AccessState::Disabled, self.disabled)
+            .action("click");
+        match self.checked_binding.clone() {
+            Some(binding) => node
+                .resolve_with(move |node, cx| node.state_if(AccessState::Checked, binding.get(cx))),
+            None => node.state_if(AccessState::Checked, self.checked),
         }
     }
 }
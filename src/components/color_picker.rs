@@ -8,6 +8,9 @@ use gpui::*;
 use std::cell::Cell;
 use std::rc::Rc;
 
+use super::anchored::AnchoredOverlay;
+use crate::state::Binding;
+
 /// Preset colors for the color grid.
 const PRESET_COLORS: &[Hsla] = &[
     // Row 1: Reds and oranges
@@ -59,7 +62,12 @@ const fn hsla(h: f32, s: f32, l: f32, a: f32) -> Hsla {
 ///     .on_change(|new_color, window, cx| {
 ///         println!("Color changed: {:?}", new_color);
 ///     })
+///
+/// // Bound to a `State<Hsla>`
+/// let accent = State::binding(&self.accent, cx);
+/// cx.new(|_| ColorPickerState::from(ColorPicker::new("accent", gpui::black()).color(accent)))
 /// ```
+#[derive(IntoElement)]
 pub struct ColorPicker {
     id: ElementId,
     color: Hsla,
@@ -67,6 +75,7 @@ pub struct ColorPicker {
     supports_opacity: bool,
     disabled: bool,
     on_change: Option<Rc<dyn Fn(Hsla, &mut Window, &mut App) + 'static>>,
+    color_binding: Option<Binding<Hsla>>,
 }
 
 impl ColorPicker {
@@ -79,6 +88,7 @@ impl ColorPicker {
             supports_opacity: false,
             disabled: false,
             on_change: None,
+            color_binding: None,
         }
     }
 
@@ -88,6 +98,15 @@ impl ColorPicker {
         self
     }
 
+    /// Sets a two-way binding for the color.
+    ///
+    /// The bound value replaces the initial color, and `ColorPickerState`
+    /// writes every adjustment back.
+    pub fn color(mut self, binding: Binding<Hsla>) -> Self {
+        self.color_binding = Some(binding);
+        self
+    }
+
     /// Sets whether the picker supports opacity/alpha channel adjustment.
     pub fn supports_opacity(mut self, supports: bool) -> Self {
         self.supports_opacity = supports;
@@ -139,10 +158,12 @@ impl ColorPickerColors {
 const WELL_SIZE: f32 = 24.0;
 const WELL_BORDER_RADIUS: f32 = 4.0;
 
-impl IntoElement for ColorPicker {
-    type Element = Stateful<Div>;
+impl RenderOnce for ColorPicker {
+    fn render(mut self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        if let Some(ref binding) = self.color_binding {
+            self.color = binding.get(cx);
+        }
 
-    fn into_element(self) -> Self::Element {
         let colors = if self.disabled {
             ColorPickerColors::disabled()
         } else {
@@ -238,6 +259,7 @@ pub struct ColorPickerState {
     disabled: bool,
     is_open: bool,
     on_change: Option<Rc<dyn Fn(&Hsla, &mut Window, &mut App) + 'static>>,
+    color_binding: Option<Binding<Hsla>>,
     /// Hex input value (for text field)
     hex_input: String,
     /// Track bounds for slider positioning
@@ -271,6 +293,16 @@ impl ColorPickerState {
         self.is_open = false;
     }
 
+    /// Reports the current color to the binding and the change handler.
+    fn notify_change(&self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ref binding) = self.color_binding {
+            binding.set_if_changed(self.color, cx);
+        }
+        if let Some(ref handler) = self.on_change {
+            handler(&self.color, window, cx);
+        }
+    }
+
     /// Handle clicking on the color well.
     fn handle_well_click(
         &mut self,
@@ -296,9 +328,7 @@ impl ColorPickerState {
         self.color = new_color;
         self.hex_input = color_to_hex(new_color);
 
-        if let Some(ref handler) = self.on_change {
-            handler(&self.color, window, cx);
-        }
+        self.notify_change(window, cx);
         cx.notify();
     }
 
@@ -316,9 +346,7 @@ impl ColorPickerState {
             self.color.h = position as f32;
             self.hex_input = color_to_hex(self.color);
 
-            if let Some(ref handler) = self.on_change {
-                handler(&self.color, window, cx);
-            }
+            self.notify_change(window, cx);
             cx.notify();
         }
     }
@@ -337,9 +365,7 @@ impl ColorPickerState {
             self.color.s = position as f32;
             self.hex_input = color_to_hex(self.color);
 
-            if let Some(ref handler) = self.on_change {
-                handler(&self.color, window, cx);
-            }
+            self.notify_change(window, cx);
             cx.notify();
         }
     }
@@ -358,9 +384,7 @@ impl ColorPickerState {
             self.color.l = position as f32;
             self.hex_input = color_to_hex(self.color);
 
-            if let Some(ref handler) = self.on_change {
-                handler(&self.color, window, cx);
-            }
+            self.notify_change(window, cx);
             cx.notify();
         }
     }
@@ -379,9 +403,7 @@ impl ColorPickerState {
             self.color.a = position as f32;
             self.hex_input = color_to_hex(self.color);
 
-            if let Some(ref handler) = self.on_change {
-                handler(&self.color, window, cx);
-            }
+            self.notify_change(window, cx);
             cx.notify();
         }
     }
@@ -489,6 +511,14 @@ impl ColorPickerState {
 
 impl Render for ColorPickerState {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // Sync color from binding
+        if let Some(ref binding) = self.color_binding {
+            let bound_color = binding.get(cx);
+            if bound_color != self.color {
+                self.set_color(bound_color);
+            }
+        }
+
         let colors = if self.disabled {
             ColorPickerColors::disabled()
         } else {
@@ -781,10 +811,6 @@ impl Render for ColorPickerState {
 
             // Build the popover panel
             let panel = div()
-                .absolute()
-                .top_full()
-                .left(px(0.0))
-                .mt(px(4.0))
                 .bg(hsla(0.0, 0.0, 1.0, 1.0))
                 .rounded(px(8.0))
                 .border_1()
@@ -809,7 +835,8 @@ impl Render for ColorPickerState {
                 .on_click(cx.listener(Self::handle_dismiss))
                 .child(panel);
 
-            container = container.child(overlay);
+            // Open below the well, flipping above when there is no room
+            container = container.child(AnchoredOverlay::new(overlay).offset(px(4.0)));
         }
 
         container
@@ -832,6 +859,7 @@ impl From<ColorPicker> for ColorPickerState {
                     handler(*color, window, cx);
                 }) as Rc<dyn Fn(&Hsla, &mut Window, &mut App) + 'static>
             }),
+            color_binding: builder.color_binding,
             hex_input,
             hue_slider_bounds: Rc::new(Cell::new(None)),
             sat_slider_bounds: Rc::new(Cell::new(None)),
@@ -0,0 +1,676 @@
+//! Combo box component for GPUI.
+//!
+//! A text field paired with a suggestion list shown in a `ControlledPopover`.
+//! Suggestions come from a static list, a synchronous provider, or an
+//! asynchronous provider, and are refreshed through a `Debouncer` while typing.
+
+use gpui::prelude::*;
+use gpui::*;
+use std::ops::Range;
+use std::rc::Rc;
+use std::time::Duration;
+
+use super::popover::ControlledPopover;
+use super::text_field::{TextField, TextFieldState};
+use crate::state::Binding;
+use crate::utils::Debouncer;
+
+type SyncProvider = Rc<dyn Fn(&str) -> Vec<SharedString> + 'static>;
+type AsyncProvider = Rc<dyn Fn(String, &mut App) -> Task<Vec<SharedString>> + 'static>;
+type CommitHandler = Rc<dyn Fn(&str, &mut Window, &mut App) + 'static>;
+
+/// Source of suggestions for a combo box.
+#[derive(Clone)]
+enum SuggestionProvider {
+    /// A fixed list filtered by case-insensitive substring match.
+    Static(Rc<Vec<SharedString>>),
+    /// A function returning suggestions for the typed text.
+    Sync(SyncProvider),
+    /// A function returning a task that resolves to suggestions.
+    Async(AsyncProvider),
+}
+
+/// A text field with a popup list of suggestions.
+///
+/// This is a builder; convert it into a `ComboBoxState` view with
+/// `ComboBoxState::new` to get keyboard navigation and the suggestion popup.
+///
+/// # Example
+///
+/// ```ignore
+/// let combo = cx.new(|cx| ComboBoxState::new(
+///     ComboBox::new("city", cx)
+///         .placeholder("City")
+///         .suggestions(vec!["Amsterdam".into(), "Berlin".into(), "Paris".into()])
+///         .restrict_to_list(true)
+///         .on_commit(|city, _window, _cx| println!("Selected {}", city)),
+///     window,
+///     cx,
+/// ));
+/// ```
+pub struct ComboBox {
+    id: ElementId,
+    text_field: TextField,
+    provider: Option<SuggestionProvider>,
+    debounce: Duration,
+    restrict_to_list: bool,
+    max_suggestions: usize,
+    on_commit: Option<CommitHandler>,
+}
+
+impl ComboBox {
+    /// Creates a new combo box with the given id.
+    pub fn new(id: impl Into<ElementId>, cx: &mut App) -> Self {
+        let id = id.into();
+        Self {
+            text_field: TextField::new(id.clone(), cx),
+            id,
+            provider: None,
+            debounce: Duration::from_millis(150),
+            restrict_to_list: false,
+            max_suggestions: 8,
+            on_commit: None,
+        }
+    }
+
+    /// Sets a two-way binding for the text value.
+    pub fn text(mut self, binding: Binding<String>) -> Self {
+        self.text_field = self.text_field.text(binding);
+        self
+    }
+
+    /// Sets the initial text value.
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.text_field = self.text_field.value(value);
+        self
+    }
+
+    /// Sets the label text shown above the input.
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.text_field = self.text_field.label(label);
+        self
+    }
+
+    /// Sets the placeholder text shown when empty.
+    pub fn placeholder(mut self, placeholder: impl Into<SharedString>) -> Self {
+        self.text_field = self.text_field.placeholder(placeholder);
+        self
+    }
+
+    /// Sets a fixed list of suggestions, filtered by the typed text.
+    pub fn suggestions(mut self, suggestions: Vec<SharedString>) -> Self {
+        self.provider = Some(SuggestionProvider::Static(Rc::new(suggestions)));
+        self
+    }
+
+    /// Sets a synchronous suggestion provider.
+    ///
+    /// The provider receives the typed text and returns matching suggestions.
+    pub fn suggestion_provider(
+        mut self,
+        provider: impl Fn(&str) -> Vec<SharedString> + 'static,
+    ) -> Self {
+        self.provider = Some(SuggestionProvider::Sync(Rc::new(provider)));
+        self
+    }
+
+    /// Sets an asynchronous suggestion provider.
+    ///
+    /// The provider receives the typed text and returns a task resolving to
+    /// suggestions. A pending task is dropped (and thereby cancelled) when the
+    /// text changes again.
+    pub fn async_suggestion_provider(
+        mut self,
+        provider: impl Fn(String, &mut App) -> Task<Vec<SharedString>> + 'static,
+    ) -> Self {
+        self.provider = Some(SuggestionProvider::Async(Rc::new(provider)));
+        self
+    }
+
+    /// Sets how long to wait after the last keystroke before querying suggestions.
+    pub fn debounce(mut self, delay: Duration) -> Self {
+        self.debounce = delay;
+        self
+    }
+
+    /// Sets whether only values from the suggestion list are accepted.
+    ///
+    /// When enabled, committing text that doesn't match a suggestion reverts
+    /// the field to the last accepted value.
+    pub fn restrict_to_list(mut self, restrict: bool) -> Self {
+        self.restrict_to_list = restrict;
+        self
+    }
+
+    /// Sets the maximum number of suggestions shown in the popup.
+    pub fn max_suggestions(mut self, max: usize) -> Self {
+        self.max_suggestions = max;
+        self
+    }
+
+    /// Sets the handler called when a value is committed.
+    ///
+    /// A value is committed when a suggestion is accepted, or when Enter is
+    /// pressed on free text.
+    pub fn on_commit(mut self, handler: impl Fn(&str, &mut Window, &mut App) + 'static) -> Self {
+        self.on_commit = Some(Rc::new(handler));
+        self
+    }
+}
+
+/// Internal state for a ComboBox rendered as a view.
+///
+/// # Example
+///
+/// ```ignore
+/// struct MyView {
+///     language: Entity<ComboBoxState>,
+/// }
+///
+/// impl MyView {
+///     fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+///         Self {
+///             language: cx.new(|cx| ComboBoxState::new(
+///                 ComboBox::new("language", cx)
+///                     .async_suggestion_provider(|query, cx| {
+///                         cx.background_executor().spawn(async move { search_languages(&query) })
+///                     }),
+///                 window,
+///                 cx,
+///             )),
+///         }
+///     }
+/// }
+/// ```
+pub struct ComboBoxState {
+    id: ElementId,
+    text_field: Entity<TextFieldState>,
+    provider: Option<SuggestionProvider>,
+    restrict_to_list: bool,
+    max_suggestions: usize,
+    on_commit: Option<CommitHandler>,
+    debouncer: Debouncer,
+    /// In-flight asynchronous query, dropped to cancel it.
+    pending_query: Option<Task<()>>,
+    /// The text the current suggestions were requested for.
+    query: String,
+    /// The last committed value, restored when restricted input is rejected.
+    committed: String,
+    suggestions: Vec<SharedString>,
+    highlighted: Option<usize>,
+    is_open: bool,
+    is_loading: bool,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ComboBoxState {
+    /// Creates the combo box view from a builder.
+    pub fn new(builder: ComboBox, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let text_field = cx.new(|_| TextFieldState::from(builder.text_field));
+        let initial = text_field.read(cx).value().to_string();
+        let focus_handle = text_field.focus_handle(cx);
+
+        let subscriptions = vec![
+            cx.observe(&text_field, |this, field, cx| {
+                let text = field.read(cx).value().to_string();
+                if text != this.query {
+                    this.query = text;
+                    this.schedule_refresh(cx);
+                }
+            }),
+            cx.on_blur(&focus_handle, window, |this, window, cx| {
+                this.commit_text(window, cx);
+            }),
+        ];
+
+        Self {
+            id: builder.id,
+            text_field,
+            provider: builder.provider,
+            restrict_to_list: builder.restrict_to_list,
+            max_suggestions: builder.max_suggestions,
+            on_commit: builder.on_commit,
+            debouncer: Debouncer::new(builder.debounce),
+            pending_query: None,
+            query: initial.clone(),
+            committed: initial,
+            suggestions: Vec::new(),
+            highlighted: None,
+            is_open: false,
+            is_loading: false,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    /// Returns the current text.
+    pub fn value<'a>(&self, cx: &'a App) -> &'a str {
+        self.text_field.read(cx).value()
+    }
+
+    /// Returns the suggestions currently shown.
+    pub fn suggestions(&self) -> &[SharedString] {
+        &self.suggestions
+    }
+
+    /// Returns true if the suggestion popup is open.
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    /// Closes the suggestion popup.
+    pub fn close(&mut self, cx: &mut Context<Self>) {
+        self.is_open = false;
+        self.highlighted = None;
+        cx.notify();
+    }
+
+    /// Clears the text and the committed value, and closes the popup.
+    pub fn clear(&mut self, cx: &mut Context<Self>) {
+        self.replace_text(String::new(), cx);
+        self.committed.clear();
+        self.suggestions.clear();
+        self.close(cx);
+    }
+
+    /// Focuses the text field.
+    pub fn focus(&self, window: &mut Window, cx: &App) {
+        self.text_field.read(cx).focus(window);
+    }
+
+    /// Queries suggestions for the current text after the debounce delay.
+    fn schedule_refresh(&mut self, cx: &mut Context<Self>) {
+        let this = cx.weak_entity();
+        self.debouncer.call(cx, move |cx| {
+            this.update(cx, |this, cx| this.refresh_suggestions(cx)).ok();
+        });
+    }
+
+    /// Queries the provider for the current text.
+    fn refresh_suggestions(&mut self, cx: &mut Context<Self>) {
+        let query = self.query.clone();
+        self.pending_query = None;
+
+        if query.is_empty() {
+            self.set_suggestions(Vec::new(), cx);
+            return;
+        }
+
+        match self.provider.clone() {
+            Some(SuggestionProvider::Static(items)) => {
+                self.set_suggestions(filter_suggestions(&items, &query), cx);
+            }
+            Some(SuggestionProvider::Sync(provider)) => {
+                self.set_suggestions(provider(&query), cx);
+            }
+            Some(SuggestionProvider::Async(provider)) => {
+                let task = provider(query, cx);
+                self.is_loading = true;
+                self.pending_query = Some(cx.spawn(async move |this, cx| {
+                    let results = task.await;
+                    this.update(cx, |this, cx| {
+                        this.pending_query = None;
+                        this.set_suggestions(results, cx);
+                    })
+                    .ok();
+                }));
+                cx.notify();
+            }
+            None => {}
+        }
+    }
+
+    fn set_suggestions(&mut self, mut suggestions: Vec<SharedString>, cx: &mut Context<Self>) {
+        suggestions.truncate(self.max_suggestions);
+        self.is_loading = false;
+        self.is_open = !suggestions.is_empty();
+        self.highlighted = None;
+        self.suggestions = suggestions;
+        cx.notify();
+    }
+
+    /// Returns the inner text field.
+    pub(crate) fn text_field(&self) -> &Entity<TextFieldState> {
+        &self.text_field
+    }
+
+    /// Replaces the text without triggering a new suggestion query.
+    pub(crate) fn replace_text(&mut self, text: String, cx: &mut Context<Self>) {
+        self.query = text.clone();
+        self.debouncer.cancel();
+        self.pending_query = None;
+        self.is_loading = false;
+        self.text_field
+            .update(cx, |field, cx| field.replace_text(text, cx));
+    }
+
+    /// Accepts the suggestion at `index` as the committed value.
+    fn accept(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(value) = self.suggestions.get(index).cloned() else {
+            return;
+        };
+        self.commit_match(Some(value), window, cx);
+    }
+
+    /// Commits the typed text, enforcing the list restriction if enabled.
+    fn commit_text(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let text = self.text_field.read(cx).value().to_string();
+        if text == self.committed {
+            self.close(cx);
+            return;
+        }
+
+        if !self.restrict_to_list {
+            self.finish_commit(text, window, cx);
+            return;
+        }
+
+        // The shown suggestions are capped and may not have caught up with
+        // the text yet, so ask the provider for every match.
+        match self.provider.clone() {
+            Some(SuggestionProvider::Static(items)) => {
+                let value = find_exact(&items, &text).map(|index| items[index].clone());
+                self.commit_match(value, window, cx);
+            }
+            Some(SuggestionProvider::Sync(provider)) => {
+                let items = provider(&text);
+                let value = find_exact(&items, &text).map(|index| items[index].clone());
+                self.commit_match(value, window, cx);
+            }
+            Some(SuggestionProvider::Async(provider)) => {
+                self.debouncer.cancel();
+                let task = provider(text.clone(), cx);
+                self.is_loading = true;
+                self.pending_query = Some(cx.spawn_in(window, async move |this, cx| {
+                    let items = task.await;
+                    this.update_in(cx, |this, window, cx| {
+                        this.pending_query = None;
+                        this.is_loading = false;
+                        // Text typed since the commit is left for the next one
+                        if this.text_field.read(cx).value() != text {
+                            return;
+                        }
+                        let value = find_exact(&items, &text).map(|index| items[index].clone());
+                        this.commit_match(value, window, cx);
+                    })
+                    .ok();
+                }));
+                cx.notify();
+            }
+            None => self.commit_match(None, window, cx),
+        }
+    }
+
+    /// Commits a value from the suggestion list, or reverts the text if there is none.
+    fn commit_match(
+        &mut self,
+        value: Option<SharedString>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match value {
+            Some(value) => {
+                self.replace_text(value.to_string(), cx);
+                self.finish_commit(value.to_string(), window, cx);
+            }
+            None => {
+                let committed = self.committed.clone();
+                self.replace_text(committed, cx);
+                self.close(cx);
+            }
+        }
+    }
+
+    fn finish_commit(&mut self, value: String, window: &mut Window, cx: &mut Context<Self>) {
+        self.committed = value;
+        self.close(cx);
+        if let Some(ref handler) = self.on_commit {
+            handler(&self.committed, window, cx);
+        }
+    }
+
+    fn handle_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        match event.keystroke.key.as_str() {
+            "down" => {
+                if !self.is_open && !self.suggestions.is_empty() {
+                    self.is_open = true;
+                }
+                self.highlighted = move_highlight(self.highlighted, self.suggestions.len(), 1);
+                cx.stop_propagation();
+                cx.notify();
+            }
+            "up" if self.is_open => {
+                self.highlighted = move_highlight(self.highlighted, self.suggestions.len(), -1);
+                cx.stop_propagation();
+                cx.notify();
+            }
+            "enter" => {
+                match self.highlighted.filter(|_| self.is_open) {
+                    Some(index) => self.accept(index, window, cx),
+                    None => self.commit_text(window, cx),
+                }
+                cx.stop_propagation();
+            }
+            "tab" => {
+                // Tab takes the highlighted suggestion, or the top one if none is highlighted
+                if self.is_open && !self.suggestions.is_empty() {
+                    self.accept(self.highlighted.unwrap_or(0), window, cx);
+                    cx.stop_propagation();
+                } else {
+                    self.commit_text(window, cx);
+                }
+            }
+            "escape" if self.is_open => {
+                self.close(cx);
+                cx.stop_propagation();
+            }
+            _ => {}
+        }
+    }
+
+    /// Builds a suggestion row with the matched part of the label emphasized.
+    fn build_row(
+        &self,
+        index: usize,
+        label: &SharedString,
+        cx: &mut Context<Self>,
+    ) -> Stateful<Div> {
+        let is_highlighted = self.highlighted == Some(index);
+        let text_color = if is_highlighted {
+            gpui::white()
+        } else {
+            hsla(0.0, 0.0, 0.20, 1.0)
+        };
+        let bg_color = if is_highlighted {
+            hsla(211.0 / 360.0, 0.95, 0.53, 1.0)
+        } else {
+            hsla(0.0, 0.0, 1.0, 1.0)
+        };
+        let hover_bg = if is_highlighted {
+            bg_color
+        } else {
+            hsla(0.0, 0.0, 0.95, 1.0)
+        };
+
+        let mut row = div()
+            .id(("combo-box-suggestion", index))
+            .flex()
+            .flex_row()
+            .items_center()
+            .px_3()
+            .py_1()
+            .text_sm()
+            .bg(bg_color)
+            .text_color(text_color)
+            .cursor_pointer()
+            .hover(move |style| style.bg(hover_bg))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, _event, window, cx| {
+                    cx.stop_propagation();
+                    this.accept(index, window, cx);
+                }),
+            );
+
+        match match_range(label, &self.query) {
+            Some(range) => {
+                row = row
+                    .child(label[..range.start].to_string())
+                    .child(
+                        div()
+                            .font_weight(FontWeight::BOLD)
+                            .child(label[range.clone()].to_string()),
+                    )
+                    .child(label[range.end..].to_string());
+            }
+            None => {
+                row = row.child(label.clone());
+            }
+        }
+
+        row
+    }
+}
+
+impl Focusable for ComboBoxState {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.text_field.focus_handle(cx)
+    }
+}
+
+impl Render for ComboBoxState {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let anchor = div()
+            .w_full()
+            .on_key_down(cx.listener(Self::handle_key_down))
+            .child(self.text_field.clone());
+
+        let mut list = div()
+            .flex()
+            .flex_col()
+            .py(px(4.0))
+            .min_w(px(200.0));
+
+        if self.suggestions.is_empty() && self.is_loading {
+            list = list.child(
+                div()
+                    .px_3()
+                    .py_1()
+                    .text_sm()
+                    .text_color(hsla(0.0, 0.0, 0.55, 1.0))
+                    .child("Searching…"),
+            );
+        }
+
+        let suggestions = self.suggestions.clone();
+        for (index, label) in suggestions.iter().enumerate() {
+            list = list.child(self.build_row(index, label, cx));
+        }
+
+        ControlledPopover::new(self.id.clone(), self.is_open, anchor)
+            .without_arrow()
+            .match_anchor_width(true)
+            .content(move || list)
+            .on_dismiss(cx.listener(|this, _event, _window, cx| {
+                this.close(cx);
+            }))
+    }
+}
+
+/// Filters `items` to those containing `query`, ignoring case.
+fn filter_suggestions(items: &[SharedString], query: &str) -> Vec<SharedString> {
+    items
+        .iter()
+        .filter(|item| match_range(item, query).is_some())
+        .cloned()
+        .collect()
+}
+
+/// Returns the byte range of the first case-insensitive occurrence of `query` in `label`.
+fn match_range(label: &str, query: &str) -> Option<Range<usize>> {
+    if query.is_empty() {
+        return None;
+    }
+
+    for (start, _) in label.char_indices() {
+        let mut label_chars = label[start..].char_indices();
+        let mut end = start;
+        let matched = query.chars().all(|q| match label_chars.next() {
+            Some((offset, c)) if c.to_lowercase().eq(q.to_lowercase()) => {
+                end = start + offset + c.len_utf8();
+                true
+            }
+            _ => false,
+        });
+        if matched {
+            return Some(start..end);
+        }
+    }
+
+    None
+}
+
+/// Returns the index of the suggestion equal to `text`, ignoring case.
+fn find_exact(suggestions: &[SharedString], text: &str) -> Option<usize> {
+    suggestions
+        .iter()
+        .position(|s| s.to_lowercase() == text.to_lowercase())
+}
+
+/// Moves the highlighted index by `delta`, stopping at the ends of the list.
+///
+/// Moving up from the first row clears the highlight.
+fn move_highlight(current: Option<usize>, len: usize, delta: isize) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    match (current, delta) {
+        (None, d) if d > 0 => Some(0),
+        (None, _) => None,
+        (Some(0), d) if d < 0 => None,
+        (Some(index), d) => Some((index as isize + d).clamp(0, len as isize - 1) as usize),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_range() {
+        assert_eq!(match_range("Berlin", "er"), Some(1..3));
+        assert_eq!(match_range("Berlin", "BER"), Some(0..3));
+        assert_eq!(match_range("Berlin", "xyz"), None);
+        assert_eq!(match_range("Berlin", ""), None);
+    }
+
+    #[test]
+    fn test_match_range_non_ascii() {
+        assert_eq!(match_range("Zürich", "ÜR"), Some(1..4));
+        assert_eq!(match_range("Málaga", "laga"), Some(3..7));
+    }
+
+    #[test]
+    fn test_filter_suggestions() {
+        let items: Vec<SharedString> = vec!["Amsterdam".into(), "Berlin".into(), "Bern".into()];
+        let filtered = filter_suggestions(&items, "ber");
+        assert_eq!(filtered, vec![SharedString::from("Berlin"), SharedString::from("Bern")]);
+    }
+
+    #[test]
+    fn test_find_exact() {
+        let items: Vec<SharedString> = vec!["Berlin".into(), "Bern".into()];
+        assert_eq!(find_exact(&items, "bern"), Some(1));
+        assert_eq!(find_exact(&items, "Ber"), None);
+    }
+
+    #[test]
+    fn test_move_highlight() {
+        assert_eq!(move_highlight(None, 3, 1), Some(0));
+        assert_eq!(move_highlight(Some(0), 3, 1), Some(1));
+        assert_eq!(move_highlight(Some(2), 3, 1), Some(2));
+        assert_eq!(move_highlight(Some(1), 3, -1), Some(0));
+        assert_eq!(move_highlight(Some(0), 3, -1), None);
+        assert_eq!(move_highlight(None, 3, -1), None);
+        assert_eq!(move_highlight(Some(0), 0, 1), None);
+    }
+}
@@ -33,8 +33,11 @@ use gpui::prelude::*;
 use gpui::*;
 use std::rc::Rc;
 
+use super::anchored::AnchoredOverlay;
 use super::label::Icon;
 use super::menu::{Menu, MenuContent, MenuItem, SubMenuBuilder};
+use super::tooltip::Tooltip;
+use crate::accessibility::{AccessNode, AccessState, Accessible, Role};
 
 /// A context menu that appears on right-click.
 ///
@@ -214,19 +217,7 @@ impl ContextMenu {
                     let on_dismiss = move |window: &mut Window, cx: &mut App| {
                         on_close_clone(window, cx);
                     };
-                    let submenu_panel = Menu::build_submenu_panel(&items, on_dismiss);
-                    panel = panel.child(
-                        div()
-                            .relative()
-                            .group("submenu")
-                            .child(Menu::build_submenu_trigger(id, label, icon))
-                            .child(
-                                div()
-                                    .invisible()
-                                    .group_hover("submenu", |style| style.visible())
-                                    .child(submenu_panel),
-                            ),
-                    );
+                    panel = panel.child(Menu::build_submenu(id, label, icon, &items, on_dismiss));
                 }
             }
         }
@@ -248,8 +239,14 @@ impl ContextMenu {
         let is_disabled = item.disabled;
         let on_select = item.on_select;
 
+        // When wrapped in a tooltip, the wrapper takes the item's id
+        let (row_id, tooltip) = match item.tooltip {
+            Some(tooltip) => (ElementId::from("menu-item-inner"), Some((item.id, tooltip))),
+            None => (item.id, None),
+        };
+
         let mut row = div()
-            .id(item.id)
+            .id(row_id)
             .flex()
             .flex_row()
             .items_center()
@@ -317,7 +314,14 @@ impl ContextMenu {
             );
         }
 
-        row
+        // The menu panel is already deferred, so the tooltip renders in place
+        if let Some((id, content)) = tooltip {
+            div()
+                .id(id)
+                .child(Tooltip::new("tooltip", row, content).right().in_overlay())
+        } else {
+            row
+        }
     }
 }
 
@@ -345,7 +349,7 @@ impl IntoElement for ContextMenu {
         }
 
         // Add menu panel if open
-        if let Some(_position) = state {
+        if let Some(position) = state {
             // Create close handler
             let on_close: Rc<dyn Fn(&mut Window, &mut App) + 'static> = if let Some(ref toggle) = on_toggle {
                 let toggle_for_close = toggle.clone();
@@ -379,23 +383,21 @@ impl IntoElement for ContextMenu {
                     }
                 });
 
-            // Position the panel just below and to the right of the element
-            // Note: We can't use the stored position directly as it's in window coordinates
-            // and we're positioning relative to the container. Instead, we position at the
-            // bottom-left of the triggering element.
-            let positioned_panel = div()
-                .absolute()
-                .left(px(0.0))
-                .top_full()  // Position at 100% of parent height (just below the element)
-                .child(panel)
-                .id("context-menu-panel")
-                .on_mouse_down(MouseButton::Left, |_event, _window, cx| {
-                    // Stop propagation so clicks inside the menu don't hit the backdrop
-                    cx.stop_propagation();
-                })
-                .on_mouse_down(MouseButton::Right, |_event, _window, cx| {
-                    cx.stop_propagation();
-                });
+            // Open the panel at the cursor, below and to the right of it. Near the
+            // window edges it flips above the cursor or shifts back inside.
+            let positioned_panel = AnchoredOverlay::new(
+                div()
+                    .child(panel)
+                    .id("context-menu-panel")
+                    .on_mouse_down(MouseButton::Left, |_event, _window, cx| {
+                        // Stop propagation so clicks inside the menu don't hit the backdrop
+                        cx.stop_propagation();
+                    })
+                    .on_mouse_down(MouseButton::Right, |_event, _window, cx| {
+                        cx.stop_propagation();
+                    }),
+            )
+            .anchor_point(position);
 
             // Use deferred drawing with high priority to ensure menu appears above all other content
             container = container
@@ -407,6 +409,16 @@ impl IntoElement for ContextMenu {
     }
 }
 
+impl Accessible for ContextMenu {
+    fn accessibility_node(&self) -> AccessNode {
+        AccessNode::new(Role::Menu)
+            .id(self.id.to_string())
+            .state(AccessState::Expandable)
+            .state_if(AccessState::Expanded, self.state.is_some())
+            .children(self.items.iter().map(Accessible::accessibility_node))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
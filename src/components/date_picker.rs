@@ -8,6 +8,9 @@ use gpui::*;
 use std::ops::RangeInclusive;
 use std::rc::Rc;
 
+use super::anchored::AnchoredOverlay;
+use crate::state::Binding;
+
 /// Components that can be displayed/selected in the date picker.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum DateComponents {
@@ -43,7 +46,11 @@ pub enum DatePickerStyle {
 ///     .on_change(|new_date, _window, _cx| {
 ///         println!("Date changed: {:?}", new_date);
 ///     })
+///
+/// // Bound to a `State<NaiveDateTime>`
+/// DatePicker::now("due-date").date(State::binding(&self.due_date, cx))
 /// ```
+#[derive(IntoElement)]
 pub struct DatePicker {
     id: ElementId,
     selected: NaiveDateTime,
@@ -55,6 +62,7 @@ pub struct DatePicker {
     is_open: bool,
     on_change: Option<Rc<dyn Fn(NaiveDateTime, &mut Window, &mut App) + 'static>>,
     on_toggle: Option<Rc<dyn Fn(bool, &mut Window, &mut App) + 'static>>,
+    date_binding: Option<Binding<NaiveDateTime>>,
 }
 
 impl DatePicker {
@@ -71,6 +79,7 @@ impl DatePicker {
             is_open: false,
             on_change: None,
             on_toggle: None,
+            date_binding: None,
         }
     }
 
@@ -125,6 +134,15 @@ impl DatePicker {
         self
     }
 
+    /// Sets a two-way binding for the selected date/time.
+    ///
+    /// The bound value replaces the initial selection, and picking a date
+    /// or time writes it back.
+    pub fn date(mut self, binding: Binding<NaiveDateTime>) -> Self {
+        self.date_binding = Some(binding);
+        self
+    }
+
     /// Sets the toggle handler called when the popup opens/closes.
     pub fn on_toggle(
         mut self,
@@ -634,10 +652,19 @@ impl DatePickerColors {
     }
 }
 
-impl IntoElement for DatePicker {
-    type Element = Stateful<Div>;
+impl RenderOnce for DatePicker {
+    fn render(mut self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        if let Some(binding) = self.date_binding.take() {
+            self.selected = binding.get(cx);
+            let handler = self.on_change.take();
+            self.on_change = Some(Rc::new(move |date, window, cx| {
+                binding.set_if_changed(date, cx);
+                if let Some(ref handler) = handler {
+                    handler(date, window, cx);
+                }
+            }));
+        }
 
-    fn into_element(self) -> Self::Element {
         let colors = DatePickerColors::new();
         let label = self.label.clone();
         let label_color = hsla(0.0, 0.0, 0.30, 1.0);
@@ -695,12 +722,7 @@ impl IntoElement for DatePicker {
                         popup_content = popup_content.child(self.build_time_picker(&colors));
                     }
 
-                    // Position the popup below the trigger
                     let popup = div()
-                        .absolute()
-                        .top_full()
-                        .left(px(0.0))
-                        .mt(px(4.0))
                         .id("date-picker-popup")
                         .on_mouse_down(MouseButton::Left, |_event, _window, cx| {
                             cx.stop_propagation();
@@ -718,7 +740,8 @@ impl IntoElement for DatePicker {
                         })
                         .child(popup);
 
-                    container = container.child(popover_overlay);
+                    // Position the popup below the trigger, flipping above when there is no room
+                    container = container.child(AnchoredOverlay::new(popover_overlay).offset(px(4.0)));
                 }
 
                 container
@@ -5,6 +5,8 @@
 use gpui::prelude::*;
 use gpui::*;
 
+use crate::state::Binding;
+
 /// A disclosure group (collapsible section).
 ///
 /// Displays a section header with a disclosure triangle and child items.
@@ -20,12 +22,18 @@ use gpui::*;
 /// })
 ///     .child(SidebarItem::new("main", "main").bold(true))
 ///     .child(SidebarItem::new("dev", "develop"))
+///
+/// // Bound to a `State<bool>`:
+/// DisclosureGroup::with_binding("tags", "Tags", State::binding(&self.tags_expanded, cx))
+///     .child(SidebarItem::new("v1", "v1.0"))
 /// ```
+#[derive(IntoElement)]
 pub struct DisclosureGroup {
     id: ElementId,
     title: SharedString,
     expanded: bool,
     on_toggle: Option<Box<dyn Fn(bool, &mut Window, &mut App) + 'static>>,
+    expanded_binding: Option<Binding<bool>>,
     children: Vec<AnyElement>,
 }
 
@@ -47,6 +55,25 @@ impl DisclosureGroup {
             title: title.into(),
             expanded,
             on_toggle: Some(Box::new(on_toggle)),
+            expanded_binding: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Creates a disclosure group whose expanded state is a two-way binding.
+    ///
+    /// Clicking the header toggles the bound value.
+    pub fn with_binding(
+        id: impl Into<ElementId>,
+        title: impl Into<SharedString>,
+        expanded: Binding<bool>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            expanded: false,
+            on_toggle: None,
+            expanded_binding: Some(expanded),
             children: Vec::new(),
         }
     }
@@ -62,6 +89,7 @@ impl DisclosureGroup {
             title: title.into(),
             expanded,
             on_toggle: None,
+            expanded_binding: None,
             children: Vec::new(),
         }
     }
@@ -80,10 +108,15 @@ impl DisclosureGroup {
     }
 }
 
-impl IntoElement for DisclosureGroup {
-    type Element = Div;
+impl RenderOnce for DisclosureGroup {
+    fn render(mut self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        if let Some(binding) = self.expanded_binding.take() {
+            self.expanded = binding.get(cx);
+            self.on_toggle = Some(Box::new(move |expanded, _window, cx| {
+                binding.set_if_changed(expanded, cx);
+            }));
+        }
 
-    fn into_element(self) -> Self::Element {
         let expanded = self.expanded;
         let new_expanded = !expanded;
 
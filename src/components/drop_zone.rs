@@ -0,0 +1,181 @@
+//! Drop zone component for importing files.
+//!
+//! A dashed area that accepts files dragged in from the file manager.
+
+use gpui::prelude::*;
+use gpui::*;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use super::file_drop::{FileDropTarget, PathsHandler};
+use super::label::Icon;
+use crate::dialogs::FileFilter;
+
+/// An area that files can be dropped onto.
+///
+/// Shows an icon, a title and an optional subtitle inside a dashed border,
+/// or custom content added with `child`. The border highlights while files
+/// the zone accepts are dragged over it.
+///
+/// # Example
+///
+/// ```ignore
+/// DropZone::new("import")
+///     .title("Drop images to import")
+///     .subtitle("PNG or JPEG")
+///     .accept_files(FileFilter::new("Images").pattern("*.png").pattern("*.jpg"))
+///     .on_drop_paths(|paths, _window, cx| import(paths, cx))
+/// ```
+pub struct DropZone {
+    id: ElementId,
+    icon: Icon,
+    title: SharedString,
+    subtitle: Option<SharedString>,
+    min_height: Pixels,
+    filter: Option<FileFilter>,
+    on_drop_paths: Option<PathsHandler>,
+    children: Vec<AnyElement>,
+}
+
+impl DropZone {
+    /// Creates a drop zone with the given id.
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            icon: Icon::Document,
+            title: "Drop files here".into(),
+            subtitle: None,
+            min_height: px(120.0),
+            filter: None,
+            on_drop_paths: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Sets the icon shown above the title (default: `Icon::Document`).
+    pub fn icon(mut self, icon: Icon) -> Self {
+        self.icon = icon;
+        self
+    }
+
+    /// Sets the title (default: "Drop files here").
+    pub fn title(mut self, title: impl Into<SharedString>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Sets a secondary line below the title, such as the accepted types.
+    pub fn subtitle(mut self, subtitle: impl Into<SharedString>) -> Self {
+        self.subtitle = Some(subtitle.into());
+        self
+    }
+
+    /// Sets the minimum height (default: 120px).
+    pub fn min_height(mut self, height: Pixels) -> Self {
+        self.min_height = height;
+        self
+    }
+
+    /// Only accepts files whose names match the filter's patterns.
+    pub fn accept_files(mut self, filter: FileFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Sets the handler called with the accepted paths of a drop.
+    pub fn on_drop_paths(
+        mut self,
+        handler: impl Fn(Vec<PathBuf>, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_drop_paths = Some(Rc::new(handler));
+        self
+    }
+
+    /// Adds content shown instead of the icon and title.
+    pub fn child(mut self, child: impl IntoElement) -> Self {
+        self.children.push(child.into_any_element());
+        self
+    }
+}
+
+impl IntoElement for DropZone {
+    type Element = Stateful<Div>;
+
+    fn into_element(self) -> Self::Element {
+        let corner_radius = px(8.0);
+        let secondary_color = hsla(0.0, 0.0, 0.50, 1.0);
+
+        let content = if self.children.is_empty() {
+            div()
+                .flex()
+                .flex_col()
+                .items_center()
+                .gap(px(4.0))
+                .child(
+                    div()
+                        .text_size(px(28.0))
+                        .text_color(secondary_color)
+                        .child(self.icon.as_str()),
+                )
+                .child(
+                    div()
+                        .text_sm()
+                        .font_weight(FontWeight::MEDIUM)
+                        .text_color(hsla(0.0, 0.0, 0.25, 1.0))
+                        .child(self.title),
+                )
+                .when_some(self.subtitle, |content, subtitle| {
+                    content.child(div().text_xs().text_color(secondary_color).child(subtitle))
+                })
+        } else {
+            div().children(self.children)
+        };
+
+        div()
+            .id(self.id)
+            .relative()
+            .flex()
+            .items_center()
+            .justify_center()
+            .min_h(self.min_height)
+            .p(px(16.0))
+            .rounded(corner_radius)
+            .border_2()
+            .border_dashed()
+            .border_color(hsla(0.0, 0.0, 0.78, 1.0))
+            .bg(hsla(0.0, 0.0, 0.98, 1.0))
+            .child(content)
+            .when_some(self.on_drop_paths, |zone, handler| {
+                // Cover the dashed border, so the highlight replaces it
+                let overlay = FileDropTarget::new(handler, self.filter)
+                    .overlay(corner_radius)
+                    .top(px(-2.0))
+                    .left(px(-2.0))
+                    .right(px(-2.0))
+                    .bottom(px(-2.0));
+                zone.child(overlay)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drop_zone_defaults() {
+        let zone = DropZone::new("import");
+        assert_eq!(zone.title.as_ref(), "Drop files here");
+        assert_eq!(zone.icon, Icon::Document);
+        assert!(zone.filter.is_none());
+        assert!(zone.on_drop_paths.is_none());
+
+        let zone = zone
+            .title("Drop images")
+            .accept_files(FileFilter::new("Images").pattern("*.png"))
+            .on_drop_paths(|_paths, _window, _cx| {});
+        assert_eq!(zone.title.as_ref(), "Drop images");
+        assert!(zone.filter.is_some());
+        assert!(zone.on_drop_paths.is_some());
+    }
+}
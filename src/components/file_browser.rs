@@ -0,0 +1,1296 @@
+//! File browser component.
+//!
+//! A places sidebar next to a breadcrumb path bar and a directory listing shown as a
+//! table or an icon grid. The listing can be sorted, filtered by file type
+//! and shows hidden files on request. It refreshes itself when the shown
+//! folder changes on disk.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let browser = cx.new(|cx| FileBrowserState::new(
+//!     FileBrowser::new("files")
+//!         .directory(dirs::home_dir().unwrap())
+//!         .filter(FileFilter::new("Images").pattern("*.png").pattern("*.jpg"))
+//!         .filter(FileFilter::new("All Files"))
+//!         .on_open(|path, _window, cx| open_document(path, cx)),
+//!     window,
+//!     cx,
+//! ));
+//! ```
+
+use gpui::prelude::*;
+use gpui::*;
+use std::cmp::Ordering;
+use std::ffi::CString;
+use std::fs::File;
+use std::io::{self, Read};
+use std::os::fd::{FromRawFd, OwnedFd};
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use super::breadcrumb::{Breadcrumb, BreadcrumbItem, BreadcrumbState};
+use super::icon_button::IconButton;
+use super::label::Icon;
+use super::lazy_vgrid::{GridColumn, LazyVGrid};
+use super::navigation_split_view::NavigationSplitView;
+use super::picker::Picker;
+use super::sidebar_item::SidebarItem;
+use super::table::{Table, TableColumn};
+use super::toggle::{Toggle, ToggleStyle};
+use crate::dialogs::FileFilter;
+use crate::state::State;
+use crate::utils::path_from_uri;
+
+type PathHandler = Rc<dyn Fn(&Path, &mut Window, &mut App) + 'static>;
+
+/// How a file browser shows the folder's contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FileBrowserViewMode {
+    /// A table with name, size, date and kind columns.
+    #[default]
+    List,
+    /// A grid of large icons.
+    Icons,
+}
+
+/// The attribute a file browser sorts by. Folders always come first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FileSortKey {
+    /// Sort by name, ignoring case.
+    #[default]
+    Name,
+    /// Sort by file size.
+    Size,
+    /// Sort by modification date.
+    Modified,
+    /// Sort by kind, such as "PNG File".
+    Kind,
+}
+
+/// A file or folder listed in a file browser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileEntry {
+    name: SharedString,
+    path: PathBuf,
+    is_dir: bool,
+    size: u64,
+    modified: Option<SystemTime>,
+}
+
+impl FileEntry {
+    /// Reads an entry's metadata, following symlinks.
+    pub fn from_path(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let metadata = std::fs::metadata(&path)?;
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        Ok(Self {
+            name: name.into(),
+            is_dir: metadata.is_dir(),
+            size: if metadata.is_dir() { 0 } else { metadata.len() },
+            modified: metadata.modified().ok(),
+            path,
+        })
+    }
+
+    /// Returns the file name.
+    pub fn name(&self) -> &SharedString {
+        &self.name
+    }
+
+    /// Returns the full path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns true for folders.
+    pub fn is_dir(&self) -> bool {
+        self.is_dir
+    }
+
+    /// Returns the size in bytes, or 0 for folders.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the modification date, if the file system provides one.
+    pub fn modified(&self) -> Option<SystemTime> {
+        self.modified
+    }
+
+    /// Returns true for dotfiles, which are hidden by default.
+    pub fn is_hidden(&self) -> bool {
+        self.name.starts_with('.')
+    }
+
+    /// Returns a description of the entry's type, such as "Folder" or "PNG File".
+    pub fn kind(&self) -> SharedString {
+        if self.is_dir {
+            return "Folder".into();
+        }
+        match Path::new(self.name.as_ref()).extension() {
+            Some(extension) if !self.is_hidden() || self.name.matches('.').count() > 1 => {
+                format!("{} File", extension.to_string_lossy().to_uppercase()).into()
+            }
+            _ => "Document".into(),
+        }
+    }
+}
+
+/// A location shown in a file browser's sidebar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Place {
+    name: SharedString,
+    path: PathBuf,
+}
+
+impl Place {
+    /// Creates a place with a display name.
+    pub fn new(name: impl Into<SharedString>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            name: name.into(),
+            path: path.into(),
+        }
+    }
+
+    /// Returns the display name.
+    pub fn name(&self) -> &SharedString {
+        &self.name
+    }
+
+    /// Returns the location.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the home folder, the XDG user folders that exist, and the
+    /// file system root.
+    pub fn standard() -> Vec<Place> {
+        let mut places = Vec::new();
+        let home = dirs::home_dir();
+        if let Some(ref home) = home {
+            places.push(Place::new("Home", home));
+        }
+
+        let user_dirs = [
+            ("Desktop", dirs::desktop_dir()),
+            ("Documents", dirs::document_dir()),
+            ("Downloads", dirs::download_dir()),
+            ("Music", dirs::audio_dir()),
+            ("Pictures", dirs::picture_dir()),
+            ("Videos", dirs::video_dir()),
+        ];
+        for (name, path) in user_dirs {
+            // Unconfigured XDG folders point at the home folder itself
+            if let Some(path) = path.filter(|path| path.is_dir() && Some(path) != home.as_ref()) {
+                places.push(Place::new(name, path));
+            }
+        }
+
+        places.push(Place::new("Computer", "/"));
+        places
+    }
+
+    /// Returns the bookmarks the user added in GTK file dialogs and file managers.
+    pub fn gtk_bookmarks() -> Vec<Place> {
+        dirs::config_dir()
+            .and_then(|config| std::fs::read_to_string(config.join("gtk-3.0/bookmarks")).ok())
+            .map(|contents| parse_bookmarks(&contents))
+            .unwrap_or_default()
+    }
+}
+
+/// Parses a GTK bookmarks file: one `file://` URI per line, optionally
+/// followed by a space and a display name.
+fn parse_bookmarks(contents: &str) -> Vec<Place> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let (uri, name) = match line.split_once(' ') {
+                Some((uri, name)) => (uri, Some(name.trim())),
+                None => (line.trim(), None),
+            };
+            let path = path_from_uri(uri)?;
+            let name = match name.filter(|name| !name.is_empty()) {
+                Some(name) => name.to_string(),
+                None => path.file_name()?.to_string_lossy().into_owned(),
+            };
+            Some(Place::new(name, path))
+        })
+        .collect()
+}
+
+/// How entries are arranged for display.
+#[derive(Debug, Clone, PartialEq, Default)]
+struct Arrangement {
+    sort_key: FileSortKey,
+    descending: bool,
+    show_hidden: bool,
+    filter: Option<FileFilter>,
+}
+
+impl Arrangement {
+    /// Returns the entries to show, in display order.
+    fn apply(&self, entries: &[FileEntry]) -> Vec<FileEntry> {
+        let mut visible: Vec<FileEntry> = entries
+            .iter()
+            .filter(|entry| self.show_hidden || !entry.is_hidden())
+            .filter(|entry| {
+                entry.is_dir
+                    || self
+                        .filter
+                        .as_ref()
+                        .is_none_or(|filter| filter.matches(&entry.name))
+            })
+            .cloned()
+            .collect();
+        visible.sort_by(|a, b| self.compare(a, b));
+        visible
+    }
+
+    fn compare(&self, a: &FileEntry, b: &FileEntry) -> Ordering {
+        let by_name = || a.name.to_lowercase().cmp(&b.name.to_lowercase());
+        let order = match self.sort_key {
+            FileSortKey::Name => by_name(),
+            FileSortKey::Size => a.size.cmp(&b.size).then_with(by_name),
+            FileSortKey::Modified => a.modified.cmp(&b.modified).then_with(by_name),
+            FileSortKey::Kind => a.kind().cmp(&b.kind()).then_with(by_name),
+        };
+        let order = if self.descending {
+            order.reverse()
+        } else {
+            order
+        };
+        // Folders stay on top in either direction
+        b.is_dir.cmp(&a.is_dir).then(order)
+    }
+}
+
+/// Returns the visible subfolders of a folder as breadcrumb items, by name.
+fn sibling_folders(directory: &Path) -> Vec<BreadcrumbItem> {
+    let mut folders: Vec<FileEntry> = read_entries(directory)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|entry| entry.is_dir && !entry.is_hidden())
+        .collect();
+    folders.sort_by_key(|entry| entry.name.to_lowercase());
+    folders
+        .into_iter()
+        .map(|entry| BreadcrumbItem::new(entry.name, entry.path.display().to_string()))
+        .collect()
+}
+
+/// Reads a folder's entries, including hidden ones.
+fn read_entries(directory: &Path) -> io::Result<Vec<FileEntry>> {
+    let mut entries = Vec::new();
+    for item in std::fs::read_dir(directory)? {
+        // Skip entries that vanish or can't be read, such as broken symlinks
+        if let Ok(entry) = FileEntry::from_path(item?.path()) {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+/// Detects changes to a folder's entries.
+enum FolderWatch {
+    /// An inotify instance watching the folder.
+    Inotify(File),
+    /// The folder's modification time when it was read, used where inotify
+    /// is unavailable, e.g. when the user's watch limit is reached.
+    Stamp(PathBuf, Option<SystemTime>),
+}
+
+impl FolderWatch {
+    /// Starts watching a folder. Call before reading it, so no change is missed.
+    fn new(directory: &Path) -> Self {
+        Self::inotify(directory)
+            .unwrap_or_else(|_| Self::Stamp(directory.to_path_buf(), modification_time(directory)))
+    }
+
+    fn inotify(directory: &Path) -> io::Result<Self> {
+        let path = CString::new(directory.as_os_str().as_bytes())?;
+        // SAFETY: inotify_init1 has no preconditions.
+        let fd = unsafe { libc::inotify_init1(libc::IN_NONBLOCK | libc::IN_CLOEXEC) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: fd is a new descriptor that nothing else owns.
+        let file = File::from(unsafe { OwnedFd::from_raw_fd(fd) });
+        let mask = libc::IN_CREATE
+            | libc::IN_DELETE
+            | libc::IN_MOVED_FROM
+            | libc::IN_MOVED_TO
+            | libc::IN_CLOSE_WRITE
+            | libc::IN_ATTRIB
+            | libc::IN_DELETE_SELF
+            | libc::IN_MOVE_SELF;
+        // SAFETY: fd is open and path is a NUL-terminated string.
+        if unsafe { libc::inotify_add_watch(fd, path.as_ptr(), mask) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self::Inotify(file))
+    }
+
+    /// Returns whether the folder changed since the last call, without blocking.
+    fn changed(&self) -> bool {
+        match self {
+            Self::Inotify(file) => {
+                let mut file: &File = file;
+                let mut events = [0u8; 4096];
+                let mut changed = false;
+                // Drain the queue; only whether there were events matters
+                while let Ok(read) = file.read(&mut events) {
+                    if read == 0 {
+                        break;
+                    }
+                    changed = true;
+                }
+                changed
+            }
+            Self::Stamp(directory, stamp) => modification_time(directory) != *stamp,
+        }
+    }
+}
+
+/// Formats a byte count with decimal units, such as "1.2 MB".
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["kB", "MB", "GB", "TB"];
+    if bytes < 1000 {
+        return format!("{} bytes", bytes);
+    }
+    let mut size = bytes as f64 / 1000.0;
+    let mut unit = 0;
+    while size >= 1000.0 && unit + 1 < UNITS.len() {
+        size /= 1000.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+fn format_modified(modified: Option<SystemTime>) -> String {
+    modified
+        .map(|time| {
+            chrono::DateTime::<chrono::Local>::from(time)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|| "—".to_string())
+}
+
+/// A file browser with a places sidebar, path bar and directory listing.
+///
+/// This is a builder; convert it into a `FileBrowserState` view with
+/// `FileBrowserState::new`.
+///
+/// # Example
+///
+/// ```ignore
+/// FileBrowser::new("downloads")
+///     .directory(dirs::download_dir().unwrap())
+///     .view_mode(FileBrowserViewMode::Icons)
+///     .sort(FileSortKey::Modified, true)
+///     .bookmark(Place::new("Projects", "/home/ada/src"))
+///     .on_open(|path, _window, _cx| println!("Open {}", path.display()))
+/// ```
+pub struct FileBrowser {
+    id: ElementId,
+    directory: Option<PathBuf>,
+    bookmarks: Vec<Place>,
+    gtk_bookmarks: bool,
+    view_mode: FileBrowserViewMode,
+    arrangement: Arrangement,
+    filters: Vec<FileFilter>,
+    watch_interval: Option<Duration>,
+    on_open: Option<PathHandler>,
+    on_navigate: Option<PathHandler>,
+}
+
+impl FileBrowser {
+    /// Creates a file browser showing the home folder.
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            directory: None,
+            bookmarks: Vec::new(),
+            gtk_bookmarks: true,
+            view_mode: FileBrowserViewMode::default(),
+            arrangement: Arrangement::default(),
+            filters: Vec::new(),
+            watch_interval: Some(Duration::from_secs(1)),
+            on_open: None,
+            on_navigate: None,
+        }
+    }
+
+    /// Sets the folder shown first.
+    pub fn directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    /// Adds a bookmark to the sidebar, below the standard places.
+    pub fn bookmark(mut self, place: Place) -> Self {
+        self.bookmarks.push(place);
+        self
+    }
+
+    /// Sets whether the user's GTK bookmarks are shown (default: true).
+    pub fn gtk_bookmarks(mut self, show: bool) -> Self {
+        self.gtk_bookmarks = show;
+        self
+    }
+
+    /// Sets whether the listing is a table or an icon grid.
+    pub fn view_mode(mut self, mode: FileBrowserViewMode) -> Self {
+        self.view_mode = mode;
+        self
+    }
+
+    /// Sets the sort attribute and direction.
+    pub fn sort(mut self, key: FileSortKey, descending: bool) -> Self {
+        self.arrangement.sort_key = key;
+        self.arrangement.descending = descending;
+        self
+    }
+
+    /// Sets whether dotfiles are listed (default: false).
+    pub fn show_hidden(mut self, show: bool) -> Self {
+        self.arrangement.show_hidden = show;
+        self
+    }
+
+    /// Adds a type filter to the filter menu. The first filter is active initially.
+    ///
+    /// Filters only apply to files; folders are always listed.
+    pub fn filter(mut self, filter: FileFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Sets how often the shown folder is checked for changes, or `None`
+    /// to only refresh on navigation (default: every second).
+    pub fn watch_interval(mut self, interval: Option<Duration>) -> Self {
+        self.watch_interval = interval;
+        self
+    }
+
+    /// Sets the handler called when a file is double-clicked or confirmed with Enter.
+    pub fn on_open(mut self, handler: impl Fn(&Path, &mut Window, &mut App) + 'static) -> Self {
+        self.on_open = Some(Rc::new(handler));
+        self
+    }
+
+    /// Sets the handler called after the browser shows another folder.
+    pub fn on_navigate(mut self, handler: impl Fn(&Path, &mut Window, &mut App) + 'static) -> Self {
+        self.on_navigate = Some(Rc::new(handler));
+        self
+    }
+}
+
+/// Internal state for a FileBrowser rendered as a view.
+///
+/// # Example
+///
+/// ```ignore
+/// struct FilesView {
+///     browser: Entity<FileBrowserState>,
+/// }
+///
+/// impl Render for FilesView {
+///     fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+///         div().size_full().child(self.browser.clone())
+///     }
+/// }
+/// ```
+pub struct FileBrowserState {
+    id: ElementId,
+    directory: PathBuf,
+    /// All entries of the folder, including hidden and filtered ones
+    all_entries: Vec<FileEntry>,
+    /// The entries shown, in display order
+    entries: Vec<FileEntry>,
+    /// Detects changes to the folder, once it has been read
+    watch: Option<Arc<FolderWatch>>,
+    error: Option<SharedString>,
+    places: Vec<Place>,
+    bookmarks: Vec<Place>,
+    view_mode: FileBrowserViewMode,
+    arrangement: Arrangement,
+    filters: Vec<FileFilter>,
+    active_filter: usize,
+    filter_menu_open: bool,
+    back: Vec<PathBuf>,
+    forward: Vec<PathBuf>,
+    selection: Entity<State<Option<usize>>>,
+    path_bar: Entity<BreadcrumbState>,
+    scroll_handle: UniformListScrollHandle,
+    focus_handle: FocusHandle,
+    on_open: Option<PathHandler>,
+    on_navigate: Option<PathHandler>,
+    _loading: Option<Task<()>>,
+    _watcher: Option<Task<()>>,
+}
+
+impl FileBrowserState {
+    /// Creates the file browser view from a builder.
+    pub fn new(builder: FileBrowser, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut bookmarks = Vec::new();
+        if builder.gtk_bookmarks {
+            bookmarks.extend(Place::gtk_bookmarks());
+        }
+        bookmarks.extend(builder.bookmarks);
+
+        let watcher = builder.watch_interval.map(|interval| {
+            cx.spawn(async move |this, cx| loop {
+                cx.background_executor().timer(interval).await;
+                let Ok(watch) = this.read_with(cx, |this, _cx| this.watch.clone()) else {
+                    break;
+                };
+                let Some(watch) = watch else {
+                    continue;
+                };
+                let changed = cx
+                    .background_executor()
+                    .spawn(async move { watch.changed() })
+                    .await;
+                if changed {
+                    this.update(cx, |this, cx| this.refresh(cx)).ok();
+                }
+            })
+        });
+
+        let (sibling_view, navigate_view) = (cx.entity().downgrade(), cx.entity().downgrade());
+        let path_bar = cx.new(|cx| {
+            BreadcrumbState::new(
+                Breadcrumb::new("file-browser-path", cx)
+                    .placeholder("Folder path")
+                    .siblings(move |index, cx| {
+                        let Some(view) = sibling_view.upgrade() else {
+                            return Task::ready(Vec::new());
+                        };
+                        let directory = view.read(cx).directory.clone();
+                        let depth = directory.ancestors().count();
+                        let Some(parent) = directory
+                            .ancestors()
+                            .nth(depth - 1 - index)
+                            .and_then(Path::parent)
+                            .map(Path::to_path_buf)
+                        else {
+                            return Task::ready(Vec::new());
+                        };
+                        cx.background_executor()
+                            .spawn(async move { sibling_folders(&parent) })
+                    })
+                    .on_navigate(move |target, window, cx| {
+                        navigate_view
+                            .update(cx, |this, cx| {
+                                this.navigate(PathBuf::from(target.as_ref()), window, cx)
+                            })
+                            .ok();
+                    }),
+                window,
+                cx,
+            )
+        });
+
+        let mut arrangement = builder.arrangement;
+        arrangement.filter = builder.filters.first().cloned();
+
+        let mut this = Self {
+            id: builder.id,
+            directory: PathBuf::new(),
+            all_entries: Vec::new(),
+            entries: Vec::new(),
+            watch: None,
+            error: None,
+            places: Place::standard(),
+            bookmarks,
+            view_mode: builder.view_mode,
+            arrangement,
+            filters: builder.filters,
+            active_filter: 0,
+            filter_menu_open: false,
+            back: Vec::new(),
+            forward: Vec::new(),
+            selection: cx.new(|_| State::new(None)),
+            path_bar,
+            scroll_handle: UniformListScrollHandle::new(),
+            focus_handle: cx.focus_handle(),
+            on_open: builder.on_open,
+            on_navigate: builder.on_navigate,
+            _loading: None,
+            _watcher: watcher,
+        };
+        let directory = builder
+            .directory
+            .or_else(dirs::home_dir)
+            .unwrap_or_else(|| PathBuf::from("/"));
+        this.load(directory, None, cx);
+        this
+    }
+
+    /// Returns the folder being shown.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Returns the listed entries, in display order.
+    pub fn entries(&self) -> &[FileEntry] {
+        &self.entries
+    }
+
+    /// Returns the selected entry.
+    pub fn selected_entry(&self, cx: &App) -> Option<&FileEntry> {
+        self.selection
+            .read(cx)
+            .get()
+            .and_then(|index| self.entries.get(index))
+    }
+
+    /// Returns the sidebar's bookmarks.
+    pub fn bookmarks(&self) -> &[Place] {
+        &self.bookmarks
+    }
+
+    /// Shows another folder, remembering the current one for `go_back`.
+    pub fn navigate(
+        &mut self,
+        directory: impl Into<PathBuf>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let directory = directory.into();
+        if directory == self.directory {
+            return;
+        }
+        self.back.push(self.directory.clone());
+        self.forward.clear();
+        self.load(directory, None, cx);
+        self.navigated(window, cx);
+    }
+
+    /// Shows the previously shown folder.
+    pub fn go_back(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(directory) = self.back.pop() {
+            self.forward.push(self.directory.clone());
+            self.load(directory, None, cx);
+            self.navigated(window, cx);
+        }
+    }
+
+    /// Undoes a `go_back`.
+    pub fn go_forward(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(directory) = self.forward.pop() {
+            self.back.push(self.directory.clone());
+            self.load(directory, None, cx);
+            self.navigated(window, cx);
+        }
+    }
+
+    /// Shows the enclosing folder.
+    pub fn go_up(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(parent) = self.directory.parent() {
+            self.navigate(parent.to_path_buf(), window, cx);
+        }
+    }
+
+    /// Reads the shown folder again, keeping the selected entry selected.
+    pub fn refresh(&mut self, cx: &mut Context<Self>) {
+        let selected = self.selected_entry(cx).map(|entry| entry.path.clone());
+        self.load(self.directory.clone(), selected, cx);
+    }
+
+    /// Sets whether the listing is a table or an icon grid.
+    pub fn set_view_mode(&mut self, mode: FileBrowserViewMode, cx: &mut Context<Self>) {
+        self.view_mode = mode;
+        cx.notify();
+    }
+
+    /// Sets the sort attribute and direction.
+    pub fn set_sort(&mut self, key: FileSortKey, descending: bool, cx: &mut Context<Self>) {
+        self.arrangement.sort_key = key;
+        self.arrangement.descending = descending;
+        self.rearrange(cx);
+    }
+
+    /// Sets whether dotfiles are listed.
+    pub fn set_show_hidden(&mut self, show: bool, cx: &mut Context<Self>) {
+        self.arrangement.show_hidden = show;
+        self.rearrange(cx);
+    }
+
+    /// Activates one of the builder's filters by index.
+    pub fn set_filter(&mut self, index: usize, cx: &mut Context<Self>) {
+        if let Some(filter) = self.filters.get(index) {
+            self.active_filter = index;
+            self.arrangement.filter = Some(filter.clone());
+            self.rearrange(cx);
+        }
+    }
+
+    /// Shows `directory` and reads its entries on a background thread,
+    /// selecting the entry at `selected` once they arrive.
+    ///
+    /// A newer load replaces this one, so a slow folder never overwrites
+    /// the listing of the folder navigated to after it.
+    fn load(&mut self, directory: PathBuf, selected: Option<PathBuf>, cx: &mut Context<Self>) {
+        if directory != self.directory {
+            self.all_entries.clear();
+            self.entries.clear();
+            self.error = None;
+            self.watch = None;
+            self.select(None, cx);
+        }
+        let target = directory.display().to_string();
+        let path_bar_stale = self
+            .path_bar
+            .read(cx)
+            .items()
+            .last()
+            .is_none_or(|item| item.target().as_ref() != target);
+        if path_bar_stale {
+            let trail = BreadcrumbItem::path_trail(&directory);
+            self.path_bar
+                .update(cx, |path_bar, cx| path_bar.set_items(trail, cx));
+        }
+        self.directory = directory.clone();
+        cx.notify();
+
+        self._loading = Some(cx.spawn(async move |this, cx| {
+            let (watch, entries) = cx
+                .background_executor()
+                .spawn({
+                    let directory = directory.clone();
+                    async move { (FolderWatch::new(&directory), read_entries(&directory)) }
+                })
+                .await;
+            this.update(cx, |this, cx| {
+                match entries {
+                    Ok(entries) => {
+                        this.all_entries = entries;
+                        this.error = None;
+                    }
+                    Err(err) => {
+                        this.all_entries.clear();
+                        this.error =
+                            Some(format!("Can't open “{}”: {}", directory.display(), err).into());
+                    }
+                }
+                this.watch = Some(Arc::new(watch));
+                this.entries = this.arrangement.apply(&this.all_entries);
+                let index = selected
+                    .and_then(|path| this.entries.iter().position(|entry| entry.path == path));
+                this.select(index, cx);
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    /// Sorts and filters again after the arrangement changed.
+    fn rearrange(&mut self, cx: &mut Context<Self>) {
+        let selected = self.selected_entry(cx).map(|entry| entry.path.clone());
+        self.entries = self.arrangement.apply(&self.all_entries);
+        let index =
+            selected.and_then(|path| self.entries.iter().position(|entry| entry.path == path));
+        self.select(index, cx);
+        cx.notify();
+    }
+
+    fn select(&mut self, index: Option<usize>, cx: &mut Context<Self>) {
+        self.selection
+            .update(cx, |selection, cx| selection.set(index, cx));
+    }
+
+    fn navigated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.scroll_handle.scroll_to_item(0, ScrollStrategy::Top);
+        if let Some(handler) = self.on_navigate.clone() {
+            handler(&self.directory, window, cx);
+        }
+    }
+
+    /// Opens a folder or reports a file to the open handler.
+    fn activate(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(index).cloned() else {
+            return;
+        };
+        if entry.is_dir {
+            self.navigate(entry.path, window, cx);
+        } else if let Some(handler) = self.on_open.clone() {
+            handler(&entry.path, window, cx);
+        }
+    }
+
+    /// Sorts by a column, toggling the direction if it's already the sort column.
+    fn sort_by_column(&mut self, key: FileSortKey, cx: &mut Context<Self>) {
+        let descending = self.arrangement.sort_key == key && !self.arrangement.descending;
+        self.set_sort(key, descending, cx);
+    }
+
+    fn render_sidebar(&self, cx: &mut Context<Self>) -> Div {
+        let header_color = hsla(0.0, 0.0, 0.45, 1.0);
+        let section =
+            |title: &'static str, places: &[Place], offset: usize, cx: &mut Context<Self>| {
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap(px(2.0))
+                    .child(
+                        div()
+                            .px(px(8.0))
+                            .pt(px(8.0))
+                            .pb(px(2.0))
+                            .text_xs()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(header_color)
+                            .child(title),
+                    )
+                    .children(places.iter().enumerate().map(|(index, place)| {
+                        let path = place.path.clone();
+                        SidebarItem::new(("file-browser-place", offset + index), place.name.clone())
+                            .selected(place.path == self.directory)
+                            .on_click(cx.listener(move |this, _event, window, cx| {
+                                this.navigate(path.clone(), window, cx);
+                            }))
+                    }))
+            };
+
+        div()
+            .flex()
+            .flex_col()
+            .p(px(8.0))
+            .child(section("PLACES", &self.places, 0, cx))
+            .when(!self.bookmarks.is_empty(), |sidebar| {
+                sidebar.child(section("BOOKMARKS", &self.bookmarks, self.places.len(), cx))
+            })
+    }
+
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> Div {
+        let view = cx.entity().downgrade();
+        let filter_picker = (self.filters.len() > 1).then(|| {
+            let (toggle_view, change_view) = (view.clone(), view.clone());
+            let mut picker = Picker::new("file-browser-filter", self.active_filter)
+                .is_open(self.filter_menu_open)
+                .on_toggle(move |open, _window, cx| {
+                    toggle_view
+                        .update(cx, |this, cx| {
+                            this.filter_menu_open = open;
+                            cx.notify();
+                        })
+                        .ok();
+                })
+                .on_change(move |index, _window, cx| {
+                    change_view
+                        .update(cx, |this, cx| {
+                            this.filter_menu_open = false;
+                            this.set_filter(index, cx);
+                        })
+                        .ok();
+                });
+            for filter in &self.filters {
+                picker = picker.option(filter.name().clone());
+            }
+            picker
+        });
+
+        let view_mode = match self.view_mode {
+            FileBrowserViewMode::List => 0,
+            FileBrowserViewMode::Icons => 1,
+        };
+
+        div()
+            .flex()
+            .items_center()
+            .gap(px(8.0))
+            .px(px(8.0))
+            .py(px(6.0))
+            .border_b_1()
+            .border_color(hsla(0.0, 0.0, 0.85, 1.0))
+            .child(
+                IconButton::new("file-browser-back", Icon::ArrowLeft.as_str())
+                    .ghost()
+                    .tooltip("Back")
+                    .disabled(self.back.is_empty())
+                    .on_click(cx.listener(|this, _event, window, cx| this.go_back(window, cx))),
+            )
+            .child(
+                IconButton::new("file-browser-forward", Icon::ArrowRight.as_str())
+                    .ghost()
+                    .tooltip("Forward")
+                    .disabled(self.forward.is_empty())
+                    .on_click(cx.listener(|this, _event, window, cx| this.go_forward(window, cx))),
+            )
+            .child(
+                IconButton::new("file-browser-up", Icon::ArrowUp.as_str())
+                    .ghost()
+                    .tooltip("Enclosing Folder")
+                    .disabled(self.directory.parent().is_none())
+                    .on_click(cx.listener(|this, _event, window, cx| this.go_up(window, cx))),
+            )
+            .child(self.path_bar.clone())
+            .children(filter_picker)
+            .child(
+                Toggle::new(
+                    "file-browser-hidden",
+                    "Hidden",
+                    self.arrangement.show_hidden,
+                )
+                .style(ToggleStyle::Checkbox)
+                .on_change(cx.listener(|this, show: &bool, _window, cx| {
+                    this.set_show_hidden(*show, cx);
+                })),
+            )
+            .child(
+                Picker::new("file-browser-view-mode", view_mode)
+                    .option("List")
+                    .option("Icons")
+                    .segmented()
+                    .on_change(move |index, _window, cx| {
+                        let mode = if index == 0 {
+                            FileBrowserViewMode::List
+                        } else {
+                            FileBrowserViewMode::Icons
+                        };
+                        view.update(cx, |this, cx| this.set_view_mode(mode, cx))
+                            .ok();
+                    }),
+            )
+    }
+
+    fn render_table(&self, cx: &mut Context<Self>) -> Div {
+        let columns = [
+            ("Name", FileSortKey::Name, TableColumn::flex()),
+            ("Size", FileSortKey::Size, TableColumn::fixed(px(90.0))),
+            (
+                "Modified",
+                FileSortKey::Modified,
+                TableColumn::fixed(px(130.0)),
+            ),
+            ("Kind", FileSortKey::Kind, TableColumn::fixed(px(110.0))),
+        ];
+        let header_color = hsla(0.0, 0.0, 0.35, 1.0);
+
+        let header = div()
+            .flex()
+            .items_center()
+            .h(px(24.0))
+            .px(px(8.0))
+            .border_b_1()
+            .border_color(hsla(0.0, 0.0, 0.85, 1.0))
+            .bg(hsla(0.0, 0.0, 0.97, 1.0))
+            .text_xs()
+            .text_color(header_color)
+            .children(columns.iter().map(|(title, key, column)| {
+                let key = *key;
+                let indicator = match (
+                    self.arrangement.sort_key == key,
+                    self.arrangement.descending,
+                ) {
+                    (true, false) => " ▲",
+                    (true, true) => " ▼",
+                    (false, _) => "",
+                };
+                let cell = div()
+                    .id(SharedString::from(format!("file-browser-column-{}", title)))
+                    .cursor_pointer()
+                    .overflow_hidden()
+                    .on_click(cx.listener(move |this, _event, _window, cx| {
+                        this.sort_by_column(key, cx);
+                    }))
+                    .child(format!("{}{}", title, indicator));
+                match column {
+                    TableColumn::Fixed(width) => cell.w(*width).flex_shrink_0(),
+                    TableColumn::Flex => cell.flex_1(),
+                }
+            }));
+
+        let entries = self.entries.clone();
+        let view = cx.entity().downgrade();
+        let confirm_view = view.clone();
+        let table = Table::new(
+            self.id.clone(),
+            entries.len(),
+            move |index, _selected, _window, _cx| {
+                let entry = &entries[index];
+                let icon = if entry.is_dir {
+                    Icon::Folder
+                } else {
+                    Icon::Document
+                };
+                let size = if entry.is_dir {
+                    "—".to_string()
+                } else {
+                    format_size(entry.size)
+                };
+                let view = view.clone();
+                vec![
+                    div()
+                        .id(("file-browser-row", index))
+                        .flex()
+                        .gap(px(6.0))
+                        .overflow_hidden()
+                        .on_click(move |event, window, cx| {
+                            if event.click_count() >= 2 {
+                                view.update(cx, |this, cx| this.activate(index, window, cx))
+                                    .ok();
+                            }
+                        })
+                        .child(icon.as_str())
+                        .child(entry.name.clone())
+                        .into_any_element(),
+                    div().child(size).into_any_element(),
+                    div()
+                        .child(format_modified(entry.modified))
+                        .into_any_element(),
+                    div().child(entry.kind()).into_any_element(),
+                ]
+            },
+        )
+        .columns(columns.into_iter().map(|(_, _, column)| column))
+        .selection(State::binding(&self.selection, cx))
+        .track_scroll(self.scroll_handle.clone())
+        .focusable(self.focus_handle.clone())
+        .on_confirm(move |index, window, cx| {
+            confirm_view
+                .update(cx, |this, cx| this.activate(index, window, cx))
+                .ok();
+        });
+
+        div().flex().flex_col().flex_1().child(header).child(table)
+    }
+
+    fn render_icons(&self, cx: &mut Context<Self>) -> Div {
+        let entries = self.entries.clone();
+        let selected = *self.selection.read(cx).get();
+        let view = cx.entity().downgrade();
+        let selected_bg = hsla(211.0 / 360.0, 0.95, 0.53, 1.0);
+
+        let grid = LazyVGrid::new(
+            self.id.clone(),
+            entries.len(),
+            vec![GridColumn::flexible(1.0); 5],
+            move |index, _window, _cx| {
+                let entry = &entries[index];
+                let is_selected = selected == Some(index);
+                let icon = if entry.is_dir {
+                    Icon::Folder
+                } else {
+                    Icon::Document
+                };
+                let view = view.clone();
+                div()
+                    .id(("file-browser-icon", index))
+                    .flex()
+                    .flex_col()
+                    .items_center()
+                    .gap(px(4.0))
+                    .p(px(8.0))
+                    .cursor_pointer()
+                    .child(div().text_3xl().child(icon.as_str()))
+                    .child(
+                        div()
+                            .max_w_full()
+                            .px(px(4.0))
+                            .rounded(px(3.0))
+                            .overflow_hidden()
+                            .text_xs()
+                            .text_center()
+                            .when(is_selected, |name| {
+                                name.bg(selected_bg).text_color(gpui::white())
+                            })
+                            .child(entry.name.clone()),
+                    )
+                    .on_click(move |event, window, cx| {
+                        view.update(cx, |this, cx| {
+                            this.select(Some(index), cx);
+                            if event.click_count() >= 2 {
+                                this.activate(index, window, cx);
+                            }
+                        })
+                        .ok();
+                    })
+            },
+        )
+        .spacing(px(8.0))
+        .p(px(8.0))
+        .track_scroll(self.scroll_handle.clone());
+
+        div()
+            .flex()
+            .flex_col()
+            .flex_1()
+            .bg(gpui::white())
+            .child(grid)
+    }
+}
+
+fn modification_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+impl Render for FileBrowserState {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let listing = match (&self.error, self.view_mode) {
+            (Some(error), _) => div()
+                .flex()
+                .flex_1()
+                .items_center()
+                .justify_center()
+                .text_sm()
+                .text_color(hsla(0.0, 0.0, 0.45, 1.0))
+                .child(error.clone()),
+            (None, FileBrowserViewMode::List) => self.render_table(cx),
+            (None, FileBrowserViewMode::Icons) => self.render_icons(cx),
+        };
+
+        NavigationSplitView::new(self.id.clone())
+            .sidebar_width(px(180.0))
+            .sidebar(self.render_sidebar(cx))
+            .detail(
+                div()
+                    .flex()
+                    .flex_col()
+                    .size_full()
+                    .child(self.render_toolbar(cx))
+                    .child(listing),
+            )
+    }
+}
+
+impl Focusable for FileBrowserState {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, is_dir: bool, size: u64, modified: u64) -> FileEntry {
+        FileEntry {
+            name: name.to_string().into(),
+            path: PathBuf::from("/data").join(name),
+            is_dir,
+            size,
+            modified: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(modified)),
+        }
+    }
+
+    fn names(entries: &[FileEntry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.name.as_ref()).collect()
+    }
+
+    fn sample() -> Vec<FileEntry> {
+        vec![
+            entry("zeta.txt", false, 300, 3),
+            entry("Alpha.png", false, 100, 2),
+            entry("music", true, 0, 1),
+            entry(".config", true, 0, 4),
+            entry(".bashrc", false, 50, 5),
+            entry("beta.PNG", false, 200, 1),
+        ]
+    }
+
+    #[test]
+    fn test_arrangement_sorts_folders_first() {
+        let entries = sample();
+        let mut arrangement = Arrangement::default();
+        assert_eq!(
+            names(&arrangement.apply(&entries)),
+            vec!["music", "Alpha.png", "beta.PNG", "zeta.txt"]
+        );
+
+        arrangement.sort_key = FileSortKey::Size;
+        arrangement.descending = true;
+        assert_eq!(
+            names(&arrangement.apply(&entries)),
+            vec!["music", "zeta.txt", "beta.PNG", "Alpha.png"]
+        );
+
+        arrangement.sort_key = FileSortKey::Modified;
+        arrangement.descending = false;
+        arrangement.show_hidden = true;
+        assert_eq!(
+            names(&arrangement.apply(&entries)),
+            vec![
+                "music",
+                ".config",
+                "beta.PNG",
+                "Alpha.png",
+                "zeta.txt",
+                ".bashrc"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_arrangement_filters_files_only() {
+        let arrangement = Arrangement {
+            filter: Some(FileFilter::new("Images").pattern("*.png")),
+            ..Arrangement::default()
+        };
+        assert_eq!(
+            names(&arrangement.apply(&sample())),
+            vec!["music", "Alpha.png", "beta.PNG"]
+        );
+    }
+
+    #[test]
+    fn test_entry_kind() {
+        assert_eq!(entry("music", true, 0, 0).kind(), "Folder");
+        assert_eq!(entry("photo.jpeg", false, 0, 0).kind(), "JPEG File");
+        assert_eq!(entry("Makefile", false, 0, 0).kind(), "Document");
+        assert_eq!(entry(".bashrc", false, 0, 0).kind(), "Document");
+        assert_eq!(entry(".config.toml", false, 0, 0).kind(), "TOML File");
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(999), "999 bytes");
+        assert_eq!(format_size(1_500), "1.5 kB");
+        assert_eq!(format_size(2_340_000), "2.3 MB");
+        assert_eq!(format_size(7_000_000_000_000_000), "7000.0 TB");
+    }
+
+    #[test]
+    fn test_parse_bookmarks() {
+        let places = parse_bookmarks(
+            "file:///home/ada/src Projects\nfile:///home/ada/My%20Music\nsftp://server/data Server\n",
+        );
+        assert_eq!(
+            places,
+            vec![
+                Place::new("Projects", "/home/ada/src"),
+                Place::new("My Music", "/home/ada/My Music"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_folder_watch() {
+        let directory = std::env::temp_dir().join(format!("applib-watch-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let watch = FolderWatch::new(&directory);
+        assert!(!watch.changed());
+        // Where inotify is unavailable the watch compares modification
+        // times, so leave the clock room to move on
+        std::thread::sleep(Duration::from_millis(20));
+
+        std::fs::write(directory.join("notes.txt"), "hello").unwrap();
+        assert!(watch.changed());
+        // A refresh reads the folder again under a new watch
+        assert!(!FolderWatch::new(&directory).changed());
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
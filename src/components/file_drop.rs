@@ -0,0 +1,199 @@
+//! Dropping files from the file manager onto components.
+//!
+//! Components with an `on_drop_paths` handler place a `FileDropTarget`
+//! overlay above their content. It highlights while acceptable files are
+//! dragged over it and reports the dropped paths, whether they come from
+//! another application (`ExternalPaths`) or from a `FileDrag` started in
+//! this one. Dragging files out to other applications isn't supported.
+
+use gpui::prelude::*;
+use gpui::*;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use crate::dialogs::FileFilter;
+
+pub(crate) type PathsHandler = Rc<dyn Fn(Vec<PathBuf>, &mut Window, &mut App) + 'static>;
+
+/// A drag carrying file paths, started inside the application.
+///
+/// Attach it to any element with gpui's `on_drag`. Every component with an
+/// `on_drop_paths` handler accepts it like files dragged in from the file
+/// manager. The value also renders the drag preview.
+///
+/// The drag stays inside the application's windows: gpui can't start a
+/// drag that other applications receive, so dropping on the file manager
+/// or another window of a different app does nothing.
+///
+/// # Example
+///
+/// ```ignore
+/// div()
+///     .id(("asset", index))
+///     .on_drag(FileDrag::new(vec![asset.path.clone()]), |drag, _offset, _window, cx| {
+///         cx.new(|_| drag.clone())
+///     })
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDrag {
+    paths: Vec<PathBuf>,
+}
+
+impl FileDrag {
+    /// Creates a drag carrying the given paths.
+    pub fn new(paths: impl IntoIterator<Item = impl Into<PathBuf>>) -> Self {
+        Self {
+            paths: paths.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Returns the dragged paths.
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+}
+
+impl Render for FileDrag {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        let title = match self.paths.as_slice() {
+            [path] => path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string()),
+            paths => format!("{} items", paths.len()),
+        };
+        div()
+            .flex()
+            .items_center()
+            .gap(px(6.0))
+            .px(px(10.0))
+            .py(px(4.0))
+            .rounded(px(4.0))
+            .bg(hsla(0.0, 0.0, 1.0, 0.9))
+            .border_1()
+            .border_color(hsla(0.0, 0.0, 0.80, 1.0))
+            .text_sm()
+            .text_color(hsla(0.0, 0.0, 0.20, 1.0))
+            .child("📄")
+            .child(title)
+    }
+}
+
+/// Returns the paths a drop target with an optional filter accepts.
+///
+/// Paths are matched by name, so folders only pass filters that match
+/// their name or have no patterns.
+pub(crate) fn accepted_paths(filter: Option<&FileFilter>, paths: &[PathBuf]) -> Vec<PathBuf> {
+    paths
+        .iter()
+        .filter(|path| {
+            filter.is_none_or(|filter| {
+                path.file_name()
+                    .is_some_and(|name| filter.matches(&name.to_string_lossy()))
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+/// The drop handling shared by components with `on_drop_paths`.
+#[derive(Clone)]
+pub(crate) struct FileDropTarget {
+    filter: Option<FileFilter>,
+    handler: PathsHandler,
+}
+
+impl FileDropTarget {
+    pub(crate) fn new(handler: PathsHandler, filter: Option<FileFilter>) -> Self {
+        Self { filter, handler }
+    }
+
+    fn accepts(&self, paths: &[PathBuf]) -> bool {
+        !accepted_paths(self.filter.as_ref(), paths).is_empty()
+    }
+
+    fn drop_paths(&self, paths: &[PathBuf], window: &mut Window, cx: &mut App) {
+        let accepted = accepted_paths(self.filter.as_ref(), paths);
+        if !accepted.is_empty() {
+            (self.handler)(accepted, window, cx);
+        }
+    }
+
+    /// Returns the overlay to add as the last child of a `relative`
+    /// container, so it covers the content and highlights above it.
+    ///
+    /// The overlay doesn't block the pointer, so the content stays
+    /// interactive.
+    pub(crate) fn overlay(self, corner_radius: Pixels) -> Div {
+        let highlight = |style: StyleRefinement| {
+            style
+                .bg(hsla(211.0 / 360.0, 0.95, 0.53, 0.08))
+                .border_color(hsla(211.0 / 360.0, 0.95, 0.53, 1.0))
+        };
+        let (external, internal) = (self.clone(), self.clone());
+        let (external_drop, internal_drop) = (self.clone(), self);
+
+        div()
+            .absolute()
+            .top(px(0.0))
+            .left(px(0.0))
+            .right(px(0.0))
+            .bottom(px(0.0))
+            .rounded(corner_radius)
+            .border_2()
+            .border_color(transparent_black())
+            .drag_over::<ExternalPaths>(move |style, paths, _window, _cx| {
+                if external.accepts(paths.paths()) {
+                    highlight(style)
+                } else {
+                    style
+                }
+            })
+            .drag_over::<FileDrag>(move |style, drag, _window, _cx| {
+                if internal.accepts(drag.paths()) {
+                    highlight(style)
+                } else {
+                    style
+                }
+            })
+            .on_drop(move |paths: &ExternalPaths, window, cx| {
+                external_drop.drop_paths(paths.paths(), window, cx);
+            })
+            .on_drop(move |drag: &FileDrag, window, cx| {
+                internal_drop.drop_paths(drag.paths(), window, cx);
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accepted_paths() {
+        let paths = vec![
+            PathBuf::from("/photos/beach.PNG"),
+            PathBuf::from("/photos/notes.txt"),
+            PathBuf::from("/photos/Trips"),
+        ];
+        assert_eq!(accepted_paths(None, &paths), paths);
+
+        let images = FileFilter::new("Images").pattern("*.png").pattern("*.jpg");
+        assert_eq!(
+            accepted_paths(Some(&images), &paths),
+            vec![PathBuf::from("/photos/beach.PNG")]
+        );
+
+        let everything = FileFilter::new("All Files");
+        assert_eq!(accepted_paths(Some(&everything), &paths).len(), 3);
+    }
+
+    #[test]
+    fn test_file_drag_paths() {
+        let drag = FileDrag::new(["/tmp/a.txt", "/tmp/b.txt"]);
+        assert_eq!(
+            drag.paths(),
+            &[PathBuf::from("/tmp/a.txt"), PathBuf::from("/tmp/b.txt")]
+        );
+    }
+}
@@ -6,6 +6,9 @@
 use gpui::prelude::*;
 use gpui::*;
 
+use super::form_model::FormFieldStatus;
+use crate::accessibility::{AccessNode, AccessState, Accessible, Role};
+
 /// A form container for grouping form controls.
 ///
 /// Form provides a vertical layout with consistent spacing between fields.
@@ -22,8 +25,18 @@ use gpui::*;
 ///         section.child(Picker::new(...))
 ///     })
 /// ```
+///
+/// Controls added with `field` and `row` are also described to assistive
+/// technology:
+///
+/// ```ignore
+/// Form::new()
+///     .row(FormRow::new("Email").field(self.email.clone()))
+///     .field(Toggle::new("subscribe", "Subscribe to newsletter", is_on))
+/// ```
 pub struct Form {
     children: Vec<AnyElement>,
+    accessibility: Vec<AccessNode>,
     label_width: Option<Pixels>,
     spacing: Pixels,
     padding: Option<Pixels>,
@@ -34,6 +47,7 @@ impl Form {
     pub fn new() -> Self {
         Self {
             children: Vec::new(),
+            accessibility: Vec::new(),
             label_width: None,
             spacing: px(16.0), // Default spacing between form rows
             padding: Some(px(16.0)),
@@ -78,13 +92,26 @@ impl Form {
         self
     }
 
+    /// Adds a control that describes itself to assistive technology.
+    pub fn field(mut self, field: impl IntoElement + Accessible) -> Self {
+        self.accessibility.push(field.accessibility_node());
+        self.child(field)
+    }
+
+    /// Adds a labeled row to the form.
+    pub fn row(mut self, row: FormRow) -> Self {
+        self.accessibility.push(row.accessibility_node());
+        self.child(row)
+    }
+
     /// Adds a section to the form with a header and grouped fields.
-    pub fn section<F>(self, title: impl Into<SharedString>, build: F) -> Self
+    pub fn section<F>(mut self, title: impl Into<SharedString>, build: F) -> Self
     where
         F: FnOnce(FormSection) -> FormSection,
     {
         let section = FormSection::new(title);
         let section = build(section);
+        self.accessibility.push(section.accessibility_node());
         self.child(section)
     }
 }
@@ -138,6 +165,7 @@ impl IntoElement for Form {
 pub struct FormSection {
     title: SharedString,
     children: Vec<AnyElement>,
+    accessibility: Vec<AccessNode>,
     spacing: Pixels,
     collapsed: bool,
 }
@@ -148,6 +176,7 @@ impl FormSection {
         Self {
             title: title.into(),
             children: Vec::new(),
+            accessibility: Vec::new(),
             spacing: px(12.0), // Slightly tighter spacing within sections
             collapsed: false,
         }
@@ -177,6 +206,18 @@ impl FormSection {
             .extend(children.into_iter().map(|c| c.into_any_element()));
         self
     }
+
+    /// Adds a control that describes itself to assistive technology.
+    pub fn field(mut self, field: impl IntoElement + Accessible) -> Self {
+        self.accessibility.push(field.accessibility_node());
+        self.child(field)
+    }
+
+    /// Adds a labeled row to the section.
+    pub fn row(mut self, row: FormRow) -> Self {
+        self.accessibility.push(row.accessibility_node());
+        self.child(row)
+    }
 }
 
 impl IntoElement for FormSection {
@@ -245,6 +286,9 @@ pub struct FormRow {
     label: SharedString,
     label_width: Option<Pixels>,
     children: Vec<AnyElement>,
+    accessibility: Vec<AccessNode>,
+    error: Option<SharedString>,
+    validating: bool,
 }
 
 impl FormRow {
@@ -254,9 +298,38 @@ impl FormRow {
             label: label.into(),
             label_width: Some(px(120.0)), // Default label width
             children: Vec::new(),
+            accessibility: Vec::new(),
+            error: None,
+            validating: false,
         }
     }
 
+    /// Shows an error message below the controls.
+    pub fn error(mut self, error: impl Into<SharedString>) -> Self {
+        self.error = Some(error.into());
+        self
+    }
+
+    /// Shows a field's validation state from a `FormModel`.
+    ///
+    /// The error is shown once the field is touched, and "Checking…" while
+    /// an async validator runs.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// FormRow::new("Email")
+    ///     .status(form.read(cx).field_status("email", cx))
+    ///     .child(TextField::new("email").text(email))
+    /// ```
+    pub fn status(mut self, status: impl Into<Option<FormFieldStatus>>) -> Self {
+        if let Some(status) = status.into() {
+            self.error = status.visible_error();
+            self.validating = status.validating;
+        }
+        self
+    }
+
     /// Sets a custom width for the label.
     pub fn label_width(mut self, width: impl Into<Pixels>) -> Self {
         self.label_width = Some(width.into());
@@ -275,6 +348,14 @@ impl FormRow {
             .extend(children.into_iter().map(|c| c.into_any_element()));
         self
     }
+
+    /// Adds a control that describes itself to assistive technology.
+    ///
+    /// A control without a name of its own is named by the row's label.
+    pub fn field(mut self, field: impl IntoElement + Accessible) -> Self {
+        self.accessibility.push(field.accessibility_node());
+        self.child(field)
+    }
 }
 
 impl IntoElement for FormRow {
@@ -303,6 +384,22 @@ impl IntoElement for FormRow {
             control_container = control_container.child(child);
         }
 
+        if let Some(error) = self.error {
+            control_container = control_container.child(
+                div()
+                    .text_xs()
+                    .text_color(hsla(0.0, 0.70, 0.45, 1.0))
+                    .child(error),
+            );
+        } else if self.validating {
+            control_container = control_container.child(
+                div()
+                    .text_xs()
+                    .text_color(hsla(0.0, 0.0, 0.50, 1.0))
+                    .child("Checking…"),
+            );
+        }
+
         // Build the row
         div()
             .flex()
@@ -314,6 +411,42 @@ impl IntoElement for FormRow {
     }
 }
 
+impl Accessible for Form {
+    fn accessibility_node(&self) -> AccessNode {
+        AccessNode::new(Role::Form).children(self.accessibility.iter().cloned())
+    }
+}
+
+impl Accessible for FormSection {
+    fn accessibility_node(&self) -> AccessNode {
+        let node = AccessNode::new(Role::Group).name(self.title.clone());
+        if self.collapsed {
+            node
+        } else {
+            node.children(self.accessibility.iter().cloned())
+        }
+    }
+}
+
+impl Accessible for FormRow {
+    fn accessibility_node(&self) -> AccessNode {
+        let fields = self.accessibility.iter().map(|field| {
+            let mut field = field.clone().or_name(&self.label);
+            if let Some(error) = &self.error {
+                if field.description.is_empty() {
+                    field.description = error.clone();
+                }
+                field = field.state(AccessState::Invalid);
+            }
+            field
+        });
+        AccessNode::new(Role::Group)
+            .name(self.label.clone())
+            .state_if(AccessState::Busy, self.validating)
+            .children(fields)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -357,9 +490,63 @@ mod tests {
         assert!(row.label_width.is_some());
     }
 
+    #[test]
+    fn test_form_row_status() {
+        let status = FormFieldStatus {
+            error: Some("Required".into()),
+            ..Default::default()
+        };
+        let row = FormRow::new("Name").status(status.clone());
+        assert_eq!(row.error, None);
+
+        let row = FormRow::new("Name").status(FormFieldStatus {
+            touched: true,
+            ..status
+        });
+        assert_eq!(row.error, Some("Required".into()));
+    }
+
     #[test]
     fn test_form_row_custom_width() {
         let row = FormRow::new("Label").label_width(px(200.0));
         assert_eq!(row.label_width, Some(px(200.0)));
     }
+
+    #[test]
+    fn test_form_accessibility_node() {
+        use crate::components::{Checkbox, Slider};
+
+        let form = Form::new()
+            .row(FormRow::new("Volume").field(Slider::new("volume", 0.5, 0.0..=1.0)))
+            .section("Notifications", |section| {
+                section
+                    .field(Checkbox::new("sounds", "Play sounds").checked(true))
+                    .row(
+                        FormRow::new("Digest")
+                            .error("Choose a day")
+                            .field(Checkbox::new("weekly", "Weekly")),
+                    )
+            })
+            .child(div().child("Not described"));
+        let node = form.accessibility_node();
+        assert_eq!(node.children[0].children[0].id, Some("volume".into()));
+        assert_eq!(
+            node.to_string(),
+            "form\n\
+             \x20 grouping \"Volume\"\n\
+             \x20   slider \"Volume\" value=0.5 range=0..=1 [focusable]\n\
+             \x20 grouping \"Notifications\"\n\
+             \x20   check box \"Play sounds\" [focusable, checkable, checked]\n\
+             \x20   grouping \"Digest\"\n\
+             \x20     check box \"Weekly\" description=\"Choose a day\" [focusable, checkable, invalid]"
+        );
+    }
+
+    #[test]
+    fn test_form_collapsed_section_hides_fields() {
+        let section = FormSection::new("Advanced")
+            .collapsed(true)
+            .row(FormRow::new("Name"));
+        assert!(section.accessibility_node().children.is_empty());
+    }
 }
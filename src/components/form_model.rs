@@ -0,0 +1,650 @@
+//! Form model with validation and dirty tracking.
+//!
+//! `FormModel` collects the bindings a form edits, validates them as they
+//! change, and tracks which fields the user touched and which differ from
+//! their initial values. It runs the submit handler once every field is
+//! valid and can reset the form to where it started. `FormRow` shows a
+//! field's error below its control.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let form = cx.new(|cx| {
+//!     FormModel::new()
+//!         .field(
+//!             "email",
+//!             FormField::new(State::binding(&email, cx))
+//!                 .required("Enter your email address")
+//!                 .validate(|email: &String| {
+//!                     if email.contains('@') {
+//!                         Ok(())
+//!                     } else {
+//!                         Err("Enter a valid email address".into())
+//!                     }
+//!                 }),
+//!             cx,
+//!         )
+//!         .on_submit(|cx| save_account(cx))
+//! });
+//!
+//! let status = form.read(cx).field_status("email", cx);
+//! FormRow::new("Email")
+//!     .status(status)
+//!     .child(TextField::new("email").text(State::binding(&email, cx)))
+//! ```
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use anyhow::Result;
+use gpui::{App, Context, SharedString, Subscription, Task};
+
+use crate::state::Binding;
+
+/// How long a field must stay unchanged before its async validator runs.
+const ASYNC_VALIDATION_DELAY: Duration = Duration::from_millis(300);
+
+type Validator<T> = Rc<dyn Fn(&T) -> Result<(), SharedString>>;
+type AsyncValidator<T> = Rc<dyn Fn(T, &mut App) -> Task<Result<(), SharedString>>>;
+type Check = Rc<dyn Fn(&App) -> Option<SharedString>>;
+type AsyncCheck = Rc<dyn Fn(&mut App) -> Task<Result<(), SharedString>>>;
+type SubmitHandler = Rc<dyn Fn(&mut App) -> Task<Result<()>>>;
+
+/// A field to register with a [`FormModel`]: a binding and its validators.
+///
+/// Validators run in the order they were added, and the first error is
+/// shown. The async validator only runs once the others pass, after the
+/// value stopped changing for a moment.
+pub struct FormField<T: Clone + 'static> {
+    binding: Binding<T>,
+    validators: Vec<Validator<T>>,
+    async_validator: Option<AsyncValidator<T>>,
+}
+
+impl<T: Clone + PartialEq + 'static> FormField<T> {
+    /// Creates a field for the value behind a binding.
+    pub fn new(binding: Binding<T>) -> Self {
+        Self {
+            binding,
+            validators: Vec::new(),
+            async_validator: None,
+        }
+    }
+
+    /// Adds a validator that returns an error message for invalid values.
+    pub fn validate(
+        mut self,
+        validator: impl Fn(&T) -> Result<(), SharedString> + 'static,
+    ) -> Self {
+        self.validators.push(Rc::new(validator));
+        self
+    }
+
+    /// Sets a validator that checks the value asynchronously, e.g. against a server.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// FormField::new(username).validate_async(|name, cx| {
+    ///     cx.background_spawn(async move {
+    ///         if is_username_taken(&name).await {
+    ///             Err("This username is taken".into())
+    ///         } else {
+    ///             Ok(())
+    ///         }
+    ///     })
+    /// })
+    /// ```
+    pub fn validate_async(
+        mut self,
+        validator: impl Fn(T, &mut App) -> Task<Result<(), SharedString>> + 'static,
+    ) -> Self {
+        self.async_validator = Some(Rc::new(validator));
+        self
+    }
+}
+
+impl<T: Clone + PartialEq + Default + 'static> FormField<T> {
+    /// Rejects the default value, such as an empty string, with the given message.
+    pub fn required(self, message: impl Into<SharedString>) -> Self {
+        let message = message.into();
+        self.validate(move |value| {
+            if *value == T::default() {
+                Err(message.clone())
+            } else {
+                Ok(())
+            }
+        })
+    }
+}
+
+/// The validation state of one field, for showing it next to its control.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FormFieldStatus {
+    /// Whether the user changed the field, or tried to submit the form
+    pub touched: bool,
+    /// Whether the value differs from its initial value
+    pub dirty: bool,
+    /// The current validation error, shown or not
+    pub error: Option<SharedString>,
+    /// Whether an async validator is still running
+    pub validating: bool,
+}
+
+impl FormFieldStatus {
+    /// Returns true if the field has no error and no validation is running.
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none() && !self.validating
+    }
+
+    /// Returns the error to show, which is hidden until the field is touched.
+    pub fn visible_error(&self) -> Option<SharedString> {
+        self.error.clone().filter(|_| self.touched)
+    }
+}
+
+/// A registered field with its type-erased operations.
+struct Field {
+    name: SharedString,
+    touched: bool,
+    error: Option<SharedString>,
+    is_dirty: Rc<dyn Fn(&App) -> bool>,
+    reset: Rc<dyn Fn(&mut App)>,
+    commit: Rc<dyn Fn(&App)>,
+    check: Check,
+    check_async: Option<AsyncCheck>,
+    /// The running async validation; dropping it cancels it
+    validation: Option<Task<()>>,
+    _subscription: Option<Subscription>,
+}
+
+/// The state of a form: its fields, their validation and submission.
+///
+/// Each field is validated whenever its binding's state changes. A field
+/// counts as touched once the user changes it away from its initial value,
+/// and errors are only meant to be shown for touched fields, so an empty
+/// form doesn't open covered in errors. Submitting touches every field.
+///
+/// The whole form is dirty if any field is, and valid if every field is.
+pub struct FormModel {
+    fields: Vec<Field>,
+    on_submit: Option<SubmitHandler>,
+    submitting: bool,
+    /// Whether to submit once the running validations finish
+    submit_queued: bool,
+    submit_error: Option<SharedString>,
+    submit_task: Option<Task<()>>,
+}
+
+impl Default for FormModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FormModel {
+    /// Creates a form without fields.
+    pub fn new() -> Self {
+        Self {
+            fields: Vec::new(),
+            on_submit: None,
+            submitting: false,
+            submit_queued: false,
+            submit_error: None,
+            submit_task: None,
+        }
+    }
+
+    /// Registers a field under a name.
+    ///
+    /// The binding's current value becomes the field's initial value.
+    pub fn field<T: Clone + PartialEq + 'static>(
+        mut self,
+        name: impl Into<SharedString>,
+        field: FormField<T>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let name = name.into();
+        let FormField {
+            binding,
+            validators,
+            async_validator,
+        } = field;
+        let initial = Rc::new(RefCell::new(binding.get(cx)));
+
+        let is_dirty = {
+            let (binding, initial) = (binding.clone(), initial.clone());
+            Rc::new(move |cx: &App| binding.get(cx) != *initial.borrow())
+        };
+        let reset = {
+            let (binding, initial) = (binding.clone(), initial.clone());
+            Rc::new(move |cx: &mut App| {
+                let value = initial.borrow().clone();
+                binding.set_if_changed(value, cx);
+            })
+        };
+        let commit = {
+            let binding = binding.clone();
+            Rc::new(move |cx: &App| *initial.borrow_mut() = binding.get(cx))
+        };
+        let check = {
+            let binding = binding.clone();
+            Rc::new(move |cx: &App| {
+                let value = binding.get(cx);
+                validators
+                    .iter()
+                    .find_map(|validator| validator(&value).err())
+            })
+        };
+        let check_async = async_validator.map(|validator| {
+            let binding = binding.clone();
+            Rc::new(move |cx: &mut App| validator(binding.get(cx), cx)) as AsyncCheck
+        });
+
+        let index = self.fields.len();
+        let this = cx.weak_entity();
+        let subscription = binding.observe(
+            move |cx| {
+                this.update(cx, |this, cx| this.field_changed(index, cx))
+                    .ok();
+            },
+            cx,
+        );
+
+        self.fields.push(Field {
+            error: check(cx),
+            name,
+            touched: false,
+            is_dirty,
+            reset,
+            commit,
+            check,
+            check_async,
+            validation: None,
+            _subscription: subscription,
+        });
+        self.validate_async(index, cx);
+        self
+    }
+
+    /// Sets the handler that saves the form once it's valid.
+    ///
+    /// The handler returns a task; the form is submitting until it
+    /// finishes. An error is kept as the submit error, and success makes
+    /// the submitted values the new initial values.
+    pub fn on_submit(mut self, handler: impl Fn(&mut App) -> Task<Result<()>> + 'static) -> Self {
+        self.on_submit = Some(Rc::new(handler));
+        self
+    }
+
+    /// Returns the status of a field, or `None` if there is no field with that name.
+    pub fn field_status(&self, name: &str, cx: &App) -> Option<FormFieldStatus> {
+        let field = self.fields.iter().find(|field| field.name == name)?;
+        Some(FormFieldStatus {
+            touched: field.touched,
+            dirty: (field.is_dirty)(cx),
+            error: field.error.clone(),
+            validating: field.validation.is_some(),
+        })
+    }
+
+    /// Returns true if any field differs from its initial value.
+    pub fn is_dirty(&self, cx: &App) -> bool {
+        self.fields.iter().any(|field| (field.is_dirty)(cx))
+    }
+
+    /// Returns true if any field was touched.
+    pub fn is_touched(&self) -> bool {
+        self.fields.iter().any(|field| field.touched)
+    }
+
+    /// Returns true if no field has an error and no validation is running.
+    pub fn is_valid(&self) -> bool {
+        self.fields
+            .iter()
+            .all(|field| field.error.is_none() && field.validation.is_none())
+    }
+
+    /// Returns true while an async validator is running.
+    pub fn is_validating(&self) -> bool {
+        self.fields.iter().any(|field| field.validation.is_some())
+    }
+
+    /// Returns true while the submit handler is running.
+    pub fn is_submitting(&self) -> bool {
+        self.submitting
+    }
+
+    /// Returns true if the submit button should be enabled.
+    ///
+    /// The form can be submitted while it isn't already submitting and has
+    /// no errors; submitting while validations run waits for them.
+    pub fn can_submit(&self) -> bool {
+        !self.submitting && self.fields.iter().all(|field| field.error.is_none())
+    }
+
+    /// Returns the error from the last submit, if it failed.
+    pub fn submit_error(&self) -> Option<SharedString> {
+        self.submit_error.clone()
+    }
+
+    /// Marks a field as touched, so its error is shown.
+    ///
+    /// Call this when a field loses focus to show errors before the
+    /// user changes the value.
+    pub fn touch(&mut self, name: &str, cx: &mut Context<Self>) {
+        if let Some(field) = self.fields.iter_mut().find(|field| field.name == name) {
+            if !field.touched {
+                field.touched = true;
+                cx.notify();
+            }
+        }
+    }
+
+    /// Validates every field and runs the submit handler if they're valid.
+    ///
+    /// Every field is touched so all errors are shown. If async validators
+    /// are still running, the form submits once they pass.
+    pub fn submit(&mut self, cx: &mut Context<Self>) {
+        if self.submitting {
+            return;
+        }
+        for field in &mut self.fields {
+            field.touched = true;
+            // Keep the async result unless the other validators now fail
+            let error = (field.check)(cx);
+            if error.is_some() || field.check_async.is_none() {
+                field.error = error;
+            }
+        }
+        self.submit_error = None;
+        cx.notify();
+
+        if !self.can_submit() {
+            return;
+        }
+        if self.is_validating() {
+            self.submit_queued = true;
+            return;
+        }
+        let Some(handler) = self.on_submit.clone() else {
+            self.commit(cx);
+            return;
+        };
+
+        self.submitting = true;
+        let task = handler(cx);
+        self.submit_task = Some(cx.spawn(async move |this, cx| {
+            let result = task.await;
+            this.update(cx, |this, cx| {
+                this.submitting = false;
+                this.submit_task = None;
+                match result {
+                    Ok(()) => this.commit(cx),
+                    Err(err) => this.submit_error = Some(err.to_string().into()),
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    /// Restores every field to its initial value and clears touched state and errors.
+    ///
+    /// A running submission is cancelled.
+    pub fn reset(&mut self, cx: &mut Context<Self>) {
+        for field in &self.fields {
+            (field.reset)(cx);
+        }
+        for field in &mut self.fields {
+            field.touched = false;
+            field.validation = None;
+            field.error = (field.check)(cx);
+        }
+        for index in 0..self.fields.len() {
+            self.validate_async(index, cx);
+        }
+        self.submitting = false;
+        self.submit_queued = false;
+        self.submit_task = None;
+        self.submit_error = None;
+        cx.notify();
+    }
+
+    /// Makes the current values the initial values, e.g. after loading saved settings.
+    pub fn commit(&mut self, cx: &mut Context<Self>) {
+        for field in &mut self.fields {
+            (field.commit)(cx);
+            field.touched = false;
+        }
+        cx.notify();
+    }
+
+    fn field_changed(&mut self, index: usize, cx: &mut Context<Self>) {
+        let Some(field) = self.fields.get_mut(index) else {
+            return;
+        };
+        // Values changed back to where they started, e.g. by `reset`, don't touch the field
+        if (field.is_dirty)(cx) {
+            field.touched = true;
+        }
+        field.error = (field.check)(cx);
+        self.validate_async(index, cx);
+        cx.notify();
+    }
+
+    /// Starts the async validator of a field whose other validators pass.
+    fn validate_async(&mut self, index: usize, cx: &mut Context<Self>) {
+        let field = &mut self.fields[index];
+        field.validation = None;
+        let Some(check_async) = field.check_async.clone() else {
+            return;
+        };
+        if field.error.is_some() {
+            return;
+        }
+        field.validation = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(ASYNC_VALIDATION_DELAY).await;
+            let Ok(task) = cx.update(|cx| check_async(cx)) else {
+                return;
+            };
+            let result = task.await;
+            this.update(cx, |this, cx| {
+                let field = &mut this.fields[index];
+                field.validation = None;
+                field.error = result.err();
+                if this.submit_queued && !this.is_validating() {
+                    this.submit_queued = false;
+                    this.submit(cx);
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::State;
+    use gpui::{AppContext as _, Entity, TestAppContext};
+    use std::cell::Cell;
+
+    /// A form with a required email field that counts its submissions.
+    fn email_form(
+        field: impl FnOnce(FormField<String>) -> FormField<String>,
+        cx: &mut TestAppContext,
+    ) -> (Entity<State<String>>, Entity<FormModel>, Rc<Cell<usize>>) {
+        let email = cx.new(|_| State::new(String::new()));
+        let submitted = Rc::new(Cell::new(0));
+        let form = cx.new(|cx| {
+            let submitted = submitted.clone();
+            let binding = State::binding(&email, cx);
+            FormModel::new()
+                .field(
+                    "email",
+                    field(FormField::new(binding).required("Required")),
+                    cx,
+                )
+                .on_submit(move |_cx| {
+                    submitted.set(submitted.get() + 1);
+                    Task::ready(Ok(()))
+                })
+        });
+        (email, form, submitted)
+    }
+
+    fn set(email: &Entity<State<String>>, value: &str, cx: &mut TestAppContext) {
+        email.update(cx, |email, cx| email.set(value.to_string(), cx));
+        cx.run_until_parked();
+    }
+
+    #[test]
+    fn test_field_status() {
+        let status = FormFieldStatus {
+            error: Some("Required".into()),
+            ..Default::default()
+        };
+        assert!(!status.is_valid());
+        assert_eq!(status.visible_error(), None);
+
+        let touched = FormFieldStatus {
+            touched: true,
+            ..status
+        };
+        assert_eq!(touched.visible_error(), Some("Required".into()));
+
+        let validating = FormFieldStatus {
+            validating: true,
+            ..Default::default()
+        };
+        assert!(!validating.is_valid());
+        assert!(FormFieldStatus::default().is_valid());
+    }
+
+    #[test]
+    fn test_required_rejects_default() {
+        let field = FormField::new(Binding::constant(String::new())).required("Required");
+        assert_eq!(
+            field.validators[0](&String::new()),
+            Err(SharedString::from("Required"))
+        );
+        assert_eq!(field.validators[0](&"Ada".to_string()), Ok(()));
+    }
+
+    #[gpui::test]
+    fn test_dirty_and_touched_tracking(cx: &mut TestAppContext) {
+        let (email, form, _) = email_form(|field| field, cx);
+        form.read_with(cx, |form, cx| {
+            assert!(!form.is_dirty(cx));
+            assert!(!form.is_touched());
+            // An untouched empty field has an error, but doesn't show it
+            let status = form.field_status("email", cx).unwrap();
+            assert_eq!(status.error, Some("Required".into()));
+            assert_eq!(status.visible_error(), None);
+        });
+
+        set(&email, "ada@example.com", cx);
+        form.read_with(cx, |form, cx| {
+            assert!(form.is_dirty(cx));
+            assert!(form.is_touched());
+            assert!(form.is_valid());
+        });
+
+        // Changing it back clears the dirty flag but the field stays touched
+        set(&email, "", cx);
+        form.read_with(cx, |form, cx| {
+            assert!(!form.is_dirty(cx));
+            assert!(form.is_touched());
+            let status = form.field_status("email", cx).unwrap();
+            assert_eq!(status.visible_error(), Some("Required".into()));
+        });
+    }
+
+    #[gpui::test]
+    fn test_submit_blocked_by_errors(cx: &mut TestAppContext) {
+        let (email, form, submitted) = email_form(|field| field, cx);
+        form.update(cx, |form, cx| form.submit(cx));
+        cx.run_until_parked();
+        assert_eq!(submitted.get(), 0);
+        form.read_with(cx, |form, cx| {
+            assert!(!form.can_submit());
+            // Submitting shows every error
+            let status = form.field_status("email", cx).unwrap();
+            assert_eq!(status.visible_error(), Some("Required".into()));
+        });
+
+        set(&email, "ada@example.com", cx);
+        form.update(cx, |form, cx| form.submit(cx));
+        cx.run_until_parked();
+        assert_eq!(submitted.get(), 1);
+        // A successful submit makes the values the new baseline
+        form.read_with(cx, |form, cx| {
+            assert!(!form.is_submitting());
+            assert!(!form.is_dirty(cx));
+        });
+    }
+
+    #[gpui::test]
+    fn test_submit_waits_for_async_validation(cx: &mut TestAppContext) {
+        let (email, form, submitted) = email_form(
+            |field| {
+                field.validate_async(|email, _cx| {
+                    Task::ready(if email == "taken@example.com" {
+                        Err("This address is taken".into())
+                    } else {
+                        Ok(())
+                    })
+                })
+            },
+            cx,
+        );
+
+        email.update(cx, |email, cx| email.set("ada@example.com".into(), cx));
+        form.update(cx, |form, cx| form.submit(cx));
+        assert!(form.read_with(cx, |form, _| form.is_validating()));
+        assert_eq!(submitted.get(), 0);
+
+        cx.executor().advance_clock(ASYNC_VALIDATION_DELAY);
+        cx.run_until_parked();
+        assert_eq!(submitted.get(), 1);
+
+        // A failed async validation cancels the queued submit
+        email.update(cx, |email, cx| email.set("taken@example.com".into(), cx));
+        form.update(cx, |form, cx| form.submit(cx));
+        cx.executor().advance_clock(ASYNC_VALIDATION_DELAY);
+        cx.run_until_parked();
+        assert_eq!(submitted.get(), 1);
+        form.read_with(cx, |form, cx| {
+            let status = form.field_status("email", cx).unwrap();
+            assert_eq!(status.error, Some("This address is taken".into()));
+        });
+    }
+
+    #[gpui::test]
+    fn test_reset_and_commit(cx: &mut TestAppContext) {
+        let (email, form, _) = email_form(|field| field, cx);
+        set(&email, "ada@example.com", cx);
+        form.update(cx, |form, cx| form.reset(cx));
+        cx.run_until_parked();
+        assert_eq!(email.read_with(cx, |email, _| email.get().clone()), "");
+        form.read_with(cx, |form, cx| {
+            assert!(!form.is_dirty(cx));
+            assert!(!form.is_touched());
+        });
+
+        // Committing moves the baseline, so a reset keeps the committed value
+        set(&email, "ada@example.com", cx);
+        form.update(cx, |form, cx| form.commit(cx));
+        assert!(form.read_with(cx, |form, cx| !form.is_dirty(cx)));
+        set(&email, "grace@example.com", cx);
+        form.update(cx, |form, cx| form.reset(cx));
+        cx.run_until_parked();
+        assert_eq!(
+            email.read_with(cx, |email, _| email.get().clone()),
+            "ada@example.com"
+        );
+    }
+}
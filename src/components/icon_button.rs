@@ -6,6 +6,9 @@
 use gpui::prelude::*;
 use gpui::*;
 
+use super::tooltip::{Tooltip, TooltipContent};
+use crate::accessibility::{AccessNode, AccessState, Accessible, Role};
+
 /// Size variants for icon buttons.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum IconButtonSize {
@@ -43,8 +46,9 @@ pub struct IconButton {
     icon: SharedString,
     size: IconButtonSize,
     style: IconButtonStyle,
-    tooltip: Option<SharedString>,
+    tooltip: Option<TooltipContent>,
     disabled: bool,
+    focus_handle: Option<FocusHandle>,
     on_click: Option<Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>>,
 }
 
@@ -61,6 +65,7 @@ impl IconButton {
             style: IconButtonStyle::default(),
             tooltip: None,
             disabled: false,
+            focus_handle: None,
             on_click: None,
         }
     }
@@ -89,8 +94,10 @@ impl IconButton {
         self
     }
 
-    /// Sets the tooltip text (shown on hover).
-    pub fn tooltip(mut self, tooltip: impl Into<SharedString>) -> Self {
+    /// Sets the tooltip shown after hovering the button.
+    ///
+    /// Accepts text or rich [`TooltipContent`].
+    pub fn tooltip(mut self, tooltip: impl Into<TooltipContent>) -> Self {
         self.tooltip = Some(tooltip.into());
         self
     }
@@ -101,6 +108,14 @@ impl IconButton {
         self
     }
 
+    /// Makes the button focusable, so Enter and Space click it.
+    ///
+    /// Screen readers are told when the button has focus.
+    pub fn focusable(mut self, focus_handle: FocusHandle) -> Self {
+        self.focus_handle = Some(focus_handle);
+        self
+    }
+
     /// Sets the click handler for this button.
     pub fn on_click(
         mut self,
@@ -152,7 +167,8 @@ impl IntoElement for IconButton {
         let colors = self.colors();
         let (width, height) = self.dimensions();
         let disabled = self.disabled;
-        let tooltip_text = self.tooltip.clone();
+        let tooltip = self.tooltip;
+        let focus_handle = self.focus_handle.filter(|_| !disabled);
 
         // Build the inner button element
         let build_button = |id: ElementId| {
@@ -166,6 +182,10 @@ impl IntoElement for IconButton {
                 .rounded(px(4.0))
                 .text_sm()
                 .font_weight(FontWeight::MEDIUM);
+            let base = match &focus_handle {
+                Some(handle) => base.track_focus(handle),
+                None => base,
+            };
 
             let styled = if disabled {
                 base.bg(hsla(0.0, 0.0, 0.0, 0.0))
@@ -191,8 +211,8 @@ impl IntoElement for IconButton {
             }
         };
 
-        // Wrap with tooltip if provided, using group hover pattern
-        if let Some(text) = tooltip_text {
+        // Wrap with tooltip if provided
+        if let Some(content) = tooltip {
             // Build button with inner ID when wrapped
             let button_id: ElementId = ("icon-btn-inner", 0u32).into();
             let button = build_button(button_id).child(self.icon.clone());
@@ -216,43 +236,11 @@ impl IntoElement for IconButton {
                 with_active
             };
 
-            // Tooltip styling (dark background, light text - system tooltip style)
-            let tooltip_bg = hsla(0.0, 0.0, 0.15, 0.95);
-            let tooltip_text_color = hsla(0.0, 0.0, 0.95, 1.0);
-            let tooltip_border = hsla(0.0, 0.0, 0.25, 1.0);
-
-            let tooltip_panel = div()
-                .absolute()
-                .top(px(-30.0))
-                .left(px(-4.0))
-                .px(px(8.0))
-                .py(px(4.0))
-                .bg(tooltip_bg)
-                .text_color(tooltip_text_color)
-                .text_xs()
-                .rounded(px(4.0))
-                .border_1()
-                .border_color(tooltip_border)
-                .shadow(vec![BoxShadow {
-                    color: hsla(0.0, 0.0, 0.0, 0.3),
-                    offset: point(px(0.0), px(2.0)),
-                    blur_radius: px(6.0),
-                    spread_radius: px(0.0),
-                }])
-                .whitespace_nowrap()
-                .child(text);
-
-            div()
-                .id(self.id)
-                .relative()
-                .group("")
-                .child(with_click)
-                .child(
-                    div()
-                        .invisible()
-                        .group_hover("", |style| style.visible())
-                        .child(tooltip_panel),
-                )
+            let mut tooltip = Tooltip::new("tooltip", with_click, content);
+            if let Some(handle) = focus_handle.clone() {
+                tooltip = tooltip.focus_handle(handle);
+            }
+            div().id(self.id).child(tooltip)
         } else {
             // No tooltip - build button with the main ID
             let button = build_button(self.id).child(self.icon);
@@ -279,6 +267,27 @@ impl IntoElement for IconButton {
     }
 }
 
+/// Icon buttons are named by their tooltip, since the icon itself is a glyph.
+impl Accessible for IconButton {
+    fn accessibility_node(&self) -> AccessNode {
+        let name = self
+            .tooltip
+            .as_ref()
+            .and_then(|tooltip| tooltip.text())
+            .unwrap_or(&self.icon);
+        let node = AccessNode::new(Role::Button)
+            .id(self.id.to_string())
+            .name(name.clone())
+            .state(AccessState::Focusable)
+            .state_if(AccessState::Disabled, self.disabled)
+            .action("click");
+        match &self.focus_handle {
+            Some(handle) => node.focus_handle(handle),
+            None => node,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
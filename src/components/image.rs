@@ -6,8 +6,11 @@
 use gpui::prelude::*;
 use gpui::*;
 use std::path::PathBuf;
+use std::rc::Rc;
 
+use super::file_drop::{FileDropTarget, PathsHandler};
 use crate::components::Icon;
+use crate::dialogs::FileFilter;
 
 /// Source of image data.
 ///
@@ -109,6 +112,8 @@ pub struct Image {
     fit: ImageFit,
     grayscale: bool,
     placeholder: Option<Icon>,
+    on_drop_paths: Option<PathsHandler>,
+    drop_filter: Option<FileFilter>,
 }
 
 impl Image {
@@ -123,6 +128,8 @@ impl Image {
             fit: ImageFit::default(),
             grayscale: false,
             placeholder: None,
+            on_drop_paths: None,
+            drop_filter: None,
         }
     }
 
@@ -182,6 +189,24 @@ impl Image {
         self
     }
 
+    /// Accepts image files dropped from the file manager, or a `FileDrag`,
+    /// for example to let users replace an avatar.
+    ///
+    /// The image highlights while acceptable files are dragged over it.
+    pub fn on_drop_paths(
+        mut self,
+        handler: impl Fn(Vec<PathBuf>, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_drop_paths = Some(Rc::new(handler));
+        self
+    }
+
+    /// Only accepts dropped files whose names match the filter's patterns.
+    pub fn accept_files(mut self, filter: FileFilter) -> Self {
+        self.drop_filter = Some(filter);
+        self
+    }
+
     /// Creates a placeholder element to show when image is loading or failed.
     fn create_placeholder(&self) -> AnyElement {
         let bg_color = hsla(0.0, 0.0, 0.94, 1.0);
@@ -222,7 +247,12 @@ impl Image {
 impl IntoElement for Image {
     type Element = Stateful<Div>;
 
-    fn into_element(self) -> Self::Element {
+    fn into_element(mut self) -> Self::Element {
+        let drop_overlay = self.on_drop_paths.take().map(|handler| {
+            FileDropTarget::new(handler, self.drop_filter.take())
+                .overlay(self.corner_radius.unwrap_or(px(0.0)))
+        });
+
         let source = match self.source {
             Some(ImageSource::Path(path)) => path.to_string_lossy().to_string(),
             Some(ImageSource::Uri(uri)) => uri.to_string(),
@@ -232,7 +262,9 @@ impl IntoElement for Image {
                 let id = self.id.clone();
                 return div()
                     .id(id)
+                    .relative()
                     .child(self.create_placeholder())
+                    .children(drop_overlay)
                     .into_element();
             }
         };
@@ -260,9 +292,11 @@ impl IntoElement for Image {
         }
 
         container
+            .relative()
             .flex()
             .overflow_hidden()
             .child(img_element)
+            .children(drop_overlay)
             .into_element()
     }
 }
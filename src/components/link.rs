@@ -6,6 +6,8 @@
 use gpui::prelude::*;
 use gpui::*;
 
+use crate::accessibility::{AccessNode, AccessState, Accessible, Role};
+
 /// Link text color (blue).
 fn link_color() -> Hsla {
     hsla(211.0 / 360.0, 0.95, 0.53, 1.0)
@@ -98,6 +100,18 @@ impl IntoElement for Link {
     }
 }
 
+/// Links with custom content are named by their URL.
+impl Accessible for Link {
+    fn accessibility_node(&self) -> AccessNode {
+        AccessNode::new(Role::Link)
+            .id(self.id.to_string())
+            .name(self.label.clone().unwrap_or_else(|| self.url.clone()))
+            .description(self.url.clone())
+            .state(AccessState::Focusable)
+            .action("click")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -5,6 +5,7 @@
 //! - Click-to-select items with mouse
 //! - Keyboard navigation (↑/↓ arrows, Cmd+↑/↓ for jump)
 //! - Enter triggers confirm callback
+//! - Optional drag-and-drop reordering (also Alt+↑/↓)
 //! - Virtualized rendering for performance (only visible items are rendered)
 //!
 //! # Example
@@ -41,8 +42,13 @@
 use gpui::prelude::*;
 use gpui::*;
 use std::cell::RefCell;
+use std::path::PathBuf;
 use std::rc::Rc;
 
+use super::file_drop::{FileDropTarget, PathsHandler};
+use super::reorder::{self, ReorderHandler};
+use crate::accessibility::{AccessNode, AccessState, Accessible, Role};
+use crate::dialogs::FileFilter;
 use crate::state::Binding;
 
 // Re-export scroll types for convenience
@@ -85,6 +91,7 @@ pub enum ListStyle {
 /// - **Click selection**: Click any item to select it
 /// - **Keyboard navigation**: Arrow keys navigate, Enter confirms
 /// - **Scroll-to-selection**: Automatically scrolls to keep selection visible
+/// - **Reordering**: Rows can be dragged into place when `on_reorder` is set
 ///
 /// # Example
 ///
@@ -108,6 +115,11 @@ pub struct List {
     focus_handle: Option<FocusHandle>,
     search_field: Option<AnyElement>,
     style: ListStyle,
+    on_reorder: Option<ReorderHandler>,
+    on_drop_paths: Option<PathsHandler>,
+    drop_filter: Option<FileFilter>,
+    accessibility_label: Option<SharedString>,
+    accessibility_item_label: Option<Rc<dyn Fn(usize) -> SharedString>>,
 }
 
 impl List {
@@ -145,6 +157,11 @@ impl List {
             focus_handle: None,
             search_field: None,
             style: ListStyle::default(),
+            on_reorder: None,
+            on_drop_paths: None,
+            drop_filter: None,
+            accessibility_label: None,
+            accessibility_item_label: None,
         }
     }
 
@@ -200,11 +217,60 @@ impl List {
         self
     }
 
+    /// Enables drag-and-drop reordering.
+    ///
+    /// Rows can be dragged to a new position, shown by an insertion line.
+    /// Dragging near the top or bottom edge scrolls a list with a tracked
+    /// scroll handle. When focusable, Alt+↑/Alt+↓ move the selected row.
+    ///
+    /// The handler receives the row's old index and the index it should end
+    /// up at. Move the item in your data to match; the selection binding is
+    /// updated to follow the moved row.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// List::new("items", count, render)
+    ///     .on_reorder(move |from, to, _window, cx| {
+    ///         view.update(cx, |this, cx| {
+    ///             let item = this.items.remove(from);
+    ///             this.items.insert(to, item);
+    ///             cx.notify();
+    ///         }).ok();
+    ///     })
+    /// ```
+    pub fn on_reorder(
+        mut self,
+        handler: impl Fn(usize, usize, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_reorder = Some(Rc::new(handler));
+        self
+    }
+
+    /// Accepts files dropped from the file manager, or a `FileDrag`.
+    ///
+    /// The list highlights while acceptable files are dragged over it, and
+    /// the handler receives the dropped paths.
+    pub fn on_drop_paths(
+        mut self,
+        handler: impl Fn(Vec<PathBuf>, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_drop_paths = Some(Rc::new(handler));
+        self
+    }
+
+    /// Only accepts dropped files whose names match the filter's patterns.
+    pub fn accept_files(mut self, filter: FileFilter) -> Self {
+        self.drop_filter = Some(filter);
+        self
+    }
+
     /// Makes the list focusable and enables keyboard navigation.
     ///
     /// When focusable, the list responds to:
     /// - Arrow keys (↑/↓) to navigate between items
     /// - Cmd+↑/Cmd+↓ to jump to first/last item
+    /// - Alt+↑/Alt+↓ to move the selected item, if reordering is enabled
     /// - Enter to trigger the on_confirm callback
     pub fn focusable(mut self, focus_handle: FocusHandle) -> Self {
         self.focus_handle = Some(focus_handle);
@@ -224,6 +290,124 @@ impl List {
         self.style = style;
         self
     }
+
+    /// Sets the name screen readers announce for the list.
+    pub fn accessibility_label(mut self, label: impl Into<SharedString>) -> Self {
+        self.accessibility_label = Some(label.into());
+        self
+    }
+
+    /// Sets how screen readers name each item.
+    ///
+    /// Rows are rendered as arbitrary elements, so their text has to be
+    /// provided separately.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// List::new("contacts", contacts.len(), render)
+    ///     .accessibility_label("Contacts")
+    ///     .accessibility_item_label(move |index| names[index].clone())
+    /// ```
+    pub fn accessibility_item_label(
+        mut self,
+        label: impl Fn(usize) -> SharedString + 'static,
+    ) -> Self {
+        self.accessibility_item_label = Some(Rc::new(label));
+        self
+    }
+}
+
+/// How many rows are described before a list has been scrolled into view.
+const ACCESSIBLE_ROW_LIMIT: usize = 100;
+
+/// Returns the rows of a list or table to describe to assistive technology.
+///
+/// Like rendering, this only covers the rows in view, taken from the last
+/// layout of the tracked scroll handle. Without one, or before the first
+/// layout, it covers the first [`ACCESSIBLE_ROW_LIMIT`] rows.
+pub(crate) fn accessible_rows(
+    scroll_handle: Option<&UniformListScrollHandle>,
+    count: usize,
+) -> std::ops::Range<usize> {
+    let visible = scroll_handle.and_then(|handle| {
+        let state = handle.0.borrow();
+        let row_height = state.last_item_size?.item.height;
+        if row_height <= px(0.0) {
+            return None;
+        }
+        let top = -state.base_handle.offset().y;
+        let bottom = top + state.base_handle.bounds().size.height;
+        Some((top / row_height).floor() as usize..(bottom / row_height).ceil() as usize)
+    });
+    let rows = visible.unwrap_or(0..ACCESSIBLE_ROW_LIMIT);
+    rows.start.min(count)..rows.end.min(count)
+}
+
+/// Marks `row` selected among the children of `node`, inserting it at
+/// `position` when it is out of view so screen readers can still announce
+/// the selection.
+pub(crate) fn select_row(mut node: AccessNode, row: AccessNode, position: usize) -> AccessNode {
+    match node.children.iter_mut().find(|child| child.id == row.id) {
+        Some(child) => *child = child.clone().state(AccessState::Selected),
+        None => {
+            let position = position.min(node.children.len());
+            node.children
+                .insert(position, row.state(AccessState::Selected));
+        }
+    }
+    node
+}
+
+impl Accessible for List {
+    /// Describes the list with the items in view, plus the selected item.
+    fn accessibility_node(&self) -> AccessNode {
+        let item = {
+            let id = self.id.clone();
+            let label = self.accessibility_item_label.clone();
+            let selectable = self.selection_mode != SelectionMode::None;
+            let confirmable = self.on_confirm.is_some();
+            move |index: usize| {
+                let mut item = AccessNode::new(Role::ListItem)
+                    .id(format!("{}-row-{}", id, index))
+                    .state_if(AccessState::Selectable, selectable)
+                    .action("click");
+                if let Some(label) = &label {
+                    item = item.name(label(index));
+                }
+                if confirmable {
+                    item = item.action("activate");
+                }
+                item
+            }
+        };
+        let rows = accessible_rows(self.scroll_handle.as_ref(), self.item_count);
+        let node = AccessNode::new(Role::List)
+            .id(self.id.to_string())
+            .name(self.accessibility_label.clone().unwrap_or_default())
+            .state_if(
+                AccessState::MultiSelectable,
+                self.selection_mode == SelectionMode::Multiple,
+            )
+            .children(rows.clone().map(&item));
+        let node = match &self.focus_handle {
+            Some(handle) => node.focus_handle(handle),
+            None => node,
+        };
+        let item_count = self.item_count;
+        match self.selection_binding.clone() {
+            Some(binding) if self.selection_mode != SelectionMode::None => {
+                node.resolve_with(move |node, cx| match binding.get(cx) {
+                    Some(index) if index < item_count => {
+                        let position = if index < rows.start { 0 } else { usize::MAX };
+                        select_row(node, item(index), position)
+                    }
+                    _ => node,
+                })
+            }
+            _ => node,
+        }
+    }
 }
 
 impl IntoElement for List {
@@ -237,6 +421,7 @@ impl IntoElement for List {
         let selection_mode = self.selection_mode;
         let on_confirm = self.on_confirm.clone();
         let scroll_handle = self.scroll_handle.clone();
+        let on_reorder = self.on_reorder.clone();
 
         // Shared state for selection that can be accessed in closures
         let current_selection = Rc::new(RefCell::new(None::<usize>));
@@ -254,8 +439,13 @@ impl IntoElement for List {
         let selection_binding_for_render = selection_binding.clone();
         let scroll_handle_for_render = scroll_handle.clone();
         let scroll_handle_for_keydown = scroll_handle.clone();
+        let reorder_owner = on_reorder.as_ref().map(|_| id.clone());
 
         let list_element = uniform_list(id.clone(), item_count, move |range, window, cx| {
+            if let (Some(owner), Some(handle)) = (&reorder_owner, &scroll_handle_for_render) {
+                reorder::auto_scroll(owner, handle, window);
+            }
+
             // Update current selection from binding
             let selected_index = if let Some(ref binding) = selection_binding_for_render {
                 let sel = binding.get(cx);
@@ -274,7 +464,7 @@ impl IntoElement for List {
                     let selection_binding_for_click = selection_binding_for_render.clone();
                     let scroll_handle_for_click = scroll_handle_for_render.clone();
 
-                    div()
+                    let row = div()
                         .id(("list-item", index))
                         .w_full()
                         .cursor_pointer()
@@ -282,15 +472,27 @@ impl IntoElement for List {
                         .on_mouse_down(MouseButton::Left, move |_event, window, cx| {
                             // Update selection on click
                             if let Some(ref binding) = selection_binding_for_click {
-                                binding.set(Some(index), cx);
+                                binding.set_if_changed(Some(index), cx);
                             }
                             // Scroll to clicked item
                             if let Some(ref handle) = scroll_handle_for_click {
                                 handle.scroll_to_item(index, ScrollStrategy::Center);
                             }
                             window.refresh();
-                        })
-                        .into_any_element()
+                        });
+
+                    match reorder_owner {
+                        Some(ref owner) => reorder::reorderable_item(
+                            row,
+                            owner,
+                            index,
+                            item_count,
+                            Axis::Vertical,
+                            None,
+                        )
+                        .into_any_element(),
+                        None => row.into_any_element(),
+                    }
                 })
                 .collect()
         });
@@ -307,7 +509,8 @@ impl IntoElement for List {
 
         // Create container
         let mut container = div()
-            .id(id)
+            .id(id.clone())
+            .relative()
             .flex()
             .flex_col()
             .flex_1()
@@ -315,6 +518,12 @@ impl IntoElement for List {
             .bg(bg_color)
             .overflow_hidden(); // Clip children to rounded corners
 
+        // Accept rows dropped into a new position
+        if let Some(ref handler) = on_reorder {
+            container =
+                reorder::reorderable_rows(container, &id, handler, selection_binding.clone());
+        }
+
         // Apply inset margins for non-plain styles
         if inset != px(0.0) {
             container = container.mx(inset);
@@ -345,6 +554,19 @@ impl IntoElement for List {
                     let mut new_selection: Option<usize> = None;
 
                     match event.keystroke.key.as_str() {
+                        key @ ("up" | "down")
+                            if event.keystroke.modifiers.alt && on_reorder.is_some() =>
+                        {
+                            let Some(from) = current else { return };
+                            let Some(to) = reorder::keyboard_destination(from, item_count, key == "down")
+                            else {
+                                return;
+                            };
+                            if let Some(ref handler) = on_reorder {
+                                handler(from, to, window, cx);
+                            }
+                            new_selection = Some(to);
+                        }
                         "down" if !event.keystroke.modifiers.platform => {
                             if let Some(current) = current {
                                 if current + 1 < item_count {
@@ -389,7 +611,7 @@ impl IntoElement for List {
                         if current != Some(new_idx) {
                             // Update binding
                             if let Some(ref binding) = selection_binding_for_key {
-                                binding.set(Some(new_idx), cx);
+                                binding.set_if_changed(Some(new_idx), cx);
                             }
                             *current_selection_for_keydown.borrow_mut() = Some(new_idx);
 
@@ -405,8 +627,12 @@ impl IntoElement for List {
             );
         }
 
+        let drop_overlay = self
+            .on_drop_paths
+            .map(|handler| FileDropTarget::new(handler, self.drop_filter).overlay(corner_radius));
+
         // Build final layout
-        if let Some(search_field) = self.search_field {
+        let container = if let Some(search_field) = self.search_field {
             // With search field: search header + list
             // Note: Apply top corner radius to search header to match parent's rounded corners
             // Use 12px as a sensible default that works well with common window corner radii
@@ -438,7 +664,8 @@ impl IntoElement for List {
         } else {
             // Just the list
             container.child(list_element)
-        }
+        };
+        container.children(drop_overlay)
     }
 }
 
@@ -547,3 +774,58 @@ impl IntoElement for ListSection {
         container
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_accessibility_node() {
+        let names: Vec<SharedString> = vec!["Ada".into(), "Grace".into()];
+        let list = List::new("contacts", names.len(), |_, _, _, _| {
+            div().into_any_element()
+        })
+        .selection_mode(SelectionMode::Multiple)
+        .accessibility_label("Contacts")
+        .accessibility_item_label(move |index| names[index].clone())
+        .on_confirm(|_, _, _| {});
+        let node = list.accessibility_node();
+        assert_eq!(node.children[1].id, Some("contacts-row-1".into()));
+        assert_eq!(node.children[1].actions, ["click", "activate"]);
+        assert_eq!(
+            node.to_string(),
+            "list \"Contacts\" [multiselectable]\n\
+             \x20 list item \"Ada\" [selectable]\n\
+             \x20 list item \"Grace\" [selectable]"
+        );
+    }
+
+    #[test]
+    fn test_list_without_selection() {
+        let list = List::new("log", 1, |_, _, _, _| div().into_any_element())
+            .selection_mode(SelectionMode::None);
+        assert_eq!(list.accessibility_node().to_string(), "list\n  list item");
+    }
+
+    #[test]
+    fn test_list_describes_rows_in_view() {
+        let list = List::new("log", 1000, |_, _, _, _| div().into_any_element());
+        let node = list.accessibility_node();
+        assert_eq!(node.children.len(), ACCESSIBLE_ROW_LIMIT);
+
+        // A selected row out of view is added, one in view is marked
+        let node = select_row(
+            node,
+            AccessNode::new(Role::ListItem).id("log-row-500"),
+            usize::MAX,
+        );
+        let node = select_row(node, AccessNode::new(Role::ListItem).id("log-row-3"), 0);
+        assert_eq!(node.children.len(), ACCESSIBLE_ROW_LIMIT + 1);
+        assert!(node.children[3].has_state(AccessState::Selected));
+        assert_eq!(
+            node.children[ACCESSIBLE_ROW_LIMIT].id,
+            Some("log-row-500".into())
+        );
+        assert!(node.children[ACCESSIBLE_ROW_LIMIT].has_state(AccessState::Selected));
+    }
+}
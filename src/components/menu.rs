@@ -7,7 +7,10 @@
 use gpui::prelude::*;
 use gpui::*;
 
+use super::anchored::{Align, AnchoredOverlay, Placement, Side};
 use super::label::Icon;
+use super::tooltip::{Tooltip, TooltipContent};
+use crate::accessibility::{AccessNode, AccessState, Accessible, Role};
 
 /// A menu item that can be added to a Menu.
 ///
@@ -28,6 +31,8 @@ pub struct MenuItem {
     pub(crate) icon: Option<Icon>,
     pub(crate) shortcut: Option<SharedString>,
     pub(crate) disabled: bool,
+    pub(crate) tooltip: Option<TooltipContent>,
+    pub(crate) focus_handle: Option<FocusHandle>,
     pub(crate) on_select: Option<Box<dyn Fn(&mut Window, &mut App) + 'static>>,
 }
 
@@ -40,6 +45,8 @@ impl MenuItem {
             icon: None,
             shortcut: None,
             disabled: false,
+            tooltip: None,
+            focus_handle: None,
             on_select: None,
         }
     }
@@ -65,6 +72,22 @@ impl MenuItem {
         self
     }
 
+    /// Sets the tooltip shown beside this menu item on hover.
+    ///
+    /// Also shown for disabled items, e.g. to explain why they are unavailable.
+    pub fn tooltip(mut self, tooltip: impl Into<TooltipContent>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Makes the item focusable, so Enter and Space select it.
+    ///
+    /// The tooltip is also shown while the item has keyboard focus.
+    pub fn focusable(mut self, focus_handle: FocusHandle) -> Self {
+        self.focus_handle = Some(focus_handle);
+        self
+    }
+
     /// Sets the handler called when this menu item is selected.
     pub fn on_select(mut self, handler: impl Fn(&mut Window, &mut App) + 'static) -> Self {
         self.on_select = Some(Box::new(handler));
@@ -192,8 +215,14 @@ impl Menu {
         };
         let shortcut_color = hsla(0.0, 0.0, 0.50, 1.0); // Muted gray for shortcuts
 
+        // When wrapped in a tooltip, the wrapper takes the item's id
+        let (row_id, tooltip) = match item.tooltip {
+            Some(tooltip) => (ElementId::from("menu-item-inner"), Some((item.id, tooltip))),
+            None => (item.id, None),
+        };
+
         let mut row = div()
-            .id(item.id)
+            .id(row_id)
             .flex()
             .flex_row()
             .items_center()
@@ -233,6 +262,11 @@ impl Menu {
             );
         }
 
+        let focus_handle = item.focus_handle.filter(|_| !item.disabled);
+        if let Some(handle) = &focus_handle {
+            row = row.track_focus(handle);
+        }
+
         // Styling and interactivity
         if item.disabled {
             row = row.cursor_default();
@@ -256,7 +290,16 @@ impl Menu {
             }
         }
 
-        row
+        // Menu panels are already deferred, so the tooltip renders in place
+        if let Some((id, content)) = tooltip {
+            let mut tooltip = Tooltip::new("tooltip", row, content).right().in_overlay();
+            if let Some(handle) = focus_handle {
+                tooltip = tooltip.focus_handle(handle);
+            }
+            div().id(id).child(tooltip)
+        } else {
+            row
+        }
     }
 
     /// Builds a divider element.
@@ -334,10 +377,6 @@ impl Menu {
         let shadow_color = hsla(0.0, 0.0, 0.0, 0.15);
 
         let mut panel = div()
-            .absolute()
-            .left_full()
-            .top(px(0.0))
-            .ml(px(-4.0))
             .min_w(px(160.0))
             .bg(panel_bg)
             .rounded(px(6.0))
@@ -361,6 +400,8 @@ impl Menu {
                         icon: item.icon,
                         shortcut: item.shortcut.clone(),
                         disabled: item.disabled,
+                        tooltip: item.tooltip.clone(),
+                        focus_handle: item.focus_handle.clone(),
                         on_select: None, // Can't clone the handler, submenu items won't have actions for now
                     };
                     panel = panel.child(Self::build_menu_item(item_clone, on_dismiss.clone()));
@@ -370,25 +411,49 @@ impl Menu {
                 }
                 MenuContent::Submenu { id, label, icon, items } => {
                     // Nested submenus shown on hover
-                    let submenu_panel = Self::build_submenu_panel(items, on_dismiss.clone());
-                    panel = panel.child(
-                        div()
-                            .relative()
-                            .group("submenu")
-                            .child(Self::build_submenu_trigger(id.clone(), label.clone(), *icon))
-                            .child(
-                                div()
-                                    .invisible()
-                                    .group_hover("submenu", |style| style.visible())
-                                    .child(submenu_panel),
-                            ),
-                    );
+                    panel = panel.child(Self::build_submenu(
+                        id.clone(),
+                        label.clone(),
+                        *icon,
+                        items,
+                        on_dismiss.clone(),
+                    ));
                 }
             }
         }
 
         panel
     }
+
+    /// Builds a submenu row together with its nested panel, shown on hover.
+    ///
+    /// The panel opens to the right of the row, or to the left when there is
+    /// not enough room in the window.
+    ///
+    /// This method is public to allow reuse by ContextMenu and other menu-like components.
+    pub fn build_submenu(
+        id: ElementId,
+        label: SharedString,
+        icon: Option<Icon>,
+        items: &[MenuContent],
+        on_dismiss: impl Fn(&mut Window, &mut App) + Clone + 'static,
+    ) -> Div {
+        let submenu_panel = Self::build_submenu_panel(items, on_dismiss);
+        div()
+            .relative()
+            .group("submenu")
+            .child(Self::build_submenu_trigger(id, label, icon))
+            .child(
+                AnchoredOverlay::new(
+                    div()
+                        .invisible()
+                        .group_hover("submenu", |style| style.visible())
+                        .child(submenu_panel),
+                )
+                .placement(Placement::new(Side::Right, Align::Start))
+                .offset(px(-4.0)),
+            )
+    }
 }
 
 /// Builder for submenu items.
@@ -686,19 +751,7 @@ impl IntoElement for ControlledMenu {
                     }
                     MenuContent::Submenu { id, label, icon, items } => {
                         let dismiss = on_dismiss.clone();
-                        let submenu_panel = Menu::build_submenu_panel(&items, move |window, cx| dismiss(window, cx));
-                        panel = panel.child(
-                            div()
-                                .relative()
-                                .group("submenu")
-                                .child(Menu::build_submenu_trigger(id, label, icon))
-                                .child(
-                                    div()
-                                        .invisible()
-                                        .group_hover("submenu", |style| style.visible())
-                                        .child(submenu_panel),
-                                ),
-                        );
+                        panel = panel.child(Menu::build_submenu(id, label, icon, &items, move |window, cx| dismiss(window, cx)));
                     }
                 }
             }
@@ -715,18 +768,17 @@ impl IntoElement for ControlledMenu {
                     on_dismiss_for_backdrop(window, cx);
                 });
 
-            // Position the panel below the button
-            let positioned_panel = div()
-                .absolute()
-                .top_full()
-                .left(px(0.0))
-                .mt(px(4.0))
-                .child(panel)
-                .id("menu-panel")
-                .on_mouse_down(MouseButton::Left, |_event, _window, cx| {
-                    // Stop propagation so clicks inside the menu don't hit the backdrop
-                    cx.stop_propagation();
-                });
+            // Position the panel below the button, flipping above when there is no room
+            let positioned_panel = AnchoredOverlay::new(
+                div()
+                    .child(panel)
+                    .id("menu-panel")
+                    .on_mouse_down(MouseButton::Left, |_event, _window, cx| {
+                        // Stop propagation so clicks inside the menu don't hit the backdrop
+                        cx.stop_propagation();
+                    }),
+            )
+            .offset(px(4.0));
 
             // Use deferred drawing with high priority to ensure menu appears above all other content
             container = container
@@ -808,10 +860,6 @@ impl IntoElement for Menu {
 
         // Build the menu panel
         let mut panel = div()
-            .absolute()
-            .top_full()
-            .left(px(0.0))
-            .mt(px(4.0))
             .min_w(px(180.0))
             .bg(panel_bg)
             .rounded(px(6.0))
@@ -836,19 +884,7 @@ impl IntoElement for Menu {
                     panel = panel.child(Self::build_divider());
                 }
                 MenuContent::Submenu { id, label, icon, items } => {
-                    let submenu_panel = Self::build_submenu_panel(&items, dismiss);
-                    panel = panel.child(
-                        div()
-                            .relative()
-                            .group("submenu")
-                            .child(Self::build_submenu_trigger(id, label, icon))
-                            .child(
-                                div()
-                                    .invisible()
-                                    .group_hover("submenu", |style| style.visible())
-                                    .child(submenu_panel),
-                            ),
-                    );
+                    panel = panel.child(Self::build_submenu(id, label, icon, &items, dismiss));
                 }
             }
         }
@@ -860,17 +896,84 @@ impl IntoElement for Menu {
             .group("menu")
             .child(button)
             .child(
-                div()
-                    .invisible()
-                    .group_hover("menu", |style| style.visible())
-                    .child(panel),
+                AnchoredOverlay::new(
+                    div()
+                        .invisible()
+                        .group_hover("menu", |style| style.visible())
+                        .child(panel),
+                )
+                .offset(px(4.0)),
             )
     }
 }
 
+impl Accessible for MenuItem {
+    fn accessibility_node(&self) -> AccessNode {
+        let mut node = AccessNode::new(Role::MenuItem)
+            .id(self.id.to_string())
+            .name(self.label.clone())
+            .state(AccessState::Focusable)
+            .state_if(AccessState::Disabled, self.disabled)
+            .action("click");
+        if let Some(shortcut) = &self.shortcut {
+            node = node.shortcut(shortcut.clone());
+        }
+        if let Some(text) = self.tooltip.as_ref().and_then(|tooltip| tooltip.text()) {
+            node = node.description(text.clone());
+        }
+        node
+    }
+}
+
+impl Accessible for MenuContent {
+    fn accessibility_node(&self) -> AccessNode {
+        match self {
+            MenuContent::Item(item) => item.accessibility_node(),
+            MenuContent::Divider => AccessNode::new(Role::Separator),
+            MenuContent::Submenu {
+                id, label, items, ..
+            } => AccessNode::new(Role::Menu)
+                .id(id.to_string())
+                .name(label.clone())
+                .state(AccessState::Focusable)
+                .state(AccessState::HasPopup)
+                .children(items.iter().map(Accessible::accessibility_node)),
+        }
+    }
+}
+
+impl Accessible for Menu {
+    fn accessibility_node(&self) -> AccessNode {
+        AccessNode::new(Role::Menu)
+            .id(self.id.to_string())
+            .name(self.label.clone())
+            .state(AccessState::Focusable)
+            .state(AccessState::HasPopup)
+            .state_if(AccessState::Disabled, self.disabled)
+            .children(self.items.iter().map(Accessible::accessibility_node))
+    }
+}
+
+impl Accessible for ControlledMenu {
+    fn accessibility_node(&self) -> AccessNode {
+        AccessNode::new(Role::Menu)
+            .id(self.id.to_string())
+            .name(self.label.clone())
+            .state(AccessState::Focusable)
+            .state(AccessState::Expandable)
+            .state_if(AccessState::Expanded, self.is_open)
+            .state(AccessState::HasPopup)
+            .state_if(AccessState::Disabled, self.disabled)
+            .children(self.items.iter().map(Accessible::accessibility_node))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    // `use gpui::*` brings in gpui's `test` attribute, which `#[gpui::test]`
+    // expands into; keep `#[test]` the built-in one
+    use std::prelude::v1::test;
 
     #[test]
     fn test_menu_item_creation() {
@@ -888,6 +991,15 @@ mod tests {
         assert_eq!(item.icon.unwrap(), Icon::Document);
     }
 
+    #[gpui::test]
+    fn test_menu_item_focusable(cx: &mut gpui::TestAppContext) {
+        let handle = cx.update(|cx| cx.focus_handle());
+        let item = MenuItem::new("copy", "Copy");
+        assert!(item.focus_handle.is_none());
+        let item = item.focusable(handle.clone());
+        assert_eq!(item.focus_handle, Some(handle));
+    }
+
     #[test]
     fn test_menu_item_with_shortcut() {
         let item = MenuItem::new("test", "Copy").shortcut("⌘C");
@@ -900,6 +1012,12 @@ mod tests {
         assert!(item.disabled);
     }
 
+    #[test]
+    fn test_menu_item_with_tooltip() {
+        let item = MenuItem::new("test", "Export").tooltip("Requires a saved document");
+        assert!(item.tooltip.is_some());
+    }
+
     #[test]
     fn test_menu_creation() {
         let menu = Menu::new("test-menu", "Actions");
@@ -944,4 +1062,27 @@ mod tests {
         let menu = ControlledMenu::new("test-menu", "Actions", true);
         assert!(menu.is_open);
     }
+
+    #[test]
+    fn test_menu_accessibility_node() {
+        let menu = ControlledMenu::new("edit-menu", "Edit", true)
+            .item(MenuItem::new("copy", "Copy").shortcut("Ctrl+C"))
+            .item(MenuItem::new("paste", "Paste").disabled(true))
+            .divider()
+            .submenu("share", "Share", |sub| {
+                sub.item(MenuItem::new("email", "Email").tooltip("Opens your mail app"))
+            });
+        let node = menu.accessibility_node();
+        assert_eq!(node.children[0].id, Some("copy".into()));
+        assert_eq!(node.children[0].shortcut, Some("Ctrl+C".into()));
+        assert_eq!(
+            node.to_string(),
+            "menu \"Edit\" [focusable, expandable, expanded, has-popup]\n\
+             \x20 menu item \"Copy\" [focusable]\n\
+             \x20 menu item \"Paste\" [disabled, focusable]\n\
+             \x20 separator\n\
+             \x20 menu \"Share\" [focusable, has-popup]\n\
+             \x20   menu item \"Email\" description=\"Opens your mail app\" [focusable]"
+        );
+    }
 }
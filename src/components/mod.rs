@@ -4,19 +4,26 @@
 //! These components can be used across multiple applications.
 
 mod alert;
+mod anchored;
 mod badge;
+mod breadcrumb;
 mod button;
 mod canvas;
 mod checkbox;
 mod color_picker;
+mod combo_box;
 mod context_menu;
 mod color_view;
 mod date_picker;
 mod disclosure_group;
 mod divider;
+mod drop_zone;
 mod empty_state;
 mod empty_view;
+mod file_browser;
+mod file_drop;
 mod form;
+mod form_model;
 mod group_box;
 mod hstack;
 mod icon_button;
@@ -29,10 +36,13 @@ mod label;
 mod link;
 mod list;
 mod menu;
+mod modal;
 mod navigation_split_view;
+mod outline_view;
 mod panel;
 mod picker;
 mod popover;
+mod reorder;
 mod list_item;
 mod progress_view;
 mod radio_group;
@@ -52,6 +62,8 @@ mod text;
 mod text_area;
 mod text_field;
 mod title_bar;
+mod toast;
+mod token_field;
 mod toggle;
 mod tooltip;
 mod traffic_lights;
@@ -60,7 +72,9 @@ mod window_frame;
 mod zstack;
 
 pub use alert::{Alert, AlertButton, AlertButtonRole, AlertIcon};
+pub use anchored::{Align, AnchoredOverlay, Placement, Side};
 pub use badge::Badge;
+pub use breadcrumb::{Breadcrumb, BreadcrumbItem, BreadcrumbState};
 pub use button::{Button, ButtonStyle};
 pub use canvas::{
     Canvas, CornerRadii, DrawingContext, FillStyle, PathBuilder, Point2D, Rect2D, Size2D,
@@ -68,14 +82,21 @@ pub use canvas::{
 };
 pub use checkbox::Checkbox;
 pub use color_picker::{ColorPicker, ColorPickerState};
+pub use combo_box::{ComboBox, ComboBoxState};
 pub use context_menu::ContextMenu;
 pub use color_view::ColorView;
 pub use date_picker::{DateComponents, DatePicker, DatePickerStyle};
 pub use disclosure_group::DisclosureGroup;
 pub use divider::Divider;
+pub use drop_zone::DropZone;
 pub use empty_state::EmptyState;
 pub use empty_view::EmptyView;
+pub use file_browser::{
+    FileBrowser, FileBrowserState, FileBrowserViewMode, FileEntry, FileSortKey, Place,
+};
+pub use file_drop::FileDrag;
 pub use form::{Form, FormRow, FormSection};
+pub use form_model::{FormField, FormFieldStatus, FormModel};
 pub use group_box::GroupBox;
 pub use hstack::HStack;
 pub use icon_button::{IconButton, IconButtonSize, IconButtonStyle};
@@ -89,9 +110,11 @@ pub use link::Link;
 pub use list::{List, ListSection, ListStyle, ScrollStrategy, SelectionMode, UniformListScrollHandle};
 pub use list_item::ListItem;
 pub use menu::{ControlledMenu, Menu, MenuContent, MenuItem, SubMenuBuilder};
+pub use modal::{ModalHost, ModalId, ModalPresenter, Modals};
 pub use navigation_split_view::NavigationSplitView;
+pub use outline_view::{OutlineNode, OutlineView, OutlineViewState};
 pub use panel::{Panel, PanelBackground};
-pub use picker::{Picker, PickerStyle};
+pub use picker::{Picker, PickerOption, PickerState, PickerStyle};
 pub use popover::{ControlledPopover, Popover, PopoverEdge};
 pub use progress_view::{ProgressStyle, ProgressView};
 pub use radio_group::RadioGroup;
@@ -111,9 +134,11 @@ pub use text::{text_accent, text_link, text_primary, text_secondary, text_tertia
 pub use text_area::{TextArea, TextAreaState};
 pub use text_field::{TextField, TextFieldState};
 pub use title_bar::TitleBar;
+pub use toast::{Toast, ToastCorner, ToastHost, ToastId, Toasts};
+pub use token_field::{TokenField, TokenFieldState};
 pub use toggle::{Toggle, ToggleStyle};
-pub use tooltip::{Tooltip, TooltipPosition, TooltipState};
+pub use tooltip::{Tooltip, TooltipContent, TooltipPosition, TooltipState};
 pub use traffic_lights::TrafficLights;
 pub use vstack::VStack;
-pub use window_frame::WindowFrame;
+pub use window_frame::{WindowChrome, WindowFrame};
 pub use zstack::{ZStack, ZStackAlignment};
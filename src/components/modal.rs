@@ -0,0 +1,635 @@
+//! Window-level presentation of sheets and alerts.
+//!
+//! [`Modals`] keeps a stack of presented sheets and alerts for each window, and
+//! a [`ModalHost`] draws the stack over the window's content. Presented modals
+//! dim the window, keep keyboard focus inside the topmost modal, and give
+//! focus back to the element that had it when they are dismissed.
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use gpui::prelude::*;
+use gpui::*;
+
+use super::{Alert, AlertButtonRole, Sheet};
+
+/// Identifies a presented sheet or alert.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModalId(usize);
+
+type SheetBuilder = Rc<dyn Fn(&mut Window, &mut App) -> Sheet + 'static>;
+type AlertBuilder = Rc<dyn Fn(&mut Window, &mut App) -> Alert + 'static>;
+
+#[derive(Clone)]
+enum ModalContent {
+    Sheet(SheetBuilder),
+    Alert(AlertBuilder),
+}
+
+struct ModalEntry {
+    id: ModalId,
+    content: ModalContent,
+    focus_handle: FocusHandle,
+    /// The element that had focus when the modal was presented.
+    restore_focus: Option<FocusHandle>,
+}
+
+/// The presented sheets and alerts of every window.
+///
+/// Modals are stacked in the order they were presented; only the topmost one
+/// receives input. Sheets and alerts are rebuilt from their builder on every
+/// render, so they can show the latest state of the views they capture.
+///
+/// Most code presents modals through the [`ModalPresenter`] methods on
+/// [`Window`] rather than calling `Modals` directly.
+///
+/// # Example
+///
+/// ```ignore
+/// let id = Modals::present_alert(window, cx, |_window, _cx| {
+///     Alert::new("Connection lost")
+///         .button(AlertButton::new("OK"))
+/// });
+/// Modals::dismiss(id, window, cx);
+/// ```
+#[derive(Default)]
+pub struct Modals {
+    windows: HashMap<WindowId, Vec<ModalEntry>>,
+    next_id: usize,
+}
+
+impl Global for Modals {}
+
+impl Modals {
+    /// Presents a sheet on top of the window's modal stack.
+    pub fn present_sheet(
+        window: &mut Window,
+        cx: &mut App,
+        build: impl Fn(&mut Window, &mut App) -> Sheet + 'static,
+    ) -> ModalId {
+        Self::present(ModalContent::Sheet(Rc::new(build)), window, cx)
+    }
+
+    /// Presents an alert on top of the window's modal stack.
+    ///
+    /// Clicking any of the alert's buttons dismisses it after running the
+    /// button's handler.
+    pub fn present_alert(
+        window: &mut Window,
+        cx: &mut App,
+        build: impl Fn(&mut Window, &mut App) -> Alert + 'static,
+    ) -> ModalId {
+        Self::present(ModalContent::Alert(Rc::new(build)), window, cx)
+    }
+
+    /// Dismisses a presented modal.
+    ///
+    /// If it was the topmost modal, focus returns to the element that had it
+    /// when the modal was presented.
+    pub fn dismiss(id: ModalId, window: &mut Window, cx: &mut App) {
+        let window_id = window.window_handle().window_id();
+        let removed = cx.update_default_global(|modals: &mut Self, _cx| {
+            let stack = modals.windows.get_mut(&window_id)?;
+            let index = stack.iter().position(|entry| entry.id == id)?;
+            let was_top = index + 1 == stack.len();
+            let entry = stack.remove(index);
+            if stack.is_empty() {
+                modals.windows.remove(&window_id);
+            }
+            Some((entry, was_top))
+        });
+
+        if let Some((entry, true)) = removed {
+            match entry.restore_focus {
+                Some(handle) => window.focus(&handle),
+                None => window.blur(),
+            }
+        }
+        window.refresh();
+    }
+
+    /// Dismisses the window's topmost modal. Returns `false` if there was none.
+    pub fn dismiss_top(window: &mut Window, cx: &mut App) -> bool {
+        match Self::top(window, cx) {
+            Some(id) => {
+                Self::dismiss(id, window, cx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the window's topmost modal.
+    pub fn top(window: &Window, cx: &App) -> Option<ModalId> {
+        Self::ids(window, cx).last().copied()
+    }
+
+    /// Returns the window's presented modals, bottom to top.
+    pub fn ids(window: &Window, cx: &App) -> Vec<ModalId> {
+        let window_id = window.window_handle().window_id();
+        cx.try_global::<Self>()
+            .and_then(|modals| modals.windows.get(&window_id))
+            .map(|stack| stack.iter().map(|entry| entry.id).collect())
+            .unwrap_or_default()
+    }
+
+    fn present(content: ModalContent, window: &mut Window, cx: &mut App) -> ModalId {
+        let window_id = window.window_handle().window_id();
+        let focus_handle = cx.focus_handle();
+        let restore_focus = window.focused(cx);
+
+        let id = cx.update_default_global(|modals: &mut Self, _cx| {
+            let id = ModalId(modals.next_id);
+            modals.next_id += 1;
+            modals
+                .windows
+                .entry(window_id)
+                .or_default()
+                .push(ModalEntry {
+                    id,
+                    content,
+                    focus_handle: focus_handle.clone(),
+                    restore_focus,
+                });
+            id
+        });
+
+        window.focus(&focus_handle);
+        window.refresh();
+        id
+    }
+
+    fn content(id: ModalId, window: &Window, cx: &App) -> Option<ModalContent> {
+        let window_id = window.window_handle().window_id();
+        cx.try_global::<Self>()?
+            .windows
+            .get(&window_id)?
+            .iter()
+            .find(|entry| entry.id == id)
+            .map(|entry| entry.content.clone())
+    }
+
+    /// Handles Escape or a backdrop click: runs the alert's cancel button or
+    /// the modal's dismiss handler, then dismisses the modal.
+    fn cancel(id: ModalId, window: &mut Window, cx: &mut App) {
+        let Some(content) = Self::content(id, window, cx) else {
+            return;
+        };
+        let event = ClickEvent::default();
+        match content {
+            ModalContent::Sheet(build) => {
+                if let Some(handler) = build(window, cx).take_dismiss_handler() {
+                    handler(&event, window, cx);
+                }
+            }
+            ModalContent::Alert(build) => {
+                let mut alert = build(window, cx);
+                let handler = alert
+                    .take_button_handler(AlertButtonRole::Cancel)
+                    .or_else(|| alert.take_dismiss_handler());
+                if let Some(handler) = handler {
+                    handler(&event, window, cx);
+                }
+            }
+        }
+        Self::dismiss(id, window, cx);
+    }
+
+    /// Handles Enter: runs the alert's default button, then dismisses it.
+    fn confirm(id: ModalId, window: &mut Window, cx: &mut App) -> bool {
+        let Some(ModalContent::Alert(build)) = Self::content(id, window, cx) else {
+            return false;
+        };
+        let mut alert = build(window, cx);
+        if !alert.has_button(AlertButtonRole::Default) {
+            return false;
+        }
+        if let Some(handler) = alert.take_button_handler(AlertButtonRole::Default) {
+            handler(&ClickEvent::default(), window, cx);
+        }
+        Self::dismiss(id, window, cx);
+        true
+    }
+
+    /// Removes the modals of a closed window.
+    fn remove_window(window_id: WindowId, cx: &mut App) {
+        if cx.has_global::<Self>() {
+            cx.update_global(|modals: &mut Self, _cx| {
+                modals.windows.remove(&window_id);
+            });
+        }
+    }
+}
+
+/// Presents sheets and alerts over a window's content.
+///
+/// Implemented for [`Window`], so modals can be presented from any listener
+/// or render method. The window needs a [`ModalHost`] to draw them.
+///
+/// # Example
+///
+/// ```ignore
+/// Button::new("delete", "Delete…").on_click(|_event, window, cx| {
+///     window.present_alert(cx, |_window, _cx| {
+///         Alert::new("Delete Item?")
+///             .message("This action cannot be undone.")
+///             .icon(AlertIcon::Warning)
+///             .button(AlertButton::cancel("Cancel"))
+///             .button(AlertButton::destructive("Delete", |_, _, cx| delete_item(cx)))
+///     });
+/// })
+/// ```
+pub trait ModalPresenter {
+    /// Presents a sheet on top of any other modals.
+    ///
+    /// The sheet's dismiss handler runs when Escape is pressed or the
+    /// backdrop is clicked, and the sheet is dismissed afterwards.
+    fn present_sheet(
+        &mut self,
+        cx: &mut App,
+        build: impl Fn(&mut Window, &mut App) -> Sheet + 'static,
+    ) -> ModalId;
+
+    /// Presents an alert on top of any other modals.
+    ///
+    /// Enter activates the first [`AlertButtonRole::Default`] button and
+    /// Escape the first [`AlertButtonRole::Cancel`] button. Any button click
+    /// dismisses the alert.
+    fn present_alert(
+        &mut self,
+        cx: &mut App,
+        build: impl Fn(&mut Window, &mut App) -> Alert + 'static,
+    ) -> ModalId;
+
+    /// Dismisses a presented sheet or alert.
+    fn dismiss_modal(&mut self, id: ModalId, cx: &mut App);
+
+    /// Dismisses the topmost sheet or alert, e.g. from a sheet's Done button.
+    fn dismiss_top_modal(&mut self, cx: &mut App) -> bool;
+
+    /// Returns whether a sheet or alert is presented.
+    fn has_modal(&self, cx: &App) -> bool;
+}
+
+impl ModalPresenter for Window {
+    fn present_sheet(
+        &mut self,
+        cx: &mut App,
+        build: impl Fn(&mut Window, &mut App) -> Sheet + 'static,
+    ) -> ModalId {
+        Modals::present_sheet(self, cx, build)
+    }
+
+    fn present_alert(
+        &mut self,
+        cx: &mut App,
+        build: impl Fn(&mut Window, &mut App) -> Alert + 'static,
+    ) -> ModalId {
+        Modals::present_alert(self, cx, build)
+    }
+
+    fn dismiss_modal(&mut self, id: ModalId, cx: &mut App) {
+        Modals::dismiss(id, self, cx);
+    }
+
+    fn dismiss_top_modal(&mut self, cx: &mut App) -> bool {
+        Modals::dismiss_top(self, cx)
+    }
+
+    fn has_modal(&self, cx: &App) -> bool {
+        Modals::top(self, cx).is_some()
+    }
+}
+
+/// Draws a window's presented sheets and alerts.
+///
+/// Add a host as the last child of the window's root view, which should be
+/// relatively positioned so modals cover its content.
+///
+/// # Example
+///
+/// ```ignore
+/// struct AppView {
+///     modals: Entity<ModalHost>,
+/// }
+///
+/// impl AppView {
+///     fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+///         Self { modals: cx.new(|cx| ModalHost::new(window, cx)) }
+///     }
+/// }
+///
+/// impl Render for AppView {
+///     fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+///         div()
+///             .relative()
+///             .size_full()
+///             .child(self.content())
+///             .child(self.modals.clone())
+///     }
+/// }
+/// ```
+pub struct ModalHost {
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ModalHost {
+    /// Creates a host that redraws whenever the window's modal stack changes.
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let window_id = window.window_handle().window_id();
+        Self {
+            _subscriptions: vec![
+                cx.observe_global::<Modals>(|_, cx| cx.notify()),
+                cx.on_release(move |_, cx| Modals::remove_window(window_id, cx)),
+            ],
+        }
+    }
+
+    fn handle_key_down(id: ModalId, event: &KeyDownEvent, window: &mut Window, cx: &mut App) {
+        let keystroke = &event.keystroke;
+        match keystroke.key.as_str() {
+            "escape" => {
+                Modals::cancel(id, window, cx);
+                cx.stop_propagation();
+            }
+            "enter" if Modals::confirm(id, window, cx) => {
+                cx.stop_propagation();
+            }
+            "tab" => {
+                Self::cycle_focus(id, keystroke.modifiers.shift, window, cx);
+                cx.stop_propagation();
+            }
+            _ => {}
+        }
+    }
+
+    /// Moves focus to the next tab stop inside the modal, skipping the
+    /// window's other tab stops so focus can't leave the modal.
+    fn cycle_focus(id: ModalId, backwards: bool, window: &mut Window, cx: &mut App) {
+        let window_id = window.window_handle().window_id();
+        let Some(modal_focus) = cx
+            .try_global::<Modals>()
+            .and_then(|modals| modals.windows.get(&window_id))
+            .and_then(|stack| stack.iter().find(|entry| entry.id == id))
+            .map(|entry| entry.focus_handle.clone())
+        else {
+            return;
+        };
+
+        let start = window.focused(cx);
+        let mut first_outside = None;
+        loop {
+            if backwards {
+                window.focus_prev();
+            } else {
+                window.focus_next();
+            }
+            if modal_focus.contains_focused(window, cx) {
+                return;
+            }
+
+            // Went all the way around without finding another stop in the modal
+            let focused = window.focused(cx);
+            if focused.is_none() || focused == start || focused == first_outside {
+                window.focus(start.as_ref().unwrap_or(&modal_focus));
+                return;
+            }
+            if first_outside.is_none() {
+                first_outside = focused;
+            }
+        }
+    }
+
+    fn build_modal(
+        id: ModalId,
+        content: &ModalContent,
+        focus_handle: &FocusHandle,
+        is_top: bool,
+        is_bottom: bool,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Stateful<Div> {
+        let panel = match content {
+            ModalContent::Sheet(build) => {
+                let mut sheet = build(window, cx);
+                sheet.take_dismiss_handler();
+                sheet.into_panel()
+            }
+            ModalContent::Alert(build) => {
+                let mut alert = build(window, cx);
+                alert.take_dismiss_handler();
+                alert
+                    .dismiss_on_click(Rc::new(move |window, cx| Modals::dismiss(id, window, cx)))
+                    .into_panel()
+            }
+        };
+
+        // Only the bottom modal dims the window, so stacking doesn't darken it further
+        let backdrop_color = if is_bottom {
+            hsla(0.0, 0.0, 0.0, 0.4)
+        } else {
+            hsla(0.0, 0.0, 0.0, 0.0)
+        };
+
+        let mut backdrop = div()
+            .id(("modal", id.0))
+            .absolute()
+            .inset_0()
+            .flex()
+            .items_center()
+            .justify_center()
+            .bg(backdrop_color)
+            .occlude();
+
+        if is_top {
+            backdrop = backdrop
+                .track_focus(focus_handle)
+                .key_context("Modal")
+                .on_key_down(move |event, window, cx| Self::handle_key_down(id, event, window, cx))
+                .on_click(move |_event, window, cx| Modals::cancel(id, window, cx));
+        }
+
+        // The panel stops click propagation to avoid dismissing when clicking inside
+        backdrop.child(
+            panel.on_mouse_down(MouseButton::Left, |_event, _window, cx| {
+                cx.stop_propagation();
+            }),
+        )
+    }
+}
+
+impl Render for ModalHost {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let window_id = window.window_handle().window_id();
+        let Some(stack) = cx
+            .try_global::<Modals>()
+            .and_then(|modals| modals.windows.get(&window_id))
+        else {
+            return div();
+        };
+
+        // Builders may read other entities, so collect the entries first
+        let entries: Vec<(ModalId, ModalContent, FocusHandle)> = stack
+            .iter()
+            .map(|entry| (entry.id, entry.content.clone(), entry.focus_handle.clone()))
+            .collect();
+
+        let count = entries.len();
+        let mut layer = div().absolute().inset_0();
+        for (index, (id, content, focus_handle)) in entries.iter().enumerate() {
+            layer = layer.child(Self::build_modal(
+                *id,
+                content,
+                focus_handle,
+                index + 1 == count,
+                index == 0,
+                window,
+                cx,
+            ));
+        }
+        layer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::AlertButton;
+    use std::cell::RefCell;
+    // `use gpui::*` brings in gpui's `test` attribute, which `#[gpui::test]`
+    // expands into; keep `#[test]` the built-in one
+    use std::prelude::v1::test;
+
+    type Log = Rc<RefCell<Vec<&'static str>>>;
+
+    /// A window with a focusable field under its modal host.
+    struct Root {
+        field: FocusHandle,
+        modals: Entity<ModalHost>,
+    }
+
+    impl Render for Root {
+        fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+            div()
+                .relative()
+                .size_full()
+                .child(div().track_focus(&self.field).size(px(20.0)))
+                .child(self.modals.clone())
+        }
+    }
+
+    fn root(cx: &mut TestAppContext) -> (Entity<Root>, &mut VisualTestContext) {
+        cx.add_window_view(|window, cx| Root {
+            field: cx.focus_handle(),
+            modals: cx.new(|cx| ModalHost::new(window, cx)),
+        })
+    }
+
+    /// Presents a "Save changes?" alert whose buttons record their label.
+    fn present_save_alert(log: &Log, window: &mut Window, cx: &mut App) -> ModalId {
+        let log = log.clone();
+        window.present_alert(cx, move |_window, _cx| {
+            let (save, cancel) = (log.clone(), log.clone());
+            Alert::new("Save changes?")
+                .button(AlertButton::new("Save").on_click(move |_, _, _| {
+                    save.borrow_mut().push("save");
+                }))
+                .button(AlertButton::cancel("Cancel").on_click(move |_, _, _| {
+                    cancel.borrow_mut().push("cancel");
+                }))
+        })
+    }
+
+    #[test]
+    fn test_alert_button_roles() {
+        let alert = Alert::new("Delete?")
+            .button(AlertButton::cancel("Cancel"))
+            .button(AlertButton::destructive("Delete", |_, _, _| {}));
+        assert!(alert.has_button(AlertButtonRole::Cancel));
+        assert!(!alert.has_button(AlertButtonRole::Default));
+    }
+
+    #[test]
+    fn test_take_first_button_handler_for_role() {
+        let mut alert = Alert::new("Save changes?")
+            .button(AlertButton::new("Save").on_click(|_, _, _| {}))
+            .button(AlertButton::new("Save As…"));
+        assert!(alert.take_button_handler(AlertButtonRole::Default).is_some());
+        // The handler was taken, and the second default button isn't consulted
+        assert!(alert.take_button_handler(AlertButtonRole::Default).is_none());
+        assert!(alert.take_button_handler(AlertButtonRole::Cancel).is_none());
+    }
+
+    #[gpui::test]
+    fn test_modals_stack_and_dismiss_in_order(cx: &mut TestAppContext) {
+        let (_root, cx) = root(cx);
+        let log = Log::default();
+        let (first, second) = cx.update(|window, cx| {
+            (
+                present_save_alert(&log, window, cx),
+                present_save_alert(&log, window, cx),
+            )
+        });
+
+        cx.update(|window, cx| {
+            assert_eq!(Modals::ids(window, cx), [first, second]);
+            assert!(Modals::dismiss_top(window, cx));
+            assert_eq!(Modals::ids(window, cx), [first]);
+            assert!(Modals::dismiss_top(window, cx));
+            assert!(!window.has_modal(cx));
+            assert!(!Modals::dismiss_top(window, cx));
+        });
+        assert!(log.borrow().is_empty());
+    }
+
+    #[gpui::test]
+    fn test_escape_runs_cancel_button(cx: &mut TestAppContext) {
+        let (_root, cx) = root(cx);
+        let log = Log::default();
+        cx.update(|window, cx| present_save_alert(&log, window, cx));
+        cx.run_until_parked();
+
+        cx.simulate_keystrokes("escape");
+        assert_eq!(*log.borrow(), ["cancel"]);
+        cx.update(|window, cx| assert!(!window.has_modal(cx)));
+    }
+
+    #[gpui::test]
+    fn test_enter_runs_default_button(cx: &mut TestAppContext) {
+        let (_root, cx) = root(cx);
+        let log = Log::default();
+        cx.update(|window, cx| present_save_alert(&log, window, cx));
+        cx.run_until_parked();
+
+        cx.simulate_keystrokes("enter");
+        assert_eq!(*log.borrow(), ["save"]);
+        cx.update(|window, cx| assert!(!window.has_modal(cx)));
+    }
+
+    #[gpui::test]
+    fn test_focus_returns_after_dismissal(cx: &mut TestAppContext) {
+        let (root, cx) = root(cx);
+        let field = root.read_with(cx, |root, _| root.field.clone());
+        let log = Log::default();
+        cx.update(|window, _cx| field.focus(window));
+
+        let (first, second) = cx.update(|window, cx| {
+            (
+                present_save_alert(&log, window, cx),
+                present_save_alert(&log, window, cx),
+            )
+        });
+        cx.run_until_parked();
+        cx.update(|window, _cx| assert!(!field.is_focused(window)));
+
+        // Dismissing the top alert focuses the one below, then the field
+        cx.update(|window, cx| window.dismiss_modal(second, cx));
+        cx.run_until_parked();
+        cx.update(|window, cx| {
+            assert!(!field.is_focused(window));
+            window.dismiss_modal(first, cx);
+        });
+        cx.run_until_parked();
+        cx.update(|window, _cx| assert!(field.is_focused(window)));
+    }
+}
@@ -0,0 +1,919 @@
+//! Outline view component for hierarchical data.
+//!
+//! A tree of expandable rows, such as the folders of a file tree or the
+//! members of a JSON document. Rows are indented by depth and rendered with
+//! `uniform_list`, so only the visible ones are built. Children can be
+//! loaded lazily when a node is first expanded, and extra columns share
+//! `TableColumn` with `Table`.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let outline = cx.new(|cx| OutlineViewState::new(
+//!     OutlineView::new("files")
+//!         .nodes(vec![OutlineNode::new("/home", "home").lazy()])
+//!         .load_children(|node, cx| {
+//!             let path = PathBuf::from(node.id().as_ref());
+//!             cx.background_spawn(async move { read_folder(&path) })
+//!         })
+//!         .on_confirm(|node, _window, cx| open(node.id(), cx)),
+//!     window,
+//!     cx,
+//! ));
+//! ```
+
+use gpui::prelude::*;
+use gpui::*;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::rc::Rc;
+use std::time::Duration;
+
+use super::label::Icon;
+use super::table::TableColumn;
+
+type NodeHandler = Rc<dyn Fn(&OutlineNode, &mut Window, &mut App) + 'static>;
+type ChildrenLoader = Rc<dyn Fn(&OutlineNode, &mut App) -> Task<Vec<OutlineNode>> + 'static>;
+
+/// Height of every row.
+const ROW_HEIGHT: f32 = 24.0;
+/// Width of the expand/collapse chevron.
+const CHEVRON_WIDTH: f32 = 14.0;
+
+/// A node of an outline view.
+///
+/// Nodes are identified by an id that must be unique within the tree, such
+/// as a path or a JSON pointer. Expansion and selection are tracked by id,
+/// so they survive replacing the nodes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutlineNode {
+    id: SharedString,
+    label: SharedString,
+    icon: Option<Icon>,
+    values: Vec<SharedString>,
+    /// `None` until lazily loaded children arrive
+    children: Option<Vec<OutlineNode>>,
+}
+
+impl OutlineNode {
+    /// Creates a leaf node with an id and a label.
+    pub fn new(id: impl Into<SharedString>, label: impl Into<SharedString>) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+            icon: None,
+            values: Vec::new(),
+            children: Some(Vec::new()),
+        }
+    }
+
+    /// Sets an icon shown before the label.
+    pub fn icon(mut self, icon: Icon) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Adds the text of the next column, after the label's column.
+    pub fn value(mut self, value: impl Into<SharedString>) -> Self {
+        self.values.push(value.into());
+        self
+    }
+
+    /// Adds a child node.
+    pub fn child(mut self, child: OutlineNode) -> Self {
+        self.children.get_or_insert_with(Vec::new).push(child);
+        self
+    }
+
+    /// Adds child nodes.
+    pub fn children(mut self, children: impl IntoIterator<Item = OutlineNode>) -> Self {
+        self.children.get_or_insert_with(Vec::new).extend(children);
+        self
+    }
+
+    /// Marks the node's children as not loaded yet.
+    ///
+    /// The node shows a chevron, and the outline view's children loader is
+    /// called the first time it's expanded.
+    pub fn lazy(mut self) -> Self {
+        self.children = None;
+        self
+    }
+
+    /// Returns the id.
+    pub fn id(&self) -> &SharedString {
+        &self.id
+    }
+
+    /// Returns the label.
+    pub fn label(&self) -> &SharedString {
+        &self.label
+    }
+
+    /// Returns the texts of the extra columns.
+    pub fn values(&self) -> &[SharedString] {
+        &self.values
+    }
+
+    /// Returns the children, or an empty slice if they aren't loaded yet.
+    pub fn child_nodes(&self) -> &[OutlineNode] {
+        self.children.as_deref().unwrap_or_default()
+    }
+
+    /// Returns true if the node has children or may have lazy ones.
+    pub fn is_expandable(&self) -> bool {
+        self.children
+            .as_ref()
+            .is_none_or(|children| !children.is_empty())
+    }
+
+    /// Returns true if the node's children have been loaded.
+    pub fn is_loaded(&self) -> bool {
+        self.children.is_some()
+    }
+}
+
+fn find_node<'a>(nodes: &'a [OutlineNode], id: &SharedString) -> Option<&'a OutlineNode> {
+    nodes.iter().find_map(|node| {
+        if &node.id == id {
+            Some(node)
+        } else {
+            find_node(node.child_nodes(), id)
+        }
+    })
+}
+
+fn find_node_mut<'a>(
+    nodes: &'a mut [OutlineNode],
+    id: &SharedString,
+) -> Option<&'a mut OutlineNode> {
+    for node in nodes {
+        if &node.id == id {
+            return Some(node);
+        }
+        if let Some(found) = node
+            .children
+            .as_deref_mut()
+            .and_then(|children| find_node_mut(children, id))
+        {
+            return Some(found);
+        }
+    }
+    None
+}
+
+/// A visible row of the flattened tree.
+#[derive(Debug, Clone)]
+struct OutlineRow {
+    id: SharedString,
+    label: SharedString,
+    icon: Option<Icon>,
+    values: Vec<SharedString>,
+    depth: usize,
+    /// Index of the parent's row
+    parent: Option<usize>,
+    expandable: bool,
+    expanded: bool,
+    loading: bool,
+}
+
+/// Appends the rows of the nodes and their expanded descendants.
+fn flatten(
+    nodes: &[OutlineNode],
+    expanded: &HashSet<SharedString>,
+    loading: &HashSet<SharedString>,
+    depth: usize,
+    parent: Option<usize>,
+    rows: &mut Vec<OutlineRow>,
+) {
+    for node in nodes {
+        let is_expanded = node.is_expandable() && expanded.contains(&node.id);
+        rows.push(OutlineRow {
+            id: node.id.clone(),
+            label: node.label.clone(),
+            icon: node.icon,
+            values: node.values.clone(),
+            depth,
+            parent,
+            expandable: node.is_expandable(),
+            expanded: is_expanded,
+            loading: loading.contains(&node.id),
+        });
+        if is_expanded {
+            let index = rows.len() - 1;
+            flatten(
+                node.child_nodes(),
+                expanded,
+                loading,
+                depth + 1,
+                Some(index),
+                rows,
+            );
+        }
+    }
+}
+
+/// What a navigation key does to the rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyAction {
+    Select(usize),
+    Expand(usize),
+    Collapse(usize),
+}
+
+/// Returns the effect of a navigation key on the selected row.
+///
+/// Left collapses an expanded row or moves to its parent; Right expands a
+/// collapsed row or moves to its first child.
+fn key_action(rows: &[OutlineRow], selected: Option<usize>, key: &str) -> Option<KeyAction> {
+    let last = rows.len().checked_sub(1)?;
+    let Some(index) = selected else {
+        return match key {
+            "down" | "right" => Some(KeyAction::Select(0)),
+            "up" => Some(KeyAction::Select(last)),
+            _ => None,
+        };
+    };
+    let row = &rows[index];
+    match key {
+        "down" => (index < last).then_some(KeyAction::Select(index + 1)),
+        "up" => index.checked_sub(1).map(KeyAction::Select),
+        "left" if row.expanded => Some(KeyAction::Collapse(index)),
+        "left" => row.parent.map(KeyAction::Select),
+        "right" if row.expandable && !row.expanded => Some(KeyAction::Expand(index)),
+        "right" if row.expanded && index < last && rows[index + 1].parent == Some(index) => {
+            Some(KeyAction::Select(index + 1))
+        }
+        _ => None,
+    }
+}
+
+/// An outline view of hierarchical data.
+///
+/// This is a builder; convert it into an `OutlineViewState` view with
+/// `OutlineViewState::new`.
+///
+/// # Example
+///
+/// ```ignore
+/// OutlineView::new("json")
+///     .columns([TableColumn::flex(), TableColumn::fixed(px(200.0)), TableColumn::fixed(px(80.0))])
+///     .header(["Key", "Value", "Type"])
+///     .node(
+///         OutlineNode::new("/", "root")
+///             .value("{2 keys}")
+///             .value("object")
+///             .child(OutlineNode::new("/name", "name").value("\"applib\"").value("string"))
+///             .child(OutlineNode::new("/version", "version").value("1").value("number")),
+///     )
+///     .expanded(["/"])
+/// ```
+pub struct OutlineView {
+    id: ElementId,
+    nodes: Vec<OutlineNode>,
+    columns: Vec<TableColumn>,
+    header: Vec<SharedString>,
+    expanded: Vec<SharedString>,
+    indent: Pixels,
+    loader: Option<ChildrenLoader>,
+    on_select: Option<NodeHandler>,
+    on_confirm: Option<NodeHandler>,
+}
+
+impl OutlineView {
+    /// Creates an empty outline view with the given id.
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            nodes: Vec::new(),
+            columns: Vec::new(),
+            header: Vec::new(),
+            expanded: Vec::new(),
+            indent: px(16.0),
+            loader: None,
+            on_select: None,
+            on_confirm: None,
+        }
+    }
+
+    /// Sets the top-level nodes.
+    pub fn nodes(mut self, nodes: impl IntoIterator<Item = OutlineNode>) -> Self {
+        self.nodes = nodes.into_iter().collect();
+        self
+    }
+
+    /// Adds a top-level node.
+    pub fn node(mut self, node: OutlineNode) -> Self {
+        self.nodes.push(node);
+        self
+    }
+
+    /// Sets the columns. The first one holds the tree; each further column
+    /// shows one of the nodes' values.
+    ///
+    /// Without columns, the tree fills the width.
+    pub fn columns(mut self, columns: impl IntoIterator<Item = TableColumn>) -> Self {
+        self.columns = columns.into_iter().collect();
+        self
+    }
+
+    /// Sets column titles shown in a header row.
+    pub fn header(mut self, titles: impl IntoIterator<Item = impl Into<SharedString>>) -> Self {
+        self.header = titles.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the ids of the nodes expanded initially.
+    pub fn expanded(mut self, ids: impl IntoIterator<Item = impl Into<SharedString>>) -> Self {
+        self.expanded = ids.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the indentation per level (default: 16px).
+    pub fn indent(mut self, indent: Pixels) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Sets the loader for the children of lazy nodes.
+    ///
+    /// It's called when a lazy node is first expanded, and returns a task
+    /// resolving to the children. A spinner replaces the node's chevron
+    /// while the task runs.
+    pub fn load_children(
+        mut self,
+        loader: impl Fn(&OutlineNode, &mut App) -> Task<Vec<OutlineNode>> + 'static,
+    ) -> Self {
+        self.loader = Some(Rc::new(loader));
+        self
+    }
+
+    /// Sets the handler called when the selection moves to a node.
+    pub fn on_select(
+        mut self,
+        handler: impl Fn(&OutlineNode, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_select = Some(Rc::new(handler));
+        self
+    }
+
+    /// Sets the handler called when a node is double-clicked or Enter is
+    /// pressed.
+    ///
+    /// Without it, double-clicking and Enter expand or collapse the node.
+    pub fn on_confirm(
+        mut self,
+        handler: impl Fn(&OutlineNode, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_confirm = Some(Rc::new(handler));
+        self
+    }
+}
+
+/// Internal state for an OutlineView rendered as a view.
+///
+/// # Example
+///
+/// ```ignore
+/// // Reveal a node and select it
+/// self.outline.update(cx, |outline, cx| {
+///     outline.expand("/home", cx);
+///     outline.select("/home/ada", cx);
+/// });
+/// ```
+pub struct OutlineViewState {
+    id: ElementId,
+    roots: Vec<OutlineNode>,
+    columns: Rc<Vec<TableColumn>>,
+    header: Vec<SharedString>,
+    indent: Pixels,
+    expanded: HashSet<SharedString>,
+    loading: HashSet<SharedString>,
+    selected: Option<SharedString>,
+    /// The visible rows, rebuilt when nodes or expansion change
+    rows: Rc<Vec<OutlineRow>>,
+    scroll_handle: UniformListScrollHandle,
+    focus_handle: FocusHandle,
+    loader: Option<ChildrenLoader>,
+    on_select: Option<NodeHandler>,
+    on_confirm: Option<NodeHandler>,
+    loads: HashMap<SharedString, Task<()>>,
+}
+
+impl OutlineViewState {
+    /// Creates the outline view from a builder.
+    pub fn new(builder: OutlineView, _window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut this = Self {
+            id: builder.id,
+            roots: builder.nodes,
+            columns: Rc::new(builder.columns),
+            header: builder.header,
+            indent: builder.indent,
+            expanded: builder.expanded.into_iter().collect(),
+            loading: HashSet::new(),
+            selected: None,
+            rows: Rc::new(Vec::new()),
+            scroll_handle: UniformListScrollHandle::new(),
+            focus_handle: cx.focus_handle(),
+            loader: builder.loader,
+            on_select: builder.on_select,
+            on_confirm: builder.on_confirm,
+            loads: HashMap::new(),
+        };
+        this.load_expanded(cx);
+        this.rebuild_rows();
+        this
+    }
+
+    /// Returns the top-level nodes.
+    pub fn nodes(&self) -> &[OutlineNode] {
+        &self.roots
+    }
+
+    /// Replaces the nodes, keeping expansion and selection by id.
+    pub fn set_nodes(&mut self, nodes: Vec<OutlineNode>, cx: &mut Context<Self>) {
+        self.roots = nodes;
+        self.loads.clear();
+        self.loading.clear();
+        self.load_expanded(cx);
+        self.rebuild_rows();
+        cx.notify();
+    }
+
+    /// Returns the node with an id.
+    pub fn node(&self, id: &SharedString) -> Option<&OutlineNode> {
+        find_node(&self.roots, id)
+    }
+
+    /// Returns the selected node.
+    pub fn selected_node(&self) -> Option<&OutlineNode> {
+        self.selected.as_ref().and_then(|id| self.node(id))
+    }
+
+    /// Selects the node with an id, without calling the select handler.
+    ///
+    /// The node must be visible, so expand its ancestors first.
+    pub fn select(&mut self, id: impl Into<SharedString>, cx: &mut Context<Self>) {
+        let id = id.into();
+        if let Some(index) = self.rows.iter().position(|row| row.id == id) {
+            self.selected = Some(id);
+            self.scroll_handle
+                .scroll_to_item(index, ScrollStrategy::Center);
+            cx.notify();
+        }
+    }
+
+    /// Returns true if the node with an id is expanded.
+    pub fn is_expanded(&self, id: &SharedString) -> bool {
+        self.expanded.contains(id)
+    }
+
+    /// Expands the node with an id, loading lazy children if needed.
+    pub fn expand(&mut self, id: impl Into<SharedString>, cx: &mut Context<Self>) {
+        let id = id.into();
+        if !self.node(&id).is_some_and(OutlineNode::is_expandable) {
+            return;
+        }
+        self.expanded.insert(id.clone());
+        self.load(id, cx);
+        self.rebuild_rows();
+        cx.notify();
+    }
+
+    /// Collapses the node with an id. A selected descendant passes the
+    /// selection to the node.
+    pub fn collapse(&mut self, id: impl Into<SharedString>, cx: &mut Context<Self>) {
+        let id = id.into();
+        if !self.expanded.remove(&id) {
+            return;
+        }
+        let hides_selection = self.selected.as_ref().is_some_and(|selected| {
+            self.node(&id)
+                .is_some_and(|node| find_node(node.child_nodes(), selected).is_some())
+        });
+        if hides_selection {
+            self.selected = Some(id);
+        }
+        self.rebuild_rows();
+        cx.notify();
+    }
+
+    /// Expands a collapsed node or collapses an expanded one.
+    pub fn toggle(&mut self, id: impl Into<SharedString>, cx: &mut Context<Self>) {
+        let id = id.into();
+        if self.is_expanded(&id) {
+            self.collapse(id, cx);
+        } else {
+            self.expand(id, cx);
+        }
+    }
+
+    /// Discards the children of a lazy node and loads them again if it's
+    /// expanded, e.g. after the folder it stands for changed.
+    pub fn reload_children(&mut self, id: impl Into<SharedString>, cx: &mut Context<Self>) {
+        let id = id.into();
+        let Some(node) = find_node_mut(&mut self.roots, &id) else {
+            return;
+        };
+        node.children = None;
+        self.loads.remove(&id);
+        self.loading.remove(&id);
+        if self.expanded.contains(&id) {
+            self.load(id, cx);
+        }
+        self.rebuild_rows();
+        cx.notify();
+    }
+
+    /// Starts loading the children of expanded nodes that don't have them.
+    fn load_expanded(&mut self, cx: &mut Context<Self>) {
+        let ids: Vec<SharedString> = self.expanded.iter().cloned().collect();
+        for id in ids {
+            self.load(id, cx);
+        }
+    }
+
+    /// Starts loading a lazy node's children, unless they're loaded or
+    /// already loading.
+    fn load(&mut self, id: SharedString, cx: &mut Context<Self>) {
+        if self.loading.contains(&id) {
+            return;
+        }
+        let Some(node) = find_node_mut(&mut self.roots, &id) else {
+            return;
+        };
+        if node.is_loaded() {
+            return;
+        }
+        let Some(loader) = self.loader.clone() else {
+            // Nothing can provide the children
+            node.children = Some(Vec::new());
+            return;
+        };
+
+        let children = loader(&node.clone(), cx);
+        self.loading.insert(id.clone());
+        let task_id = id.clone();
+        let task = cx.spawn(async move |this, cx| {
+            let children = children.await;
+            this.update(cx, |this, cx| {
+                this.loads.remove(&task_id);
+                this.loading.remove(&task_id);
+                if let Some(node) = find_node_mut(&mut this.roots, &task_id) {
+                    node.children = Some(children);
+                }
+                this.rebuild_rows();
+                cx.notify();
+            })
+            .ok();
+        });
+        self.loads.insert(id, task);
+    }
+
+    fn rebuild_rows(&mut self) {
+        let mut rows = Vec::new();
+        flatten(
+            &self.roots,
+            &self.expanded,
+            &self.loading,
+            0,
+            None,
+            &mut rows,
+        );
+        self.rows = Rc::new(rows);
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        let selected = self.selected.as_ref()?;
+        self.rows.iter().position(|row| &row.id == selected)
+    }
+
+    /// Selects a row and reports it to the select handler.
+    fn select_row(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(row) = self.rows.get(index) else {
+            return;
+        };
+        if self.selected.as_ref() != Some(&row.id) {
+            self.selected = Some(row.id.clone());
+            if let (Some(handler), Some(node)) =
+                (self.on_select.clone(), self.selected_node().cloned())
+            {
+                handler(&node, window, cx);
+            }
+        }
+        self.scroll_handle
+            .scroll_to_item(index, ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    /// Confirms a row, or toggles it without a confirm handler.
+    fn confirm_row(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(row) = self.rows.get(index).cloned() else {
+            return;
+        };
+        match self.on_confirm.clone() {
+            Some(handler) => {
+                if let Some(node) = self.node(&row.id).cloned() {
+                    handler(&node, window, cx);
+                }
+            }
+            None if row.expandable => self.toggle(row.id, cx),
+            None => {}
+        }
+    }
+
+    fn handle_key_down(
+        &mut self,
+        event: &KeyDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let key = event.keystroke.key.as_str();
+        if key == "enter" {
+            if let Some(index) = self.selected_index() {
+                self.confirm_row(index, window, cx);
+            }
+            return;
+        }
+        match key_action(&self.rows, self.selected_index(), key) {
+            Some(KeyAction::Select(index)) => self.select_row(index, window, cx),
+            Some(KeyAction::Expand(index)) => self.expand(self.rows[index].id.clone(), cx),
+            Some(KeyAction::Collapse(index)) => self.collapse(self.rows[index].id.clone(), cx),
+            None => return,
+        }
+        cx.stop_propagation();
+    }
+
+    fn render_header(&self) -> Div {
+        div()
+            .flex()
+            .items_center()
+            .h(px(ROW_HEIGHT))
+            .px(px(8.0))
+            .border_b_1()
+            .border_color(hsla(0.0, 0.0, 0.85, 1.0))
+            .bg(hsla(0.0, 0.0, 0.97, 1.0))
+            .text_xs()
+            .text_color(hsla(0.0, 0.0, 0.35, 1.0))
+            .children(
+                self.header.iter().enumerate().map(|(index, title)| {
+                    column_cell(self.columns.get(index)).child(title.clone())
+                }),
+            )
+    }
+
+    fn render_rows(&mut self, range: Range<usize>, cx: &mut Context<Self>) -> Vec<AnyElement> {
+        let selected = self.selected_index();
+        let rows = self.rows.clone();
+        range
+            .filter_map(|index| rows.get(index).map(|row| (index, row)))
+            .map(|(index, row)| {
+                let is_selected = selected == Some(index);
+                let text_color = if is_selected {
+                    gpui::white()
+                } else {
+                    hsla(0.0, 0.0, 0.20, 1.0)
+                };
+                let secondary_color = if is_selected {
+                    hsla(0.0, 0.0, 1.0, 0.8)
+                } else {
+                    hsla(0.0, 0.0, 0.45, 1.0)
+                };
+
+                let disclosure = if row.loading {
+                    spinner().into_any_element()
+                } else if row.expandable {
+                    let id = row.id.clone();
+                    div()
+                        .id(("outline-chevron", index))
+                        .cursor_pointer()
+                        .text_color(secondary_color)
+                        .on_mouse_down(MouseButton::Left, |_event, _window, cx| {
+                            cx.stop_propagation()
+                        })
+                        .on_click(cx.listener(move |this, _event, _window, cx| {
+                            this.toggle(id.clone(), cx);
+                        }))
+                        .child(if row.expanded { "▼" } else { "▶" })
+                        .into_any_element()
+                } else {
+                    div().into_any_element()
+                };
+
+                let tree_cell = column_cell(self.columns.first())
+                    .flex()
+                    .items_center()
+                    .gap(px(4.0))
+                    .pl(self.indent * row.depth as f32)
+                    .child(
+                        div()
+                            .w(px(CHEVRON_WIDTH))
+                            .flex_shrink_0()
+                            .flex()
+                            .justify_center()
+                            .child(disclosure),
+                    )
+                    .when_some(row.icon, |cell, icon| cell.child(icon.as_str()))
+                    .child(
+                        div()
+                            .overflow_hidden()
+                            .text_ellipsis()
+                            .child(row.label.clone()),
+                    );
+
+                let value_cells = row.values.iter().enumerate().map(|(column, value)| {
+                    column_cell(self.columns.get(column + 1))
+                        .text_color(secondary_color)
+                        .child(value.clone())
+                });
+
+                div()
+                    .id(("outline-row", index))
+                    .flex()
+                    .items_center()
+                    .w_full()
+                    .h(px(ROW_HEIGHT))
+                    .px(px(8.0))
+                    .text_xs()
+                    .text_color(text_color)
+                    .cursor_pointer()
+                    .map(|row| {
+                        if is_selected {
+                            row.bg(hsla(211.0 / 360.0, 0.95, 0.53, 1.0))
+                        } else {
+                            row.hover(|style| style.bg(hsla(0.0, 0.0, 0.96, 1.0)))
+                        }
+                    })
+                    .child(tree_cell)
+                    .children(value_cells)
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, _event, window, cx| {
+                            this.focus_handle.focus(window);
+                            this.select_row(index, window, cx);
+                        }),
+                    )
+                    .on_click(cx.listener(move |this, event: &ClickEvent, window, cx| {
+                        if event.click_count() >= 2 {
+                            this.confirm_row(index, window, cx);
+                        }
+                    }))
+                    .into_any_element()
+            })
+            .collect()
+    }
+}
+
+/// Returns a cell sized for a column, truncating its text.
+fn column_cell(column: Option<&TableColumn>) -> Div {
+    let cell = div()
+        .min_w(px(0.0))
+        .overflow_hidden()
+        .whitespace_nowrap()
+        .text_ellipsis()
+        .px(px(4.0));
+    match column {
+        Some(TableColumn::Fixed(width)) => cell.w(*width).flex_shrink_0(),
+        Some(TableColumn::Flex) | None => cell.flex_1(),
+    }
+}
+
+/// A small spinning indicator shown while children load.
+fn spinner() -> impl IntoElement {
+    const FRAMES: [&str; 4] = ["◐", "◓", "◑", "◒"];
+    div().text_color(hsla(0.0, 0.0, 0.45, 1.0)).with_animation(
+        "outline-spinner",
+        Animation::new(Duration::from_millis(800)).repeat(),
+        |spinner, delta| {
+            let frame = ((delta * FRAMES.len() as f32) as usize).min(FRAMES.len() - 1);
+            spinner.child(FRAMES[frame])
+        },
+    )
+}
+
+impl Focusable for OutlineViewState {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for OutlineViewState {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let list = uniform_list(
+            self.id.clone(),
+            self.rows.len(),
+            cx.processor(|this, range, _window, cx| this.render_rows(range, cx)),
+        )
+        .track_scroll(self.scroll_handle.clone())
+        .size_full();
+
+        div()
+            .id(self.id.clone())
+            .track_focus(&self.focus_handle)
+            .on_key_down(cx.listener(Self::handle_key_down))
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(hsla(0.0, 0.0, 1.0, 1.0))
+            .overflow_hidden()
+            .when(!self.header.is_empty(), |outline| {
+                outline.child(self.render_header())
+            })
+            .child(div().relative().flex_1().min_h(px(0.0)).child(list))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> Vec<OutlineNode> {
+        vec![
+            OutlineNode::new("a", "a")
+                .child(OutlineNode::new("a/1", "1"))
+                .child(OutlineNode::new("a/2", "2").child(OutlineNode::new("a/2/x", "x"))),
+            OutlineNode::new("b", "b").lazy(),
+            OutlineNode::new("c", "c"),
+        ]
+    }
+
+    fn rows(expanded: &[&str]) -> Vec<OutlineRow> {
+        let expanded = expanded
+            .iter()
+            .map(|id| SharedString::from(id.to_string()))
+            .collect();
+        let mut rows = Vec::new();
+        flatten(&tree(), &expanded, &HashSet::new(), 0, None, &mut rows);
+        rows
+    }
+
+    #[test]
+    fn test_flatten() {
+        let ids = |rows: Vec<OutlineRow>| rows.into_iter().map(|row| row.id).collect::<Vec<_>>();
+        assert_eq!(ids(rows(&[])), vec!["a", "b", "c"]);
+        // Collapsed ancestors hide expanded descendants
+        assert_eq!(ids(rows(&["a/2"])), vec!["a", "b", "c"]);
+        assert_eq!(
+            ids(rows(&["a", "a/2"])),
+            vec!["a", "a/1", "a/2", "a/2/x", "b", "c"]
+        );
+
+        let rows = rows(&["a", "a/2"]);
+        assert_eq!(rows[3].depth, 2);
+        assert_eq!(rows[3].parent, Some(2));
+        // Lazy nodes are expandable until loaded; leaves aren't
+        assert!(rows[4].expandable);
+        assert!(!rows[5].expandable);
+    }
+
+    #[test]
+    fn test_key_action() {
+        let collapsed = rows(&[]);
+        assert_eq!(
+            key_action(&collapsed, None, "down"),
+            Some(KeyAction::Select(0))
+        );
+        assert_eq!(
+            key_action(&collapsed, Some(0), "right"),
+            Some(KeyAction::Expand(0))
+        );
+        assert_eq!(key_action(&collapsed, Some(0), "left"), None);
+        assert_eq!(key_action(&collapsed, Some(2), "down"), None);
+        assert_eq!(key_action(&collapsed, Some(2), "right"), None);
+
+        let expanded = rows(&["a", "a/2"]);
+        // Right moves into an expanded node, Left collapses it
+        assert_eq!(
+            key_action(&expanded, Some(0), "right"),
+            Some(KeyAction::Select(1))
+        );
+        assert_eq!(
+            key_action(&expanded, Some(2), "left"),
+            Some(KeyAction::Collapse(2))
+        );
+        // Left on a child goes to its parent
+        assert_eq!(
+            key_action(&expanded, Some(3), "left"),
+            Some(KeyAction::Select(2))
+        );
+        assert_eq!(
+            key_action(&expanded, Some(1), "left"),
+            Some(KeyAction::Select(0))
+        );
+    }
+
+    #[test]
+    fn test_find_node() {
+        let mut nodes = tree();
+        assert_eq!(
+            find_node(&nodes, &"a/2/x".into()).map(|node| node.label().clone()),
+            Some("x".into())
+        );
+        assert!(find_node(&nodes, &"missing".into()).is_none());
+
+        find_node_mut(&mut nodes, &"b".into()).unwrap().children =
+            Some(vec![OutlineNode::new("b/1", "1")]);
+        assert!(find_node(&nodes, &"b/1".into()).is_some());
+        assert!(!find_node(&nodes, &"c".into()).unwrap().is_expandable());
+    }
+}
@@ -7,6 +7,8 @@ use gpui::*;
 use std::marker::PhantomData;
 use anyhow::Result;
 
+use crate::persistence::WindowStates;
+
 /// A builder for creating floating panel windows.
 ///
 /// Panel provides a high-level API that wraps GPUI's window creation to make
@@ -39,7 +41,6 @@ where
     V: Render + 'static,
     F: FnOnce(&mut App) -> V + 'static,
 {
-    #[allow(dead_code)]
     id: &'static str,
     view_builder: F,
     width: Pixels,
@@ -48,6 +49,7 @@ where
     floating: bool,
     titlebar_hidden: bool,
     background: PanelBackground,
+    restore_state: bool,
     _phantom: PhantomData<V>,
 }
 
@@ -63,7 +65,8 @@ where
     ///
     /// # Arguments
     ///
-    /// * `id` - A static string identifier for the panel (used for debugging)
+    /// * `id` - A static string identifier for the panel, also used as the
+    ///   window ID for [`restore_state`](Self::restore_state)
     /// * `view_builder` - A closure that creates the root view for the panel
     ///
     /// # Example
@@ -81,6 +84,7 @@ where
             floating: true,
             titlebar_hidden: false,
             background: PanelBackground::default(),
+            restore_state: false,
             _phantom: PhantomData,
         }
     }
@@ -172,6 +176,28 @@ where
         self
     }
 
+    /// Restores the window's bounds and maximized state from the last launch.
+    ///
+    /// The saved state is looked up in [`WindowStates`] by the panel's id and
+    /// fitted to the current displays. Changes to the window's bounds are saved
+    /// while it is open. Without saved state, the configured size is used.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// WindowStates::init("mail", cx);
+    ///
+    /// Panel::new("main", |cx| MailView::new(cx))
+    ///     .size(px(900.0), px(600.0))
+    ///     .center_on_screen()
+    ///     .restore_state()
+    ///     .open(cx)
+    /// ```
+    pub fn restore_state(mut self) -> Self {
+        self.restore_state = true;
+        self
+    }
+
     /// Opens the panel window with the configured options.
     ///
     /// This consumes the builder and creates the window, returning a handle
@@ -204,6 +230,12 @@ where
             Bounds::new(Point::default(), window_size)
         };
 
+        let saved_bounds = if self.restore_state {
+            WindowStates::window_bounds(self.id, cx)
+        } else {
+            None
+        };
+
         let window_background = match self.background {
             PanelBackground::Opaque => WindowBackgroundAppearance::Opaque,
             PanelBackground::Transparent => WindowBackgroundAppearance::Transparent,
@@ -211,7 +243,7 @@ where
         };
 
         let options = WindowOptions {
-            window_bounds: Some(WindowBounds::Windowed(bounds)),
+            window_bounds: Some(saved_bounds.unwrap_or(WindowBounds::Windowed(bounds))),
             kind: if self.floating {
                 WindowKind::PopUp
             } else {
@@ -234,8 +266,20 @@ where
 
         // Store the view builder to move into the closure
         let view_builder = self.view_builder;
+        let id = self.id;
+        let restore_state = self.restore_state;
 
-        cx.open_window(options, |_, cx| cx.new(|cx| (view_builder)(cx)))
+        cx.open_window(options, move |window, cx| {
+            cx.new(|cx| {
+                if restore_state {
+                    cx.observe_window_bounds(window, move |_, window, cx| {
+                        WindowStates::save_window_bounds(id, window, cx);
+                    })
+                    .detach();
+                }
+                (view_builder)(cx)
+            })
+        })
     }
 }
 
@@ -262,6 +306,13 @@ mod tests {
         assert!(!panel.centered);
         assert!(panel.floating);
         assert!(!panel.titlebar_hidden);
+        assert!(!panel.restore_state);
+    }
+
+    #[test]
+    fn test_panel_restore_state() {
+        let panel = Panel::new("test", |_cx| MockView).restore_state();
+        assert!(panel.restore_state);
     }
 
     #[test]
@@ -3,15 +3,85 @@
 //! This module provides a picker component for selecting from options.
 //! Supports multiple styles: Menu (dropdown), Segmented (horizontal button group),
 //! and Inline (vertical list).
+//!
+//! Options carry a typed value and may have an icon, a subtitle, and a section
+//! header. `PickerState` adds a filter field and built-in open state for long
+//! option lists.
 
 use gpui::prelude::*;
 use gpui::*;
 use std::rc::Rc;
 
+use super::anchored::AnchoredOverlay;
+use super::label::Icon;
+use super::text_field::{TextField, TextFieldState};
+use crate::accessibility::{AccessNode, AccessState, Accessible, Role};
+use crate::state::Binding;
+
+type ChangeHandler<T> = Rc<dyn Fn(T, &mut Window, &mut App) + 'static>;
+type SelectionHandler<T> = Rc<dyn Fn(Vec<T>, &mut Window, &mut App) + 'static>;
+type ToggleHandler = Rc<dyn Fn(bool, &mut Window, &mut App) + 'static>;
+type ClickHandler<T> = Rc<dyn Fn(&T, &mut Window, &mut App) + 'static>;
+type RemoveHandler = Rc<dyn Fn(&mut Window, &mut App) + 'static>;
+
 /// A single option in a picker.
+///
+/// # Example
+///
+/// ```ignore
+/// PickerOption::new(Tz::Berlin, "Berlin")
+///     .subtitle("UTC+01:00")
+///     .icon(Icon::Clock)
+/// ```
 #[derive(Clone)]
-struct PickerOption {
+pub struct PickerOption<T> {
+    value: T,
     label: SharedString,
+    subtitle: Option<SharedString>,
+    icon: Option<SharedString>,
+    /// Index into the picker's section headers, if the option belongs to a section.
+    section: Option<usize>,
+}
+
+impl<T> PickerOption<T> {
+    /// Creates an option with the given value and label.
+    pub fn new(value: T, label: impl Into<SharedString>) -> Self {
+        Self {
+            value,
+            label: label.into(),
+            subtitle: None,
+            icon: None,
+            section: None,
+        }
+    }
+
+    /// Sets secondary text shown below the label.
+    pub fn subtitle(mut self, subtitle: impl Into<SharedString>) -> Self {
+        self.subtitle = Some(subtitle.into());
+        self
+    }
+
+    /// Sets the icon shown before the label.
+    pub fn icon(mut self, icon: Icon) -> Self {
+        self.icon = Some(icon.as_str().into());
+        self
+    }
+
+    /// Sets a custom icon string shown before the label.
+    pub fn icon_str(mut self, icon: impl Into<SharedString>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Returns true if the label or subtitle contains `query`, ignoring case.
+    fn matches(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        self.label.to_lowercase().contains(&query)
+            || self
+                .subtitle
+                .as_ref()
+                .is_some_and(|subtitle| subtitle.to_lowercase().contains(&query))
+    }
 }
 
 /// Picker style variants.
@@ -28,6 +98,10 @@ pub enum PickerStyle {
 
 /// A picker component for selecting from multiple options.
 ///
+/// `Picker::new` creates an index-based picker where each `option` is
+/// identified by its position. Use `Picker::with_value` and `item` for
+/// typed option values.
+///
 /// # Example
 ///
 /// ```ignore
@@ -40,32 +114,88 @@ pub enum PickerStyle {
 ///     .on_change(|new_index, window, cx| {
 ///         // Handle selection change
 ///     })
+///
+/// // Typed values with sections and multi-selection
+/// Picker::with_value("languages", None)
+///     .selection(self.languages.clone())
+///     .section("Europe", vec![
+///         PickerOption::new(Lang::De, "German").subtitle("Deutsch"),
+///         PickerOption::new(Lang::Fr, "French").subtitle("Français"),
+///     ])
+///     .multi_select(true)
+///     .on_selection_change(|languages, window, cx| {
+///         // Handle new selection
+///     })
+///
+/// // Bound to a `State<usize>`
+/// Picker::new("size", 0)
+///     .option("Small")
+///     .option("Large")
+///     .value(State::binding(&self.size, cx))
 /// ```
-pub struct Picker {
+pub struct Picker<T: Clone + 'static = usize> {
     id: ElementId,
     label: Option<SharedString>,
-    options: Vec<PickerOption>,
-    selected_index: usize,
+    options: Vec<PickerOption<T>>,
+    sections: Vec<SharedString>,
+    selection: Vec<T>,
+    multi_select: bool,
+    searchable: bool,
     picker_style: PickerStyle,
     disabled: bool,
     is_open: bool,
-    on_change: Option<Rc<dyn Fn(usize, &mut Window, &mut App) + 'static>>,
-    on_toggle: Option<Box<dyn Fn(bool, &mut Window, &mut App) + 'static>>,
+    on_change: Option<ChangeHandler<T>>,
+    on_selection_change: Option<SelectionHandler<T>>,
+    on_toggle: Option<ToggleHandler>,
+    value_binding: Option<Binding<T>>,
+    values_binding: Option<Binding<Vec<T>>>,
+    /// Element shown above the options in the dropdown, used by `PickerState` for the filter field.
+    dropdown_header: Option<AnyElement>,
+    /// Text the dropdown rows are filtered by, set by `PickerState`.
+    query: String,
+    /// Called with a chip's value when it is removed, used by `PickerState`
+    /// instead of `on_selection_change`.
+    on_remove: Option<ChangeHandler<T>>,
 }
 
-impl Picker {
+impl Picker<usize> {
     /// Creates a new picker with the given id and selected index.
     pub fn new(id: impl Into<ElementId>, selected_index: usize) -> Self {
+        Self::with_value(id, Some(selected_index))
+    }
+
+    /// Adds an option to the picker.
+    ///
+    /// The option's value is its index.
+    pub fn option(mut self, label: impl Into<SharedString>) -> Self {
+        let index = self.options.len();
+        self.push_option(PickerOption::new(index, label));
+        self
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> Picker<T> {
+    /// Creates a new picker with typed option values and an optional selected value.
+    pub fn with_value(id: impl Into<ElementId>, value: Option<T>) -> Self {
         Self {
             id: id.into(),
             label: None,
             options: Vec::new(),
-            selected_index,
+            sections: Vec::new(),
+            selection: value.into_iter().collect(),
+            multi_select: false,
+            searchable: false,
             picker_style: PickerStyle::default(),
             disabled: false,
             is_open: false,
             on_change: None,
+            on_selection_change: None,
             on_toggle: None,
+            value_binding: None,
+            values_binding: None,
+            dropdown_header: None,
+            query: String::new(),
+            on_remove: None,
         }
     }
 
@@ -75,11 +205,49 @@ impl Picker {
         self
     }
 
-    /// Adds an option to the picker.
-    pub fn option(mut self, label: impl Into<SharedString>) -> Self {
-        self.options.push(PickerOption {
-            label: label.into(),
-        });
+    /// Adds a typed option to the picker.
+    ///
+    /// The option is placed in the most recently added section, if any.
+    pub fn item(mut self, option: PickerOption<T>) -> Self {
+        self.push_option(option);
+        self
+    }
+
+    /// Adds a section with a header and its options.
+    pub fn section(
+        mut self,
+        header: impl Into<SharedString>,
+        options: impl IntoIterator<Item = PickerOption<T>>,
+    ) -> Self {
+        self.sections.push(header.into());
+        for option in options {
+            self.push_option(option);
+        }
+        self
+    }
+
+    /// Sets the selected values.
+    ///
+    /// Single-selection pickers use the first value.
+    pub fn selection(mut self, selection: Vec<T>) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Sets whether multiple options can be selected.
+    ///
+    /// In multi-select mode the Menu style shows the selected options as
+    /// removable chips, and clicking an option toggles it.
+    pub fn multi_select(mut self, multi_select: bool) -> Self {
+        self.multi_select = multi_select;
+        self
+    }
+
+    /// Sets whether the dropdown shows a filter field.
+    ///
+    /// Only applies to Menu style rendered through `PickerState`.
+    pub fn searchable(mut self, searchable: bool) -> Self {
+        self.searchable = searchable;
         self
     }
 
@@ -107,6 +275,25 @@ impl Picker {
         self
     }
 
+    /// Sets a two-way binding for the selected value in single-selection
+    /// mode.
+    ///
+    /// The bound value replaces the initial selection, and choosing an
+    /// option writes its value back.
+    pub fn value(mut self, binding: Binding<T>) -> Self {
+        self.value_binding = Some(binding);
+        self
+    }
+
+    /// Sets a two-way binding for the selected values in multi-select mode.
+    ///
+    /// The bound values replace `selection`, and every change to the
+    /// selection is written back.
+    pub fn values(mut self, binding: Binding<Vec<T>>) -> Self {
+        self.values_binding = Some(binding);
+        self
+    }
+
     /// Sets whether the picker is disabled.
     pub fn disabled(mut self, disabled: bool) -> Self {
         self.disabled = disabled;
@@ -121,15 +308,27 @@ impl Picker {
 
     /// Sets the change handler for this picker.
     ///
-    /// The handler receives the index of the newly selected option.
+    /// The handler receives the value of the clicked option. For pickers
+    /// created with `Picker::new`, this is the option's index.
     pub fn on_change(
         mut self,
-        handler: impl Fn(usize, &mut Window, &mut App) + 'static,
+        handler: impl Fn(T, &mut Window, &mut App) + 'static,
     ) -> Self {
         self.on_change = Some(Rc::new(handler));
         self
     }
 
+    /// Sets the handler called with the complete selection after it changes.
+    ///
+    /// This is the handler to use in multi-select mode.
+    pub fn on_selection_change(
+        mut self,
+        handler: impl Fn(Vec<T>, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_selection_change = Some(Rc::new(handler));
+        self
+    }
+
     /// Sets the toggle handler for menu open/close state.
     ///
     /// Only applies to Menu style. The handler receives the new open state.
@@ -137,10 +336,146 @@ impl Picker {
         mut self,
         handler: impl Fn(bool, &mut Window, &mut App) + 'static,
     ) -> Self {
-        self.on_toggle = Some(Box::new(handler));
+        self.on_toggle = Some(Rc::new(handler));
         self
     }
 
+    /// Reads the selection from the bindings and routes changes back to them.
+    fn apply_bindings(&mut self, cx: &App) {
+        if let Some(binding) = self.value_binding.take() {
+            self.selection = vec![binding.get(cx)];
+            let handler = self.on_change.take();
+            self.on_change = Some(Rc::new(move |value: T, window, cx| {
+                binding.set_if_changed(value.clone(), cx);
+                if let Some(ref handler) = handler {
+                    handler(value, window, cx);
+                }
+            }));
+        }
+        if let Some(binding) = self.values_binding.take() {
+            self.selection = binding.get(cx);
+            let handler = self.on_selection_change.take();
+            self.on_selection_change = Some(Rc::new(move |selection: Vec<T>, window, cx| {
+                binding.set_if_changed(selection.clone(), cx);
+                if let Some(ref handler) = handler {
+                    handler(selection, window, cx);
+                }
+            }));
+        }
+    }
+
+    fn push_option(&mut self, mut option: PickerOption<T>) {
+        option.section = self.sections.len().checked_sub(1);
+        self.options.push(option);
+    }
+
+    /// Returns the selection after the user clicks `value`.
+    fn selection_after_click(selection: &[T], value: &T, multi_select: bool) -> Vec<T> {
+        if !multi_select {
+            return vec![value.clone()];
+        }
+        let mut selection = selection.to_vec();
+        if let Some(position) = selection.iter().position(|v| v == value) {
+            selection.remove(position);
+        } else {
+            selection.push(value.clone());
+        }
+        selection
+    }
+
+    /// Builds the click handler shared by all option rows.
+    fn click_handler(&self) -> ClickHandler<T> {
+        let selection = self.selection.clone();
+        let multi_select = self.multi_select;
+        let on_change = self.on_change.clone();
+        let on_selection_change = self.on_selection_change.clone();
+
+        Rc::new(move |value: &T, window: &mut Window, cx: &mut App| {
+            if let Some(ref handler) = on_change {
+                handler(value.clone(), window, cx);
+            }
+            if let Some(ref handler) = on_selection_change {
+                handler(
+                    Self::selection_after_click(&selection, value, multi_select),
+                    window,
+                    cx,
+                );
+            }
+        })
+    }
+
+    /// Builds the label column for an option, with its optional icon and subtitle.
+    fn option_content(option: &PickerOption<T>, subtitle_color: Hsla) -> Div {
+        let mut content = div().flex().flex_row().items_center().gap(px(6.0));
+
+        if let Some(ref icon) = option.icon {
+            content = content.child(div().child(icon.clone()));
+        }
+
+        let mut text = div().flex().flex_col().child(option.label.clone());
+        if let Some(ref subtitle) = option.subtitle {
+            text = text.child(
+                div()
+                    .text_xs()
+                    .text_color(subtitle_color)
+                    .child(subtitle.clone()),
+            );
+        }
+
+        content.child(text)
+    }
+
+    /// Builds a section header row.
+    fn section_header(title: SharedString) -> Div {
+        div()
+            .px_3()
+            .pt(px(6.0))
+            .pb(px(2.0))
+            .text_xs()
+            .font_weight(FontWeight::SEMIBOLD)
+            .text_color(hsla(0.0, 0.0, 0.50, 1.0))
+            .child(title)
+    }
+
+    /// Builds a removable chip for a selected option in multi-select mode.
+    fn build_chip(
+        index: usize,
+        label: SharedString,
+        on_remove: Option<RemoveHandler>,
+    ) -> Stateful<Div> {
+        let mut chip = div()
+            .id(("picker-chip", index))
+            .flex()
+            .flex_row()
+            .items_center()
+            .gap(px(4.0))
+            .px(px(6.0))
+            .h(px(18.0))
+            .rounded(px(9.0))
+            .bg(hsla(211.0 / 360.0, 0.95, 0.53, 0.15))
+            .text_xs()
+            .font_weight(FontWeight::MEDIUM)
+            .text_color(hsla(211.0 / 360.0, 0.80, 0.35, 1.0))
+            .child(label);
+
+        if let Some(on_remove) = on_remove {
+            chip = chip.child(
+                div()
+                    .id(("picker-chip-remove", index))
+                    .cursor_pointer()
+                    .text_color(hsla(211.0 / 360.0, 0.50, 0.45, 1.0))
+                    .hover(|style| style.text_color(hsla(211.0 / 360.0, 0.80, 0.25, 1.0)))
+                    .child("✕")
+                    .on_mouse_down(MouseButton::Left, move |_event, window, cx| {
+                        cx.stop_propagation();
+                        on_remove(window, cx);
+                    }),
+            );
+        }
+
+        chip
+    }
+
     /// Builds the Menu style picker (dropdown).
     ///
     /// When `on_toggle` is provided, the picker operates in controlled mode where
@@ -151,16 +486,28 @@ impl Picker {
     fn build_menu(self) -> Stateful<Div> {
         let disabled = self.disabled;
         let is_open = self.is_open;
-        let selected_index = self.selected_index;
-        let on_change = self.on_change;
+        let multi_select = self.multi_select;
+        let on_click = self.click_handler();
+        let selection = self.selection.clone();
+        let on_selection_change = self.on_selection_change.clone();
         let on_toggle = self.on_toggle;
         let options = self.options.clone();
+        let sections = self.sections.clone();
+        let dropdown_header = self.dropdown_header;
+        let on_remove = self.on_remove;
+        // Chips show every selected option, the dropdown only the matching ones
+        let dropdown_options: Vec<PickerOption<T>> = options
+            .iter()
+            .filter(|option| self.query.is_empty() || option.matches(&self.query))
+            .cloned()
+            .collect();
         let has_toggle_handler = on_toggle.is_some();
 
         // Get the selected label
         let selected_label = self
             .options
-            .get(selected_index)
+            .iter()
+            .find(|o| self.selection.first() == Some(&o.value))
             .map(|o| o.label.clone())
             .unwrap_or_else(|| "Select...".into());
 
@@ -204,10 +551,10 @@ impl Picker {
             .flex_row()
             .items_center()
             .justify_between()
+            .gap(px(6.0))
             .px_3()
             .py_1()
             .min_w(px(120.0))
-            .h(px(24.0))
             .rounded(px(6.0))
             .text_sm()
             .bg(bg_color)
@@ -231,7 +578,38 @@ impl Picker {
             .text_color(hsla(0.0, 0.0, 0.50, 1.0))
             .child(if has_toggle_handler && is_open { "▲" } else { "▼" });
 
-        trigger = trigger.child(selected_label).child(arrow);
+        if multi_select && !selection.is_empty() {
+            // Selected options as removable chips
+            let mut chips = div().flex().flex_row().flex_wrap().gap(px(4.0));
+            for (index, value) in selection.iter().enumerate() {
+                let Some(option) = options.iter().find(|o| &o.value == value) else {
+                    continue;
+                };
+                let remove_chip = match (&on_remove, &on_selection_change) {
+                    _ if disabled => None,
+                    (Some(handler), _) => {
+                        let handler = handler.clone();
+                        let value = value.clone();
+                        Some(Rc::new(move |window: &mut Window, cx: &mut App| {
+                            handler(value.clone(), window, cx);
+                        }) as RemoveHandler)
+                    }
+                    (None, Some(handler)) => {
+                        let handler = handler.clone();
+                        let remaining: Vec<T> =
+                            selection.iter().filter(|v| *v != value).cloned().collect();
+                        Some(Rc::new(move |window: &mut Window, cx: &mut App| {
+                            handler(remaining.clone(), window, cx);
+                        }) as RemoveHandler)
+                    }
+                    (None, None) => None,
+                };
+                chips = chips.child(Self::build_chip(index, option.label.clone(), remove_chip));
+            }
+            trigger = trigger.min_h(px(24.0)).child(chips).child(arrow);
+        } else {
+            trigger = trigger.h(px(24.0)).child(selected_label).child(arrow);
+        }
 
         // Add click handler for toggle (only in controlled mode)
         if !disabled {
@@ -244,13 +622,14 @@ impl Picker {
         }
 
         // Build the dropdown menu
-        let build_dropdown = |options: Vec<PickerOption>, selected_index: usize, on_change: Option<Rc<dyn Fn(usize, &mut Window, &mut App) + 'static>>| {
+        let build_dropdown = |options: Vec<PickerOption<T>>,
+                              header: Option<AnyElement>,
+                              on_click: ClickHandler<T>| {
             let mut dropdown = div()
-                .absolute()
-                .top_full()
-                .left(px(0.0))
-                .mt(px(4.0))
+                .id("picker-dropdown")
                 .min_w(px(120.0))
+                .max_h(px(320.0))
+                .overflow_y_scroll()
                 .bg(hsla(0.0, 0.0, 1.0, 1.0))
                 .rounded(px(6.0))
                 .border_1()
@@ -260,27 +639,56 @@ impl Picker {
                     offset: point(px(0.0), px(4.0)),
                     blur_radius: px(12.0),
                     spread_radius: px(0.0),
-                }])
-                .overflow_hidden();
+                }]);
+
+            if let Some(header) = header {
+                dropdown = dropdown.child(header);
+            }
+
+            if options.is_empty() {
+                dropdown = dropdown.child(
+                    div()
+                        .px_3()
+                        .py_1()
+                        .text_sm()
+                        .text_color(hsla(0.0, 0.0, 0.55, 1.0))
+                        .child("No matches"),
+                );
+            }
 
             // Add options
+            let mut current_section = None;
             for (index, option) in options.into_iter().enumerate() {
-                let is_selected = index == selected_index;
-                let option_bg = if is_selected {
+                if option.section != current_section {
+                    current_section = option.section;
+                    if let Some(title) = option.section.and_then(|i| sections.get(i)) {
+                        dropdown = dropdown.child(Self::section_header(title.clone()));
+                    }
+                }
+
+                let is_selected = selection.contains(&option.value);
+                // Multi-select marks selected options with a checkmark instead of a fill
+                let is_filled = is_selected && !multi_select;
+                let option_bg = if is_filled {
                     hsla(211.0 / 360.0, 0.95, 0.53, 1.0)
                 } else {
                     hsla(0.0, 0.0, 1.0, 1.0)
                 };
-                let option_text = if is_selected {
+                let option_text = if is_filled {
                     gpui::white()
                 } else {
                     hsla(0.0, 0.0, 0.20, 1.0)
                 };
-                let option_hover_bg = if is_selected {
+                let option_hover_bg = if is_filled {
                     hsla(211.0 / 360.0, 0.95, 0.48, 1.0)
                 } else {
                     hsla(0.0, 0.0, 0.95, 1.0)
                 };
+                let subtitle_color = if is_filled {
+                    hsla(0.0, 0.0, 1.0, 0.8)
+                } else {
+                    hsla(0.0, 0.0, 0.50, 1.0)
+                };
 
                 let mut option_row = div()
                     .id(("picker-option", index))
@@ -292,16 +700,25 @@ impl Picker {
                     .bg(option_bg)
                     .text_color(option_text)
                     .cursor_pointer()
-                    .hover(move |style| style.bg(option_hover_bg))
-                    .child(option.label);
+                    .hover(move |style| style.bg(option_hover_bg));
+
+                if multi_select {
+                    option_row = option_row.child(
+                        div()
+                            .mr(px(6.0))
+                            .w(px(12.0))
+                            .text_xs()
+                            .child(if is_selected { "✓" } else { "" }),
+                    );
+                }
 
-                // Add click handler
-                if let Some(ref handler) = on_change {
-                    let handler = handler.clone();
-                    option_row = option_row.on_click(move |_event, window, cx| {
-                        handler(index, window, cx);
+                let on_click = on_click.clone();
+                let value = option.value.clone();
+                option_row = option_row
+                    .child(Self::option_content(&option, subtitle_color))
+                    .on_click(move |_event, window, cx| {
+                        on_click(&value, window, cx);
                     });
-                }
 
                 dropdown = dropdown.child(option_row);
             }
@@ -309,27 +726,38 @@ impl Picker {
             dropdown
         };
 
+        // Open below the trigger (or above when there is no room), at least as wide as it
+        let position_dropdown = |dropdown: AnyElement| {
+            AnchoredOverlay::new(dropdown)
+                .offset(px(4.0))
+                .match_anchor_width(true)
+        };
+
         // Wrap trigger in relative container for dropdown positioning
         let trigger_container = if has_toggle_handler {
             // Controlled mode: show dropdown based on is_open state
             let mut tc = div().relative().child(trigger);
             if is_open && !disabled {
-                tc = tc.child(build_dropdown(options, selected_index, on_change));
+                tc = tc.child(position_dropdown(
+                    build_dropdown(dropdown_options, dropdown_header, on_click)
+                        .into_any_element(),
+                ));
             }
             tc
         } else if !disabled {
             // Hover mode: use group hover to show dropdown (like Menu component)
-            let dropdown = build_dropdown(options, selected_index, on_change);
+            let dropdown = build_dropdown(dropdown_options, dropdown_header, on_click);
             div()
                 .relative()
                 .group("picker-dropdown")
                 .child(trigger)
-                .child(
+                .child(position_dropdown(
                     div()
                         .invisible()
                         .group_hover("picker-dropdown", |style| style.visible())
-                        .child(dropdown),
-                )
+                        .child(dropdown)
+                        .into_any_element(),
+                ))
         } else {
             // Disabled: no dropdown
             div().relative().child(trigger)
@@ -341,8 +769,7 @@ impl Picker {
     /// Builds the Segmented style picker (horizontal button group).
     fn build_segmented(self) -> Stateful<Div> {
         let disabled = self.disabled;
-        let selected_index = self.selected_index;
-        let on_change = self.on_change;
+        let on_click = self.click_handler();
 
         // Colors
         let text_color = if disabled {
@@ -388,7 +815,7 @@ impl Picker {
         let option_count = self.options.len();
 
         for (index, option) in self.options.into_iter().enumerate() {
-            let is_selected = index == selected_index;
+            let is_selected = self.selection.contains(&option.value);
             let is_last = index == option_count - 1;
 
             // Segment colors
@@ -417,16 +844,22 @@ impl Picker {
             let mut segment = div()
                 .id(("picker-segment", index))
                 .flex()
+                .flex_row()
                 .items_center()
                 .justify_center()
+                .gap(px(4.0))
                 .px_3()
                 .py_1()
                 .min_w(px(60.0))
                 .text_sm()
                 .font_weight(FontWeight::MEDIUM)
                 .bg(segment_bg)
-                .text_color(segment_text)
-                .child(option.label);
+                .text_color(segment_text);
+
+            if let Some(icon) = option.icon {
+                segment = segment.child(div().child(icon));
+            }
+            segment = segment.child(option.label);
 
             // Add right border separator (except for last item)
             if !is_last {
@@ -441,12 +874,11 @@ impl Picker {
                 segment = segment.cursor_pointer().hover(move |style| style.bg(segment_hover_bg));
 
                 // Add click handler
-                if let Some(ref handler) = on_change {
-                    let handler = handler.clone();
-                    segment = segment.on_click(move |_event, window, cx| {
-                        handler(index, window, cx);
-                    });
-                }
+                let on_click = on_click.clone();
+                let value = option.value;
+                segment = segment.on_click(move |_event, window, cx| {
+                    on_click(&value, window, cx);
+                });
             }
 
             segments = segments.child(segment);
@@ -458,8 +890,7 @@ impl Picker {
     /// Builds the Inline style picker (vertical list).
     fn build_inline(self) -> Stateful<Div> {
         let disabled = self.disabled;
-        let selected_index = self.selected_index;
-        let on_change = self.on_change;
+        let on_click = self.click_handler();
 
         // Colors
         let text_color = if disabled {
@@ -485,8 +916,16 @@ impl Picker {
         }
 
         // Build the options list
+        let mut current_section = None;
         for (index, option) in self.options.into_iter().enumerate() {
-            let is_selected = index == selected_index;
+            if option.section != current_section {
+                current_section = option.section;
+                if let Some(title) = option.section.and_then(|i| self.sections.get(i)) {
+                    container = container.child(Self::section_header(title.clone()));
+                }
+            }
+
+            let is_selected = self.selection.contains(&option.value);
 
             // Option colors
             let option_bg = if is_selected && !disabled {
@@ -509,6 +948,12 @@ impl Picker {
                 hsla(0.0, 0.0, 0.95, 1.0)
             };
 
+            let subtitle_color = if is_selected && !disabled {
+                hsla(0.0, 0.0, 1.0, 0.8)
+            } else {
+                hsla(0.0, 0.0, 0.50, 1.0)
+            };
+
             let mut option_row = div()
                 .id(("picker-inline-option", index))
                 .flex()
@@ -540,18 +985,17 @@ impl Picker {
                 );
             }
 
-            option_row = option_row.child(option.label);
+            option_row = option_row.child(Self::option_content(&option, subtitle_color));
 
             if !disabled {
                 option_row = option_row.cursor_pointer().hover(move |style| style.bg(option_hover_bg));
 
                 // Add click handler
-                if let Some(ref handler) = on_change {
-                    let handler = handler.clone();
-                    option_row = option_row.on_click(move |_event, window, cx| {
-                        handler(index, window, cx);
-                    });
-                }
+                let on_click = on_click.clone();
+                let value = option.value;
+                option_row = option_row.on_click(move |_event, window, cx| {
+                    on_click(&value, window, cx);
+                });
             }
 
             container = container.child(option_row);
@@ -561,10 +1005,17 @@ impl Picker {
     }
 }
 
-impl IntoElement for Picker {
-    type Element = Stateful<Div>;
+impl<T: Clone + PartialEq + 'static> IntoElement for Picker<T> {
+    type Element = Component<Self>;
 
     fn into_element(self) -> Self::Element {
+        Component::new(self)
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> RenderOnce for Picker<T> {
+    fn render(mut self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        self.apply_bindings(cx);
         match self.picker_style {
             PickerStyle::Menu => self.build_menu(),
             PickerStyle::Segmented => self.build_segmented(),
@@ -573,6 +1024,321 @@ impl IntoElement for Picker {
     }
 }
 
+/// Internal state for a Menu style Picker rendered as a view.
+///
+/// `PickerState` manages the open state and selection itself and, when the
+/// picker is `searchable`, shows a filter field at the top of the dropdown.
+///
+/// # Example
+///
+/// ```ignore
+/// struct SettingsView {
+///     timezone: Entity<PickerState<Tz>>,
+/// }
+///
+/// impl SettingsView {
+///     fn new(cx: &mut Context<Self>) -> Self {
+///         Self {
+///             timezone: cx.new(|cx| PickerState::new(
+///                 Picker::with_value("timezone", Some(Tz::Utc))
+///                     .searchable(true)
+///                     .section("Europe", europe_options())
+///                     .section("America", america_options())
+///                     .on_change(|tz, _window, _cx| save_timezone(tz)),
+///                 cx,
+///             )),
+///         }
+///     }
+/// }
+/// ```
+pub struct PickerState<T: Clone + PartialEq + 'static> {
+    id: ElementId,
+    label: Option<SharedString>,
+    options: Vec<PickerOption<T>>,
+    sections: Vec<SharedString>,
+    selection: Vec<T>,
+    multi_select: bool,
+    disabled: bool,
+    is_open: bool,
+    on_change: Option<ChangeHandler<T>>,
+    on_selection_change: Option<SelectionHandler<T>>,
+    value_binding: Option<Binding<T>>,
+    values_binding: Option<Binding<Vec<T>>>,
+    /// Filter field shown in the dropdown when the picker is searchable.
+    filter: Option<Entity<TextFieldState>>,
+    query: String,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl<T: Clone + PartialEq + 'static> PickerState<T> {
+    /// Creates the picker view from a builder.
+    pub fn new(builder: Picker<T>, cx: &mut Context<Self>) -> Self {
+        let mut subscriptions = Vec::new();
+        let filter = builder.searchable.then(|| {
+            let field = TextField::new("picker-filter", cx)
+                .placeholder("Filter");
+            let filter = cx.new(|_| TextFieldState::from(field));
+            subscriptions.push(cx.observe(&filter, |this, filter, cx| {
+                let query = filter.read(cx).value().to_string();
+                if query != this.query {
+                    this.query = query;
+                    cx.notify();
+                }
+            }));
+            filter
+        });
+
+        Self {
+            id: builder.id,
+            label: builder.label,
+            options: builder.options,
+            sections: builder.sections,
+            selection: builder.selection,
+            multi_select: builder.multi_select,
+            disabled: builder.disabled,
+            is_open: false,
+            on_change: builder.on_change,
+            on_selection_change: builder.on_selection_change,
+            value_binding: builder.value_binding,
+            values_binding: builder.values_binding,
+            filter,
+            query: String::new(),
+            _subscriptions: subscriptions,
+        }
+    }
+
+    /// Returns the selected values.
+    pub fn selection(&self) -> &[T] {
+        &self.selection
+    }
+
+    /// Returns the selected value in single-selection mode.
+    pub fn value(&self) -> Option<&T> {
+        self.selection.first()
+    }
+
+    /// Sets the selected values programmatically.
+    pub fn set_selection(&mut self, selection: Vec<T>) {
+        self.selection = selection;
+    }
+
+    /// Opens the dropdown.
+    pub fn open(&mut self) {
+        self.is_open = true;
+    }
+
+    /// Closes the dropdown and clears the filter.
+    pub fn close(&mut self, cx: &mut Context<Self>) {
+        self.is_open = false;
+        self.query.clear();
+        if let Some(ref filter) = self.filter {
+            filter.update(cx, |field, cx| field.replace_text(String::new(), cx));
+        }
+        cx.notify();
+    }
+
+    fn set_open(&mut self, is_open: bool, window: &mut Window, cx: &mut Context<Self>) {
+        if is_open {
+            self.is_open = true;
+            if let Some(ref filter) = self.filter {
+                filter.read(cx).focus(window);
+            }
+            cx.notify();
+        } else {
+            self.close(cx);
+        }
+    }
+
+    fn select(&mut self, value: T, window: &mut Window, cx: &mut Context<Self>) {
+        self.selection = Picker::selection_after_click(&self.selection, &value, self.multi_select);
+        if !self.multi_select {
+            self.close(cx);
+        }
+        if let Some(ref binding) = self.value_binding {
+            binding.set_if_changed(value.clone(), cx);
+        }
+        if let Some(handler) = self.on_change.clone() {
+            handler(value, window, cx);
+        }
+        self.notify_selection(window, cx);
+        cx.notify();
+    }
+
+    fn remove(&mut self, value: &T, window: &mut Window, cx: &mut Context<Self>) {
+        self.selection.retain(|selected| selected != value);
+        self.notify_selection(window, cx);
+        cx.notify();
+    }
+
+    fn notify_selection(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ref binding) = self.values_binding {
+            binding.set_if_changed(self.selection.clone(), cx);
+        }
+        if let Some(handler) = self.on_selection_change.clone() {
+            handler(self.selection.clone(), window, cx);
+        }
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> Render for PickerState<T> {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // Sync selection from bindings
+        if let Some(ref binding) = self.value_binding {
+            self.selection = vec![binding.get(cx)];
+        } else if let Some(ref binding) = self.values_binding {
+            self.selection = binding.get(cx);
+        }
+
+        let this = cx.weak_entity();
+        let on_change: ChangeHandler<T> = Rc::new(move |value, window, cx| {
+            this.update(cx, |this, cx| this.select(value, window, cx)).ok();
+        });
+
+        let this = cx.weak_entity();
+        let on_remove: ChangeHandler<T> = Rc::new(move |value, window, cx| {
+            this.update(cx, |this, cx| this.remove(&value, window, cx)).ok();
+        });
+
+        let this = cx.weak_entity();
+        let on_toggle: ToggleHandler = Rc::new(move |is_open, window, cx| {
+            this.update(cx, |this, cx| this.set_open(is_open, window, cx)).ok();
+        });
+
+        let dropdown_header = self.filter.clone().map(|filter| {
+            div()
+                .p(px(6.0))
+                .border_b_1()
+                .border_color(hsla(0.0, 0.0, 0.88, 1.0))
+                .child(filter)
+                .into_any_element()
+        });
+
+        Picker {
+            id: self.id.clone(),
+            label: self.label.clone(),
+            options: self.options.clone(),
+            sections: self.sections.clone(),
+            selection: self.selection.clone(),
+            multi_select: self.multi_select,
+            searchable: self.filter.is_some(),
+            picker_style: PickerStyle::Menu,
+            disabled: self.disabled,
+            is_open: self.is_open,
+            on_change: Some(on_change),
+            on_selection_change: None,
+            on_toggle: Some(on_toggle),
+            value_binding: None,
+            values_binding: None,
+            dropdown_header,
+            query: self.query.clone(),
+            on_remove: Some(on_remove),
+        }
+        .build_menu()
+    }
+}
+
+/// Adds the options to a picker's node and marks the selected ones.
+///
+/// A combo box lists its options in a menu and shows the selected labels as
+/// its text. Bound selections are read when the tree is resolved.
+fn picker_node<T: Clone + PartialEq + 'static>(
+    node: AccessNode,
+    option_role: Role,
+    options: &[PickerOption<T>],
+    selection: &[T],
+    value_binding: Option<Binding<T>>,
+    values_binding: Option<Binding<Vec<T>>>,
+) -> AccessNode {
+    let id = node.id.clone().unwrap_or_default();
+    let options: Vec<(T, SharedString)> = options
+        .iter()
+        .map(|option| (option.value.clone(), option.label.clone()))
+        .collect();
+    let with_selection = move |mut node: AccessNode, selection: &[T]| {
+        let items = options.iter().enumerate().map(|(index, (value, label))| {
+            AccessNode::new(option_role)
+                .id(format!("{}-option-{}", id, index))
+                .name(label.clone())
+                .state(AccessState::Checkable)
+                .state_if(AccessState::Checked, selection.contains(value))
+                .action("click")
+        });
+        if node.role == Role::ComboBox {
+            let text: Vec<&str> = options
+                .iter()
+                .filter(|(value, _)| selection.contains(value))
+                .map(|(_, label)| label.as_ref())
+                .collect();
+            node.text = Some(text.join(", ").into());
+            node.children = vec![AccessNode::new(Role::Menu).children(items)];
+        } else {
+            node.children = items.collect();
+        }
+        node
+    };
+    match (value_binding, values_binding) {
+        (Some(binding), _) => {
+            node.resolve_with(move |node, cx| with_selection(node, &[binding.get(cx)]))
+        }
+        (None, Some(binding)) => {
+            node.resolve_with(move |node, cx| with_selection(node, &binding.get(cx)))
+        }
+        (None, None) => with_selection(node, selection),
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> Accessible for Picker<T> {
+    fn accessibility_node(&self) -> AccessNode {
+        let (role, option_role) = match self.picker_style {
+            PickerStyle::Menu => (Role::ComboBox, Role::MenuItem),
+            PickerStyle::Segmented | PickerStyle::Inline if self.multi_select => {
+                (Role::Group, Role::CheckBox)
+            }
+            PickerStyle::Segmented | PickerStyle::Inline => (Role::Group, Role::RadioButton),
+        };
+        let node = AccessNode::new(role)
+            .id(self.id.to_string())
+            .name(self.label.clone().unwrap_or_default())
+            .state_if(AccessState::Focusable, role == Role::ComboBox)
+            .state_if(AccessState::Expandable, role == Role::ComboBox)
+            .state_if(AccessState::HasPopup, role == Role::ComboBox)
+            .state_if(
+                AccessState::Expanded,
+                role == Role::ComboBox && self.is_open,
+            )
+            .state_if(AccessState::Disabled, self.disabled);
+        picker_node(
+            node,
+            option_role,
+            &self.options,
+            &self.selection,
+            self.value_binding.clone(),
+            self.values_binding.clone(),
+        )
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> Accessible for PickerState<T> {
+    fn accessibility_node(&self) -> AccessNode {
+        let node = AccessNode::new(Role::ComboBox)
+            .id(self.id.to_string())
+            .name(self.label.clone().unwrap_or_default())
+            .state(AccessState::Focusable)
+            .state(AccessState::Expandable)
+            .state(AccessState::HasPopup)
+            .state_if(AccessState::Expanded, self.is_open)
+            .state_if(AccessState::Disabled, self.disabled);
+        picker_node(
+            node,
+            Role::MenuItem,
+            &self.options,
+            &self.selection,
+            self.value_binding.clone(),
+            self.values_binding.clone(),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -580,7 +1346,7 @@ mod tests {
     #[test]
     fn test_picker_creation() {
         let picker = Picker::new("test", 0);
-        assert_eq!(picker.selected_index, 0);
+        assert_eq!(picker.selection, vec![0]);
         assert_eq!(picker.picker_style, PickerStyle::Menu);
         assert!(!picker.disabled);
         assert!(picker.options.is_empty());
@@ -593,7 +1359,8 @@ mod tests {
             .option("Blue")
             .option("Green");
         assert_eq!(picker.options.len(), 3);
-        assert_eq!(picker.selected_index, 1);
+        assert_eq!(picker.selection, vec![1]);
+        assert_eq!(picker.options[2].value, 2);
     }
 
     #[test]
@@ -628,4 +1395,66 @@ mod tests {
         let picker = Picker::new("test", 0).is_open(true);
         assert!(picker.is_open);
     }
+
+    #[test]
+    fn test_picker_sections() {
+        let picker = Picker::with_value("test", Some("utc"))
+            .item(PickerOption::new("utc", "UTC"))
+            .section(
+                "Europe",
+                vec![
+                    PickerOption::new("berlin", "Berlin"),
+                    PickerOption::new("paris", "Paris"),
+                ],
+            )
+            .section("Asia", vec![PickerOption::new("tokyo", "Tokyo")]);
+        assert_eq!(picker.sections.len(), 2);
+        assert_eq!(picker.options[0].section, None);
+        assert_eq!(picker.options[1].section, Some(0));
+        assert_eq!(picker.options[2].section, Some(0));
+        assert_eq!(picker.options[3].section, Some(1));
+    }
+
+    #[test]
+    fn test_picker_option_matches() {
+        let option = PickerOption::new(1, "Berlin").subtitle("UTC+01:00");
+        assert!(option.matches("berl"));
+        assert!(option.matches("utc+01"));
+        assert!(!option.matches("paris"));
+    }
+
+    #[test]
+    fn test_selection_after_click() {
+        assert_eq!(Picker::selection_after_click(&[1, 2], &3, false), vec![3]);
+        assert_eq!(Picker::selection_after_click(&[1, 2], &3, true), vec![1, 2, 3]);
+        assert_eq!(Picker::selection_after_click(&[1, 2], &1, true), vec![2]);
+    }
+
+    #[test]
+    fn test_picker_accessibility_node() {
+        let picker = Picker::new("color", 1)
+            .label("Color")
+            .option("Red")
+            .option("Blue");
+        let node = picker.accessibility_node();
+        assert_eq!(
+            node.children[0].children[1].id,
+            Some("color-option-1".into())
+        );
+        assert_eq!(
+            node.to_string(),
+            "combo box \"Color\" text=\"Blue\" [focusable, expandable, has-popup]\n\
+             \x20 menu\n\
+             \x20   menu item \"Red\" [checkable]\n\
+             \x20   menu item \"Blue\" [checkable, checked]"
+        );
+
+        let picker = Picker::new("size", 0).segmented().option("S").option("M");
+        assert_eq!(
+            picker.accessibility_node().to_string(),
+            "grouping\n\
+             \x20 radio button \"S\" [checkable, checked]\n\
+             \x20 radio button \"M\" [checkable]"
+        );
+    }
 }
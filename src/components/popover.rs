@@ -2,11 +2,18 @@
 //!
 //! A transient view that appears above content, attached to an anchor element.
 //! Features arrow pointing to anchor, multiple edge attachments, and click-outside-to-dismiss.
+//! Placement goes through `AnchoredOverlay`, so popovers flip to the opposite
+//! edge or shift to stay inside the window.
 
 use gpui::prelude::*;
 use gpui::*;
 
+use super::anchored::{Align, AnchoredOverlay, Placement, Side};
+
 /// Edge where the popover appears relative to the anchor element.
+///
+/// This is the preferred edge; the popover flips to the opposite edge when
+/// there is not enough room in the window.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum PopoverEdge {
     /// Popover appears above the anchor.
@@ -20,6 +27,19 @@ pub enum PopoverEdge {
     Trailing,
 }
 
+impl PopoverEdge {
+    /// Returns the anchored placement for this edge.
+    pub(crate) fn placement(self) -> Placement {
+        let side = match self {
+            PopoverEdge::Top => Side::Top,
+            PopoverEdge::Bottom => Side::Bottom,
+            PopoverEdge::Leading => Side::Left,
+            PopoverEdge::Trailing => Side::Right,
+        };
+        Placement::new(side, Align::Start)
+    }
+}
+
 /// A popover component that displays floating content attached to an anchor.
 ///
 /// Popover is a transient view that appears above other content, with an optional arrow
@@ -117,54 +137,26 @@ impl Popover {
         self
     }
 
-    /// Builds the arrow element for the given edge.
-    /// Uses a simple diamond/square shape rotated to create arrow effect.
-    fn build_arrow(edge: PopoverEdge, panel_bg: Hsla, border_color: Hsla) -> Div {
-        let arrow_size = 10.0;
-        let half_size = arrow_size / 2.0;
-
-        // Create a small square that will act as the arrow
-        // Position it so half overlaps the panel
+    /// Builds the arrow element for the side the panel is attached to.
+    ///
+    /// Uses a small square with two borders removed; `AnchoredOverlay` centers
+    /// it on the panel edge facing the anchor.
+    pub(crate) fn build_arrow(side: Side, panel_bg: Hsla, border_color: Hsla) -> Div {
         let arrow = div()
-            .absolute()
-            .size(px(arrow_size))
+            .size(px(10.0))
             .bg(panel_bg)
             .border_1()
             .border_color(border_color);
 
-        match edge {
-            PopoverEdge::Top => {
-                // Arrow points down, at bottom of popover
-                arrow
-                    .bottom(px(-half_size))
-                    .left(px(16.0))
-                    .border_t_0()
-                    .border_l_0()
-            }
-            PopoverEdge::Bottom => {
-                // Arrow points up, at top of popover
-                arrow
-                    .top(px(-half_size))
-                    .left(px(16.0))
-                    .border_b_0()
-                    .border_r_0()
-            }
-            PopoverEdge::Leading => {
-                // Arrow points right, at right of popover
-                arrow
-                    .right(px(-half_size))
-                    .top(px(12.0))
-                    .border_t_0()
-                    .border_l_0()
-            }
-            PopoverEdge::Trailing => {
-                // Arrow points left, at left of popover
-                arrow
-                    .left(px(-half_size))
-                    .top(px(12.0))
-                    .border_b_0()
-                    .border_r_0()
-            }
+        match side {
+            // Arrow points down, at bottom of popover
+            Side::Top => arrow.border_t_0().border_l_0(),
+            // Arrow points up, at top of popover
+            Side::Bottom => arrow.border_b_0().border_r_0(),
+            // Arrow points right, at right of popover
+            Side::Left => arrow.border_t_0().border_l_0(),
+            // Arrow points left, at left of popover
+            Side::Right => arrow.border_b_0().border_r_0(),
         }
     }
 }
@@ -188,8 +180,7 @@ impl IntoElement for Popover {
             .unwrap_or_else(|| div().into_any_element());
 
         // Build the popover panel
-        let mut panel = div()
-            .absolute()
+        let panel = div()
             .overflow_hidden()
             .bg(panel_bg)
             .rounded(px(8.0))
@@ -212,19 +203,26 @@ impl IntoElement for Popover {
             ])
             .child(content_element);
 
+        // Popover that appears on group hover, positioned next to the anchor
+        let mut positioned_panel = AnchoredOverlay::new(
+            div()
+                .invisible()
+                .group_hover("", |style| style.visible())
+                .child(panel),
+        )
+        .placement(edge.placement())
+        .offset(px(8.0));
+
         // Add arrow if enabled
         if show_arrow {
-            panel = panel.child(Self::build_arrow(edge, panel_bg, border_color));
+            positioned_panel = positioned_panel.arrow(move |side| {
+                div()
+                    .invisible()
+                    .group_hover("", |style| style.visible())
+                    .child(Self::build_arrow(side, panel_bg, border_color))
+            });
         }
 
-        // Position panel based on edge
-        let positioned_panel = match edge {
-            PopoverEdge::Top => panel.bottom_full().left(px(0.0)).mb(px(8.0)),
-            PopoverEdge::Bottom => panel.top_full().left(px(0.0)).mt(px(8.0)),
-            PopoverEdge::Leading => panel.right_full().top(px(0.0)).mr(px(8.0)),
-            PopoverEdge::Trailing => panel.left_full().top(px(0.0)).ml(px(8.0)),
-        };
-
         // The popover container with the anchor and overlay
         // Uses group hover pattern for showing on hover
         div()
@@ -232,13 +230,7 @@ impl IntoElement for Popover {
             .relative()
             .group("")
             .child(self.anchor)
-            .child(
-                // Popover that appears on group hover
-                div()
-                    .invisible()
-                    .group_hover("", |style| style.visible())
-                    .child(positioned_panel),
-            )
+            .child(positioned_panel)
     }
 }
 
@@ -275,6 +267,7 @@ pub struct ControlledPopover {
     content: Option<Box<dyn FnOnce() -> AnyElement>>,
     on_dismiss: Option<Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>>,
     show_arrow: bool,
+    match_anchor_width: bool,
 }
 
 impl ControlledPopover {
@@ -293,6 +286,7 @@ impl ControlledPopover {
             content: None,
             on_dismiss: None,
             show_arrow: true,
+            match_anchor_width: false,
         }
     }
 
@@ -348,6 +342,12 @@ impl ControlledPopover {
         self
     }
 
+    /// Makes the popover at least as wide as the anchor, as for dropdowns.
+    pub fn match_anchor_width(mut self, match_width: bool) -> Self {
+        self.match_anchor_width = match_width;
+        self
+    }
+
     /// Sets the dismiss handler, called when clicking outside the popover.
     pub fn on_dismiss(
         mut self,
@@ -356,42 +356,6 @@ impl ControlledPopover {
         self.on_dismiss = Some(Box::new(handler));
         self
     }
-
-    /// Builds the arrow element for the given edge.
-    fn build_arrow(edge: PopoverEdge, panel_bg: Hsla, border_color: Hsla) -> Div {
-        let arrow_size = 10.0;
-        let half_size = arrow_size / 2.0;
-
-        let arrow = div()
-            .absolute()
-            .size(px(arrow_size))
-            .bg(panel_bg)
-            .border_1()
-            .border_color(border_color);
-
-        match edge {
-            PopoverEdge::Top => arrow
-                .bottom(px(-half_size))
-                .left(px(16.0))
-                .border_t_0()
-                .border_l_0(),
-            PopoverEdge::Bottom => arrow
-                .top(px(-half_size))
-                .left(px(16.0))
-                .border_b_0()
-                .border_r_0(),
-            PopoverEdge::Leading => arrow
-                .right(px(-half_size))
-                .top(px(12.0))
-                .border_t_0()
-                .border_l_0(),
-            PopoverEdge::Trailing => arrow
-                .left(px(-half_size))
-                .top(px(12.0))
-                .border_b_0()
-                .border_r_0(),
-        }
-    }
 }
 
 impl IntoElement for ControlledPopover {
@@ -415,8 +379,7 @@ impl IntoElement for ControlledPopover {
                 .map(|builder| builder())
                 .unwrap_or_else(|| div().into_any_element());
 
-            let mut panel = div()
-                .absolute()
+            let panel = div()
                 .overflow_hidden()
                 .bg(panel_bg)
                 .rounded(px(8.0))
@@ -438,19 +401,8 @@ impl IntoElement for ControlledPopover {
                 ])
                 .child(content_element);
 
-            if show_arrow {
-                panel = panel.child(Self::build_arrow(edge, panel_bg, border_color));
-            }
-
-            let positioned_panel = match edge {
-                PopoverEdge::Top => panel.bottom_full().left(px(0.0)).mb(px(8.0)),
-                PopoverEdge::Bottom => panel.top_full().left(px(0.0)).mt(px(8.0)),
-                PopoverEdge::Leading => panel.right_full().top(px(0.0)).mr(px(8.0)),
-                PopoverEdge::Trailing => panel.left_full().top(px(0.0)).ml(px(8.0)),
-            };
-
             // Stop propagation when clicking inside the panel
-            let panel_with_events = positioned_panel
+            let panel_with_events = panel
                 .id("popover-panel")
                 .on_mouse_down(MouseButton::Left, |_event, _window, cx| {
                     cx.stop_propagation();
@@ -468,7 +420,17 @@ impl IntoElement for ControlledPopover {
                 div().id("popover-no-dismiss").child(panel_with_events)
             };
 
-            container = container.child(popover_overlay);
+            let mut positioned_overlay = AnchoredOverlay::new(popover_overlay)
+                .placement(edge.placement())
+                .offset(px(8.0))
+                .match_anchor_width(self.match_anchor_width);
+            if show_arrow {
+                positioned_overlay = positioned_overlay.arrow(move |side| {
+                    Popover::build_arrow(side, panel_bg, border_color)
+                });
+            }
+
+            container = container.child(positioned_overlay);
         }
 
         container
@@ -533,6 +495,17 @@ mod tests {
         assert_eq!(popover.edge, PopoverEdge::Trailing);
     }
 
+    #[test]
+    fn test_popover_edge_placement() {
+        assert_eq!(PopoverEdge::Top.placement().side, Side::Top);
+        assert_eq!(PopoverEdge::Leading.placement().side, Side::Left);
+        assert_eq!(PopoverEdge::Trailing.placement().side, Side::Right);
+        assert_eq!(PopoverEdge::Bottom.placement().align, Align::Start);
+
+        let popover = ControlledPopover::new("test", true, div()).match_anchor_width(true);
+        assert!(popover.match_anchor_width);
+    }
+
     #[test]
     fn test_popover_default_edge() {
         let popover = Popover::new("test", div());
@@ -6,6 +6,8 @@
 use gpui::prelude::*;
 use gpui::*;
 
+use crate::accessibility::{AccessNode, AccessState, Accessible, Role, ValueRange};
+
 /// Style variants for the progress indicator.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum ProgressStyle {
@@ -203,6 +205,17 @@ impl IntoElement for ProgressView {
     }
 }
 
+/// Indeterminate progress is reported as busy, without a value.
+impl Accessible for ProgressView {
+    fn accessibility_node(&self) -> AccessNode {
+        let node = AccessNode::new(Role::ProgressBar).name(self.label.clone().unwrap_or_default());
+        match self.value {
+            Some(value) => node.value(ValueRange::new(value as f64, 0.0..=1.0)),
+            None => node.state(AccessState::Busy),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
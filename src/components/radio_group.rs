@@ -6,6 +6,9 @@ use gpui::prelude::*;
 use gpui::*;
 use std::rc::Rc;
 
+use crate::accessibility::{AccessNode, AccessState, Accessible, Role};
+use crate::state::Binding;
+
 /// A single option in a radio group.
 #[derive(Clone)]
 struct RadioOption {
@@ -28,7 +31,14 @@ struct RadioOption {
 ///         this.local_changes = value.to_string();
 ///         cx.notify();
 ///     }))
+///
+/// // Bound to a `State<SharedString>`
+/// RadioGroup::new("theme")
+///     .option("light", "Light")
+///     .option("dark", "Dark")
+///     .selection(State::binding(&self.theme, cx))
 /// ```
+#[derive(IntoElement)]
 pub struct RadioGroup {
     id: ElementId,
     label: Option<SharedString>,
@@ -36,6 +46,7 @@ pub struct RadioGroup {
     selected: Option<SharedString>,
     disabled: bool,
     on_change: Option<Rc<dyn Fn(&SharedString, &mut Window, &mut App) + 'static>>,
+    selection_binding: Option<Binding<SharedString>>,
 }
 
 impl RadioGroup {
@@ -48,6 +59,7 @@ impl RadioGroup {
             selected: None,
             disabled: false,
             on_change: None,
+            selection_binding: None,
         }
     }
 
@@ -72,6 +84,15 @@ impl RadioGroup {
         self
     }
 
+    /// Sets a two-way binding for the selected value.
+    ///
+    /// The bound value replaces `selected`, and clicking an option writes
+    /// its value back.
+    pub fn selection(mut self, binding: Binding<SharedString>) -> Self {
+        self.selection_binding = Some(binding);
+        self
+    }
+
     /// Sets whether the radio group is disabled.
     pub fn disabled(mut self, disabled: bool) -> Self {
         self.disabled = disabled;
@@ -90,10 +111,19 @@ impl RadioGroup {
     }
 }
 
-impl IntoElement for RadioGroup {
-    type Element = Stateful<Div>;
+impl RenderOnce for RadioGroup {
+    fn render(mut self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        if let Some(binding) = self.selection_binding.take() {
+            self.selected = Some(binding.get(cx));
+            let handler = self.on_change.take();
+            self.on_change = Some(Rc::new(move |value, window, cx| {
+                binding.set_if_changed(value.clone(), cx);
+                if let Some(ref handler) = handler {
+                    handler(value, window, cx);
+                }
+            }));
+        }
 
-    fn into_element(self) -> Self::Element {
         let disabled = self.disabled;
         let selected = self.selected.clone();
         let on_change = self.on_change;
@@ -241,6 +271,53 @@ impl IntoElement for RadioGroup {
     }
 }
 
+impl RadioGroup {
+    fn option_nodes(&self, selected: Option<&SharedString>) -> Vec<AccessNode> {
+        self.options
+            .iter()
+            .map(|option| {
+                AccessNode::new(Role::RadioButton)
+                    .id(format!("{}-{}", self.id, option.value))
+                    .name(option.label.clone())
+                    .state(AccessState::Focusable)
+                    .state(AccessState::Checkable)
+                    .state_if(AccessState::Checked, selected == Some(&option.value))
+                    .state_if(AccessState::Disabled, self.disabled)
+                    .action("click")
+            })
+            .collect()
+    }
+}
+
+impl Accessible for RadioGroup {
+    fn accessibility_node(&self) -> AccessNode {
+        let node = AccessNode::new(Role::Group)
+            .id(self.id.to_string())
+            .name(self.label.clone().unwrap_or_default())
+            .state_if(AccessState::Disabled, self.disabled);
+        match self.selection_binding.clone() {
+            Some(binding) => {
+                let options: Vec<_> = self
+                    .options
+                    .iter()
+                    .map(|option| option.value.clone())
+                    .collect();
+                node.children(self.option_nodes(None))
+                    .resolve_with(move |mut node, cx| {
+                        let selected = binding.get(cx);
+                        for (child, value) in node.children.iter_mut().zip(&options) {
+                            if *value == selected {
+                                *child = child.clone().state(AccessState::Checked);
+                            }
+                        }
+                        node
+                    })
+            }
+            None => node.children(self.option_nodes(self.selected.as_ref())),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,382 @@
+//! Drag-and-drop reordering shared by List, Table, Sidebar and TabView.
+//!
+//! A drag carries a `ReorderDrag` naming the container it started in. While
+//! it moves, the item under the pointer records the insertion gap, which is
+//! drawn as an indicator line and turned into an `on_reorder(from, to)` call
+//! when the drag is dropped on the same container.
+
+use gpui::prelude::*;
+use gpui::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::state::Binding;
+
+pub(crate) type ReorderHandler = Rc<dyn Fn(usize, usize, &mut Window, &mut App) + 'static>;
+
+/// Distance from a scrolling container's edge where dragging auto-scrolls.
+const AUTO_SCROLL_EDGE: f32 = 32.0;
+/// Largest auto-scroll step, per frame, right at the edge.
+const AUTO_SCROLL_STEP: f32 = 12.0;
+
+/// The drag in progress. Only one drag can be active at a time, so it's kept
+/// per thread like the split view's active divider.
+struct Session {
+    owner: ElementId,
+    from: usize,
+    /// The insertion gap under the pointer, from 0 to the item count
+    gap: Option<usize>,
+    /// Pixels to scroll per frame while the pointer is near an edge
+    scroll_step: Pixels,
+}
+
+thread_local! {
+    static SESSION: RefCell<Option<Session>> = const { RefCell::new(None) };
+}
+
+fn with_session<R>(owner: &ElementId, f: impl FnOnce(&mut Session) -> R) -> Option<R> {
+    SESSION.with(|session| {
+        session
+            .borrow_mut()
+            .as_mut()
+            .filter(|session| &session.owner == owner)
+            .map(f)
+    })
+}
+
+/// Drag payload for reordering the items of one container.
+#[derive(Clone)]
+pub(crate) struct ReorderDrag {
+    owner: ElementId,
+    from: usize,
+}
+
+/// Drag preview showing the dragged item's label, if it has one.
+///
+/// The session ends when gpui releases the preview at the end of the drag,
+/// whether or not it was dropped.
+struct ReorderGhost {
+    label: Option<SharedString>,
+}
+
+impl Render for ReorderGhost {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        match self.label.clone() {
+            Some(label) => div()
+                .px(px(10.0))
+                .py(px(4.0))
+                .rounded(px(4.0))
+                .bg(hsla(0.0, 0.0, 1.0, 0.9))
+                .border_1()
+                .border_color(hsla(0.0, 0.0, 0.80, 1.0))
+                .text_sm()
+                .text_color(hsla(0.0, 0.0, 0.20, 1.0))
+                .child(label),
+            None => div().w(px(0.0)).h(px(0.0)),
+        }
+    }
+}
+
+impl Drop for ReorderGhost {
+    fn drop(&mut self) {
+        SESSION.with(|session| session.borrow_mut().take());
+    }
+}
+
+/// Returns the final index of an item moved from `from` into an insertion
+/// gap, or `None` if the move leaves it where it is.
+///
+/// Gaps are numbered from 0 (before the first item) to the item count
+/// (after the last one).
+pub(crate) fn destination(from: usize, gap: usize) -> Option<usize> {
+    let to = if gap > from { gap - 1 } else { gap };
+    (to != from).then_some(to)
+}
+
+/// Returns where the item at `index` ends up after moving `from` to `to`.
+///
+/// Used to keep selections on the same item across a reorder.
+pub(crate) fn moved_index(index: usize, from: usize, to: usize) -> usize {
+    if index == from {
+        to
+    } else if from < index && index <= to {
+        index - 1
+    } else if to <= index && index < from {
+        index + 1
+    } else {
+        index
+    }
+}
+
+/// Returns the destination of a keyboard move by one position, or `None`
+/// at either end.
+pub(crate) fn keyboard_destination(from: usize, count: usize, forward: bool) -> Option<usize> {
+    if forward {
+        (from + 1 < count).then_some(from + 1)
+    } else {
+        from.checked_sub(1)
+    }
+}
+
+/// Returns the insertion gap for a pointer over the item at `index`: the
+/// gap before it on its leading half and after it on its trailing half.
+fn gap_at(index: usize, bounds: Bounds<Pixels>, position: Point<Pixels>, axis: Axis) -> usize {
+    let leading = match axis {
+        Axis::Vertical => position.y < bounds.center().y,
+        Axis::Horizontal => position.x < bounds.center().x,
+    };
+    if leading {
+        index
+    } else {
+        index + 1
+    }
+}
+
+/// Returns the per-frame scroll for a pointer at `y` in a container spanning
+/// `top` to `bottom`: positive towards the start, negative towards the end.
+fn auto_scroll_step(top: Pixels, bottom: Pixels, y: Pixels) -> Pixels {
+    let edge = px(AUTO_SCROLL_EDGE);
+    if y < top + edge {
+        let depth = (top + edge - y).min(edge);
+        px(AUTO_SCROLL_STEP) * (depth / edge)
+    } else if y > bottom - edge {
+        let depth = (y - (bottom - edge)).min(edge);
+        -px(AUTO_SCROLL_STEP) * (depth / edge)
+    } else {
+        px(0.0)
+    }
+}
+
+/// Makes an item of a reorderable container draggable and a drop target.
+///
+/// Draws the insertion indicator before the item, or after it for the last
+/// item, and fades the item while it's being dragged.
+pub(crate) fn reorderable_item(
+    item: Stateful<Div>,
+    owner: &ElementId,
+    index: usize,
+    count: usize,
+    axis: Axis,
+    label: Option<SharedString>,
+) -> Stateful<Div> {
+    let session = with_session(owner, |session| (session.from, session.gap));
+    let is_dragged = session.is_some_and(|(from, _)| from == index);
+    let gap = session.and_then(|(from, gap)| gap.filter(|&gap| destination(from, gap).is_some()));
+
+    let drag = ReorderDrag {
+        owner: owner.clone(),
+        from: index,
+    };
+    let drag_owner = owner.clone();
+    item.relative()
+        .when(is_dragged, |item| item.opacity(0.4))
+        .when(gap == Some(index), |item| {
+            item.child(indicator(axis, false))
+        })
+        .when(gap == Some(count) && index + 1 == count, |item| {
+            item.child(indicator(axis, true))
+        })
+        .on_drag(drag, move |drag: &ReorderDrag, _offset, _window, cx| {
+            SESSION.with(|session| {
+                *session.borrow_mut() = Some(Session {
+                    owner: drag.owner.clone(),
+                    from: drag.from,
+                    gap: None,
+                    scroll_step: px(0.0),
+                })
+            });
+            let label = label.clone();
+            cx.new(|_| ReorderGhost { label })
+        })
+        .on_drag_move(move |event: &DragMoveEvent<ReorderDrag>, _window, _cx| {
+            if event.bounds.contains(&event.event.position) {
+                let gap = gap_at(index, event.bounds, event.event.position, axis);
+                with_session(&drag_owner, |session| session.gap = Some(gap));
+            }
+        })
+}
+
+fn indicator(axis: Axis, trailing: bool) -> Div {
+    let line = div()
+        .absolute()
+        .bg(hsla(211.0 / 360.0, 0.95, 0.53, 1.0))
+        .rounded(px(1.0));
+    match (axis, trailing) {
+        (Axis::Vertical, false) => line.top(px(-1.0)).left(px(0.0)).right(px(0.0)).h(px(2.0)),
+        (Axis::Vertical, true) => line
+            .bottom(px(-1.0))
+            .left(px(0.0))
+            .right(px(0.0))
+            .h(px(2.0)),
+        (Axis::Horizontal, false) => line.left(px(-2.0)).top(px(0.0)).bottom(px(0.0)).w(px(2.0)),
+        (Axis::Horizontal, true) => line.right(px(-2.0)).top(px(0.0)).bottom(px(0.0)).w(px(2.0)),
+    }
+}
+
+/// Ends a drop on a container, returning `(from, to)` if the drag started in
+/// it and the item moves.
+pub(crate) fn take_drop(owner: &ElementId, drag: &ReorderDrag) -> Option<(usize, usize)> {
+    if &drag.owner != owner {
+        return None;
+    }
+    let gap = with_session(owner, |session| session.gap.take()).flatten()?;
+    destination(drag.from, gap).map(|to| (drag.from, to))
+}
+
+/// Makes a scrolling list of rows accept drops of its own rows and
+/// auto-scroll while a drag rests near its edges.
+///
+/// After the handler runs, a bound selection moves along with its row.
+pub(crate) fn reorderable_rows<E: InteractiveElement>(
+    container: E,
+    owner: &ElementId,
+    handler: &ReorderHandler,
+    selection: Option<Binding<Option<usize>>>,
+) -> E {
+    let handler = handler.clone();
+    let (drop_owner, scroll_owner) = (owner.clone(), owner.clone());
+    container
+        .on_drop(move |drag: &ReorderDrag, window, cx| {
+            if let Some((from, to)) = take_drop(&drop_owner, drag) {
+                handler(from, to, window, cx);
+                if let Some(ref binding) = selection {
+                    if let Some(selected) = binding.get(cx) {
+                        binding.set_if_changed(Some(moved_index(selected, from, to)), cx);
+                    }
+                }
+            }
+        })
+        .on_drag_move(move |event: &DragMoveEvent<ReorderDrag>, _window, _cx| {
+            track_auto_scroll(&scroll_owner, event);
+        })
+}
+
+/// Records the pointer position of a drag over a scrolling container, for
+/// `auto_scroll`.
+///
+/// gpui reports every move of a drag to every listener, so a pointer
+/// outside the container stops the scrolling.
+fn track_auto_scroll(owner: &ElementId, event: &DragMoveEvent<ReorderDrag>) {
+    let step = container_scroll_step(event.bounds, event.event.position);
+    with_session(owner, |session| session.scroll_step = step);
+}
+
+/// Returns the auto-scroll step for a pointer over a container, or zero
+/// when the pointer is outside it.
+fn container_scroll_step(bounds: Bounds<Pixels>, position: Point<Pixels>) -> Pixels {
+    if bounds.contains(&position) {
+        auto_scroll_step(bounds.top(), bounds.bottom(), position.y)
+    } else {
+        px(0.0)
+    }
+}
+
+/// Scrolls a list while a drag rests near one of its edges.
+///
+/// Called while rendering the list; keeps requesting frames until the
+/// pointer leaves the edge or the list can't scroll further.
+pub(crate) fn auto_scroll(
+    owner: &ElementId,
+    handle: &UniformListScrollHandle,
+    window: &mut Window,
+) {
+    let Some(step) = with_session(owner, |session| session.scroll_step) else {
+        return;
+    };
+    if step == px(0.0) {
+        return;
+    }
+    let scroll = handle.0.borrow().base_handle.clone();
+    let offset = scroll.offset();
+    let y = (offset.y + step).clamp(-scroll.max_offset().height, px(0.0));
+    if y != offset.y {
+        scroll.set_offset(point(offset.x, y));
+        window.request_animation_frame();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_destination() {
+        // Gaps next to the item leave it in place
+        assert_eq!(destination(2, 2), None);
+        assert_eq!(destination(2, 3), None);
+        assert_eq!(destination(2, 0), Some(0));
+        assert_eq!(destination(2, 5), Some(4));
+        assert_eq!(destination(0, 1), None);
+        assert_eq!(destination(0, 2), Some(1));
+    }
+
+    #[test]
+    fn test_moved_index() {
+        // Moving item 1 to index 3 in [a, b, c, d] gives [a, c, d, b]
+        assert_eq!(moved_index(0, 1, 3), 0);
+        assert_eq!(moved_index(1, 1, 3), 3);
+        assert_eq!(moved_index(2, 1, 3), 1);
+        assert_eq!(moved_index(3, 1, 3), 2);
+        // Moving item 3 to index 1 gives [a, d, b, c]
+        assert_eq!(moved_index(1, 3, 1), 2);
+        assert_eq!(moved_index(2, 3, 1), 3);
+        assert_eq!(moved_index(3, 3, 1), 1);
+    }
+
+    #[test]
+    fn test_keyboard_destination() {
+        assert_eq!(keyboard_destination(0, 3, false), None);
+        assert_eq!(keyboard_destination(0, 3, true), Some(1));
+        assert_eq!(keyboard_destination(2, 3, true), None);
+        assert_eq!(keyboard_destination(2, 3, false), Some(1));
+    }
+
+    #[test]
+    fn test_gap_and_auto_scroll() {
+        let bounds = Bounds::new(point(px(0.0), px(100.0)), size(px(200.0), px(20.0)));
+        assert_eq!(
+            gap_at(4, bounds, point(px(50.0), px(105.0)), Axis::Vertical),
+            4
+        );
+        assert_eq!(
+            gap_at(4, bounds, point(px(50.0), px(115.0)), Axis::Vertical),
+            5
+        );
+        assert_eq!(
+            gap_at(4, bounds, point(px(150.0), px(105.0)), Axis::Horizontal),
+            5
+        );
+
+        assert_eq!(auto_scroll_step(px(0.0), px(300.0), px(150.0)), px(0.0));
+        assert_eq!(
+            auto_scroll_step(px(0.0), px(300.0), px(0.0)),
+            px(AUTO_SCROLL_STEP)
+        );
+        assert_eq!(
+            auto_scroll_step(px(0.0), px(300.0), px(16.0)),
+            px(AUTO_SCROLL_STEP / 2.0)
+        );
+        assert_eq!(
+            auto_scroll_step(px(0.0), px(300.0), px(320.0)),
+            -px(AUTO_SCROLL_STEP)
+        );
+    }
+
+    #[test]
+    fn test_container_scroll_step() {
+        let list = Bounds::new(point(px(0.0), px(0.0)), size(px(200.0), px(300.0)));
+        assert_eq!(
+            container_scroll_step(list, point(px(100.0), px(1.0))),
+            px(AUTO_SCROLL_STEP) * (31.0 / AUTO_SCROLL_EDGE)
+        );
+        // A pointer beside the list at the height of its edges doesn't scroll it
+        assert_eq!(
+            container_scroll_step(list, point(px(400.0), px(1.0))),
+            px(0.0)
+        );
+        assert_eq!(
+            container_scroll_step(list, point(px(400.0), px(299.0))),
+            px(0.0)
+        );
+    }
+}
@@ -5,6 +5,9 @@
 use gpui::prelude::*;
 use gpui::*;
 
+use crate::accessibility::{AccessNode, AccessState, Accessible, Role};
+use crate::state::Binding;
+
 /// A secure text input component that masks characters.
 ///
 /// # Example
@@ -18,6 +21,10 @@ use gpui::*;
 ///         println!("Password changed");
 ///     })
 ///     .show_toggle(true)  // Optional: adds reveal button
+///
+/// // Using a binding:
+/// SecureField::new("password", cx)
+///     .text(State::binding(&self.password, cx))
 /// ```
 pub struct SecureField {
     id: ElementId,
@@ -26,6 +33,7 @@ pub struct SecureField {
     placeholder: SharedString,
     focus_handle: FocusHandle,
     on_change: Option<Box<dyn Fn(&String) + 'static>>,
+    text_binding: Option<Binding<String>>,
     show_toggle: bool,
 }
 
@@ -39,10 +47,17 @@ impl SecureField {
             placeholder: "".into(),
             focus_handle: cx.focus_handle(),
             on_change: None,
+            text_binding: None,
             show_toggle: false,
         }
     }
 
+    /// Sets a two-way binding for the text value.
+    pub fn text(mut self, binding: Binding<String>) -> Self {
+        self.text_binding = Some(binding);
+        self
+    }
+
     /// Sets the current text value.
     pub fn value(mut self, value: impl Into<String>) -> Self {
         self.value = value.into();
@@ -84,6 +99,7 @@ pub struct SecureFieldState {
     focus_handle: FocusHandle,
     cursor_position: usize,
     on_change: Option<Box<dyn Fn(&String) + 'static>>,
+    text_binding: Option<Binding<String>>,
     show_toggle: bool,
     is_revealed: bool,
 }
@@ -217,6 +233,9 @@ impl SecureFieldState {
         if let Some(ref handler) = self.on_change {
             handler(&self.value);
         }
+        if let Some(ref binding) = self.text_binding {
+            binding.set_if_changed(self.value.clone(), cx);
+        }
         cx.notify();
     }
 
@@ -238,6 +257,15 @@ impl SecureFieldState {
 
 impl Render for SecureFieldState {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // Sync value from binding
+        if let Some(ref binding) = self.text_binding {
+            let bound_value = binding.get(cx);
+            if bound_value != self.value {
+                self.value = bound_value;
+                self.cursor_position = self.cursor_position.min(self.value.chars().count());
+            }
+        }
+
         let is_focused = self.focus_handle.is_focused(window);
         let is_empty = self.value.is_empty();
         let placeholder = self.placeholder.clone();
@@ -389,6 +417,34 @@ impl Render for SecureFieldState {
     }
 }
 
+impl Accessible for SecureFieldState {
+    fn accessibility_node(&self) -> AccessNode {
+        // Screen readers only learn the length of a hidden password.
+        let is_revealed = self.is_revealed;
+        let text = move |value: &str| -> SharedString {
+            if is_revealed {
+                value.to_string().into()
+            } else {
+                "•".repeat(value.chars().count()).into()
+            }
+        };
+        let node = AccessNode::new(Role::PasswordText)
+            .id(self.id.to_string())
+            .name(
+                self.label
+                    .clone()
+                    .unwrap_or_else(|| self.placeholder.clone()),
+            )
+            .text(text(&self.value))
+            .focus_handle(&self.focus_handle)
+            .state(AccessState::Editable);
+        match self.text_binding.clone() {
+            Some(binding) => node.resolve_with(move |node, cx| node.text(text(&binding.get(cx)))),
+            None => node,
+        }
+    }
+}
+
 /// Creates a SecureFieldState from a SecureField builder.
 impl From<SecureField> for SecureFieldState {
     fn from(builder: SecureField) -> Self {
@@ -401,6 +457,7 @@ impl From<SecureField> for SecureFieldState {
             focus_handle: builder.focus_handle,
             cursor_position,
             on_change: builder.on_change,
+            text_binding: builder.text_binding,
             show_toggle: builder.show_toggle,
             is_revealed: false,
         }
@@ -6,6 +6,8 @@
 use gpui::prelude::*;
 use gpui::*;
 
+type ClickHandler = Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>;
+
 /// A modal sheet (dialog) component.
 ///
 /// Sheet displays content in a centered panel with a semi-transparent backdrop.
@@ -29,7 +31,7 @@ pub struct Sheet {
     width: Pixels,
     children: Vec<AnyElement>,
     actions: Option<AnyElement>,
-    on_dismiss: Option<Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>>,
+    on_dismiss: Option<ClickHandler>,
 }
 
 impl Sheet {
@@ -88,12 +90,17 @@ impl Sheet {
     }
 }
 
-impl IntoElement for Sheet {
-    type Element = Stateful<Div>;
+impl Sheet {
+    /// Takes the dismiss handler, leaving the sheet without one.
+    pub(crate) fn take_dismiss_handler(
+        &mut self,
+    ) -> Option<ClickHandler> {
+        self.on_dismiss.take()
+    }
 
-    fn into_element(self) -> Self::Element {
+    /// Builds the dialog panel without the backdrop.
+    pub(crate) fn into_panel(self) -> Div {
         // Colors
-        let backdrop_color = hsla(0.0, 0.0, 0.0, 0.4);
         let panel_bg = hsla(0.0, 0.0, 0.97, 1.0);
         let title_color = hsla(0.0, 0.0, 0.15, 1.0);
         let border_color = hsla(0.0, 0.0, 0.78, 1.0);
@@ -180,9 +187,22 @@ impl IntoElement for Sheet {
             panel = panel.child(actions_bar);
         }
 
+        panel
+    }
+}
+
+impl IntoElement for Sheet {
+    type Element = Stateful<Div>;
+
+    fn into_element(mut self) -> Self::Element {
+        let backdrop_color = hsla(0.0, 0.0, 0.0, 0.4);
+        let id = self.id.clone();
+        let on_dismiss = self.take_dismiss_handler();
+        let panel = self.into_panel();
+
         // Build the backdrop with click-to-dismiss
         let mut backdrop = div()
-            .id(self.id)
+            .id(id)
             .absolute()
             .inset_0()
             .flex()
@@ -191,7 +211,7 @@ impl IntoElement for Sheet {
             .bg(backdrop_color);
 
         // Add dismiss handler to backdrop
-        if let Some(handler) = self.on_dismiss {
+        if let Some(handler) = on_dismiss {
             backdrop = backdrop.on_click(move |event, window, cx| {
                 handler(event, window, cx);
             });
@@ -4,6 +4,9 @@
 
 use gpui::prelude::*;
 use gpui::*;
+use std::rc::Rc;
+
+use super::reorder::{self, ReorderDrag, ReorderHandler};
 
 /// A sidebar container.
 ///
@@ -20,18 +23,34 @@ use gpui::*;
 ///     .child(SidebarItem::new("local", "Local Changes"))
 ///     .child(SidebarSection::new("branches", "BRANCHES"))
 /// ```
+///
+/// With `on_reorder`, every child can be dragged into a new position, so
+/// a reorderable sidebar should only contain items, such as the bookmarks
+/// of one section.
 pub struct Sidebar {
+    id: ElementId,
     children: Vec<AnyElement>,
+    on_reorder: Option<ReorderHandler>,
 }
 
 impl Sidebar {
     /// Creates a new sidebar.
     pub fn new() -> Self {
         Self {
+            id: "sidebar".into(),
             children: Vec::new(),
+            on_reorder: None,
         }
     }
 
+    /// Sets the sidebar's id (default: "sidebar").
+    ///
+    /// Only needed to tell reorderable sidebars in one window apart.
+    pub fn id(mut self, id: impl Into<ElementId>) -> Self {
+        self.id = id.into();
+        self
+    }
+
     /// Adds a child element to the sidebar.
     pub fn child(mut self, child: impl IntoElement) -> Self {
         self.children.push(child.into_any_element());
@@ -44,6 +63,18 @@ impl Sidebar {
             .extend(children.into_iter().map(|c| c.into_any_element()));
         self
     }
+
+    /// Enables drag-and-drop reordering of the children.
+    ///
+    /// The handler receives the child's old index and the index it should
+    /// end up at.
+    pub fn on_reorder(
+        mut self,
+        handler: impl Fn(usize, usize, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_reorder = Some(Rc::new(handler));
+        self
+    }
 }
 
 impl Default for Sidebar {
@@ -56,6 +87,23 @@ impl IntoElement for Sidebar {
     type Element = Div;
 
     fn into_element(self) -> Self::Element {
+        let count = self.children.len();
+        let children: Vec<AnyElement> = match self.on_reorder {
+            Some(_) => self
+                .children
+                .into_iter()
+                .enumerate()
+                .map(|(index, child)| {
+                    let item = div().id(("sidebar-item", index)).child(child);
+                    reorder::reorderable_item(item, &self.id, index, count, Axis::Vertical, None)
+                        .into_any_element()
+                })
+                .collect(),
+            None => self.children,
+        };
+
+        let owner = self.id;
+        let on_reorder = self.on_reorder;
         div()
             .flex()
             .flex_col()
@@ -65,6 +113,13 @@ impl IntoElement for Sidebar {
             // Source list background - light blue-gray
             // Note: No border - SplitView handles the separator (SwiftUI pattern)
             .bg(hsla(210.0 / 360.0, 0.08, 0.93, 1.0))
-            .children(self.children)
+            .children(children)
+            .when_some(on_reorder, |sidebar, handler| {
+                sidebar.on_drop(move |drag: &ReorderDrag, window, cx| {
+                    if let Some((from, to)) = reorder::take_drop(&owner, drag) {
+                        handler(from, to, window, cx);
+                    }
+                })
+            })
     }
 }
@@ -8,6 +8,9 @@ use std::cell::Cell;
 use std::ops::RangeInclusive;
 use std::rc::Rc;
 
+use crate::accessibility::{AccessNode, AccessState, Accessible, Role, ValueRange};
+use crate::state::Binding;
+
 /// A slider control for selecting a value from a continuous range.
 ///
 /// This is a builder pattern for creating a slider. For stateful use with
@@ -33,7 +36,11 @@ use std::rc::Rc;
 ///             cx.notify();
 ///         }))
 /// ));
+///
+/// // Bound to a `State<f64>`
+/// Slider::new("volume", 0.0, 0.0..=100.0).value(State::binding(&self.volume, cx))
 /// ```
+#[derive(IntoElement)]
 pub struct Slider {
     id: ElementId,
     value: f64,
@@ -42,6 +49,7 @@ pub struct Slider {
     label: Option<SharedString>,
     disabled: bool,
     on_change: Option<Rc<dyn Fn(f64, &mut Window, &mut App) + 'static>>,
+    value_binding: Option<Binding<f64>>,
 }
 
 impl Slider {
@@ -56,9 +64,19 @@ impl Slider {
             label: None,
             disabled: false,
             on_change: None,
+            value_binding: None,
         }
     }
 
+    /// Sets a two-way binding for the value.
+    ///
+    /// The bound value replaces the initial value, and dragging the slider
+    /// writes new values back.
+    pub fn value(mut self, binding: Binding<f64>) -> Self {
+        self.value_binding = Some(binding);
+        self
+    }
+
     /// Sets the step increment for the slider.
     /// When set, values will snap to the nearest step.
     pub fn step(mut self, step: f64) -> Self {
@@ -155,10 +173,19 @@ const TRACK_WIDTH: f32 = 200.0;
 const TRACK_HEIGHT: f32 = 6.0;
 const THUMB_SIZE: f32 = 18.0;
 
-impl IntoElement for Slider {
-    type Element = Stateful<Div>;
+impl RenderOnce for Slider {
+    fn render(mut self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        if let Some(binding) = self.value_binding.take() {
+            self.value = binding.get(cx).clamp(*self.range.start(), *self.range.end());
+            let handler = self.on_change.take();
+            self.on_change = Some(Rc::new(move |value, window, cx| {
+                binding.set_if_changed(value, cx);
+                if let Some(ref handler) = handler {
+                    handler(value, window, cx);
+                }
+            }));
+        }
 
-    fn into_element(self) -> Self::Element {
         let colors = if self.disabled {
             SliderColors::disabled()
         } else {
@@ -390,6 +417,7 @@ pub struct SliderState {
     label: Option<SharedString>,
     disabled: bool,
     on_change: Option<Rc<dyn Fn(&f64, &mut Window, &mut App) + 'static>>,
+    value_binding: Option<Binding<f64>>,
     /// Track bounds from the last paint, used for mouse position calculations.
     /// Uses Rc<Cell<>> to allow sharing between paint callback and event handlers.
     track_bounds: Rc<Cell<Option<Bounds<Pixels>>>>,
@@ -462,6 +490,9 @@ impl SliderState {
 
             if (new_value - self.value).abs() > f64::EPSILON {
                 self.value = new_value;
+                if let Some(ref binding) = self.value_binding {
+                    binding.set_if_changed(self.value, cx);
+                }
                 if let Some(ref handler) = self.on_change {
                     handler(&self.value, window, cx);
                 }
@@ -473,6 +504,11 @@ impl SliderState {
 
 impl Render for SliderState {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // Sync value from binding
+        if let Some(ref binding) = self.value_binding {
+            self.value = binding.get(cx).clamp(*self.range.start(), *self.range.end());
+        }
+
         let colors = if self.disabled {
             SliderColors::disabled()
         } else {
@@ -616,12 +652,71 @@ impl From<Slider> for SliderState {
                     handler(*value, window, cx);
                 }) as Rc<dyn Fn(&f64, &mut Window, &mut App) + 'static>
             }),
+            value_binding: builder.value_binding,
             track_bounds: Rc::new(Cell::new(None)),
             is_dragging: false,
         }
     }
 }
 
+/// Describes a slider; a bound value replaces `value` when the tree is published.
+fn slider_node(
+    id: &ElementId,
+    value: f64,
+    range: &RangeInclusive<f64>,
+    step: Option<f64>,
+    label: Option<&SharedString>,
+    disabled: bool,
+    binding: Option<&Binding<f64>>,
+) -> AccessNode {
+    let range = range.clone();
+    let value_range = ValueRange::new(value, range.clone()).step(step.unwrap_or(0.0));
+    let node = AccessNode::new(Role::Slider)
+        .id(id.to_string())
+        .name(label.cloned().unwrap_or_default())
+        .value(value_range)
+        .state(AccessState::Focusable)
+        .state_if(AccessState::Disabled, disabled);
+    match binding.cloned() {
+        Some(binding) => node.resolve_with(move |node, cx| {
+            let current = binding.get(cx).clamp(*range.start(), *range.end());
+            node.value(ValueRange {
+                current,
+                ..value_range
+            })
+        }),
+        None => node,
+    }
+}
+
+impl Accessible for Slider {
+    fn accessibility_node(&self) -> AccessNode {
+        slider_node(
+            &self.id,
+            self.value,
+            &self.range,
+            self.step,
+            self.label.as_ref(),
+            self.disabled,
+            self.value_binding.as_ref(),
+        )
+    }
+}
+
+impl Accessible for SliderState {
+    fn accessibility_node(&self) -> AccessNode {
+        slider_node(
+            &self.id,
+            self.value,
+            &self.range,
+            self.step,
+            self.label.as_ref(),
+            self.disabled,
+            self.value_binding.as_ref(),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
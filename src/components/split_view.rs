@@ -14,6 +14,8 @@ use gpui::*;
 use std::cell::Cell;
 use std::rc::Rc;
 
+use crate::persistence::WindowStates;
+
 // Track which SplitView entity is currently being dragged.
 // This ensures only one SplitView responds to drag events at a time,
 // similar to how SwiftUI isolates gestures per view.
@@ -87,6 +89,7 @@ pub struct SplitView {
     max_first_size: Pixels,
     divider_color: Hsla,
     on_resize: Option<Rc<dyn Fn(Pixels, &mut Window, &mut App) + 'static>>,
+    autosave: Option<(SharedString, SharedString)>,
 }
 
 impl SplitView {
@@ -99,6 +102,7 @@ impl SplitView {
             max_first_size: px(DEFAULT_MAX_SIZE),
             divider_color: hsla(0.0, 0.0, 0.85, 1.0),
             on_resize: None,
+            autosave: None,
         }
     }
 
@@ -111,6 +115,7 @@ impl SplitView {
             max_first_size: px(DEFAULT_MAX_SIZE),
             divider_color: hsla(0.0, 0.0, 0.85, 1.0),
             on_resize: None,
+            autosave: None,
         }
     }
 
@@ -161,6 +166,28 @@ impl SplitView {
         self.on_resize = Some(Rc::new(handler));
         self
     }
+
+    /// Saves the divider position in [`WindowStates`] and restores it on the next launch.
+    ///
+    /// The position is stored under `key` in the state of the window `window_id`.
+    /// A restored position replaces the initial `first_size` and is clamped to
+    /// the minimum and maximum sizes.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// SplitView::horizontal()
+    ///     .first_size(px(250.0))
+    ///     .autosave("main", "sidebar")
+    /// ```
+    pub fn autosave(
+        mut self,
+        window_id: impl Into<SharedString>,
+        key: impl Into<SharedString>,
+    ) -> Self {
+        self.autosave = Some((window_id.into(), key.into()));
+        self
+    }
 }
 
 /// Stateful split view that manages its own divider position and handles drag events.
@@ -199,6 +226,8 @@ pub struct SplitViewState {
     max_first_size: Pixels,
     divider_color: Hsla,
     on_resize: Option<Rc<dyn Fn(Pixels, &mut Window, &mut App) + 'static>>,
+    autosave: Option<(SharedString, SharedString)>,
+    restored: bool,
 
     // Content (set each render)
     first_content: Option<AnyElement>,
@@ -232,11 +261,22 @@ impl SplitViewState {
 
 impl Render for SplitViewState {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // Restore the saved position once the app context is available
+        if !self.restored {
+            self.restored = true;
+            if let Some((ref window_id, ref key)) = self.autosave {
+                if let Some(size) = WindowStates::split_size(window_id, key, cx) {
+                    self.set_first_size(size);
+                }
+            }
+        }
+
         let is_horizontal = self.orientation == SplitOrientation::Horizontal;
         let orientation = self.orientation;
         let min_size = self.min_first_size;
         let max_size = self.max_first_size;
         let on_resize = self.on_resize.clone();
+        let autosave = self.autosave.clone();
 
         // Container with bounds tracking
         let bounds_ref = self.container_bounds.clone();
@@ -349,6 +389,10 @@ impl Render for SplitViewState {
                                 handler(this.first_size, window, cx);
                             }
 
+                            if let Some((ref window_id, ref key)) = autosave {
+                                WindowStates::set_split_size(window_id, key, clamped, cx);
+                            }
+
                             cx.notify();
                         }
                     }
@@ -397,6 +441,8 @@ impl From<SplitView> for SplitViewState {
             max_first_size: builder.max_first_size,
             divider_color: builder.divider_color,
             on_resize: builder.on_resize,
+            autosave: builder.autosave,
+            restored: false,
             first_content: None,
             second_content: None,
             container_bounds: Rc::new(Cell::new(None)),
@@ -465,4 +511,13 @@ mod tests {
         state.set_first_size(px(500.0));
         assert_eq!(state.first_size, px(400.0));
     }
+
+    #[test]
+    fn test_split_view_autosave() {
+        let split = SplitView::horizontal().autosave("main", "sidebar");
+        assert_eq!(split.autosave, Some(("main".into(), "sidebar".into())));
+
+        let state: SplitViewState = split.into();
+        assert!(!state.restored);
+    }
 }
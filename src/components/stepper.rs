@@ -7,6 +7,9 @@ use gpui::*;
 use std::ops::RangeInclusive;
 use std::rc::Rc;
 
+use crate::accessibility::{AccessNode, AccessState, Accessible, Role, ValueRange};
+use crate::state::Binding;
+
 /// A stepper control for adjusting numeric values.
 ///
 /// # Example
@@ -18,7 +21,11 @@ use std::rc::Rc;
 ///     .on_change(|new_value, _window, _cx| {
 ///         println!("Value changed to: {}", new_value);
 ///     })
+///
+/// // Bound to a `State<i32>`
+/// Stepper::new("copies", 1, 1..=99).value(State::binding(&self.copies, cx))
 /// ```
+#[derive(IntoElement)]
 pub struct Stepper {
     id: ElementId,
     value: i32,
@@ -27,6 +34,7 @@ pub struct Stepper {
     label: Option<SharedString>,
     disabled: bool,
     on_change: Option<Rc<dyn Fn(i32, &mut Window, &mut App) + 'static>>,
+    value_binding: Option<Binding<i32>>,
 }
 
 impl Stepper {
@@ -40,9 +48,19 @@ impl Stepper {
             label: None,
             disabled: false,
             on_change: None,
+            value_binding: None,
         }
     }
 
+    /// Sets a two-way binding for the value.
+    ///
+    /// The bound value replaces the initial value, and the buttons write
+    /// new values back.
+    pub fn value(mut self, binding: Binding<i32>) -> Self {
+        self.value_binding = Some(binding);
+        self
+    }
+
     /// Sets the step amount for increment/decrement.
     pub fn step(mut self, step: i32) -> Self {
         self.step = step;
@@ -119,10 +137,19 @@ impl StepperColors {
     }
 }
 
-impl IntoElement for Stepper {
-    type Element = Stateful<Div>;
+impl RenderOnce for Stepper {
+    fn render(mut self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        if let Some(binding) = self.value_binding.take() {
+            self.value = binding.get(cx);
+            let handler = self.on_change.take();
+            self.on_change = Some(Rc::new(move |value, window, cx| {
+                binding.set_if_changed(value, cx);
+                if let Some(ref handler) = handler {
+                    handler(value, window, cx);
+                }
+            }));
+        }
 
-    fn into_element(self) -> Self::Element {
         let colors = if self.disabled {
             StepperColors::disabled()
         } else {
@@ -264,6 +291,28 @@ impl IntoElement for Stepper {
     }
 }
 
+impl Accessible for Stepper {
+    fn accessibility_node(&self) -> AccessNode {
+        let range = *self.range.start() as f64..=*self.range.end() as f64;
+        let value_range = ValueRange::new(self.value as f64, range).step(self.step as f64);
+        let node = AccessNode::new(Role::SpinButton)
+            .id(self.id.to_string())
+            .name(self.label.clone().unwrap_or_default())
+            .value(value_range)
+            .state(AccessState::Focusable)
+            .state_if(AccessState::Disabled, self.disabled);
+        match self.value_binding.clone() {
+            Some(binding) => node.resolve_with(move |node, cx| {
+                node.value(ValueRange {
+                    current: binding.get(cx) as f64,
+                    ..value_range
+                })
+            }),
+            None => node,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
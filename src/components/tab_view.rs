@@ -8,7 +8,13 @@ use gpui::*;
 use std::rc::Rc;
 
 use super::label::Icon;
+use super::reorder::{self, ReorderDrag, ReorderHandler};
 use super::Badge;
+use crate::accessibility::{AccessNode, AccessState, Accessible, Role};
+use crate::persistence::WindowStates;
+use crate::state::Binding;
+
+type SelectionHandler = Rc<dyn Fn(usize, &mut Window, &mut App) + 'static>;
 
 /// A single tab in a TabView.
 ///
@@ -68,12 +74,21 @@ impl Tab {
 ///         this.selected_tab = index;
 ///         cx.notify();
 ///     }))
+///
+/// // Bound to a `State<usize>`
+/// TabView::new("main-tabs", 0)
+///     .tab(Tab::new("First", first_view))
+///     .selection(State::binding(&self.selected_tab, cx))
 /// ```
+#[derive(IntoElement)]
 pub struct TabView {
     id: ElementId,
     tabs: Vec<Tab>,
     selected_index: usize,
-    on_selection_change: Option<Rc<dyn Fn(usize, &mut Window, &mut App) + 'static>>,
+    on_selection_change: Option<SelectionHandler>,
+    on_reorder: Option<ReorderHandler>,
+    selection_binding: Option<Binding<usize>>,
+    autosave: Option<(SharedString, SharedString)>,
 }
 
 impl TabView {
@@ -84,6 +99,9 @@ impl TabView {
             tabs: Vec::new(),
             selected_index,
             on_selection_change: None,
+            on_reorder: None,
+            selection_binding: None,
+            autosave: None,
         }
     }
 
@@ -93,6 +111,15 @@ impl TabView {
         self
     }
 
+    /// Sets a two-way binding for the selected index.
+    ///
+    /// The bound value replaces the initial index, and selecting or moving a
+    /// tab writes the new index back.
+    pub fn selection(mut self, binding: Binding<usize>) -> Self {
+        self.selection_binding = Some(binding);
+        self
+    }
+
     /// Sets the selection change handler.
     ///
     /// The handler receives the newly selected index.
@@ -103,14 +130,103 @@ impl TabView {
         self.on_selection_change = Some(Rc::new(handler));
         self
     }
+
+    /// Enables reordering tabs by dragging them along the tab bar, or with
+    /// Alt+Left/Alt+Right for the selected tab.
+    ///
+    /// The handler receives the tab's old index and the index it should end
+    /// up at. If the selected tab changes position, the selection change
+    /// handler is called with its new index.
+    pub fn on_reorder(
+        mut self,
+        handler: impl Fn(usize, usize, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_reorder = Some(Rc::new(handler));
+        self
+    }
+
+    /// Saves the selected index in [`WindowStates`] and restores it on the next launch.
+    ///
+    /// The index is stored under `key` in the state of the window `window_id`.
+    /// When the tab view first appears, a saved index replaces the initial
+    /// one and is passed to the binding or selection change handler, so
+    /// combine this with [`selection`](Self::selection) or
+    /// [`on_selection_change`](Self::on_selection_change).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// TabView::new("inspector", 0)
+    ///     .selection(State::binding(&self.selected_tab, cx))
+    ///     .autosave("main", "inspector")
+    /// ```
+    pub fn autosave(
+        mut self,
+        window_id: impl Into<SharedString>,
+        key: impl Into<SharedString>,
+    ) -> Self {
+        self.autosave = Some((window_id.into(), key.into()));
+        self
+    }
 }
 
-impl IntoElement for TabView {
-    type Element = Stateful<Div>;
+/// Calls the reorder handler, then moves the selection along with the tab.
+fn reorder_tabs(
+    from: usize,
+    to: usize,
+    selected_index: usize,
+    on_reorder: &ReorderHandler,
+    on_selection_change: Option<&SelectionHandler>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    on_reorder(from, to, window, cx);
+    let selected = reorder::moved_index(selected_index, from, to);
+    if let Some(handler) = on_selection_change.filter(|_| selected != selected_index) {
+        handler(selected, window, cx);
+    }
+}
+
+impl RenderOnce for TabView {
+    fn render(mut self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        if let Some(binding) = self.selection_binding.take() {
+            self.selected_index = binding.get(cx);
+            let handler = self.on_selection_change.take();
+            self.on_selection_change = Some(Rc::new(move |index, window, cx| {
+                binding.set_if_changed(index, cx);
+                if let Some(ref handler) = handler {
+                    handler(index, window, cx);
+                }
+            }));
+        }
+
+        if let Some((window_id, key)) = self.autosave.take() {
+            // Restore the saved index once, when the tab view first appears
+            let mut restored = None;
+            window.use_keyed_state((self.id.clone(), "autosave"), cx, |_, cx| {
+                restored = WindowStates::selected_tab(&window_id, &key, cx);
+            });
+            let restored =
+                restored.filter(|&index| index < self.tabs.len() && index != self.selected_index);
+            if let Some(index) = restored {
+                self.selected_index = index;
+                if let Some(handler) = self.on_selection_change.clone() {
+                    window.defer(cx, move |window, cx| handler(index, window, cx));
+                }
+            }
+
+            let handler = self.on_selection_change.take();
+            self.on_selection_change = Some(Rc::new(move |index, window, cx| {
+                WindowStates::set_selected_tab(&window_id, &key, index, cx);
+                if let Some(ref handler) = handler {
+                    handler(index, window, cx);
+                }
+            }));
+        }
 
-    fn into_element(self) -> Self::Element {
         let selected_index = self.selected_index;
         let on_selection_change = self.on_selection_change;
+        let on_reorder = self.on_reorder;
         let tab_count = self.tabs.len();
 
         // Colors
@@ -147,6 +263,7 @@ impl IntoElement for TabView {
 
         // Add individual tab buttons
         for (index, (label, icon, badge)) in tab_items.into_iter().enumerate() {
+            let drag_label = label.clone();
             let is_selected = index == selected_index;
 
             // Build tab button content
@@ -231,9 +348,40 @@ impl IntoElement for TabView {
                 });
             }
 
+            if on_reorder.is_some() {
+                tab_button = reorder::reorderable_item(
+                    tab_button,
+                    &self.id,
+                    index,
+                    tab_count,
+                    Axis::Horizontal,
+                    Some(drag_label),
+                );
+            }
+
             tab_bar = tab_bar.child(tab_button);
         }
 
+        // Accept tabs dropped into a new position
+        if let Some(ref handler) = on_reorder {
+            let handler = handler.clone();
+            let on_selection_change = on_selection_change.clone();
+            let owner = self.id.clone();
+            tab_bar = tab_bar.on_drop(move |drag: &ReorderDrag, window, cx| {
+                if let Some((from, to)) = reorder::take_drop(&owner, drag) {
+                    reorder_tabs(
+                        from,
+                        to,
+                        selected_index,
+                        &handler,
+                        on_selection_change.as_ref(),
+                        window,
+                        cx,
+                    );
+                }
+            });
+        }
+
         // Build content area - only render the selected tab's content (lazy rendering)
         let content_area = div()
             .flex_1()
@@ -270,6 +418,28 @@ impl IntoElement for TabView {
             .on_key_down({
                 let on_selection_change = on_selection_change.clone();
                 move |event, window, cx| {
+                    if let Some(ref handler) = on_reorder {
+                        let key = event.keystroke.key.as_str();
+                        if event.keystroke.modifiers.alt && (key == "left" || key == "right") {
+                            let to = reorder::keyboard_destination(
+                                selected_index,
+                                tab_count,
+                                key == "right",
+                            );
+                            if let Some(to) = to {
+                                reorder_tabs(
+                                    selected_index,
+                                    to,
+                                    selected_index,
+                                    handler,
+                                    on_selection_change.as_ref(),
+                                    window,
+                                    cx,
+                                );
+                            }
+                            return;
+                        }
+                    }
                     if let Some(ref handler) = on_selection_change {
                         match event.keystroke.key.as_str() {
                             "left" => {
@@ -290,9 +460,41 @@ impl IntoElement for TabView {
     }
 }
 
+impl Accessible for TabView {
+    fn accessibility_node(&self) -> AccessNode {
+        let tabs = |selected: usize| {
+            self.tabs.iter().enumerate().map(move |(index, tab)| {
+                AccessNode::new(Role::PageTab)
+                    .id(format!("{}-tab-{}", self.id, index))
+                    .name(tab.label.clone())
+                    .state(AccessState::Focusable)
+                    .state(AccessState::Selectable)
+                    .state_if(AccessState::Selected, index == selected)
+                    .action("click")
+            })
+        };
+        let node = AccessNode::new(Role::PageTabList).id(self.id.to_string());
+        match self.selection_binding.clone() {
+            Some(binding) => node
+                .children(tabs(usize::MAX))
+                .resolve_with(move |mut node, cx| {
+                    if let Some(tab) = node.children.get_mut(binding.get(cx)) {
+                        *tab = tab.clone().state(AccessState::Selected);
+                    }
+                    node
+                }),
+            None => node.children(tabs(self.selected_index)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::cell::{Cell, RefCell};
+    // `use gpui::*` brings in gpui's `test` attribute, which `#[gpui::test]`
+    // expands into; keep `#[test]` the built-in one
+    use std::prelude::v1::test;
 
     #[test]
     fn test_tab_creation() {
@@ -341,4 +543,56 @@ mod tests {
         assert_eq!(tabview.tabs[0].label.as_ref(), "Documents");
         assert_eq!(tabview.tabs[1].label.as_ref(), "Downloads");
     }
+
+    #[gpui::test]
+    fn test_tabview_reorder(cx: &mut TestAppContext) {
+        let tabs = Rc::new(RefCell::new(vec!["a", "b", "c", "d"]));
+        let selected = Rc::new(Cell::new(1));
+        let on_reorder: ReorderHandler = Rc::new({
+            let tabs = tabs.clone();
+            move |from, to, _window, _cx| {
+                let mut tabs = tabs.borrow_mut();
+                let tab = tabs.remove(from);
+                tabs.insert(to, tab);
+            }
+        });
+        let on_selection_change: SelectionHandler = Rc::new({
+            let selected = selected.clone();
+            move |index, _window, _cx| selected.set(index)
+        });
+
+        let cx = cx.add_empty_window();
+        cx.update(|window, cx| {
+            // Moving the selected tab takes the selection along
+            reorder_tabs(1, 3, 1, &on_reorder, Some(&on_selection_change), window, cx);
+            assert_eq!(*tabs.borrow(), ["a", "c", "d", "b"]);
+            assert_eq!(selected.get(), 3);
+
+            // Moving a tab in front of the selected one shifts it
+            reorder_tabs(0, 3, 3, &on_reorder, Some(&on_selection_change), window, cx);
+            assert_eq!(*tabs.borrow(), ["c", "d", "b", "a"]);
+            assert_eq!(selected.get(), 2);
+        });
+    }
+
+    #[test]
+    fn test_tabview_autosave() {
+        let tabview = TabView::new("test-tabs", 0).autosave("main", "inspector");
+        assert_eq!(tabview.autosave, Some(("main".into(), "inspector".into())));
+    }
+
+    #[test]
+    fn test_tabview_accessibility_node() {
+        let tabview = TabView::new("test-tabs", 1)
+            .tab(Tab::new("General", div()))
+            .tab(Tab::new("Advanced", div()));
+        let node = tabview.accessibility_node();
+        assert_eq!(node.children[1].id, Some("test-tabs-tab-1".into()));
+        assert_eq!(
+            node.to_string(),
+            "page tab list\n\
+             \x20 page tab \"General\" [focusable, selectable]\n\
+             \x20 page tab \"Advanced\" [focusable, selectable, selected]"
+        );
+    }
 }
@@ -45,8 +45,14 @@
 use gpui::prelude::*;
 use gpui::*;
 use std::cell::RefCell;
+use std::path::PathBuf;
 use std::rc::Rc;
 
+use super::file_drop::{FileDropTarget, PathsHandler};
+use super::list::{accessible_rows, select_row};
+use super::reorder::{self, ReorderHandler};
+use crate::accessibility::{AccessNode, AccessState, Accessible, Role};
+use crate::dialogs::FileFilter;
 use crate::state::Binding;
 
 // Re-export scroll types for convenience
@@ -90,6 +96,7 @@ impl TableColumn {
 /// - **Keyboard navigation**: Arrow keys navigate, Enter confirms
 /// - **Scroll-to-selection**: Automatically scrolls to keep selection visible
 /// - **Column layout**: Fixed and flexible column widths
+/// - **Reordering**: Rows can be dragged into place when `on_reorder` is set
 ///
 /// # Example
 ///
@@ -114,6 +121,12 @@ pub struct Table {
     on_confirm: Option<Rc<dyn Fn(usize, &mut Window, &mut App) + 'static>>,
     on_row_right_click: Option<Rc<dyn Fn(usize, Point<Pixels>, &mut Window, &mut App) + 'static>>,
     focus_handle: Option<FocusHandle>,
+    on_reorder: Option<ReorderHandler>,
+    on_drop_paths: Option<PathsHandler>,
+    drop_filter: Option<FileFilter>,
+    accessibility_label: Option<SharedString>,
+    accessibility_headers: Vec<SharedString>,
+    accessibility_cells: Option<Rc<dyn Fn(usize) -> Vec<SharedString>>>,
 }
 
 impl Table {
@@ -152,6 +165,12 @@ impl Table {
             on_confirm: None,
             on_row_right_click: None,
             focus_handle: None,
+            on_reorder: None,
+            on_drop_paths: None,
+            drop_filter: None,
+            accessibility_label: None,
+            accessibility_headers: Vec::new(),
+            accessibility_cells: None,
         }
     }
 
@@ -239,16 +258,147 @@ impl Table {
         self
     }
 
+    /// Enables drag-and-drop reordering of rows.
+    ///
+    /// Works like `List::on_reorder`: the handler receives the row's old
+    /// index and the index it should end up at, and the selection binding
+    /// follows the moved row. When focusable, Alt+Up/Alt+Down move the
+    /// selected row.
+    pub fn on_reorder(
+        mut self,
+        handler: impl Fn(usize, usize, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_reorder = Some(Rc::new(handler));
+        self
+    }
+
+    /// Accepts files dropped from the file manager, or a `FileDrag`.
+    ///
+    /// The table highlights while acceptable files are dragged over it, and
+    /// the handler receives the dropped paths.
+    pub fn on_drop_paths(
+        mut self,
+        handler: impl Fn(Vec<PathBuf>, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_drop_paths = Some(Rc::new(handler));
+        self
+    }
+
+    /// Only accepts dropped files whose names match the filter's patterns.
+    pub fn accept_files(mut self, filter: FileFilter) -> Self {
+        self.drop_filter = Some(filter);
+        self
+    }
+
     /// Makes the table focusable and enables keyboard navigation.
     ///
     /// When focusable, the table responds to:
     /// - Arrow keys (Up/Down) to navigate between rows
     /// - Cmd+Up/Cmd+Down to jump to first/last row
+    /// - Alt+Up/Alt+Down to move the selected row, if reordering is enabled
     /// - Enter to trigger the on_confirm callback
     pub fn focusable(mut self, focus_handle: FocusHandle) -> Self {
         self.focus_handle = Some(focus_handle);
         self
     }
+
+    /// Sets the name screen readers announce for the table.
+    pub fn accessibility_label(mut self, label: impl Into<SharedString>) -> Self {
+        self.accessibility_label = Some(label.into());
+        self
+    }
+
+    /// Sets the column titles screen readers announce.
+    pub fn accessibility_headers(
+        mut self,
+        headers: impl IntoIterator<Item = impl Into<SharedString>>,
+    ) -> Self {
+        self.accessibility_headers = headers.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the text screen readers announce for each cell of a row.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// Table::new("files", files.len(), render_cells)
+    ///     .accessibility_headers(["Name", "Size"])
+    ///     .accessibility_cells(move |row| {
+    ///         vec![files[row].name.clone(), files[row].size.clone()]
+    ///     })
+    /// ```
+    pub fn accessibility_cells(
+        mut self,
+        cells: impl Fn(usize) -> Vec<SharedString> + 'static,
+    ) -> Self {
+        self.accessibility_cells = Some(Rc::new(cells));
+        self
+    }
+}
+
+impl Accessible for Table {
+    /// Describes the table with the rows in view, plus the selected row.
+    fn accessibility_node(&self) -> AccessNode {
+        let headers = self
+            .accessibility_headers
+            .iter()
+            .enumerate()
+            .map(|(column, header)| {
+                AccessNode::new(Role::ColumnHeader)
+                    .id(format!("{}-column-{}", self.id, column))
+                    .name(header.clone())
+            });
+        let row_node = {
+            let id = self.id.clone();
+            let cells = self.accessibility_cells.clone();
+            let confirmable = self.on_confirm.is_some();
+            move |row: usize| {
+                let cells = cells.as_ref().map(|cells| cells(row)).unwrap_or_default();
+                let cells = cells.into_iter().enumerate().map(|(column, text)| {
+                    AccessNode::new(Role::TableCell)
+                        .id(format!("{}-row-{}-cell-{}", id, row, column))
+                        .name(text)
+                });
+                let mut node = AccessNode::new(Role::TableRow)
+                    .id(format!("{}-row-{}", id, row))
+                    .state(AccessState::Selectable)
+                    .action("click")
+                    .children(cells);
+                if confirmable {
+                    node = node.action("activate");
+                }
+                node
+            }
+        };
+        let rows = accessible_rows(self.scroll_handle.as_ref(), self.row_count);
+        let node = AccessNode::new(Role::Table)
+            .id(self.id.to_string())
+            .name(self.accessibility_label.clone().unwrap_or_default())
+            .children(headers)
+            .children(rows.clone().map(&row_node));
+        let node = match &self.focus_handle {
+            Some(handle) => node.focus_handle(handle),
+            None => node,
+        };
+        match self.selection_binding.clone() {
+            Some(binding) => {
+                let (header_count, row_count) = (self.accessibility_headers.len(), self.row_count);
+                node.resolve_with(move |node, cx| match binding.get(cx) {
+                    Some(row) if row < row_count => {
+                        let position = if row < rows.start {
+                            header_count
+                        } else {
+                            usize::MAX
+                        };
+                        select_row(node, row_node(row), position)
+                    }
+                    _ => node,
+                })
+            }
+            None => node,
+        }
+    }
 }
 
 impl IntoElement for Table {
@@ -263,6 +413,7 @@ impl IntoElement for Table {
         let on_confirm = self.on_confirm.clone();
         let on_row_right_click = self.on_row_right_click.clone();
         let scroll_handle = self.scroll_handle.clone();
+        let on_reorder = self.on_reorder.clone();
 
         // Shared state for selection that can be accessed in closures
         let current_selection = Rc::new(RefCell::new(None::<usize>));
@@ -275,9 +426,14 @@ impl IntoElement for Table {
         let scroll_handle_for_keydown = scroll_handle.clone();
         let columns_for_render = columns.clone();
         let on_row_right_click_for_render = on_row_right_click.clone();
+        let reorder_owner = on_reorder.as_ref().map(|_| id.clone());
 
         // Create the uniform list with click-to-select rows
         let list_element = uniform_list(id.clone(), row_count, move |range, window, cx| {
+            if let (Some(owner), Some(handle)) = (&reorder_owner, &scroll_handle_for_render) {
+                reorder::auto_scroll(owner, handle, window);
+            }
+
             // Update current selection from binding
             let selected_index = if let Some(ref binding) = selection_binding_for_render {
                 let sel = binding.get(cx);
@@ -359,7 +515,7 @@ impl IntoElement for Table {
                     // This avoids sub-pixel rendering issues with thin separate divs
                     row = row.on_mouse_down(MouseButton::Left, move |_event, window, cx| {
                         if let Some(ref binding) = selection_binding_for_click {
-                            binding.set(Some(index), cx);
+                            binding.set_if_changed(Some(index), cx);
                         }
                         if let Some(ref handle) = scroll_handle_for_click {
                             handle.scroll_to_item(index, ScrollStrategy::Center);
@@ -374,6 +530,17 @@ impl IntoElement for Table {
                         });
                     }
 
+                    if let Some(ref owner) = reorder_owner {
+                        row = reorder::reorderable_item(
+                            row,
+                            owner,
+                            index,
+                            row_count,
+                            Axis::Vertical,
+                            None,
+                        );
+                    }
+
                     row.into_any_element()
                 })
                 .collect()
@@ -393,7 +560,8 @@ impl IntoElement for Table {
 
         // Create container
         let mut container = div()
-            .id(id)
+            .id(id.clone())
+            .relative()
             .flex()
             .flex_col()
             .flex_1()
@@ -401,6 +569,12 @@ impl IntoElement for Table {
             .bg(hsla(0.0, 0.0, 1.0, 1.0))
             .overflow_hidden();
 
+        // Accept rows dropped into a new position
+        if let Some(ref handler) = on_reorder {
+            container =
+                reorder::reorderable_rows(container, &id, handler, selection_binding.clone());
+        }
+
         // Add keyboard navigation if focus handle is provided
         if let Some(handle) = self.focus_handle {
             let selection_binding_for_key = selection_binding.clone();
@@ -417,6 +591,19 @@ impl IntoElement for Table {
                     let mut new_selection: Option<usize> = None;
 
                     match event.keystroke.key.as_str() {
+                        key @ ("up" | "down")
+                            if event.keystroke.modifiers.alt && on_reorder.is_some() =>
+                        {
+                            let Some(from) = current else { return };
+                            let Some(to) = reorder::keyboard_destination(from, row_count, key == "down")
+                            else {
+                                return;
+                            };
+                            if let Some(ref handler) = on_reorder {
+                                handler(from, to, window, cx);
+                            }
+                            new_selection = Some(to);
+                        }
                         "down" if !event.keystroke.modifiers.platform => {
                             if let Some(current) = current {
                                 if current + 1 < row_count {
@@ -461,7 +648,7 @@ impl IntoElement for Table {
                         if current != Some(new_idx) {
                             // Update binding
                             if let Some(ref binding) = selection_binding_for_key {
-                                binding.set(Some(new_idx), cx);
+                                binding.set_if_changed(Some(new_idx), cx);
                             }
                             *current_selection_for_keydown.borrow_mut() = Some(new_idx);
 
@@ -477,6 +664,45 @@ impl IntoElement for Table {
             );
         }
 
-        container.child(list_wrapper)
+        let drop_overlay = self
+            .on_drop_paths
+            .map(|handler| FileDropTarget::new(handler, self.drop_filter).overlay(px(0.0)));
+
+        container.child(list_wrapper).children(drop_overlay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_accessibility_node() {
+        let files: Vec<(SharedString, SharedString)> = vec![
+            ("notes.txt".into(), "2 KB".into()),
+            ("photo.png".into(), "1 MB".into()),
+        ];
+        let table = Table::new("files", files.len(), |_, _, _, _| Vec::new())
+            .accessibility_label("Files")
+            .accessibility_headers(["Name", "Size"])
+            .accessibility_cells(move |row| vec![files[row].0.clone(), files[row].1.clone()]);
+        let node = table.accessibility_node();
+        assert_eq!(node.children[1].id, Some("files-column-1".into()));
+        assert_eq!(
+            node.children[3].children[1].id,
+            Some("files-row-1-cell-1".into())
+        );
+        assert_eq!(
+            node.to_string(),
+            "table \"Files\"\n\
+             \x20 table column header \"Name\"\n\
+             \x20 table column header \"Size\"\n\
+             \x20 table row [selectable]\n\
+             \x20   table cell \"notes.txt\"\n\
+             \x20   table cell \"2 KB\"\n\
+             \x20 table row [selectable]\n\
+             \x20   table cell \"photo.png\"\n\
+             \x20   table cell \"1 MB\""
+        );
     }
 }
@@ -5,6 +5,9 @@
 use gpui::prelude::*;
 use gpui::*;
 
+use crate::accessibility::{AccessNode, AccessState, Accessible, Role};
+use crate::state::Binding;
+
 /// A multi-line text input component.
 ///
 /// # Example
@@ -16,6 +19,10 @@ use gpui::*;
 ///     .on_change(|text| {
 ///         println!("Text changed: {}", text);
 ///     })
+///
+/// // Using a binding:
+/// TextArea::new("notes", cx)
+///     .text(State::binding(&self.notes, cx))
 /// ```
 pub struct TextArea {
     id: ElementId,
@@ -24,6 +31,7 @@ pub struct TextArea {
     rows: u8,
     focus_handle: FocusHandle,
     on_change: Option<Box<dyn Fn(&String) + 'static>>,
+    text_binding: Option<Binding<String>>,
 }
 
 impl TextArea {
@@ -36,9 +44,16 @@ impl TextArea {
             rows: 4,
             focus_handle: cx.focus_handle(),
             on_change: None,
+            text_binding: None,
         }
     }
 
+    /// Sets a two-way binding for the text value.
+    pub fn text(mut self, binding: Binding<String>) -> Self {
+        self.text_binding = Some(binding);
+        self
+    }
+
     /// Sets the current text value.
     pub fn value(mut self, value: impl Into<String>) -> Self {
         self.value = value.into();
@@ -77,6 +92,7 @@ pub struct TextAreaState {
     focus_handle: FocusHandle,
     cursor_position: usize,
     on_change: Option<Box<dyn Fn(&String) + 'static>>,
+    text_binding: Option<Binding<String>>,
 }
 
 impl TextAreaState {
@@ -203,6 +219,9 @@ impl TextAreaState {
         if let Some(ref handler) = self.on_change {
             handler(&self.value);
         }
+        if let Some(ref binding) = self.text_binding {
+            binding.set_if_changed(self.value.clone(), cx);
+        }
         cx.notify();
     }
 
@@ -219,6 +238,15 @@ impl TextAreaState {
 
 impl Render for TextAreaState {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // Sync value from binding
+        if let Some(ref binding) = self.text_binding {
+            let bound_value = binding.get(cx);
+            if bound_value != self.value {
+                self.value = bound_value;
+                self.cursor_position = self.cursor_position.min(self.value.chars().count());
+            }
+        }
+
         let is_focused = self.focus_handle.is_focused(window);
         let is_empty = self.value.is_empty();
         let rows = self.rows;
@@ -317,6 +345,22 @@ impl Render for TextAreaState {
     }
 }
 
+impl Accessible for TextAreaState {
+    fn accessibility_node(&self) -> AccessNode {
+        let node = AccessNode::new(Role::Text)
+            .id(self.id.to_string())
+            .name(self.placeholder.clone())
+            .text(self.value.clone())
+            .focus_handle(&self.focus_handle)
+            .state(AccessState::Editable)
+            .state(AccessState::MultiLine);
+        match self.text_binding.clone() {
+            Some(binding) => node.resolve_with(move |node, cx| node.text(binding.get(cx))),
+            None => node,
+        }
+    }
+}
+
 /// Creates a TextAreaState from a TextArea builder.
 impl From<TextArea> for TextAreaState {
     fn from(builder: TextArea) -> Self {
@@ -329,6 +373,7 @@ impl From<TextArea> for TextAreaState {
             focus_handle: builder.focus_handle,
             cursor_position,
             on_change: builder.on_change,
+            text_binding: builder.text_binding,
         }
     }
 }
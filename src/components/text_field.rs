@@ -10,6 +10,7 @@ use std::ops::Range;
 use std::rc::Rc;
 use std::sync::Arc;
 
+use crate::accessibility::{AccessNode, AccessState, Accessible, Role};
 use crate::state::Binding;
 
 /// A single-line text input component.
@@ -123,6 +124,18 @@ impl TextFieldState {
         self.focus_handle.focus(window);
     }
 
+    /// Replace the text and propagate it to the change handler and binding.
+    pub(crate) fn replace_text(&mut self, text: String, cx: &mut Context<Self>) {
+        self.set_value(text);
+        if let Some(ref handler) = self.on_change {
+            handler(&self.value);
+        }
+        if let Some(ref binding) = self.text_binding {
+            binding.set_if_changed(self.value.clone(), cx);
+        }
+        cx.notify();
+    }
+
     /// Returns the selection range (start, end) in byte offsets.
     /// Start is always <= end.
     fn selection_range(&self) -> Option<Range<usize>> {
@@ -377,7 +390,7 @@ impl TextFieldState {
             handler(&self.value);
         }
         if let Some(ref binding) = self.text_binding {
-            binding.set(self.value.clone(), &mut **cx);
+            binding.set_if_changed(self.value.clone(), &mut **cx);
         }
         cx.notify();
     }
@@ -651,6 +664,38 @@ impl Render for TextFieldState {
     }
 }
 
+impl Focusable for TextFieldState {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Accessible for TextFieldState {
+    fn accessibility_node(&self) -> AccessNode {
+        let node = AccessNode::new(Role::Text)
+            .id(self.id.to_string())
+            .name(
+                self.label
+                    .clone()
+                    .unwrap_or_else(|| self.placeholder.clone()),
+            )
+            .text(self.value.clone())
+            .focus_handle(&self.focus_handle)
+            .state(AccessState::Editable);
+        // The bounds are recorded when the field is painted
+        let bounds = self.input_bounds.get();
+        let node = if bounds.size.width > px(0.0) {
+            node.bounds(bounds)
+        } else {
+            node
+        };
+        match self.text_binding.clone() {
+            Some(binding) => node.resolve_with(move |node, cx| node.text(binding.get(cx))),
+            None => node,
+        }
+    }
+}
+
 /// Creates a TextFieldState from a TextField builder.
 impl From<TextField> for TextFieldState {
     fn from(builder: TextField) -> Self {
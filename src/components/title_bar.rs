@@ -1,14 +1,19 @@
 //! Window title bar component.
 //!
 //! A title bar component with gradient background, traffic light buttons,
-//! and centered title text. Supports window dragging.
+//! and centered title text. Supports window dragging, double-click to
+//! maximize and the compositor's window menu.
 
-use super::TrafficLights;
+use std::rc::Rc;
+
+use super::{TrafficLights, WindowChrome};
 use gpui::prelude::*;
 use gpui::*;
 
 const TITLE_BAR_HEIGHT: f32 = 22.0;
 
+type WindowMenuHandler = Rc<dyn Fn(Point<Pixels>, &mut Window, &mut App) + 'static>;
+
 /// A window title bar.
 ///
 /// Displays a title bar with:
@@ -16,17 +21,26 @@ const TITLE_BAR_HEIGHT: f32 = 22.0;
 /// - Traffic light buttons (close, minimize, maximize) on the left
 /// - Centered window title
 /// - Window dragging support (click and drag to move window)
+/// - Double-click to maximize or restore the window
+/// - Right-click for the window menu (Move, Resize, Always on Top, Close)
+///
+/// Pass the window's [`WindowChrome`] to gray out the title bar while the
+/// window is inactive and drop the top highlight when it is maximized or tiled.
 ///
 /// # Example
 ///
 /// ```ignore
 /// TitleBar::new("My Window")
+///     .chrome(WindowChrome::from_window(window))
 ///     .draggable(true)
 ///     .on_close(cx.listener(|_, _, _, cx| cx.quit()))
 /// ```
+#[derive(IntoElement)]
 pub struct TitleBar {
     title: SharedString,
     draggable: bool,
+    chrome: WindowChrome,
+    on_window_menu: Option<WindowMenuHandler>,
     on_close: Option<Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>>,
     on_minimize: Option<Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>>,
     on_maximize: Option<Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>>,
@@ -40,6 +54,8 @@ impl TitleBar {
         Self {
             title: title.into(),
             draggable: true,
+            chrome: WindowChrome::default(),
+            on_window_menu: None,
             on_close: None,
             on_minimize: None,
             on_maximize: None,
@@ -54,6 +70,26 @@ impl TitleBar {
         self
     }
 
+    /// Sets the window's decoration state.
+    ///
+    /// Defaults to an active, floating window.
+    pub fn chrome(mut self, chrome: WindowChrome) -> Self {
+        self.chrome = chrome;
+        self
+    }
+
+    /// Sets the handler for right-clicks on the title bar.
+    ///
+    /// The handler receives the click position in window coordinates. By
+    /// default, right-clicking opens the compositor's window menu.
+    pub fn on_window_menu(
+        mut self,
+        handler: impl Fn(Point<Pixels>, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_window_menu = Some(Rc::new(handler));
+        self
+    }
+
     /// Sets the handler for the close button.
     pub fn on_close(
         mut self,
@@ -82,12 +118,59 @@ impl TitleBar {
     }
 }
 
-impl IntoElement for TitleBar {
-    type Element = Div;
+impl TitleBar {
+    /// Toggles maximize on a double-click, or arms a window move that
+    /// starts once the mouse moves.
+    ///
+    /// Starting the move right away would hand the pointer to the
+    /// compositor, which on Wayland swallows the second click.
+    fn handle_drag_area_down(
+        armed: &Entity<bool>,
+        event: &MouseDownEvent,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let double_click = event.click_count == 2;
+        if double_click {
+            window.zoom_window();
+        }
+        armed.update(cx, |armed, _cx| *armed = !double_click);
+    }
+
+    /// Starts the armed window move while the left button is held.
+    fn handle_drag_area_move(
+        armed: &Entity<bool>,
+        event: &MouseMoveEvent,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let armed = armed.update(cx, |armed, _cx| std::mem::take(armed));
+        if armed && event.pressed_button == Some(MouseButton::Left) {
+            window.start_window_move();
+        }
+    }
+
+    /// Runs the window menu handler, or opens the compositor's window menu.
+    fn show_window_menu(
+        handler: &Option<WindowMenuHandler>,
+        event: &MouseDownEvent,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        match handler {
+            Some(handler) => handler(event.position, window, cx),
+            None => window.show_window_menu(event.position),
+        }
+        cx.stop_propagation();
+    }
+}
+
+impl RenderOnce for TitleBar {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let chrome = self.chrome;
 
-    fn into_element(self) -> Self::Element {
         // Create traffic lights with handlers
-        let mut traffic_lights = TrafficLights::new();
+        let mut traffic_lights = TrafficLights::new().active(chrome.active);
         if let Some(handler) = self.on_close {
             traffic_lights = traffic_lights.on_close(handler);
         }
@@ -103,6 +186,11 @@ impl IntoElement for TitleBar {
         // Center: Title with inset shadow effect (draggable area)
         // White text 1px below creates embossed/inset look
         let title = self.title.clone();
+        let title_color = if chrome.active {
+            hsla(0.0, 0.0, 0.30, 1.0)
+        } else {
+            hsla(0.0, 0.0, 0.55, 1.0)
+        };
         let mut center = div()
             .id("title-bar-center")
             .relative()
@@ -132,7 +220,7 @@ impl IntoElement for TitleBar {
                             .relative()
                             .text_sm()
                             .font_weight(FontWeight::SEMIBOLD)
-                            .text_color(hsla(0.0, 0.0, 0.30, 1.0))
+                            .text_color(title_color)
                             .child(title),
                     ),
             );
@@ -144,21 +232,52 @@ impl IntoElement for TitleBar {
             .w(px(100.0))
             .h_full();
 
-        // Add drag handlers if draggable
+        // Add drag handlers if draggable. Pressing the button arms a move
+        // that starts on the first mouse move; double-clicking maximizes or
+        // restores the window instead.
         if draggable {
+            let armed = window.use_keyed_state("title-bar-move", cx, |_, _| false);
+
             center = center
                 .cursor(CursorStyle::Arrow)
-                .on_mouse_down(MouseButton::Left, |_event, window, _cx| {
-                    window.start_window_move();
+                .on_mouse_down(MouseButton::Left, {
+                    let armed = armed.clone();
+                    move |event, window, cx| Self::handle_drag_area_down(&armed, event, window, cx)
+                })
+                .on_mouse_move({
+                    let armed = armed.clone();
+                    move |event, window, cx| Self::handle_drag_area_move(&armed, event, window, cx)
                 });
 
             right = right
                 .cursor(CursorStyle::Arrow)
-                .on_mouse_down(MouseButton::Left, |_event, window, _cx| {
-                    window.start_window_move();
+                .on_mouse_down(MouseButton::Left, {
+                    let armed = armed.clone();
+                    move |event, window, cx| Self::handle_drag_area_down(&armed, event, window, cx)
+                })
+                .on_mouse_move(move |event, window, cx| {
+                    Self::handle_drag_area_move(&armed, event, window, cx)
                 });
         }
 
+        // Right-click opens the window menu
+        let on_window_menu = self.on_window_menu;
+        center = center.on_mouse_down(MouseButton::Right, {
+            let on_window_menu = on_window_menu.clone();
+            move |event, window, cx| Self::show_window_menu(&on_window_menu, event, window, cx)
+        });
+        right = right.on_mouse_down(MouseButton::Right, move |event, window, cx| {
+            Self::show_window_menu(&on_window_menu, event, window, cx)
+        });
+
+        // Inactive windows use a flatter, lighter gradient
+        let (gradient_top, gradient_bottom) = if chrome.active {
+            // #F3EEF0 at top to #BDBDBD at bottom
+            (hsla(0.933, 0.17, 0.94, 1.0), hsla(0.0, 0.0, 0.741, 1.0))
+        } else {
+            (hsla(0.0, 0.0, 0.96, 1.0), hsla(0.0, 0.0, 0.87, 1.0))
+        };
+
         // Title bar styling - native GPU gradient
         div()
             .relative()
@@ -168,26 +287,32 @@ impl IntoElement for TitleBar {
             .h(px(TITLE_BAR_HEIGHT))
             .w_full()
             .px(px(10.0))
-            // Smooth gradient: #F3EEF0 at top to #BDBDBD at bottom
             // Angle 180.0 = top-to-bottom direction
             .bg(linear_gradient(
                 180.0,
-                linear_color_stop(hsla(0.933, 0.17, 0.94, 1.0), 0.0),
-                linear_color_stop(hsla(0.0, 0.0, 0.741, 1.0), 1.0),
+                linear_color_stop(gradient_top, 0.0),
+                linear_color_stop(gradient_bottom, 1.0),
             ))
-            // Top highlight line (1px bright line at very top edge)
-            .child(
-                div()
-                    .absolute()
-                    .top_0()
-                    .left_0()
-                    .w_full()
-                    .h(px(1.0))
-                    .bg(hsla(0.0, 0.0, 1.0, 0.5)),
-            )
+            // Top highlight line (1px bright line at very top edge), hidden
+            // when the top edge touches the screen or another window
+            .when(!chrome.tiled_edges().top, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .top_0()
+                        .left_0()
+                        .w_full()
+                        .h(px(1.0))
+                        .bg(hsla(0.0, 0.0, 1.0, 0.5)),
+                )
+            })
             // Bottom border for depth
             .border_b_1()
-            .border_color(hsla(0.0, 0.0, 0.50, 1.0))
+            .border_color(if chrome.active {
+                hsla(0.0, 0.0, 0.50, 1.0)
+            } else {
+                hsla(0.0, 0.0, 0.70, 1.0)
+            })
             // Left: Traffic lights (not draggable)
             .child(
                 div()
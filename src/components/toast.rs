@@ -0,0 +1,517 @@
+//! Toast notifications for transient feedback.
+//!
+//! Banners that appear in a corner of the window and dismiss themselves after
+//! a delay. Toasts are queued in the `Toasts` global and drawn by a `ToastHost`.
+
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use gpui::prelude::*;
+use gpui::*;
+
+use super::{AlertIcon, ProgressView};
+
+type ActionHandler = Rc<dyn Fn(&mut Window, &mut App) + 'static>;
+
+/// The default time a toast stays visible.
+const DEFAULT_DURATION: Duration = Duration::from_secs(4);
+
+/// Identifies a toast shown with [`Toasts::show`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ToastId(usize);
+
+/// The window corner toasts are stacked in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ToastCorner {
+    /// Top left corner
+    TopLeft,
+    /// Top right corner
+    TopRight,
+    /// Bottom left corner
+    BottomLeft,
+    /// Bottom right corner
+    #[default]
+    BottomRight,
+}
+
+impl ToastCorner {
+    fn is_top(&self) -> bool {
+        matches!(self, ToastCorner::TopLeft | ToastCorner::TopRight)
+    }
+
+    fn is_left(&self) -> bool {
+        matches!(self, ToastCorner::TopLeft | ToastCorner::BottomLeft)
+    }
+}
+
+/// A transient notification banner.
+///
+/// # Example
+///
+/// ```ignore
+/// Toasts::show(
+///     Toast::success("Message sent")
+///         .message("Your message was delivered.")
+///         .action("Undo", |_window, cx| unsend(cx)),
+///     cx,
+/// );
+/// ```
+#[derive(Clone)]
+pub struct Toast {
+    title: SharedString,
+    message: Option<SharedString>,
+    icon: Option<AlertIcon>,
+    duration: Option<Duration>,
+    action: Option<(SharedString, ActionHandler)>,
+    /// Progress from 0.0 to 1.0; `Some(None)` shows indeterminate progress.
+    progress: Option<Option<f32>>,
+}
+
+impl Toast {
+    /// Creates a toast with the given title and no icon.
+    pub fn new(title: impl Into<SharedString>) -> Self {
+        Self {
+            title: title.into(),
+            message: None,
+            icon: None,
+            duration: Some(DEFAULT_DURATION),
+            action: None,
+            progress: None,
+        }
+    }
+
+    /// Creates an informational toast.
+    pub fn info(title: impl Into<SharedString>) -> Self {
+        Self::new(title).icon(AlertIcon::Info)
+    }
+
+    /// Creates a success toast.
+    pub fn success(title: impl Into<SharedString>) -> Self {
+        Self::new(title).icon(AlertIcon::Success)
+    }
+
+    /// Creates a warning toast.
+    pub fn warning(title: impl Into<SharedString>) -> Self {
+        Self::new(title).icon(AlertIcon::Warning)
+    }
+
+    /// Creates an error toast.
+    pub fn error(title: impl Into<SharedString>) -> Self {
+        Self::new(title).icon(AlertIcon::Error)
+    }
+
+    /// Creates a toast showing indeterminate progress for a long-running job.
+    ///
+    /// Progress toasts stay visible until dismissed or replaced.
+    pub fn progress(title: impl Into<SharedString>) -> Self {
+        Self {
+            duration: None,
+            progress: Some(None),
+            ..Self::new(title)
+        }
+    }
+
+    /// Sets the message text displayed below the title.
+    pub fn message(mut self, message: impl Into<SharedString>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Sets the icon displayed before the title.
+    pub fn icon(mut self, icon: AlertIcon) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Sets how long the toast stays visible (default: 4 seconds).
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Keeps the toast visible until it is dismissed.
+    pub fn persistent(mut self) -> Self {
+        self.duration = None;
+        self
+    }
+
+    /// Adds an action button. Clicking it calls `handler` and dismisses the toast.
+    pub fn action(
+        mut self,
+        label: impl Into<SharedString>,
+        handler: impl Fn(&mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.action = Some((label.into(), Rc::new(handler)));
+        self
+    }
+
+    /// Sets the progress value (0.0 to 1.0) of a progress toast.
+    pub fn value(mut self, value: f32) -> Self {
+        self.progress = Some(Some(value.clamp(0.0, 1.0)));
+        self
+    }
+}
+
+struct ToastEntry {
+    id: ToastId,
+    toast: Toast,
+    /// Time left before the toast dismisses itself, or `None` if persistent.
+    remaining: Option<Duration>,
+    /// When the running timer was started.
+    started_at: Option<Instant>,
+    hovered: bool,
+    /// Running dismiss timer, dropped to pause it.
+    timer: Option<Task<()>>,
+}
+
+/// The toast queue for the application.
+///
+/// Toasts are shown in the order they were added. At most `max_visible` are
+/// on screen at once; the rest wait until earlier ones are dismissed. Timers
+/// only run for visible toasts and pause while the pointer is over a toast.
+///
+/// # Example
+///
+/// ```ignore
+/// Toasts::set_corner(ToastCorner::TopRight, cx);
+///
+/// let id = Toasts::show(Toast::progress("Exporting…"), cx);
+/// Toasts::set_progress(id, 0.5, cx);
+/// Toasts::replace(id, Toast::success("Export finished"), cx);
+/// ```
+pub struct Toasts {
+    entries: Vec<ToastEntry>,
+    corner: ToastCorner,
+    max_visible: usize,
+    next_id: usize,
+}
+
+impl Default for Toasts {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            corner: ToastCorner::default(),
+            max_visible: 3,
+            next_id: 0,
+        }
+    }
+}
+
+impl Global for Toasts {}
+
+impl Toasts {
+    /// Queues a toast and returns its identifier.
+    pub fn show(toast: Toast, cx: &mut App) -> ToastId {
+        cx.update_default_global(|toasts: &mut Self, cx| {
+            let id = toasts.push(toast);
+            toasts.start_timers(cx);
+            id
+        })
+    }
+
+    /// Removes a toast, shown or queued.
+    pub fn dismiss(id: ToastId, cx: &mut App) {
+        cx.update_default_global(|toasts: &mut Self, cx| {
+            if toasts.remove(id) {
+                toasts.start_timers(cx);
+            }
+        });
+    }
+
+    /// Removes all toasts.
+    pub fn dismiss_all(cx: &mut App) {
+        cx.update_default_global(|toasts: &mut Self, _cx| toasts.entries.clear());
+    }
+
+    /// Replaces the content of a toast and restarts its timer.
+    ///
+    /// Use this to turn a progress toast into a success or error toast.
+    pub fn replace(id: ToastId, toast: Toast, cx: &mut App) {
+        cx.update_default_global(|toasts: &mut Self, cx| {
+            if let Some(entry) = toasts.entry_mut(id) {
+                entry.remaining = toast.duration;
+                entry.started_at = None;
+                entry.timer = None;
+                entry.toast = toast;
+                toasts.start_timers(cx);
+            }
+        });
+    }
+
+    /// Updates the progress value (0.0 to 1.0) of a progress toast.
+    pub fn set_progress(id: ToastId, value: f32, cx: &mut App) {
+        cx.update_default_global(|toasts: &mut Self, _cx| {
+            if let Some(entry) = toasts.entry_mut(id) {
+                entry.toast.progress = Some(Some(value.clamp(0.0, 1.0)));
+            }
+        });
+    }
+
+    /// Sets the corner toasts are stacked in (default: bottom right).
+    pub fn set_corner(corner: ToastCorner, cx: &mut App) {
+        cx.update_default_global(|toasts: &mut Self, _cx| toasts.corner = corner);
+    }
+
+    /// Sets how many toasts are visible at once (default: 3).
+    pub fn set_max_visible(max_visible: usize, cx: &mut App) {
+        cx.update_default_global(|toasts: &mut Self, cx| {
+            toasts.max_visible = max_visible.max(1);
+            toasts.start_timers(cx);
+        });
+    }
+
+    /// Returns the identifiers of all queued toasts, visible ones first.
+    pub fn ids(cx: &App) -> Vec<ToastId> {
+        cx.try_global::<Self>()
+            .map(|toasts| toasts.entries.iter().map(|entry| entry.id).collect())
+            .unwrap_or_default()
+    }
+
+    fn push(&mut self, toast: Toast) -> ToastId {
+        let id = ToastId(self.next_id);
+        self.next_id += 1;
+        self.entries.push(ToastEntry {
+            id,
+            remaining: toast.duration,
+            toast,
+            started_at: None,
+            hovered: false,
+            timer: None,
+        });
+        id
+    }
+
+    fn remove(&mut self, id: ToastId) -> bool {
+        let len = self.entries.len();
+        self.entries.retain(|entry| entry.id != id);
+        self.entries.len() != len
+    }
+
+    fn entry_mut(&mut self, id: ToastId) -> Option<&mut ToastEntry> {
+        self.entries.iter_mut().find(|entry| entry.id == id)
+    }
+
+    /// Starts dismiss timers for visible toasts that don't have one running.
+    fn start_timers(&mut self, cx: &mut App) {
+        for entry in self.entries.iter_mut().take(self.max_visible) {
+            if entry.timer.is_some() || entry.hovered {
+                continue;
+            }
+            let Some(remaining) = entry.remaining else {
+                continue;
+            };
+
+            let id = entry.id;
+            entry.started_at = Some(Instant::now());
+            entry.timer = Some(cx.spawn(async move |cx| {
+                cx.background_executor().timer(remaining).await;
+                let _ = cx.update(|cx| Toasts::dismiss(id, cx));
+            }));
+        }
+    }
+
+    /// Pauses a toast's timer while hovered and resumes it afterwards.
+    fn set_hovered(id: ToastId, hovered: bool, cx: &mut App) {
+        cx.update_default_global(|toasts: &mut Self, cx| {
+            let Some(entry) = toasts.entry_mut(id) else {
+                return;
+            };
+            entry.hovered = hovered;
+            if hovered {
+                entry.timer = None;
+                if let (Some(remaining), Some(started_at)) =
+                    (entry.remaining, entry.started_at.take())
+                {
+                    entry.remaining = Some(remaining.saturating_sub(started_at.elapsed()));
+                }
+            } else {
+                toasts.start_timers(cx);
+            }
+        });
+    }
+}
+
+/// Draws the application's toasts in a corner of the window.
+///
+/// Add a host as the last child of the window's root view, which should be
+/// relatively positioned so the toasts are placed over its content.
+///
+/// # Example
+///
+/// ```ignore
+/// struct AppView {
+///     toasts: Entity<ToastHost>,
+/// }
+///
+/// impl Render for AppView {
+///     fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+///         div()
+///             .relative()
+///             .size_full()
+///             .child(self.content())
+///             .child(self.toasts.clone())
+///     }
+/// }
+/// ```
+pub struct ToastHost {
+    _subscription: Subscription,
+}
+
+impl ToastHost {
+    /// Creates a host that redraws whenever the toast queue changes.
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        Self {
+            _subscription: cx.observe_global::<Toasts>(|_, cx| cx.notify()),
+        }
+    }
+
+    fn build_toast(entry: &ToastEntry) -> Stateful<Div> {
+        let id = entry.id;
+        let toast = &entry.toast;
+
+        let mut body = div().flex().flex_col().flex_1().gap(px(2.0)).child(
+            div()
+                .text_sm()
+                .font_weight(FontWeight::SEMIBOLD)
+                .text_color(hsla(0.0, 0.0, 0.15, 1.0))
+                .child(toast.title.clone()),
+        );
+
+        if let Some(ref message) = toast.message {
+            body = body.child(
+                div()
+                    .text_xs()
+                    .text_color(hsla(0.0, 0.0, 0.40, 1.0))
+                    .child(message.clone()),
+            );
+        }
+
+        if let Some(progress) = toast.progress {
+            let view = match progress {
+                Some(value) => ProgressView::new(value),
+                None => ProgressView::indeterminate(),
+            };
+            body = body.child(div().pt(px(4.0)).child(view));
+        }
+
+        if let Some((label, handler)) = toast.action.clone() {
+            body = body.child(
+                div()
+                    .id(("toast-action", id.0))
+                    .pt(px(4.0))
+                    .text_xs()
+                    .font_weight(FontWeight::MEDIUM)
+                    .text_color(hsla(211.0 / 360.0, 0.95, 0.53, 1.0))
+                    .cursor_pointer()
+                    .hover(|style| style.text_color(hsla(211.0 / 360.0, 0.95, 0.40, 1.0)))
+                    .child(label)
+                    .on_click(move |_event, window, cx| {
+                        handler(window, cx);
+                        Toasts::dismiss(id, cx);
+                    }),
+            );
+        }
+
+        let mut row = div()
+            .id(("toast", id.0))
+            .flex()
+            .flex_row()
+            .items_start()
+            .gap(px(10.0))
+            .w(px(320.0))
+            .p(px(12.0))
+            .rounded(px(8.0))
+            .bg(hsla(0.0, 0.0, 1.0, 1.0))
+            .border_1()
+            .border_color(hsla(0.0, 0.0, 0.85, 1.0))
+            .shadow(vec![BoxShadow {
+                color: hsla(0.0, 0.0, 0.0, 0.15),
+                offset: point(px(0.0), px(4.0)),
+                blur_radius: px(12.0),
+                spread_radius: px(0.0),
+            }])
+            .on_hover(move |hovered, _window, cx| Toasts::set_hovered(id, *hovered, cx));
+
+        if let Some(icon) = toast.icon {
+            row = row.child(
+                div()
+                    .text_base()
+                    .text_color(icon.color())
+                    .child(icon.as_str()),
+            );
+        }
+
+        row.child(body).child(
+            div()
+                .id(("toast-close", id.0))
+                .text_xs()
+                .text_color(hsla(0.0, 0.0, 0.55, 1.0))
+                .cursor_pointer()
+                .hover(|style| style.text_color(hsla(0.0, 0.0, 0.25, 1.0)))
+                .child("✕")
+                .on_click(move |_event, _window, cx| Toasts::dismiss(id, cx)),
+        )
+    }
+}
+
+impl Render for ToastHost {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let Some(toasts) = cx.try_global::<Toasts>() else {
+            return div();
+        };
+        let corner = toasts.corner;
+
+        // Toasts stack away from the corner, so the newest is furthest in
+        let mut stack = div().absolute().flex().gap(px(8.0)).p(px(16.0));
+        stack = if corner.is_top() {
+            stack.top_0().flex_col()
+        } else {
+            stack.bottom_0().flex_col_reverse()
+        };
+        stack = if corner.is_left() {
+            stack.left_0().items_start()
+        } else {
+            stack.right_0().items_end()
+        };
+
+        for entry in toasts.entries.iter().take(toasts.max_visible) {
+            stack = stack.child(Self::build_toast(entry));
+        }
+
+        stack
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toast_variants() {
+        assert_eq!(Toast::new("Saved").icon, None);
+        assert_eq!(Toast::success("Saved").icon, Some(AlertIcon::Success));
+        assert_eq!(Toast::error("Failed").icon, Some(AlertIcon::Error));
+        assert_eq!(Toast::info("Saved").duration, Some(DEFAULT_DURATION));
+        assert_eq!(Toast::info("Saved").persistent().duration, None);
+    }
+
+    #[test]
+    fn test_progress_toast() {
+        let toast = Toast::progress("Exporting");
+        assert_eq!(toast.duration, None);
+        assert_eq!(toast.progress, Some(None));
+        assert_eq!(toast.value(1.5).progress, Some(Some(1.0)));
+    }
+
+    #[test]
+    fn test_toast_queue() {
+        let mut toasts = Toasts::default();
+        let first = toasts.push(Toast::new("First"));
+        let second = toasts.push(Toast::new("Second"));
+        assert_ne!(first, second);
+        assert!(toasts.remove(first));
+        assert!(!toasts.remove(first));
+        assert_eq!(toasts.entries.len(), 1);
+        assert_eq!(toasts.entries[0].id, second);
+    }
+}
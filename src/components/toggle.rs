@@ -5,6 +5,9 @@
 use gpui::prelude::*;
 use gpui::*;
 
+use crate::accessibility::{AccessNode, AccessState, Accessible, Role};
+use crate::state::Binding;
+
 /// Style variants for the Toggle component.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum ToggleStyle {
@@ -36,7 +39,11 @@ pub enum ToggleStyle {
 ///         this.dark_mode = *is_on;
 ///         cx.notify();
 ///     }))
+///
+/// // Bound to a `State<bool>`
+/// Toggle::new("wifi", "Wi-Fi", false).is_on(State::binding(&self.wifi, cx))
 /// ```
+#[derive(IntoElement)]
 pub struct Toggle {
     id: ElementId,
     label: SharedString,
@@ -44,6 +51,7 @@ pub struct Toggle {
     style: ToggleStyle,
     disabled: bool,
     on_change: Option<Box<dyn Fn(&bool, &mut Window, &mut App) + 'static>>,
+    is_on_binding: Option<Binding<bool>>,
 }
 
 impl Toggle {
@@ -56,6 +64,7 @@ impl Toggle {
             style: ToggleStyle::default(),
             disabled: false,
             on_change: None,
+            is_on_binding: None,
         }
     }
 
@@ -71,6 +80,15 @@ impl Toggle {
         self
     }
 
+    /// Sets a two-way binding for the on/off state.
+    ///
+    /// The bound value replaces the initial state, and clicking the toggle
+    /// writes the new state back.
+    pub fn is_on(mut self, binding: Binding<bool>) -> Self {
+        self.is_on_binding = Some(binding);
+        self
+    }
+
     /// Sets the change handler for this toggle.
     ///
     /// The handler receives a reference to the new on/off state.
@@ -222,10 +240,11 @@ impl Toggle {
     }
 }
 
-impl IntoElement for Toggle {
-    type Element = Stateful<Div>;
-
-    fn into_element(self) -> Self::Element {
+impl RenderOnce for Toggle {
+    fn render(mut self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        if let Some(ref binding) = self.is_on_binding {
+            self.is_on = binding.get(cx);
+        }
         let is_on = self.is_on;
         let disabled = self.disabled;
 
@@ -274,17 +293,40 @@ impl IntoElement for Toggle {
         let container = container.child(toggle_control).child(label);
 
         // Add click handler if provided and not disabled
-        if let Some(handler) = self.on_change {
-            if !disabled {
-                let new_state = !is_on;
-                container.on_click(move |_event, window, cx| {
-                    handler(&new_state, window, cx);
-                })
-            } else {
-                container
+        if disabled || (self.on_change.is_none() && self.is_on_binding.is_none()) {
+            return container;
+        }
+        let (handler, binding) = (self.on_change, self.is_on_binding);
+        let new_state = !is_on;
+        container.on_click(move |_event, window, cx| {
+            if let Some(ref binding) = binding {
+                binding.set_if_changed(new_state, cx);
             }
-        } else {
-            container
+            if let Some(ref handler) = handler {
+                handler(&new_state, window, cx);
+            }
+        })
+    }
+}
+
+/// Switches are toggle buttons; checkbox-style toggles are check boxes.
+impl Accessible for Toggle {
+    fn accessibility_node(&self) -> AccessNode {
+        let role = match self.style {
+            ToggleStyle::Checkbox => Role::CheckBox,
+            ToggleStyle::Switch => Role::ToggleButton,
+        };
+        let node = AccessNode::new(role)
+            .id(self.id.to_string())
+            .name(self.label.clone())
+            .state(AccessState::Focusable)
+            .state(AccessState::Checkable)
+            .state_if(AccessState::Disabled, self.disabled)
+            .action("click");
+        match self.is_on_binding.clone() {
+            Some(binding) => node
+                .resolve_with(move |node, cx| node.state_if(AccessState::Checked, binding.get(cx))),
+            None => node.state_if(AccessState::Checked, self.is_on),
         }
     }
 }
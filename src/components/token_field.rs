@@ -0,0 +1,470 @@
+//! TokenField component for entering a list of tokens.
+//!
+//! A text field that turns typed text into removable chips, with optional
+//! suggestions and drag-to-reorder.
+
+use std::rc::Rc;
+use std::time::Duration;
+
+use gpui::prelude::*;
+use gpui::*;
+
+use super::{Badge, ComboBox, ComboBoxState};
+use crate::state::Binding;
+
+type ChangeHandler = Rc<dyn Fn(&[String], &mut Window, &mut App) + 'static>;
+
+/// A field that collects a list of tokens, shown as removable chips.
+///
+/// Text is committed as a token when the user presses Enter, accepts a
+/// suggestion, types a separator character, or moves focus away. Tokens can be
+/// selected with the arrow keys, removed with Backspace or Delete, and
+/// reordered by dragging.
+///
+/// # Example
+///
+/// ```ignore
+/// let tags = cx.new(|cx| TokenFieldState::new(
+///     TokenField::new("tags", cx)
+///         .tokens(State::binding(&self.tags, cx))
+///         .placeholder("Add tags")
+///         .suggestions(vec!["design".into(), "rust".into(), "ui".into()]),
+///     window,
+///     cx,
+/// ));
+/// ```
+pub struct TokenField {
+    id: ElementId,
+    combo: ComboBox,
+    tokens: Vec<String>,
+    tokens_binding: Option<Binding<Vec<String>>>,
+    separators: Vec<char>,
+    allow_duplicates: bool,
+    on_change: Option<ChangeHandler>,
+}
+
+impl TokenField {
+    /// Creates a new token field with the given identifier.
+    pub fn new(id: impl Into<ElementId>, cx: &mut App) -> Self {
+        let id = id.into();
+        Self {
+            combo: ComboBox::new(id.clone(), cx),
+            id,
+            tokens: Vec::new(),
+            tokens_binding: None,
+            separators: vec![','],
+            allow_duplicates: false,
+            on_change: None,
+        }
+    }
+
+    /// Sets a two-way binding for the tokens.
+    pub fn tokens(mut self, binding: Binding<Vec<String>>) -> Self {
+        self.tokens_binding = Some(binding);
+        self
+    }
+
+    /// Sets the initial tokens (use `tokens()` for two-way binding).
+    pub fn value(mut self, tokens: Vec<String>) -> Self {
+        self.tokens = tokens;
+        self
+    }
+
+    /// Sets the label displayed above the field.
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.combo = self.combo.label(label);
+        self
+    }
+
+    /// Sets the placeholder text shown when no text is entered.
+    pub fn placeholder(mut self, placeholder: impl Into<SharedString>) -> Self {
+        self.combo = self.combo.placeholder(placeholder);
+        self
+    }
+
+    /// Sets the characters that commit the typed text as a token (default: comma).
+    pub fn separators(mut self, separators: impl IntoIterator<Item = char>) -> Self {
+        self.separators = separators.into_iter().collect();
+        self
+    }
+
+    /// Sets whether the same token can be added more than once (default: false).
+    pub fn allow_duplicates(mut self, allow: bool) -> Self {
+        self.allow_duplicates = allow;
+        self
+    }
+
+    /// Sets a fixed list of suggestions, filtered by the typed text.
+    pub fn suggestions(mut self, suggestions: Vec<SharedString>) -> Self {
+        self.combo = self.combo.suggestions(suggestions);
+        self
+    }
+
+    /// Sets a function that returns suggestions for the typed text.
+    pub fn suggestion_provider(
+        mut self,
+        provider: impl Fn(&str) -> Vec<SharedString> + 'static,
+    ) -> Self {
+        self.combo = self.combo.suggestion_provider(provider);
+        self
+    }
+
+    /// Sets a function that loads suggestions for the typed text in the background.
+    pub fn async_suggestion_provider(
+        mut self,
+        provider: impl Fn(String, &mut App) -> Task<Vec<SharedString>> + 'static,
+    ) -> Self {
+        self.combo = self.combo.async_suggestion_provider(provider);
+        self
+    }
+
+    /// Sets how long typing must pause before suggestions are queried.
+    pub fn debounce(mut self, delay: Duration) -> Self {
+        self.combo = self.combo.debounce(delay);
+        self
+    }
+
+    /// Sets the handler called with all tokens whenever they change.
+    pub fn on_change(
+        mut self,
+        handler: impl Fn(&[String], &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_change = Some(Rc::new(handler));
+        self
+    }
+}
+
+/// Drag payload for a token being reordered.
+#[derive(Clone)]
+struct DraggedToken {
+    field: EntityId,
+    index: usize,
+    label: SharedString,
+}
+
+impl Render for DraggedToken {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .opacity(0.85)
+            .child(Badge::new(self.label.clone()).selected(true))
+    }
+}
+
+/// Internal state for a TokenField rendered as a view.
+pub struct TokenFieldState {
+    id: ElementId,
+    combo: Entity<ComboBoxState>,
+    tokens: Vec<String>,
+    tokens_binding: Option<Binding<Vec<String>>>,
+    separators: Vec<char>,
+    allow_duplicates: bool,
+    on_change: Option<ChangeHandler>,
+    selected: Option<usize>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl TokenFieldState {
+    /// Creates the token field view from a builder.
+    pub fn new(builder: TokenField, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let this = cx.weak_entity();
+        let combo_builder = builder.combo.on_commit(move |value, window, cx| {
+            // The combo box is still being updated here, so add the token afterwards
+            let value = value.to_string();
+            let this = this.clone();
+            window.defer(cx, move |window, cx| {
+                this.update(cx, |this, cx| this.commit_input(&value, window, cx))
+                    .ok();
+            });
+        });
+        let combo = cx.new(|cx| ComboBoxState::new(combo_builder, window, cx));
+        let text_field = combo.read(cx).text_field().clone();
+
+        let subscriptions = vec![
+            cx.observe_in(&text_field, window, |this, field, window, cx| {
+                let text = field.read(cx).value().to_string();
+                if !text.is_empty() && this.selected.take().is_some() {
+                    cx.notify();
+                }
+                if let Some((parts, rest)) = split_separated(&text, &this.separators) {
+                    this.combo
+                        .update(cx, |combo, cx| combo.replace_text(rest, cx));
+                    for part in parts {
+                        this.push_token(part, window, cx);
+                    }
+                }
+            }),
+        ];
+
+        let tokens = match builder.tokens_binding {
+            Some(ref binding) => binding.get(cx),
+            None => builder.tokens,
+        };
+
+        Self {
+            id: builder.id,
+            combo,
+            tokens,
+            tokens_binding: builder.tokens_binding,
+            separators: builder.separators,
+            allow_duplicates: builder.allow_duplicates,
+            on_change: builder.on_change,
+            selected: None,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    /// Returns the current tokens.
+    pub fn tokens(&self) -> &[String] {
+        &self.tokens
+    }
+
+    /// Replaces all tokens.
+    pub fn set_tokens(&mut self, tokens: Vec<String>, cx: &mut Context<Self>) {
+        self.tokens = tokens;
+        self.selected = None;
+        if let Some(ref binding) = self.tokens_binding {
+            binding.set_if_changed(self.tokens.clone(), cx);
+        }
+        cx.notify();
+    }
+
+    /// Returns the index of the selected token.
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Focuses the text input.
+    pub fn focus(&self, window: &mut Window, cx: &App) {
+        self.combo.read(cx).focus(window, cx);
+    }
+
+    /// Adds the committed text as a token and clears the input.
+    fn commit_input(&mut self, value: &str, window: &mut Window, cx: &mut Context<Self>) {
+        self.combo.update(cx, |combo, cx| combo.clear(cx));
+        self.push_token(value.to_string(), window, cx);
+    }
+
+    /// Adds a token unless it is blank or a disallowed duplicate.
+    fn push_token(&mut self, token: String, window: &mut Window, cx: &mut Context<Self>) {
+        let token = token.trim();
+        if token.is_empty() || (!self.allow_duplicates && self.tokens.iter().any(|t| t == token)) {
+            return;
+        }
+        self.tokens.push(token.to_string());
+        self.tokens_changed(window, cx);
+    }
+
+    fn remove_token(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if index >= self.tokens.len() {
+            return;
+        }
+        self.tokens.remove(index);
+        self.selected = None;
+        self.tokens_changed(window, cx);
+    }
+
+    fn move_token(&mut self, from: usize, to: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if move_item(&mut self.tokens, from, to) {
+            self.selected = Some(to);
+            self.tokens_changed(window, cx);
+        }
+    }
+
+    fn tokens_changed(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ref binding) = self.tokens_binding {
+            binding.set_if_changed(self.tokens.clone(), cx);
+        }
+        if let Some(ref handler) = self.on_change {
+            handler(&self.tokens, window, cx);
+        }
+        cx.notify();
+    }
+
+    fn handle_key_down(
+        &mut self,
+        event: &KeyDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        // Token navigation only applies while the text input is empty
+        if !self.combo.read(cx).value(cx).is_empty() || self.tokens.is_empty() {
+            return;
+        }
+
+        let last = self.tokens.len() - 1;
+        match event.keystroke.key.as_str() {
+            "backspace" => {
+                // The first press selects the last token, the second removes it
+                match self.selected {
+                    Some(index) => self.remove_token(index, window, cx),
+                    None => self.selected = Some(last),
+                }
+                cx.stop_propagation();
+                cx.notify();
+            }
+            "delete" => {
+                if let Some(index) = self.selected {
+                    self.remove_token(index, window, cx);
+                    cx.stop_propagation();
+                }
+            }
+            "left" => {
+                self.selected = Some(self.selected.map_or(last, |i| i.saturating_sub(1)));
+                cx.stop_propagation();
+                cx.notify();
+            }
+            "right" if self.selected.is_some() => {
+                self.selected = self.selected.map(|i| i + 1).filter(|&i| i <= last);
+                cx.stop_propagation();
+                cx.notify();
+            }
+            "escape" if self.selected.is_some() => {
+                self.selected = None;
+                cx.stop_propagation();
+                cx.notify();
+            }
+            _ => {}
+        }
+    }
+
+    fn build_token(&self, index: usize, label: &str, cx: &mut Context<Self>) -> Stateful<Div> {
+        let entity = cx.entity();
+        let field = entity.entity_id();
+        let label: SharedString = label.to_string().into();
+        let drag = DraggedToken {
+            field,
+            index,
+            label: label.clone(),
+        };
+
+        div()
+            .id(("token", index))
+            .border_l_2()
+            .border_color(gpui::transparent_black())
+            .cursor_pointer()
+            .child(
+                Badge::new(label)
+                    .selected(self.selected == Some(index))
+                    .removable(move |window, cx| {
+                        entity.update(cx, |this, cx| this.remove_token(index, window, cx));
+                    }),
+            )
+            .on_click(cx.listener(move |this, _event, window, cx| {
+                this.selected = Some(index);
+                this.focus(window, cx);
+                cx.notify();
+            }))
+            .on_drag(drag, |drag, _offset, _window, cx| cx.new(|_| drag.clone()))
+            .drag_over::<DraggedToken>(move |style, drag, _window, _cx| {
+                if drag.field == field {
+                    style.border_color(hsla(211.0 / 360.0, 0.95, 0.53, 1.0))
+                } else {
+                    style
+                }
+            })
+            .on_drop(cx.listener(move |this, drag: &DraggedToken, window, cx| {
+                if drag.field == field {
+                    this.move_token(drag.index, index, window, cx);
+                }
+            }))
+    }
+}
+
+impl Focusable for TokenFieldState {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.combo.focus_handle(cx)
+    }
+}
+
+impl Render for TokenFieldState {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // Sync tokens from binding
+        if let Some(ref binding) = self.tokens_binding {
+            let bound = binding.get(cx);
+            if bound != self.tokens {
+                self.tokens = bound;
+                self.selected = self.selected.filter(|&i| i < self.tokens.len());
+            }
+        }
+
+        let mut chips = div()
+            .flex()
+            .flex_row()
+            .flex_wrap()
+            .items_center()
+            .gap(px(4.0));
+        for (index, token) in self.tokens.iter().enumerate() {
+            chips = chips.child(self.build_token(index, token, cx));
+        }
+
+        div()
+            .id(self.id.clone())
+            .flex()
+            .flex_col()
+            .gap(px(6.0))
+            .capture_key_down(cx.listener(Self::handle_key_down))
+            .when(!self.tokens.is_empty(), |this| this.child(chips))
+            .child(self.combo.clone())
+    }
+}
+
+/// Splits `text` at separator characters.
+///
+/// Returns the completed parts, trimmed and without blanks, and the text after
+/// the last separator, or `None` if `text` contains no separator.
+fn split_separated(text: &str, separators: &[char]) -> Option<(Vec<String>, String)> {
+    let last = text.rfind(|c| separators.contains(&c))?;
+    let (done, rest) = text.split_at(last);
+    let rest = &rest[rest.chars().next().map_or(0, char::len_utf8)..];
+
+    let parts = done
+        .split(|c| separators.contains(&c))
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    Some((parts, rest.to_string()))
+}
+
+/// Moves the item at `from` to `to`. Returns false if nothing changed.
+fn move_item<T>(items: &mut Vec<T>, from: usize, to: usize) -> bool {
+    if from == to || from >= items.len() || to >= items.len() {
+        return false;
+    }
+    let item = items.remove(from);
+    items.insert(to, item);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_separated() {
+        assert_eq!(split_separated("rust", &[',']), None);
+        assert_eq!(
+            split_separated("rust, gpui,ui", &[',']),
+            Some((
+                vec!["rust".to_string(), "gpui".to_string()],
+                "ui".to_string()
+            ))
+        );
+        assert_eq!(
+            split_separated("a;;b;", &[',', ';']),
+            Some((vec!["a".to_string(), "b".to_string()], String::new()))
+        );
+    }
+
+    #[test]
+    fn test_move_item() {
+        let mut items = vec!["a", "b", "c"];
+        assert!(move_item(&mut items, 0, 2));
+        assert_eq!(items, vec!["b", "c", "a"]);
+        assert!(move_item(&mut items, 2, 0));
+        assert_eq!(items, vec!["a", "b", "c"]);
+        assert!(!move_item(&mut items, 1, 1));
+        assert!(!move_item(&mut items, 3, 0));
+    }
+}
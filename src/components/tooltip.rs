@@ -1,12 +1,34 @@
 //! Tooltip component for GPUI.
 //!
-//! A hover hint that appears on hover near the wrapped element.
+//! A hover hint that appears near the wrapped element after a short delay.
 //! Uses dark background with light text for visibility.
+//!
+//! Tooltips share a warm-up window: once one tooltip has been shown, moving
+//! to another trigger shortly afterwards shows its tooltip immediately. The
+//! tooltip flips to the opposite side or shifts along its edge when it would
+//! otherwise leave the window.
+
+use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 use gpui::prelude::*;
 use gpui::*;
 
+use super::anchored::{Align, AnchoredOverlay, Placement, Side};
+
+/// Delay before a tooltip appears when no other tooltip was shown recently.
+const DEFAULT_DELAY: Duration = Duration::from_millis(500);
+
+/// How long after a tooltip hides the next one shows without delay.
+const WARMUP_WINDOW: Duration = Duration::from_millis(500);
+
+/// Distance between the trigger element and the tooltip.
+const TOOLTIP_GAP: f32 = 4.0;
+
 /// Position where the tooltip appears relative to the wrapped element.
+///
+/// This is the preferred side; the tooltip flips to the opposite side when
+/// there is not enough room in the window.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum TooltipPosition {
     /// Tooltip appears above the element (default).
@@ -20,8 +42,97 @@ pub enum TooltipPosition {
     Right,
 }
 
+impl TooltipPosition {
+    fn side(self) -> Side {
+        match self {
+            TooltipPosition::Top => Side::Top,
+            TooltipPosition::Bottom => Side::Bottom,
+            TooltipPosition::Left => Side::Left,
+            TooltipPosition::Right => Side::Right,
+        }
+    }
+}
+
+/// Builder for rich tooltip content.
+type ContentBuilder = Rc<dyn Fn(&mut Window, &mut App) -> AnyElement>;
+
+/// The content displayed inside a tooltip.
+///
+/// Plain strings convert into text content. Use [`TooltipContent::element`]
+/// for rich content such as a title with a shortcut hint.
+#[derive(Clone)]
+pub enum TooltipContent {
+    /// A single line of text.
+    Text(SharedString),
+    /// An element built each time the tooltip is shown.
+    Element(ContentBuilder),
+}
+
+impl TooltipContent {
+    /// Creates rich tooltip content from an element builder.
+    ///
+    /// The builder only runs while the tooltip is about to be shown.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// TooltipContent::element(|_window, _cx| {
+    ///     div()
+    ///         .flex()
+    ///         .gap(px(8.0))
+    ///         .child("Save")
+    ///         .child(div().text_color(hsla(0.0, 0.0, 0.65, 1.0)).child("Ctrl+S"))
+    /// })
+    /// ```
+    pub fn element<E: IntoElement>(build: impl Fn(&mut Window, &mut App) -> E + 'static) -> Self {
+        TooltipContent::Element(Rc::new(move |window, cx| {
+            build(window, cx).into_any_element()
+        }))
+    }
+
+    /// Returns the text of plain text content.
+    pub(crate) fn text(&self) -> Option<&SharedString> {
+        match self {
+            TooltipContent::Text(text) => Some(text),
+            TooltipContent::Element(_) => None,
+        }
+    }
+
+    fn render(&self, window: &mut Window, cx: &mut App) -> AnyElement {
+        match self {
+            TooltipContent::Text(text) => div()
+                .whitespace_nowrap()
+                .child(text.clone())
+                .into_any_element(),
+            TooltipContent::Element(build) => build(window, cx),
+        }
+    }
+}
+
+impl From<&'static str> for TooltipContent {
+    fn from(text: &'static str) -> Self {
+        TooltipContent::Text(text.into())
+    }
+}
+
+impl From<String> for TooltipContent {
+    fn from(text: String) -> Self {
+        TooltipContent::Text(text.into())
+    }
+}
+
+impl From<SharedString> for TooltipContent {
+    fn from(text: SharedString) -> Self {
+        TooltipContent::Text(text)
+    }
+}
+
 /// A tooltip component that wraps content and shows a hint on hover.
 ///
+/// The hint appears after a delay while the pointer rests on the element, or
+/// while the element holds keyboard focus when a focus handle is given.
+/// Clicking the element hides it until the pointer leaves.
+///
 /// # Example
 ///
 /// ```ignore
@@ -32,12 +143,17 @@ pub enum TooltipPosition {
 /// ```ignore
 /// Tooltip::new("tooltip-id", my_element, "Tooltip text")
 ///     .position(TooltipPosition::Bottom)
+///     .focus_handle(self.focus_handle.clone())
 /// ```
+#[derive(IntoElement)]
 pub struct Tooltip {
     id: ElementId,
     child: AnyElement,
-    text: SharedString,
+    content: TooltipContent,
     position: TooltipPosition,
+    delay: Duration,
+    focus_handle: Option<FocusHandle>,
+    deferred: bool,
 }
 
 impl Tooltip {
@@ -46,21 +162,24 @@ impl Tooltip {
     /// # Arguments
     /// * `id` - Unique identifier for this tooltip
     /// * `child` - The element to wrap (tooltip trigger)
-    /// * `text` - The tooltip text to display
+    /// * `content` - The tooltip text, or rich [`TooltipContent`]
     pub fn new(
         id: impl Into<ElementId>,
         child: impl IntoElement,
-        text: impl Into<SharedString>,
+        content: impl Into<TooltipContent>,
     ) -> Self {
         Self {
             id: id.into(),
             child: child.into_any_element(),
-            text: text.into(),
+            content: content.into(),
             position: TooltipPosition::default(),
+            delay: DEFAULT_DELAY,
+            focus_handle: None,
+            deferred: true,
         }
     }
 
-    /// Sets the tooltip position relative to the wrapped element.
+    /// Sets the preferred tooltip position relative to the wrapped element.
     pub fn position(mut self, position: TooltipPosition) -> Self {
         self.position = position;
         self
@@ -89,20 +208,73 @@ impl Tooltip {
         self.position = TooltipPosition::Right;
         self
     }
+
+    /// Sets how long the pointer must rest on the element before the tooltip
+    /// appears (default: 500ms).
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Also shows the tooltip while the given focus handle is focused.
+    pub fn focus_handle(mut self, focus_handle: FocusHandle) -> Self {
+        self.focus_handle = Some(focus_handle);
+        self
+    }
+
+    /// Renders the tooltip in place instead of deferring it.
+    ///
+    /// Used by components that are already drawn in a deferred overlay,
+    /// such as menu panels, where nested deferred drawing is not allowed.
+    pub(crate) fn in_overlay(mut self) -> Self {
+        self.deferred = false;
+        self
+    }
 }
 
-impl IntoElement for Tooltip {
-    type Element = Stateful<Div>;
+impl RenderOnce for Tooltip {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state = window.use_keyed_state(self.id.clone(), cx, |_, _| TooltipState::new());
+        let delay = self.delay;
+        let focus_handle = self.focus_handle.clone();
+        state.update(cx, |state, cx| {
+            state.delay = delay;
+            if let Some(handle) = focus_handle {
+                state.follow_focus(handle, window, cx);
+            }
+        });
+
+        let visible = state.read(cx).visible;
+        let hover_state = state.clone();
+        let click_state = state;
+        let container = div()
+            .id(self.id)
+            .relative()
+            .child(self.child)
+            .on_hover(move |hovered, _window, cx| {
+                hover_state.update(cx, |state, cx| {
+                    if *hovered {
+                        state.on_mouse_enter(cx);
+                    } else {
+                        state.on_mouse_leave(cx);
+                    }
+                });
+            })
+            .on_any_mouse_down(move |_event, _window, cx| {
+                click_state.update(cx, |state, cx| state.dismiss(cx));
+            });
+
+        if !visible {
+            return container;
+        }
 
-    fn into_element(self) -> Self::Element {
         // Colors (dark background, light text)
         let tooltip_bg = hsla(0.0, 0.0, 0.15, 0.95);
         let tooltip_text_color = hsla(0.0, 0.0, 0.95, 1.0);
         let tooltip_border = hsla(0.0, 0.0, 0.25, 1.0);
 
-        // Build the tooltip panel with position-specific offsets
         let tooltip_panel = div()
-            .absolute()
+            .debug_selector(|| "tooltip".into())
             .px(px(8.0))
             .py(px(4.0))
             .bg(tooltip_bg)
@@ -117,80 +289,151 @@ impl IntoElement for Tooltip {
                 blur_radius: px(6.0),
                 spread_radius: px(0.0),
             }])
-            .whitespace_nowrap()
-            .child(self.text.clone());
-
-        // Position tooltip based on the chosen position
-        // Offsets are relative to the trigger element
-        let positioned_tooltip = match self.position {
-            TooltipPosition::Top => tooltip_panel.bottom_full().left(px(0.0)).mb(px(4.0)),
-            TooltipPosition::Bottom => tooltip_panel.top_full().left(px(0.0)).mt(px(4.0)),
-            TooltipPosition::Left => tooltip_panel.right_full().top(px(0.0)).mr(px(4.0)),
-            TooltipPosition::Right => tooltip_panel.left_full().top(px(0.0)).ml(px(4.0)),
-        };
-
-        // The tooltip container with hover logic using group hover pattern
-        div()
-            .id(self.id)
-            .relative()
-            .group("")
-            .child(self.child)
-            .child(
-                // Tooltip that appears on group hover
-                div()
-                    .invisible()
-                    .group_hover("", |style| style.visible())
-                    .child(positioned_tooltip),
-            )
+            .child(self.content.render(window, cx));
+
+        let positioned_tooltip = AnchoredOverlay::new(tooltip_panel)
+            .placement(Placement::new(self.position.side(), Align::Center))
+            .offset(px(TOOLTIP_GAP));
+
+        if self.deferred {
+            container.child(deferred(positioned_tooltip).with_priority(1001))
+        } else {
+            container.child(positioned_tooltip)
+        }
     }
 }
 
-/// A stateful tooltip wrapper for elements that need hover delay tracking.
-///
-/// This is a more advanced version that tracks hover state and shows the tooltip
-/// after a delay. It requires being used within a view that can handle state.
+/// Shared warm-up state so moving between tooltips skips the show delay.
+#[derive(Default)]
+struct TooltipWarmup {
+    last_hidden: Option<Instant>,
+}
+
+impl Global for TooltipWarmup {}
+
+fn is_warm(last_hidden: Option<Instant>, now: Instant) -> bool {
+    last_hidden.is_some_and(|hidden| now.duration_since(hidden) < WARMUP_WINDOW)
+}
+
+/// Hover and focus tracking for a tooltip.
 ///
-/// For simple use cases, prefer the stateless `Tooltip` component.
+/// `Tooltip` keeps one of these per trigger element. It can also drive a
+/// custom tooltip: create it with `cx.new(|_| TooltipState::new())`, forward
+/// pointer enter/leave events, and render the hint while
+/// [`is_visible`](Self::is_visible) returns true. The show delay runs on a
+/// timer and notifies observers when the tooltip appears.
 pub struct TooltipState {
-    /// Whether the tooltip is currently visible
-    pub visible: bool,
-    /// Timer ID for tracking hover delay
-    hover_start: Option<std::time::Instant>,
+    visible: bool,
+    hovered: bool,
+    focused: bool,
+    dismissed: bool,
+    delay: Duration,
+    show_task: Option<Task<()>>,
+    focus_handle: Option<FocusHandle>,
+    _focus_subscriptions: Vec<Subscription>,
 }
 
 impl TooltipState {
-    /// Creates a new tooltip state.
+    /// Creates a new tooltip state with the default show delay.
     pub fn new() -> Self {
         Self {
             visible: false,
-            hover_start: None,
+            hovered: false,
+            focused: false,
+            dismissed: false,
+            delay: DEFAULT_DELAY,
+            show_task: None,
+            focus_handle: None,
+            _focus_subscriptions: Vec::new(),
         }
     }
 
-    /// Call this when mouse enters the trigger element.
-    pub fn on_mouse_enter(&mut self) {
-        self.hover_start = Some(std::time::Instant::now());
+    /// Returns whether the tooltip is currently visible.
+    pub fn is_visible(&self) -> bool {
+        self.visible
     }
 
-    /// Call this when mouse leaves the trigger element.
-    pub fn on_mouse_leave(&mut self) {
-        self.visible = false;
-        self.hover_start = None;
+    /// Sets the delay before the tooltip appears.
+    pub fn set_delay(&mut self, delay: Duration) {
+        self.delay = delay;
     }
 
-    /// Check if tooltip should be shown based on hover duration.
-    /// Call this on each render to update visibility.
-    ///
-    /// Returns true if visibility changed.
-    pub fn update(&mut self, delay_ms: u64) -> bool {
-        if let Some(start) = self.hover_start {
-            let elapsed = start.elapsed().as_millis() as u64;
-            if elapsed >= delay_ms && !self.visible {
-                self.visible = true;
-                return true;
-            }
+    /// Call this when the pointer enters the trigger element.
+    pub fn on_mouse_enter(&mut self, cx: &mut Context<Self>) {
+        self.hovered = true;
+        self.schedule_show(cx);
+    }
+
+    /// Call this when the pointer leaves the trigger element.
+    pub fn on_mouse_leave(&mut self, cx: &mut Context<Self>) {
+        self.hovered = false;
+        self.release(cx);
+    }
+
+    /// Shows the tooltip immediately.
+    pub fn show(&mut self, cx: &mut Context<Self>) {
+        self.show_task = None;
+        if !self.visible {
+            self.visible = true;
+            cx.notify();
+        }
+    }
+
+    /// Hides the tooltip and cancels a pending show.
+    pub fn hide(&mut self, cx: &mut Context<Self>) {
+        self.show_task = None;
+        if self.visible {
+            self.visible = false;
+            cx.default_global::<TooltipWarmup>().last_hidden = Some(Instant::now());
+            cx.notify();
+        }
+    }
+
+    /// Hides the tooltip until the pointer leaves and focus moves away.
+    pub fn dismiss(&mut self, cx: &mut Context<Self>) {
+        self.dismissed = true;
+        self.hide(cx);
+    }
+
+    fn schedule_show(&mut self, cx: &mut Context<Self>) {
+        if self.visible || self.dismissed || self.show_task.is_some() {
+            return;
+        }
+        let last_hidden = cx.try_global::<TooltipWarmup>().and_then(|w| w.last_hidden);
+        if is_warm(last_hidden, Instant::now()) {
+            self.show(cx);
+            return;
+        }
+
+        let delay = self.delay;
+        self.show_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(delay).await;
+            this.update(cx, |this, cx| this.show(cx)).ok();
+        }));
+    }
+
+    fn release(&mut self, cx: &mut Context<Self>) {
+        if !self.hovered && !self.focused {
+            self.dismissed = false;
+            self.hide(cx);
         }
-        false
+    }
+
+    fn follow_focus(&mut self, handle: FocusHandle, window: &mut Window, cx: &mut Context<Self>) {
+        if self.focus_handle.as_ref() == Some(&handle) {
+            return;
+        }
+        self._focus_subscriptions = vec![
+            cx.on_focus(&handle, window, |this, _window, cx| {
+                this.focused = true;
+                this.schedule_show(cx);
+            }),
+            cx.on_blur(&handle, window, |this, _window, cx| {
+                this.focused = false;
+                this.release(cx);
+            }),
+        ];
+        self.focus_handle = Some(handle);
     }
 }
 
@@ -221,15 +464,18 @@ mod tests {
     }
 
     #[test]
-    fn test_tooltip_state() {
-        let mut state = TooltipState::new();
-        assert!(!state.visible);
-
-        state.on_mouse_enter();
-        assert!(state.hover_start.is_some());
+    fn test_tooltip_warmup() {
+        let now = Instant::now();
+        assert!(!is_warm(None, now));
+        assert!(is_warm(Some(now), now + Duration::from_millis(100)));
+        assert!(!is_warm(Some(now), now + WARMUP_WINDOW));
+    }
 
-        state.on_mouse_leave();
-        assert!(!state.visible);
-        assert!(state.hover_start.is_none());
+    #[test]
+    fn test_tooltip_side() {
+        assert_eq!(TooltipPosition::Top.side(), Side::Top);
+        assert_eq!(TooltipPosition::Bottom.side(), Side::Bottom);
+        assert_eq!(TooltipPosition::Left.side(), Side::Left);
+        assert_eq!(TooltipPosition::Right.side(), Side::Right);
     }
 }
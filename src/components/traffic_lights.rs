@@ -9,6 +9,7 @@ use gpui::*;
 /// Traffic light window control buttons (close, minimize, maximize).
 ///
 /// The three colored circular buttons that appear in the top-left of windows.
+/// They turn gray while the window is inactive.
 ///
 /// # Example
 ///
@@ -17,6 +18,7 @@ use gpui::*;
 ///     .on_close(cx.listener(|_, _, _, cx| cx.quit()))
 /// ```
 pub struct TrafficLights {
+    active: bool,
     on_close: Option<Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>>,
     on_minimize: Option<Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>>,
     on_maximize: Option<Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>>,
@@ -26,12 +28,21 @@ impl TrafficLights {
     /// Creates new traffic light buttons.
     pub fn new() -> Self {
         Self {
+            active: true,
             on_close: None,
             on_minimize: None,
             on_maximize: None,
         }
     }
 
+    /// Sets whether the window is active.
+    ///
+    /// Inactive windows show gray buttons. Defaults to `true`.
+    pub fn active(mut self, active: bool) -> Self {
+        self.active = active;
+        self
+    }
+
     /// Sets the handler for the close button (red).
     pub fn on_close(
         mut self,
@@ -163,6 +174,28 @@ impl IntoElement for TrafficLights {
     type Element = Div;
 
     fn into_element(self) -> Self::Element {
+        let white = hsla(0.0, 0.0, 1.0, 1.0);
+        // Inactive windows show all three buttons in the same light gray
+        let (close, minimize, maximize) = if self.active {
+            (
+                (
+                    hsla(4.0 / 360.0, 0.70, 0.52, 1.0),  // Base red
+                    hsla(4.0 / 360.0, 0.80, 0.35, 1.0),  // Dark red (shadow/border)
+                ),
+                (
+                    hsla(42.0 / 360.0, 0.85, 0.55, 1.0), // Base yellow/amber
+                    hsla(35.0 / 360.0, 0.80, 0.35, 1.0), // Dark amber (shadow/border)
+                ),
+                (
+                    hsla(120.0 / 360.0, 0.55, 0.45, 1.0), // Base green
+                    hsla(120.0 / 360.0, 0.65, 0.28, 1.0), // Dark green (shadow/border)
+                ),
+            )
+        } else {
+            let gray = (hsla(0.0, 0.0, 0.82, 1.0), hsla(0.0, 0.0, 0.62, 1.0));
+            (gray, gray, gray)
+        };
+
         div()
             .flex()
             .flex_row()
@@ -170,25 +203,25 @@ impl IntoElement for TrafficLights {
             .items_center()
             // Close button (red)
             .child(Self::button(
-                hsla(4.0 / 360.0, 0.70, 0.52, 1.0),  // Base red
-                hsla(4.0 / 360.0, 0.80, 0.35, 1.0),  // Dark red (shadow/border)
-                hsla(0.0, 0.0, 1.0, 1.0),            // White (highlight)
+                close.0,
+                close.1,
+                white,
                 "traffic-light-close",
                 self.on_close,
             ))
             // Minimize button (yellow)
             .child(Self::button(
-                hsla(42.0 / 360.0, 0.85, 0.55, 1.0), // Base yellow/amber
-                hsla(35.0 / 360.0, 0.80, 0.35, 1.0), // Dark amber (shadow/border)
-                hsla(0.0, 0.0, 1.0, 1.0),            // White (highlight)
+                minimize.0,
+                minimize.1,
+                white,
                 "traffic-light-minimize",
                 self.on_minimize,
             ))
             // Maximize button (green)
             .child(Self::button(
-                hsla(120.0 / 360.0, 0.55, 0.45, 1.0), // Base green
-                hsla(120.0 / 360.0, 0.65, 0.28, 1.0), // Dark green (shadow/border)
-                hsla(0.0, 0.0, 1.0, 1.0),             // White (highlight)
+                maximize.0,
+                maximize.1,
+                white,
                 "traffic-light-maximize",
                 self.on_maximize,
             ))
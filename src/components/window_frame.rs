@@ -1,7 +1,8 @@
 //! Window frame component with resize handles.
 //!
 //! Provides a container that adds invisible resize handles around the window edges
-//! for client-side decorated windows.
+//! for client-side decorated windows, and [`WindowChrome`], a snapshot of the
+//! window's decoration state used by [`WindowFrame`] and [`TitleBar`](super::TitleBar).
 
 use gpui::prelude::*;
 use gpui::*;
@@ -9,17 +10,119 @@ use gpui::*;
 /// The width of the resize handle hit area in pixels.
 const RESIZE_HANDLE_SIZE: f32 = 6.0;
 
+/// The decoration state of a window: focus, maximized and tiled edges.
+///
+/// Take a snapshot with [`WindowChrome::from_window`] while rendering and pass
+/// it to [`WindowFrame::chrome`] and [`TitleBar::chrome`](super::TitleBar::chrome)
+/// so client-side decorations match the compositor's state.
+///
+/// # Example
+///
+/// ```ignore
+/// let chrome = WindowChrome::from_window(window);
+///
+/// WindowFrame::new()
+///     .chrome(chrome)
+///     .child(TitleBar::new("Notes").chrome(chrome))
+///     .child(content)
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WindowChrome {
+    /// Whether the window has keyboard focus.
+    pub active: bool,
+    /// Whether the window is maximized.
+    pub maximized: bool,
+    /// Whether the window is fullscreen.
+    pub fullscreen: bool,
+    /// Whether the application draws the window decorations.
+    pub client_side: bool,
+    /// The edges the compositor has tiled against other windows or the screen.
+    pub tiling: Tiling,
+}
+
+impl WindowChrome {
+    /// Reads the decoration state of a window.
+    pub fn from_window(window: &Window) -> Self {
+        let (client_side, tiling) = match window.window_decorations() {
+            Decorations::Server => (false, Tiling::default()),
+            Decorations::Client { tiling } => (true, tiling),
+        };
+        Self {
+            active: window.is_window_active(),
+            maximized: window.is_maximized(),
+            fullscreen: window.is_fullscreen(),
+            client_side,
+            tiling,
+        }
+    }
+
+    /// Returns the edges that touch another window or the screen.
+    ///
+    /// Maximized and fullscreen windows count as tiled on every edge.
+    pub fn tiled_edges(&self) -> Tiling {
+        if self.maximized || self.fullscreen {
+            Tiling::tiled()
+        } else {
+            self.tiling
+        }
+    }
+
+    /// Returns whether any edge of the window is tiled.
+    pub fn is_tiled(&self) -> bool {
+        self.tiled_edges().is_tiled()
+    }
+
+    /// Returns whether the given edge or corner can be dragged to resize the window.
+    ///
+    /// Only client-side decorated windows have resize handles, and tiled
+    /// edges stay fixed. A corner is resizable when both of its edges are.
+    pub fn can_resize(&self, edge: ResizeEdge) -> bool {
+        if !self.client_side {
+            return false;
+        }
+        let tiled = self.tiled_edges();
+        match edge {
+            ResizeEdge::Top => !tiled.top,
+            ResizeEdge::Bottom => !tiled.bottom,
+            ResizeEdge::Left => !tiled.left,
+            ResizeEdge::Right => !tiled.right,
+            ResizeEdge::TopLeft => !tiled.top && !tiled.left,
+            ResizeEdge::TopRight => !tiled.top && !tiled.right,
+            ResizeEdge::BottomLeft => !tiled.bottom && !tiled.left,
+            ResizeEdge::BottomRight => !tiled.bottom && !tiled.right,
+        }
+    }
+}
+
+impl Default for WindowChrome {
+    /// An active, floating window with client-side decorations.
+    fn default() -> Self {
+        Self {
+            active: true,
+            maximized: false,
+            fullscreen: false,
+            client_side: true,
+            tiling: Tiling::default(),
+        }
+    }
+}
+
 /// A window frame that provides resize handles for client-side decorated windows.
 ///
 /// Wraps content with invisible resize handles at all edges and corners.
+/// Pass the window's [`WindowChrome`] to also draw a 1px border along edges
+/// that aren't tiled, drop the handles and border on tiled, maximized and
+/// server-decorated windows, and dim the border when the window is inactive.
 ///
 /// # Example
 ///
 /// ```ignore
 /// WindowFrame::new()
+///     .chrome(WindowChrome::from_window(window))
 ///     .child(your_content)
 /// ```
 pub struct WindowFrame {
+    chrome: Option<WindowChrome>,
     children: Vec<AnyElement>,
 }
 
@@ -27,10 +130,19 @@ impl WindowFrame {
     /// Creates a new window frame.
     pub fn new() -> Self {
         Self {
+            chrome: None,
             children: Vec::new(),
         }
     }
 
+    /// Sets the window's decoration state.
+    ///
+    /// Without it, the frame is borderless and has resize handles on every edge.
+    pub fn chrome(mut self, chrome: WindowChrome) -> Self {
+        self.chrome = Some(chrome);
+        self
+    }
+
     /// Adds a child element to the frame content.
     pub fn child(mut self, child: impl IntoElement) -> Self {
         self.children.push(child.into_any_element());
@@ -56,6 +168,8 @@ impl IntoElement for WindowFrame {
 
     fn into_element(self) -> Self::Element {
         let handle_size = px(RESIZE_HANDLE_SIZE);
+        let chrome = self.chrome.unwrap_or_default();
+        let tiled = chrome.tiled_edges();
 
         // Content area
         let mut content = div().flex().flex_col().size_full();
@@ -63,118 +177,213 @@ impl IntoElement for WindowFrame {
             content = content.child(child);
         }
 
-        div()
-            .relative()
-            .size_full()
+        let mut frame = div().relative().size_full();
+
+        // 1px border along floating edges, lighter when the window is inactive
+        if self.chrome.is_some() && chrome.client_side && !chrome.fullscreen {
+            let border_color = if chrome.active {
+                hsla(0.0, 0.0, 0.0, 0.35)
+            } else {
+                hsla(0.0, 0.0, 0.0, 0.2)
+            };
+            frame = frame.border_color(border_color);
+            if !tiled.top {
+                frame = frame.border_t_1();
+            }
+            if !tiled.bottom {
+                frame = frame.border_b_1();
+            }
+            if !tiled.left {
+                frame = frame.border_l_1();
+            }
+            if !tiled.right {
+                frame = frame.border_r_1();
+            }
+        }
+
+        frame
             // Main content
             .child(content)
             // Top edge
-            .child(
-                div()
-                    .id("resize-top")
-                    .absolute()
-                    .top_0()
-                    .left(handle_size)
-                    .right(handle_size)
-                    .h(handle_size)
-                    .cursor(CursorStyle::ResizeUpDown)
-                    .on_mouse_down(MouseButton::Left, |_event, window, _cx| {
-                        window.start_window_resize(ResizeEdge::Top);
-                    }),
-            )
+            .when(chrome.can_resize(ResizeEdge::Top), |this| {
+                this.child(
+                    div()
+                        .id("resize-top")
+                        .absolute()
+                        .top_0()
+                        .left(handle_size)
+                        .right(handle_size)
+                        .h(handle_size)
+                        .cursor(CursorStyle::ResizeUpDown)
+                        .on_mouse_down(MouseButton::Left, |_event, window, _cx| {
+                            window.start_window_resize(ResizeEdge::Top);
+                        }),
+                )
+            })
             // Bottom edge
-            .child(
-                div()
-                    .id("resize-bottom")
-                    .absolute()
-                    .bottom_0()
-                    .left(handle_size)
-                    .right(handle_size)
-                    .h(handle_size)
-                    .cursor(CursorStyle::ResizeUpDown)
-                    .on_mouse_down(MouseButton::Left, |_event, window, _cx| {
-                        window.start_window_resize(ResizeEdge::Bottom);
-                    }),
-            )
+            .when(chrome.can_resize(ResizeEdge::Bottom), |this| {
+                this.child(
+                    div()
+                        .id("resize-bottom")
+                        .absolute()
+                        .bottom_0()
+                        .left(handle_size)
+                        .right(handle_size)
+                        .h(handle_size)
+                        .cursor(CursorStyle::ResizeUpDown)
+                        .on_mouse_down(MouseButton::Left, |_event, window, _cx| {
+                            window.start_window_resize(ResizeEdge::Bottom);
+                        }),
+                )
+            })
             // Left edge
-            .child(
-                div()
-                    .id("resize-left")
-                    .absolute()
-                    .left_0()
-                    .top(handle_size)
-                    .bottom(handle_size)
-                    .w(handle_size)
-                    .cursor(CursorStyle::ResizeLeftRight)
-                    .on_mouse_down(MouseButton::Left, |_event, window, _cx| {
-                        window.start_window_resize(ResizeEdge::Left);
-                    }),
-            )
+            .when(chrome.can_resize(ResizeEdge::Left), |this| {
+                this.child(
+                    div()
+                        .id("resize-left")
+                        .absolute()
+                        .left_0()
+                        .top(handle_size)
+                        .bottom(handle_size)
+                        .w(handle_size)
+                        .cursor(CursorStyle::ResizeLeftRight)
+                        .on_mouse_down(MouseButton::Left, |_event, window, _cx| {
+                            window.start_window_resize(ResizeEdge::Left);
+                        }),
+                )
+            })
             // Right edge
-            .child(
-                div()
-                    .id("resize-right")
-                    .absolute()
-                    .right_0()
-                    .top(handle_size)
-                    .bottom(handle_size)
-                    .w(handle_size)
-                    .cursor(CursorStyle::ResizeLeftRight)
-                    .on_mouse_down(MouseButton::Left, |_event, window, _cx| {
-                        window.start_window_resize(ResizeEdge::Right);
-                    }),
-            )
+            .when(chrome.can_resize(ResizeEdge::Right), |this| {
+                this.child(
+                    div()
+                        .id("resize-right")
+                        .absolute()
+                        .right_0()
+                        .top(handle_size)
+                        .bottom(handle_size)
+                        .w(handle_size)
+                        .cursor(CursorStyle::ResizeLeftRight)
+                        .on_mouse_down(MouseButton::Left, |_event, window, _cx| {
+                            window.start_window_resize(ResizeEdge::Right);
+                        }),
+                )
+            })
             // Top-left corner
-            .child(
-                div()
-                    .id("resize-top-left")
-                    .absolute()
-                    .top_0()
-                    .left_0()
-                    .size(handle_size)
-                    .cursor(CursorStyle::ResizeUpLeftDownRight)
-                    .on_mouse_down(MouseButton::Left, |_event, window, _cx| {
-                        window.start_window_resize(ResizeEdge::TopLeft);
-                    }),
-            )
+            .when(chrome.can_resize(ResizeEdge::TopLeft), |this| {
+                this.child(
+                    div()
+                        .id("resize-top-left")
+                        .absolute()
+                        .top_0()
+                        .left_0()
+                        .size(handle_size)
+                        .cursor(CursorStyle::ResizeUpLeftDownRight)
+                        .on_mouse_down(MouseButton::Left, |_event, window, _cx| {
+                            window.start_window_resize(ResizeEdge::TopLeft);
+                        }),
+                )
+            })
             // Top-right corner
-            .child(
-                div()
-                    .id("resize-top-right")
-                    .absolute()
-                    .top_0()
-                    .right_0()
-                    .size(handle_size)
-                    .cursor(CursorStyle::ResizeUpRightDownLeft)
-                    .on_mouse_down(MouseButton::Left, |_event, window, _cx| {
-                        window.start_window_resize(ResizeEdge::TopRight);
-                    }),
-            )
+            .when(chrome.can_resize(ResizeEdge::TopRight), |this| {
+                this.child(
+                    div()
+                        .id("resize-top-right")
+                        .absolute()
+                        .top_0()
+                        .right_0()
+                        .size(handle_size)
+                        .cursor(CursorStyle::ResizeUpRightDownLeft)
+                        .on_mouse_down(MouseButton::Left, |_event, window, _cx| {
+                            window.start_window_resize(ResizeEdge::TopRight);
+                        }),
+                )
+            })
             // Bottom-left corner
-            .child(
-                div()
-                    .id("resize-bottom-left")
-                    .absolute()
-                    .bottom_0()
-                    .left_0()
-                    .size(handle_size)
-                    .cursor(CursorStyle::ResizeUpRightDownLeft)
-                    .on_mouse_down(MouseButton::Left, |_event, window, _cx| {
-                        window.start_window_resize(ResizeEdge::BottomLeft);
-                    }),
-            )
+            .when(chrome.can_resize(ResizeEdge::BottomLeft), |this| {
+                this.child(
+                    div()
+                        .id("resize-bottom-left")
+                        .absolute()
+                        .bottom_0()
+                        .left_0()
+                        .size(handle_size)
+                        .cursor(CursorStyle::ResizeUpRightDownLeft)
+                        .on_mouse_down(MouseButton::Left, |_event, window, _cx| {
+                            window.start_window_resize(ResizeEdge::BottomLeft);
+                        }),
+                )
+            })
             // Bottom-right corner
-            .child(
-                div()
-                    .id("resize-bottom-right")
-                    .absolute()
-                    .bottom_0()
-                    .right_0()
-                    .size(handle_size)
-                    .cursor(CursorStyle::ResizeUpLeftDownRight)
-                    .on_mouse_down(MouseButton::Left, |_event, window, _cx| {
-                        window.start_window_resize(ResizeEdge::BottomRight);
-                    }),
-            )
+            .when(chrome.can_resize(ResizeEdge::BottomRight), |this| {
+                this.child(
+                    div()
+                        .id("resize-bottom-right")
+                        .absolute()
+                        .bottom_0()
+                        .right_0()
+                        .size(handle_size)
+                        .cursor(CursorStyle::ResizeUpLeftDownRight)
+                        .on_mouse_down(MouseButton::Left, |_event, window, _cx| {
+                            window.start_window_resize(ResizeEdge::BottomRight);
+                        }),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_without_chrome_keeps_the_borderless_default() {
+        let frame = WindowFrame::new();
+        assert!(frame.chrome.is_none());
+        let frame = frame.chrome(WindowChrome::default());
+        assert_eq!(frame.chrome, Some(WindowChrome::default()));
+    }
+
+    #[test]
+    fn test_floating_window_resizes_on_every_edge() {
+        let chrome = WindowChrome::default();
+        assert!(!chrome.is_tiled());
+        assert!(chrome.can_resize(ResizeEdge::Top));
+        assert!(chrome.can_resize(ResizeEdge::BottomRight));
+    }
+
+    #[test]
+    fn test_maximized_window_has_no_resize_handles() {
+        let chrome = WindowChrome {
+            maximized: true,
+            ..WindowChrome::default()
+        };
+        assert!(chrome.is_tiled());
+        assert!(!chrome.can_resize(ResizeEdge::Left));
+        assert!(!chrome.can_resize(ResizeEdge::TopRight));
+    }
+
+    #[test]
+    fn test_tiled_edges_and_their_corners_stay_fixed() {
+        let chrome = WindowChrome {
+            tiling: Tiling {
+                left: true,
+                top: true,
+                bottom: true,
+                right: false,
+            },
+            ..WindowChrome::default()
+        };
+        assert!(chrome.can_resize(ResizeEdge::Right));
+        assert!(!chrome.can_resize(ResizeEdge::Left));
+        assert!(!chrome.can_resize(ResizeEdge::TopRight));
+    }
+
+    #[test]
+    fn test_server_decorations_have_no_resize_handles() {
+        let chrome = WindowChrome {
+            client_side: false,
+            ..WindowChrome::default()
+        };
+        assert!(!chrome.can_resize(ResizeEdge::Bottom));
     }
 }
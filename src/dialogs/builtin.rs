@@ -0,0 +1,593 @@
+//! Built-in file dialog, used when no desktop portal is available.
+
+use std::collections::BTreeSet;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use futures::channel::oneshot;
+use gpui::prelude::*;
+use gpui::*;
+
+use super::{DialogKind, FileDialogOptions, FileFilter};
+use crate::components::{
+    Button, Icon, IconButton, Label, List, ModalId, ModalPresenter, Modals, Sheet, TextField,
+    TextFieldState,
+};
+use crate::state::State;
+
+type Responder = oneshot::Sender<Option<Vec<PathBuf>>>;
+
+/// A directory entry shown in the built-in dialog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Entry {
+    pub name: String,
+    pub is_dir: bool,
+}
+
+/// What activating an entry did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Activation {
+    /// Moved into the activated folder
+    Navigated,
+    /// Chose the paths, which answers the dialog
+    Chosen(Vec<PathBuf>),
+    /// Nothing happened, e.g. the folder couldn't be read
+    Ignored,
+}
+
+/// The state of the built-in dialog, independent of its view.
+pub(crate) struct FileChooserModel {
+    kind: DialogKind,
+    filters: Vec<FileFilter>,
+    directory: PathBuf,
+    entries: Vec<Entry>,
+    selected: Option<usize>,
+    /// Entries marked for opening when several files may be chosen
+    marked: BTreeSet<usize>,
+}
+
+impl FileChooserModel {
+    /// Creates a model showing the options' folder, or the home folder.
+    pub fn new(kind: DialogKind, options: &FileDialogOptions) -> Self {
+        let mut model = Self {
+            kind,
+            filters: options.filters.clone(),
+            directory: PathBuf::new(),
+            entries: Vec::new(),
+            selected: None,
+            marked: BTreeSet::new(),
+        };
+        let start = options
+            .current_folder
+            .clone()
+            .or_else(dirs::home_dir)
+            .unwrap_or_else(|| PathBuf::from("/"));
+        if model.set_directory(start).is_err() {
+            model.set_directory(PathBuf::from("/")).ok();
+        }
+        model
+    }
+
+    /// Shows the contents of another folder. On error the current folder stays.
+    pub fn set_directory(&mut self, directory: PathBuf) -> io::Result<()> {
+        let mut entries = Vec::new();
+        for item in std::fs::read_dir(&directory)? {
+            let item = item?;
+            let name = item.file_name().to_string_lossy().into_owned();
+            if name.starts_with('.') {
+                continue;
+            }
+            // Follow symlinks so linked folders can be entered
+            let is_dir = item.path().is_dir();
+            if self.shows(&name, is_dir) {
+                entries.push(Entry { name, is_dir });
+            }
+        }
+        entries.sort_by(|a, b| {
+            b.is_dir
+                .cmp(&a.is_dir)
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+
+        self.directory = directory;
+        self.entries = entries;
+        self.selected = None;
+        self.marked.clear();
+        Ok(())
+    }
+
+    /// Moves to the parent folder.
+    pub fn go_up(&mut self) -> io::Result<()> {
+        match self.directory.parent() {
+            Some(parent) => self.set_directory(parent.to_path_buf()),
+            None => Ok(()),
+        }
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    pub fn select(&mut self, index: Option<usize>) {
+        self.selected = index.filter(|&index| index < self.entries.len());
+    }
+
+    pub fn is_marked(&self, index: usize) -> bool {
+        self.marked.contains(&index)
+    }
+
+    /// Marks or unmarks a file when several files may be chosen.
+    pub fn toggle_marked(&mut self, index: usize) {
+        let is_file = self.entries.get(index).is_some_and(|entry| !entry.is_dir);
+        if self.kind == DialogKind::OpenFiles && is_file && !self.marked.remove(&index) {
+            self.marked.insert(index);
+        }
+    }
+
+    /// Opens a folder, or chooses a file, as on double-click or Enter.
+    pub fn activate(&mut self, index: usize) -> Activation {
+        let Some(entry) = self.entries.get(index) else {
+            return Activation::Ignored;
+        };
+        let path = self.directory.join(&entry.name);
+        if entry.is_dir {
+            match self.set_directory(path) {
+                Ok(()) => Activation::Navigated,
+                Err(_) => Activation::Ignored,
+            }
+        } else {
+            Activation::Chosen(vec![path])
+        }
+    }
+
+    /// Returns the paths the accept button chooses, or `None` if it's disabled.
+    ///
+    /// `file_name` is the contents of a save dialog's name field.
+    pub fn accept(&self, file_name: &str) -> Option<Vec<PathBuf>> {
+        let selected = self.selected.and_then(|index| self.entries.get(index));
+        match self.kind {
+            DialogKind::OpenFile => selected
+                .filter(|entry| !entry.is_dir)
+                .map(|entry| vec![self.directory.join(&entry.name)]),
+            DialogKind::OpenFiles => {
+                let mut paths: Vec<_> = self
+                    .marked
+                    .iter()
+                    .map(|&index| self.directory.join(&self.entries[index].name))
+                    .collect();
+                if paths.is_empty() {
+                    if let Some(entry) = selected.filter(|entry| !entry.is_dir) {
+                        paths.push(self.directory.join(&entry.name));
+                    }
+                }
+                (!paths.is_empty()).then_some(paths)
+            }
+            DialogKind::ChooseDirectory => Some(vec![match selected {
+                Some(entry) => self.directory.join(&entry.name),
+                None => self.directory.clone(),
+            }]),
+            DialogKind::SaveFile => {
+                let name = file_name.trim();
+                (!name.is_empty() && !name.contains('/')).then(|| vec![self.directory.join(name)])
+            }
+        }
+    }
+
+    /// Returns whether an entry is listed: folders always, files if they
+    /// match a filter and files can be chosen.
+    fn shows(&self, name: &str, is_dir: bool) -> bool {
+        if is_dir {
+            return true;
+        }
+        match self.kind {
+            DialogKind::ChooseDirectory => false,
+            // Existing files are listed so their names can be reused
+            _ => self.filters.is_empty() || self.filters.iter().any(|filter| filter.matches(name)),
+        }
+    }
+}
+
+/// The view shown inside the built-in dialog's sheet.
+struct FileChooser {
+    model: FileChooserModel,
+    selection: Entity<State<Option<usize>>>,
+    name_field: Option<Entity<TextFieldState>>,
+    scroll_handle: UniformListScrollHandle,
+    focus_handle: FocusHandle,
+    responder: Option<Responder>,
+    modal: Option<ModalId>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl FileChooser {
+    fn new(
+        kind: DialogKind,
+        options: &FileDialogOptions,
+        responder: Responder,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let selection = cx.new(|_| State::new(None));
+        let mut subscriptions = vec![cx.observe(&selection, |this, selection, cx| {
+            let index = *selection.read(cx).get();
+            this.model.select(index);
+            this.fill_name(cx);
+            cx.notify();
+        })];
+
+        let name_field = (kind == DialogKind::SaveFile).then(|| {
+            let field = TextField::new("file-chooser-name", cx).label("Name").value(
+                options
+                    .suggested_name
+                    .as_ref()
+                    .map(|name| name.to_string())
+                    .unwrap_or_default(),
+            );
+            let field = cx.new(|_| TextFieldState::from(field));
+            subscriptions.push(cx.observe(&field, |_, _, cx| cx.notify()));
+            field
+        });
+
+        Self {
+            model: FileChooserModel::new(kind, options),
+            selection,
+            name_field,
+            scroll_handle: UniformListScrollHandle::new(),
+            focus_handle: cx.focus_handle(),
+            responder: Some(responder),
+            modal: None,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    fn file_name(&self, cx: &App) -> String {
+        self.name_field
+            .as_ref()
+            .map(|field| field.read(cx).value().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Copies a selected file's name into the save dialog's name field.
+    fn fill_name(&self, cx: &mut Context<Self>) {
+        let Some(ref field) = self.name_field else {
+            return;
+        };
+        let selected = self
+            .model
+            .selected()
+            .map(|index| &self.model.entries()[index]);
+        if let Some(entry) = selected.filter(|entry| !entry.is_dir) {
+            let name = entry.name.clone();
+            field.update(cx, |field, cx| field.replace_text(name, cx));
+        }
+    }
+
+    fn navigated(&mut self, cx: &mut Context<Self>) {
+        self.selection
+            .update(cx, |selection, cx| selection.set(None, cx));
+        self.scroll_handle.scroll_to_item(0, ScrollStrategy::Top);
+        cx.notify();
+    }
+
+    fn go_up(&mut self, cx: &mut Context<Self>) {
+        if self.model.go_up().is_ok() {
+            self.navigated(cx);
+        }
+    }
+
+    fn activate(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        match self.model.activate(index) {
+            Activation::Navigated => self.navigated(cx),
+            Activation::Chosen(paths) => self.finish(Some(paths), window, cx),
+            Activation::Ignored => {}
+        }
+    }
+
+    fn accept(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(paths) = self.model.accept(&self.file_name(cx)) {
+            self.finish(Some(paths), window, cx);
+        }
+    }
+
+    /// Answers the dialog without dismissing it.
+    fn respond(&mut self, paths: Option<Vec<PathBuf>>) {
+        if let Some(responder) = self.responder.take() {
+            responder.send(paths).ok();
+        }
+    }
+
+    /// Answers and dismisses the dialog.
+    fn finish(&mut self, paths: Option<Vec<PathBuf>>, window: &mut Window, cx: &mut Context<Self>) {
+        self.respond(paths);
+        if let Some(id) = self.modal.take() {
+            Modals::dismiss(id, window, cx);
+        }
+    }
+}
+
+impl Render for FileChooser {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let entries = self.model.entries().to_vec();
+        let marked: Vec<bool> = (0..entries.len())
+            .map(|index| self.model.is_marked(index))
+            .collect();
+        let multiple = self.model.kind == DialogKind::OpenFiles;
+        let view = cx.entity().downgrade();
+        let confirm_view = view.clone();
+
+        let text_color = hsla(0.0, 0.0, 0.15, 1.0);
+        let secondary_color = hsla(0.0, 0.0, 0.45, 1.0);
+        let border_color = hsla(0.0, 0.0, 0.82, 1.0);
+        let selected_bg = hsla(211.0 / 360.0, 0.9, 0.5, 1.0);
+
+        let list = List::new(
+            "file-chooser-list",
+            entries.len(),
+            move |index, selected, _window, _cx| {
+                let entry = &entries[index];
+                let icon = if entry.is_dir {
+                    Icon::Folder
+                } else {
+                    Icon::Document
+                };
+                let color = if selected { gpui::white() } else { text_color };
+                let view = view.clone();
+
+                div()
+                    .id(("file-chooser-entry", index))
+                    .flex()
+                    .items_center()
+                    .gap(px(6.0))
+                    .px(px(10.0))
+                    .py(px(4.0))
+                    .when(selected, |row| row.bg(selected_bg))
+                    .when(multiple && !entry.is_dir, |row| {
+                        let checkmark = if marked[index] { "✓" } else { "" };
+                        row.child(
+                            div()
+                                .w(px(14.0))
+                                .text_sm()
+                                .text_color(color)
+                                .child(checkmark),
+                        )
+                    })
+                    .child(
+                        Label::new(entry.name.clone(), icon)
+                            .text_color(color)
+                            .icon_color(color),
+                    )
+                    .on_click(move |event, window, cx| {
+                        view.update(cx, |this, cx| {
+                            if event.click_count() >= 2 {
+                                this.activate(index, window, cx);
+                            } else if multiple && event.modifiers().control {
+                                this.model.toggle_marked(index);
+                                cx.notify();
+                            }
+                        })
+                        .ok();
+                    })
+                    .into_any_element()
+            },
+        )
+        .selection(State::binding(&self.selection, cx))
+        .track_scroll(self.scroll_handle.clone())
+        .focusable(self.focus_handle.clone())
+        .on_confirm(move |index, window, cx| {
+            confirm_view
+                .update(cx, |this, cx| this.activate(index, window, cx))
+                .ok();
+        });
+
+        let path_bar = div()
+            .flex()
+            .items_center()
+            .gap(px(8.0))
+            .child(
+                IconButton::new("file-chooser-up", Icon::ArrowUp.as_str())
+                    .ghost()
+                    .tooltip("Enclosing Folder")
+                    .disabled(self.model.directory().parent().is_none())
+                    .on_click(cx.listener(|this, _event, _window, cx| this.go_up(cx))),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .overflow_hidden()
+                    .text_sm()
+                    .text_color(secondary_color)
+                    .child(self.model.directory().display().to_string()),
+            );
+
+        div()
+            .flex()
+            .flex_col()
+            .gap(px(10.0))
+            .when_some(self.name_field.clone(), |this, field| this.child(field))
+            .child(path_bar)
+            .child(
+                div()
+                    .flex()
+                    .h(px(280.0))
+                    .border_1()
+                    .border_color(border_color)
+                    .rounded(px(4.0))
+                    .overflow_hidden()
+                    .child(list),
+            )
+    }
+}
+
+/// Presents the built-in dialog as a sheet and returns the receiver of its answer.
+pub(crate) fn present(
+    kind: DialogKind,
+    options: FileDialogOptions,
+    window: &mut Window,
+    cx: &mut App,
+) -> oneshot::Receiver<Option<Vec<PathBuf>>> {
+    let (responder, receiver) = oneshot::channel();
+    let title = options
+        .title
+        .clone()
+        .unwrap_or_else(|| kind.default_title().into());
+    let accept_label = options
+        .accept_label
+        .clone()
+        .unwrap_or_else(|| kind.default_accept_label().into());
+    let chooser = cx.new(|cx| FileChooser::new(kind, &options, responder, cx));
+
+    let view = chooser.clone();
+    let id = window.present_sheet(cx, move |_window, cx| {
+        let chooser = view.read(cx);
+        let can_accept = chooser.model.accept(&chooser.file_name(cx)).is_some();
+        let (dismiss_view, cancel_view, accept_view) = (view.clone(), view.clone(), view.clone());
+
+        Sheet::new("file-chooser")
+            .title(title.clone())
+            .width(px(520.0))
+            .child(view.clone())
+            .actions(
+                div()
+                    .flex()
+                    .justify_end()
+                    .gap(px(8.0))
+                    .child(Button::new("file-chooser-cancel", "Cancel").on_click(
+                        move |_event, window, cx| {
+                            cancel_view.update(cx, |this, cx| this.finish(None, window, cx));
+                        },
+                    ))
+                    .child(
+                        Button::new("file-chooser-accept", accept_label.clone())
+                            .primary()
+                            .disabled(!can_accept)
+                            .on_click(move |_event, window, cx| {
+                                accept_view.update(cx, |this, cx| this.accept(window, cx));
+                            }),
+                    ),
+            )
+            .on_dismiss(move |_event, _window, cx| {
+                dismiss_view.update(cx, |this, _cx| this.respond(None));
+            })
+    });
+
+    let focus_handle = chooser.update(cx, |chooser, _cx| {
+        chooser.modal = Some(id);
+        chooser.focus_handle.clone()
+    });
+    window.focus(&focus_handle);
+    receiver
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// Creates a fresh folder with a few files and subfolders.
+    fn fixture(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("applib-dialogs-{}-{}", name, std::process::id()));
+        fs::remove_dir_all(&root).ok();
+        fs::create_dir_all(root.join("Photos")).unwrap();
+        fs::create_dir_all(root.join("archive")).unwrap();
+        fs::create_dir_all(root.join(".cache")).unwrap();
+        for file in ["notes.txt", "Beach.PNG", "cat.png", ".hidden.png"] {
+            fs::write(root.join(file), b"").unwrap();
+        }
+        root
+    }
+
+    fn names(model: &FileChooserModel) -> Vec<&str> {
+        model
+            .entries()
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_entries_sorted_and_filtered() {
+        let root = fixture("entries");
+        let options = FileDialogOptions::new().current_folder(&root);
+        let model = FileChooserModel::new(DialogKind::OpenFile, &options);
+        assert_eq!(
+            names(&model),
+            vec!["archive", "Photos", "Beach.PNG", "cat.png", "notes.txt"]
+        );
+
+        let images = options
+            .clone()
+            .filter(FileFilter::new("Images").pattern("*.png"));
+        let model = FileChooserModel::new(DialogKind::OpenFile, &images);
+        assert_eq!(
+            names(&model),
+            vec!["archive", "Photos", "Beach.PNG", "cat.png"]
+        );
+
+        let model = FileChooserModel::new(DialogKind::ChooseDirectory, &options);
+        assert_eq!(names(&model), vec!["archive", "Photos"]);
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn test_activate_and_accept() {
+        let root = fixture("activate");
+        let options = FileDialogOptions::new().current_folder(&root);
+        let mut model = FileChooserModel::new(DialogKind::OpenFile, &options);
+
+        model.select(Some(0));
+        assert_eq!(model.accept(""), None, "folders can't be opened as files");
+        assert_eq!(model.activate(0), Activation::Navigated);
+        assert_eq!(model.directory(), root.join("archive"));
+        assert!(model.entries().is_empty());
+
+        model.go_up().unwrap();
+        assert_eq!(model.directory(), root);
+        assert_eq!(
+            model.activate(3),
+            Activation::Chosen(vec![root.join("cat.png")])
+        );
+        model.select(Some(4));
+        assert_eq!(model.accept(""), Some(vec![root.join("notes.txt")]));
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn test_accept_by_kind() {
+        let root = fixture("accept");
+        let options = FileDialogOptions::new().current_folder(&root);
+
+        let mut model = FileChooserModel::new(DialogKind::OpenFiles, &options);
+        model.toggle_marked(2);
+        model.toggle_marked(3);
+        model.toggle_marked(0);
+        assert!(!model.is_marked(0), "folders can't be marked");
+        assert_eq!(
+            model.accept(""),
+            Some(vec![root.join("Beach.PNG"), root.join("cat.png")])
+        );
+        model.toggle_marked(2);
+        assert_eq!(model.accept(""), Some(vec![root.join("cat.png")]));
+
+        let mut model = FileChooserModel::new(DialogKind::ChooseDirectory, &options);
+        assert_eq!(model.accept(""), Some(vec![root.clone()]));
+        model.select(Some(1));
+        assert_eq!(model.accept(""), Some(vec![root.join("Photos")]));
+
+        let model = FileChooserModel::new(DialogKind::SaveFile, &options);
+        assert_eq!(model.accept("  "), None);
+        assert_eq!(model.accept("a/b.txt"), None);
+        assert_eq!(
+            model.accept("report.pdf"),
+            Some(vec![root.join("report.pdf")])
+        );
+        fs::remove_dir_all(root).ok();
+    }
+}
@@ -0,0 +1,214 @@
+//! In-process file chooser portal for tests.
+
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::os::unix::ffi::OsStrExt;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use zbus::message::Header;
+use zbus::object_server::SignalEmitter;
+use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
+use zbus::{interface, Connection};
+
+use crate::utils::uri_from_path;
+use super::proxy::{request_path, PATH};
+
+/// A request received by a [`MockFileChooserPortal`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceivedFileRequest {
+    /// `"OpenFile"` or `"SaveFile"`
+    pub method: String,
+    /// The dialog title
+    pub title: String,
+    /// The accept button label
+    pub accept_label: Option<String>,
+    /// Whether several files may be chosen
+    pub multiple: bool,
+    /// Whether a folder is chosen instead of files
+    pub directory: bool,
+    /// The suggested file name of a save dialog
+    pub current_name: Option<String>,
+    /// The folder the dialog starts in
+    pub current_folder: Option<PathBuf>,
+    /// Filter names with their `(kind, pattern)` entries; kind 0 is a glob, 1 a MIME type
+    pub filters: Vec<(String, Vec<(u32, String)>)>,
+}
+
+#[derive(Default)]
+struct MockState {
+    requests: Vec<ReceivedFileRequest>,
+    /// The paths the next requests answer with, or `None` to cancel them.
+    selection: Option<Vec<PathBuf>>,
+}
+
+/// A file chooser portal that answers every request with a preset selection.
+///
+/// Serve it on a private connection to test file dialog code without a
+/// desktop session. Requests are cancelled until a selection is set.
+///
+/// # Example
+///
+/// ```ignore
+/// let mock = MockFileChooserPortal::new();
+/// mock.serve(&server_connection).await?;
+/// mock.set_selection(Some(vec!["/tmp/notes.txt".into()]));
+///
+/// let portal = PortalFileChooser::with_connection(&client_connection).await?;
+/// let path = portal.open_file(&FileDialogOptions::new()).await?;
+/// assert_eq!(mock.requests()[0].method, "OpenFile");
+/// ```
+#[derive(Clone, Default)]
+pub struct MockFileChooserPortal {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockFileChooserPortal {
+    /// Creates a portal that cancels every request.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serves the FileChooser interface on `connection`.
+    pub async fn serve(&self, connection: &Connection) -> Result<()> {
+        connection
+            .object_server()
+            .at(
+                PATH,
+                MockInterface {
+                    state: self.state.clone(),
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Sets the paths later requests answer with, or `None` to cancel them.
+    pub fn set_selection(&self, selection: Option<Vec<PathBuf>>) {
+        self.state.lock().unwrap().selection = selection;
+    }
+
+    /// Returns the requests received so far.
+    pub fn requests(&self) -> Vec<ReceivedFileRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+struct MockInterface {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockInterface {
+    /// Records a request and answers it on its request object.
+    async fn handle(
+        &self,
+        method: &str,
+        title: String,
+        options: HashMap<String, OwnedValue>,
+        header: &Header<'_>,
+        connection: &Connection,
+    ) -> zbus::fdo::Result<OwnedObjectPath> {
+        let string = |key: &str| {
+            options
+                .get(key)
+                .and_then(|value| String::try_from(value.clone()).ok())
+        };
+        let flag = |key: &str| {
+            options
+                .get(key)
+                .and_then(|value| bool::try_from(value).ok())
+                .unwrap_or(false)
+        };
+        let current_folder = options
+            .get("current_folder")
+            .and_then(|value| Vec::<u8>::try_from(value.clone()).ok())
+            .map(|bytes| {
+                let bytes = bytes.strip_suffix(&[0]).unwrap_or(&bytes);
+                PathBuf::from(OsStr::from_bytes(bytes))
+            });
+        let filters = options
+            .get("filters")
+            .and_then(|value| Vec::<(String, Vec<(u32, String)>)>::try_from(value.clone()).ok())
+            .unwrap_or_default();
+        let token = string("handle_token").unwrap_or_default();
+
+        let selection = {
+            let mut state = self.state.lock().unwrap();
+            state.requests.push(ReceivedFileRequest {
+                method: method.to_string(),
+                title,
+                accept_label: string("accept_label"),
+                multiple: flag("multiple"),
+                directory: flag("directory"),
+                current_name: string("current_name"),
+                current_folder,
+                filters,
+            });
+            state.selection.clone()
+        };
+
+        let sender = header.sender().map(|sender| sender.as_str());
+        let path = request_path(sender, &token);
+
+        // The client subscribed to the request path before calling, so the
+        // response can be sent before the method returns
+        let emitter = SignalEmitter::new(connection, path.clone())?;
+        let (response, results) = match selection {
+            Some(paths) => {
+                let uris: Vec<String> = paths.iter().map(|path| uri_from_path(path)).collect();
+                let mut results = HashMap::new();
+                results.insert("uris", Value::from(uris));
+                (0, results)
+            }
+            None => (1, HashMap::new()),
+        };
+        MockRequest::response(&emitter, response, results).await?;
+        Ok(path)
+    }
+}
+
+#[interface(name = "org.freedesktop.portal.FileChooser")]
+impl MockInterface {
+    async fn open_file(
+        &self,
+        _parent_window: String,
+        title: String,
+        options: HashMap<String, OwnedValue>,
+        #[zbus(header)] header: Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+    ) -> zbus::fdo::Result<OwnedObjectPath> {
+        self.handle("OpenFile", title, options, &header, connection)
+            .await
+    }
+
+    async fn save_file(
+        &self,
+        _parent_window: String,
+        title: String,
+        options: HashMap<String, OwnedValue>,
+        #[zbus(header)] header: Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+    ) -> zbus::fdo::Result<OwnedObjectPath> {
+        self.handle("SaveFile", title, options, &header, connection)
+            .await
+    }
+
+    #[zbus(property, name = "version")]
+    fn version(&self) -> u32 {
+        4
+    }
+}
+
+/// The `org.freedesktop.portal.Request` interface, used only to emit responses.
+struct MockRequest;
+
+#[interface(name = "org.freedesktop.portal.Request")]
+impl MockRequest {
+    #[zbus(signal)]
+    async fn response(
+        emitter: &SignalEmitter<'_>,
+        response: u32,
+        results: HashMap<&str, Value<'_>>,
+    ) -> zbus::Result<()>;
+}
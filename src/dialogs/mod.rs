@@ -0,0 +1,327 @@
+//! File open and save dialogs.
+//!
+//! The dialogs go through the `org.freedesktop.portal.FileChooser` interface of
+//! the XDG desktop portal, so they look native on every desktop and work inside
+//! Flatpak and Snap sandboxes. When no portal is running, a built-in dialog is
+//! presented as a [`Sheet`](crate::Sheet) in the window instead.
+//!
+//! Each function returns a task that resolves once the user has answered the
+//! dialog; cancelling resolves to `None` or an empty list.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use applib::dialogs::{self, FileDialogOptions, FileFilter};
+//!
+//! let task = dialogs::open_file(
+//!     FileDialogOptions::new()
+//!         .title("Open Image")
+//!         .filter(FileFilter::new("Images").pattern("*.png").pattern("*.jpg"))
+//!         .filter(FileFilter::new("All Files").pattern("*")),
+//!     window,
+//!     cx,
+//! );
+//! cx.spawn(async move |this, cx| {
+//!     if let Some(path) = task.await {
+//!         this.update(cx, |this, cx| this.load(path, cx)).ok();
+//!     }
+//! })
+//! .detach();
+//! ```
+
+mod builtin;
+#[cfg(any(test, feature = "test-support"))]
+mod mock;
+mod portal;
+mod proxy;
+
+use std::path::PathBuf;
+
+use gpui::{App, AppContext as _, BorrowAppContext, Global, SharedString, Task, Window};
+
+#[cfg(any(test, feature = "test-support"))]
+pub use mock::{MockFileChooserPortal, ReceivedFileRequest};
+pub use portal::PortalFileChooser;
+
+/// Which implementation shows file dialogs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FileDialogBackend {
+    /// The desktop portal, falling back to the built-in dialog when it isn't available
+    #[default]
+    Auto,
+    /// Only the desktop portal; dialogs resolve as cancelled when it isn't available
+    Portal,
+    /// Only the built-in dialog
+    BuiltIn,
+}
+
+#[derive(Default)]
+struct FileDialogSettings {
+    backend: FileDialogBackend,
+}
+
+impl Global for FileDialogSettings {}
+
+/// Sets which implementation shows file dialogs (default: [`FileDialogBackend::Auto`]).
+pub fn set_backend(backend: FileDialogBackend, cx: &mut App) {
+    cx.update_default_global(|settings: &mut FileDialogSettings, _cx| {
+        settings.backend = backend;
+    });
+}
+
+/// A named set of file name patterns and MIME types, such as "Images".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileFilter {
+    name: SharedString,
+    patterns: Vec<SharedString>,
+    mime_types: Vec<SharedString>,
+}
+
+impl FileFilter {
+    /// Creates a filter with the given display name and no patterns.
+    pub fn new(name: impl Into<SharedString>) -> Self {
+        Self {
+            name: name.into(),
+            patterns: Vec::new(),
+            mime_types: Vec::new(),
+        }
+    }
+
+    /// Adds a glob pattern such as `"*.png"`. Patterns ignore case.
+    pub fn pattern(mut self, pattern: impl Into<SharedString>) -> Self {
+        self.patterns.push(pattern.into());
+        self
+    }
+
+    /// Adds a MIME type such as `"image/png"`.
+    ///
+    /// The built-in dialog can't detect MIME types and only matches patterns.
+    pub fn mime_type(mut self, mime_type: impl Into<SharedString>) -> Self {
+        self.mime_types.push(mime_type.into());
+        self
+    }
+
+    /// Returns the filter's display name.
+    pub fn name(&self) -> &SharedString {
+        &self.name
+    }
+
+    /// Returns whether a file name matches one of the filter's patterns.
+    ///
+    /// A filter without patterns matches every file.
+    pub fn matches(&self, file_name: &str) -> bool {
+        self.patterns.is_empty()
+            || self
+                .patterns
+                .iter()
+                .any(|pattern| glob_match(&pattern.to_lowercase(), &file_name.to_lowercase()))
+    }
+}
+
+/// Options shared by all file dialogs.
+#[derive(Debug, Clone, Default)]
+pub struct FileDialogOptions {
+    title: Option<SharedString>,
+    accept_label: Option<SharedString>,
+    filters: Vec<FileFilter>,
+    current_folder: Option<PathBuf>,
+    suggested_name: Option<SharedString>,
+}
+
+impl FileDialogOptions {
+    /// Creates options with the default title and button label.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the dialog title.
+    pub fn title(mut self, title: impl Into<SharedString>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the label of the accept button, such as "Import".
+    pub fn accept_label(mut self, label: impl Into<SharedString>) -> Self {
+        self.accept_label = Some(label.into());
+        self
+    }
+
+    /// Adds a filter. The first filter is selected when the dialog opens.
+    pub fn filter(mut self, filter: FileFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Sets the folder the dialog starts in.
+    pub fn current_folder(mut self, folder: impl Into<PathBuf>) -> Self {
+        self.current_folder = Some(folder.into());
+        self
+    }
+
+    /// Sets the file name a save dialog suggests.
+    pub fn suggested_name(mut self, name: impl Into<SharedString>) -> Self {
+        self.suggested_name = Some(name.into());
+        self
+    }
+}
+
+/// What a dialog asks the user to choose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DialogKind {
+    OpenFile,
+    OpenFiles,
+    ChooseDirectory,
+    SaveFile,
+}
+
+impl DialogKind {
+    fn default_title(&self) -> &'static str {
+        match self {
+            DialogKind::OpenFile => "Open File",
+            DialogKind::OpenFiles => "Open Files",
+            DialogKind::ChooseDirectory => "Choose Folder",
+            DialogKind::SaveFile => "Save File",
+        }
+    }
+
+    fn default_accept_label(&self) -> &'static str {
+        match self {
+            DialogKind::OpenFile | DialogKind::OpenFiles => "Open",
+            DialogKind::ChooseDirectory => "Choose",
+            DialogKind::SaveFile => "Save",
+        }
+    }
+}
+
+/// Asks the user to choose a file to open.
+pub fn open_file(
+    options: FileDialogOptions,
+    window: &mut Window,
+    cx: &mut App,
+) -> Task<Option<PathBuf>> {
+    let task = show(DialogKind::OpenFile, options, window, cx);
+    cx.spawn(async move |_| task.await.into_iter().next())
+}
+
+/// Asks the user to choose one or more files to open.
+pub fn open_files(
+    options: FileDialogOptions,
+    window: &mut Window,
+    cx: &mut App,
+) -> Task<Vec<PathBuf>> {
+    show(DialogKind::OpenFiles, options, window, cx)
+}
+
+/// Asks the user to choose a folder.
+pub fn choose_directory(
+    options: FileDialogOptions,
+    window: &mut Window,
+    cx: &mut App,
+) -> Task<Option<PathBuf>> {
+    let task = show(DialogKind::ChooseDirectory, options, window, cx);
+    cx.spawn(async move |_| task.await.into_iter().next())
+}
+
+/// Asks the user where to save a file.
+///
+/// Use [`FileDialogOptions::suggested_name`] to prefill the file name.
+pub fn save_file(
+    options: FileDialogOptions,
+    window: &mut Window,
+    cx: &mut App,
+) -> Task<Option<PathBuf>> {
+    let task = show(DialogKind::SaveFile, options, window, cx);
+    cx.spawn(async move |_| task.await.into_iter().next())
+}
+
+fn show(
+    kind: DialogKind,
+    options: FileDialogOptions,
+    window: &mut Window,
+    cx: &mut App,
+) -> Task<Vec<PathBuf>> {
+    let backend = cx
+        .try_global::<FileDialogSettings>()
+        .map(|settings| settings.backend)
+        .unwrap_or_default();
+    let window = window.window_handle();
+
+    cx.spawn(async move |cx| {
+        if backend != FileDialogBackend::BuiltIn {
+            let result = match PortalFileChooser::connect().await {
+                Ok(portal) => portal.choose(kind, &options).await,
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(paths) => return paths.unwrap_or_default(),
+                Err(_) if backend == FileDialogBackend::Portal => return Vec::new(),
+                // No portal, fall back to the built-in dialog
+                Err(_) => {}
+            }
+        }
+
+        let Ok(receiver) = cx.update_window(window, |_, window, cx| {
+            builtin::present(kind, options, window, cx)
+        }) else {
+            return Vec::new();
+        };
+        receiver.await.ok().flatten().unwrap_or_default()
+    })
+}
+
+/// Matches a file name against a glob pattern with `*` and `?` wildcards.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` and the name position it was tried at
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` swallow one more character
+                Some((star, at)) => {
+                    p = star + 1;
+                    n = at + 1;
+                    backtrack = Some((star, at + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*", "notes.txt"));
+        assert!(glob_match("*.txt", "notes.txt"));
+        assert!(glob_match("*.tar.gz", "backup.tar.gz"));
+        assert!(glob_match("img_??.png", "img_01.png"));
+        assert!(!glob_match("*.txt", "notes.md"));
+        assert!(!glob_match("img_??.png", "img_1.png"));
+        assert!(glob_match("*a*b", "xxaxxb"));
+        assert!(!glob_match("*a*b", "xxaxxbx"));
+    }
+
+    #[test]
+    fn test_filter_matches_ignoring_case() {
+        let images = FileFilter::new("Images").pattern("*.png").pattern("*.jpg");
+        assert!(images.matches("Photo.JPG"));
+        assert!(!images.matches("notes.txt"));
+        assert!(FileFilter::new("Everything").matches("notes.txt"));
+    }
+}
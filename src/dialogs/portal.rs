@@ -0,0 +1,311 @@
+//! File dialogs through the XDG desktop portal.
+
+use std::collections::HashMap;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use anyhow::{anyhow, Context as _, Result};
+use futures::StreamExt;
+use zbus::zvariant::{OwnedValue, Value};
+use zbus::Connection;
+
+use super::proxy::{request_path, FileChooserProxy, RequestProxy};
+use super::{DialogKind, FileDialogOptions, FileFilter};
+use crate::utils::path_from_uri;
+
+/// Response code of a request the user answered.
+const RESPONSE_SUCCESS: u32 = 0;
+/// Response code of a request the user cancelled.
+const RESPONSE_CANCELLED: u32 = 1;
+
+static NEXT_TOKEN: AtomicUsize = AtomicUsize::new(0);
+
+/// A connection to the desktop portal's FileChooser interface.
+///
+/// The [`open_file`](super::open_file) family of functions uses this and falls
+/// back to a built-in dialog when it fails. Use it directly to talk to a
+/// specific connection, e.g. a [`MockFileChooserPortal`](super::MockFileChooserPortal)
+/// in tests.
+///
+/// # Example
+///
+/// ```ignore
+/// let portal = PortalFileChooser::connect().await?;
+/// let paths = portal
+///     .open_files(&FileDialogOptions::new().title("Import Photos"))
+///     .await?;
+/// ```
+#[derive(Clone)]
+pub struct PortalFileChooser {
+    connection: Connection,
+    proxy: FileChooserProxy<'static>,
+}
+
+impl PortalFileChooser {
+    /// Connects to the desktop portal on the session bus.
+    pub async fn connect() -> Result<Self> {
+        let connection = Connection::session().await?;
+        Self::with_connection(&connection).await
+    }
+
+    /// Uses an existing D-Bus connection, e.g. to a private bus in tests.
+    pub async fn with_connection(connection: &Connection) -> Result<Self> {
+        Ok(Self {
+            connection: connection.clone(),
+            proxy: FileChooserProxy::new(connection).await?,
+        })
+    }
+
+    /// Asks the user to choose a file. Resolves to `None` if cancelled.
+    pub async fn open_file(&self, options: &FileDialogOptions) -> Result<Option<PathBuf>> {
+        let paths = self.choose(DialogKind::OpenFile, options).await?;
+        Ok(paths.and_then(|paths| paths.into_iter().next()))
+    }
+
+    /// Asks the user to choose files. Resolves to an empty list if cancelled.
+    pub async fn open_files(&self, options: &FileDialogOptions) -> Result<Vec<PathBuf>> {
+        let paths = self.choose(DialogKind::OpenFiles, options).await?;
+        Ok(paths.unwrap_or_default())
+    }
+
+    /// Asks the user to choose a folder. Resolves to `None` if cancelled.
+    pub async fn choose_directory(&self, options: &FileDialogOptions) -> Result<Option<PathBuf>> {
+        let paths = self.choose(DialogKind::ChooseDirectory, options).await?;
+        Ok(paths.and_then(|paths| paths.into_iter().next()))
+    }
+
+    /// Asks the user where to save a file. Resolves to `None` if cancelled.
+    pub async fn save_file(&self, options: &FileDialogOptions) -> Result<Option<PathBuf>> {
+        let paths = self.choose(DialogKind::SaveFile, options).await?;
+        Ok(paths.and_then(|paths| paths.into_iter().next()))
+    }
+
+    /// Shows a dialog and waits for the answer. Resolves to `None` if cancelled.
+    pub(crate) async fn choose(
+        &self,
+        kind: DialogKind,
+        options: &FileDialogOptions,
+    ) -> Result<Option<Vec<PathBuf>>> {
+        let token = format!("applib{}", NEXT_TOKEN.fetch_add(1, Ordering::Relaxed));
+        let sender = self.connection.unique_name().map(|name| name.as_str());
+
+        // Subscribe before making the request so the response can't be missed
+        let expected_path = request_path(sender, &token);
+        let mut responses = RequestProxy::builder(&self.connection)
+            .path(expected_path.clone())?
+            .build()
+            .await?
+            .receive_response()
+            .await?;
+
+        let title = options
+            .title
+            .clone()
+            .unwrap_or_else(|| kind.default_title().into());
+        let portal_options = request_options(kind, options, &token);
+        let handle = match kind {
+            DialogKind::SaveFile => self.proxy.save_file("", &title, portal_options).await?,
+            _ => self.proxy.open_file("", &title, portal_options).await?,
+        };
+
+        // Older portals don't derive the path from the token
+        if handle != expected_path {
+            responses = RequestProxy::builder(&self.connection)
+                .path(handle)?
+                .build()
+                .await?
+                .receive_response()
+                .await?;
+        }
+
+        let response = responses
+            .next()
+            .await
+            .context("portal closed the request without responding")?;
+        let args = response.args()?;
+        match args.response {
+            RESPONSE_SUCCESS => Ok(Some(paths_from_results(&args.results))),
+            RESPONSE_CANCELLED => Ok(None),
+            code => Err(anyhow!("portal request failed with response {}", code)),
+        }
+    }
+}
+
+/// Builds the `a{sv}` options of an `OpenFile` or `SaveFile` call.
+fn request_options<'a>(
+    kind: DialogKind,
+    options: &'a FileDialogOptions,
+    token: &'a str,
+) -> HashMap<&'static str, Value<'a>> {
+    let mut map = HashMap::new();
+    map.insert("handle_token", Value::from(token));
+    map.insert("modal", Value::Bool(true));
+    let accept_label = options
+        .accept_label
+        .as_ref()
+        .map_or(kind.default_accept_label(), |label| label.as_ref());
+    map.insert("accept_label", Value::from(accept_label));
+
+    match kind {
+        DialogKind::OpenFiles => {
+            map.insert("multiple", Value::Bool(true));
+        }
+        DialogKind::ChooseDirectory => {
+            map.insert("directory", Value::Bool(true));
+        }
+        DialogKind::SaveFile => {
+            if let Some(ref name) = options.suggested_name {
+                map.insert("current_name", Value::from(name.as_ref()));
+            }
+        }
+        DialogKind::OpenFile => {}
+    }
+
+    if !options.filters.is_empty() {
+        let filters: Vec<_> = options.filters.iter().map(portal_filter).collect();
+        map.insert(
+            "current_filter",
+            Value::from(portal_filter(&options.filters[0])),
+        );
+        map.insert("filters", Value::from(filters));
+    }
+
+    if let Some(ref folder) = options.current_folder {
+        map.insert("current_folder", Value::from(nul_terminated(folder)));
+    }
+    map
+}
+
+/// Converts a filter to the portal's `(sa(us))` form, where 0 marks a glob
+/// pattern and 1 a MIME type.
+fn portal_filter(filter: &FileFilter) -> (String, Vec<(u32, String)>) {
+    let patterns = filter
+        .patterns
+        .iter()
+        .map(|pattern| (0, pattern.to_string()));
+    let mime_types = filter
+        .mime_types
+        .iter()
+        .map(|mime_type| (1, mime_type.to_string()));
+    (
+        filter.name.to_string(),
+        patterns.chain(mime_types).collect(),
+    )
+}
+
+/// Encodes a path as the NUL-terminated byte string the portal expects.
+fn nul_terminated(path: &Path) -> Vec<u8> {
+    let mut bytes = path.as_os_str().as_bytes().to_vec();
+    bytes.push(0);
+    bytes
+}
+
+fn paths_from_results(results: &HashMap<String, OwnedValue>) -> Vec<PathBuf> {
+    let uris = results
+        .get("uris")
+        .and_then(|uris| Vec::<String>::try_from(uris.clone()).ok())
+        .unwrap_or_default();
+    uris.iter().filter_map(|uri| path_from_uri(uri)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::MockFileChooserPortal;
+    use super::*;
+    use std::os::unix::net::UnixStream;
+    use zbus::connection::Builder;
+    use zbus::Guid;
+
+    /// Connects a client to a mock portal over a private socket pair.
+    async fn connect_mock() -> (PortalFileChooser, MockFileChooserPortal, Connection) {
+        let (server_stream, client_stream) = UnixStream::pair().unwrap();
+        let guid = Guid::generate();
+        let server = Builder::unix_stream(server_stream)
+            .server(guid)
+            .unwrap()
+            .p2p()
+            .build();
+        let client = Builder::unix_stream(client_stream).p2p().build();
+        let (server, client) = futures::join!(server, client);
+        let (server, client) = (server.unwrap(), client.unwrap());
+
+        let mock = MockFileChooserPortal::new();
+        mock.serve(&server).await.unwrap();
+        let portal = PortalFileChooser::with_connection(&client).await.unwrap();
+        (portal, mock, client)
+    }
+
+    #[test]
+    fn test_open_files_with_filters() {
+        zbus::block_on(async {
+            let (portal, mock, _connection) = connect_mock().await;
+            mock.set_selection(Some(vec![
+                PathBuf::from("/tmp/a.png"),
+                PathBuf::from("/tmp/b c.png"),
+            ]));
+
+            let paths = portal
+                .open_files(
+                    &FileDialogOptions::new()
+                        .title("Import")
+                        .filter(
+                            FileFilter::new("Images")
+                                .pattern("*.png")
+                                .mime_type("image/jpeg"),
+                        )
+                        .current_folder("/tmp"),
+                )
+                .await
+                .unwrap();
+            assert_eq!(
+                paths,
+                vec![PathBuf::from("/tmp/a.png"), PathBuf::from("/tmp/b c.png")]
+            );
+
+            let requests = mock.requests();
+            assert_eq!(requests.len(), 1);
+            assert_eq!(requests[0].method, "OpenFile");
+            assert_eq!(requests[0].title, "Import");
+            assert_eq!(requests[0].accept_label.as_deref(), Some("Open"));
+            assert!(requests[0].multiple);
+            assert!(!requests[0].directory);
+            assert_eq!(requests[0].current_folder, Some(PathBuf::from("/tmp")));
+            assert_eq!(
+                requests[0].filters,
+                vec![(
+                    "Images".to_string(),
+                    vec![(0, "*.png".to_string()), (1, "image/jpeg".to_string())]
+                )]
+            );
+        });
+    }
+
+    #[test]
+    fn test_save_file_and_cancel() {
+        zbus::block_on(async {
+            let (portal, mock, _connection) = connect_mock().await;
+
+            mock.set_selection(Some(vec![PathBuf::from("/tmp/report.pdf")]));
+            let path = portal
+                .save_file(&FileDialogOptions::new().suggested_name("report.pdf"))
+                .await
+                .unwrap();
+            assert_eq!(path, Some(PathBuf::from("/tmp/report.pdf")));
+
+            mock.set_selection(None);
+            let path = portal
+                .choose_directory(&FileDialogOptions::new())
+                .await
+                .unwrap();
+            assert_eq!(path, None);
+
+            let requests = mock.requests();
+            assert_eq!(requests[0].method, "SaveFile");
+            assert_eq!(requests[0].title, "Save File");
+            assert_eq!(requests[0].current_name.as_deref(), Some("report.pdf"));
+            assert_eq!(requests[1].method, "OpenFile");
+            assert!(requests[1].directory);
+        });
+    }
+}
@@ -0,0 +1,56 @@
+//! D-Bus proxies for the `org.freedesktop.portal.FileChooser` interface.
+
+use std::collections::HashMap;
+
+use zbus::proxy;
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Value};
+
+pub(crate) const PATH: &str = "/org/freedesktop/portal/desktop";
+
+#[proxy(
+    interface = "org.freedesktop.portal.FileChooser",
+    default_service = "org.freedesktop.portal.Desktop",
+    default_path = "/org/freedesktop/portal/desktop"
+)]
+pub(crate) trait FileChooser {
+    /// Asks the user to choose one or more files or a folder to open.
+    fn open_file(
+        &self,
+        parent_window: &str,
+        title: &str,
+        options: HashMap<&str, Value<'_>>,
+    ) -> zbus::Result<OwnedObjectPath>;
+
+    /// Asks the user where to save a file.
+    fn save_file(
+        &self,
+        parent_window: &str,
+        title: &str,
+        options: HashMap<&str, Value<'_>>,
+    ) -> zbus::Result<OwnedObjectPath>;
+}
+
+#[proxy(
+    interface = "org.freedesktop.portal.Request",
+    default_service = "org.freedesktop.portal.Desktop"
+)]
+pub(crate) trait Request {
+    /// Emitted once the user has answered the dialog.
+    #[zbus(signal)]
+    fn response(&self, response: u32, results: HashMap<String, OwnedValue>) -> zbus::Result<()>;
+}
+
+/// Returns the object path the portal uses for a request.
+///
+/// The path is derived from the caller's unique bus name so the response can
+/// be subscribed to before the request is made. Peer-to-peer connections have
+/// no unique name; both ends use `_` in its place.
+pub(crate) fn request_path(sender: Option<&str>, token: &str) -> OwnedObjectPath {
+    let sender = sender
+        .map(|name| name.trim_start_matches(':').replace('.', "_"))
+        .unwrap_or_else(|| "_".to_string());
+    let path = format!("{}/request/{}/{}", PATH, sender, token);
+    ObjectPath::try_from(path)
+        .expect("bus names and tokens only contain valid path characters")
+        .into()
+}
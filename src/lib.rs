@@ -56,12 +56,16 @@
 //! **Input:**
 //! - [`TextField`] - Text input field
 //! - [`SecureField`] - Password input field
+//! - [`ComboBox`] - Text input with suggestion popup
+//! - [`TokenField`] - Text input that collects removable tokens
 //! - [`TextArea`] - Multi-line text input
+//! - [`DropZone`] - Target for files dropped from the file manager
 //!
 //! **Lists & Tables:**
 //! - [`List`] - Scrollable list with sections
 //! - [`ListItem`] - Generic list item with selection and hover states
 //! - [`Table`] - Data table
+//! - [`OutlineView`] - Tree of expandable rows
 //! - [`LazyVStack`] - Virtualized vertical list
 //! - [`LazyHStack`] - Virtualized horizontal list
 //! - [`LazyVGrid`] - Virtualized vertical grid
@@ -71,24 +75,31 @@
 //! - [`TabView`] - Tab-based navigation
 //! - [`Sidebar`] - Source list container
 //! - [`SidebarItem`] - Individual sidebar navigation item
+//! - [`FileBrowser`] - Folder browser with places, path bar and listing
+//! - [`Breadcrumb`] - Clickable path trail with an editable location
 //!
 //! **Windows & Containers:**
 //! - [`WindowFrame`] - Window frame with title bar and traffic lights
+//! - [`WindowChrome`] - Focus, maximized and tiling state for client-side decorations
 //! - [`TitleBar`] - Window title bar
 //! - [`TrafficLights`] - Window control buttons
 //! - [`SplitView`] - Resizable split view
 //! - [`ScrollView`] - Scrollable container
 //! - [`Sheet`] - Modal sheet
 //! - [`Alert`] - Alert dialog
+//! - [`ModalPresenter`] - Window-level stack of presented sheets and alerts
+//! - [`Toast`] - Transient notification banner
 //! - [`GroupBox`] - Grouped content container
 //! - [`Section`] - Content section with optional header
 //! - [`Form`] - Form layout container
+//! - [`FormModel`] - Form validation and submission state
 //!
 //! **Disclosure & Menus:**
 //! - [`DisclosureGroup`] - Collapsible section
 //! - [`Menu`] - Context menu
 //! - [`ContextMenu`] - Contextual menu
 //! - [`Popover`] - Popover overlay
+//! - [`AnchoredOverlay`] - Window-aware placement for floating panels
 //!
 //! **Display:**
 //! - [`Text`] - Styled text
@@ -100,13 +111,27 @@
 //! - [`Canvas`] - Custom drawing surface
 //! - [`ColorView`] - Color display
 //! - [`Link`] - Clickable link
-//! - [`Tooltip`] - Hover tooltip
+//! - [`Tooltip`] - Delayed hover and focus hint
+//!
+//! **Application:**
+//! - [`application`] - Window tracking, commands and document windows
+//!
+//! **System Integration:**
+//! - [`accessibility`] - Accessibility tree published to screen readers through AT-SPI
+//! - [`dialogs`] - File open and save dialogs through the desktop portal
+//! - [`notifications`] - Desktop notifications through D-Bus
+//! - [`persistence`] - Window state and preferences saved across launches
 
+pub mod accessibility;
+pub mod application;
 pub mod components;
+pub mod dialogs;
+pub mod notifications;
+pub mod persistence;
 pub mod prelude;
 pub mod state;
 pub mod utils;
 
 // Re-export all components at crate root for convenience
 pub use components::*;
-pub use state::{Binding, State};
+pub use state::{Binding, Computed, ObservableVec, State, UndoManager, VecChange};
@@ -0,0 +1,203 @@
+//! In-process notification server for tests.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context as _, Result};
+use zbus::object_server::SignalEmitter;
+use zbus::zvariant::OwnedValue;
+use zbus::{interface, Connection};
+
+use super::proxy::PATH;
+
+/// A notification received by a [`MockNotificationServer`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReceivedNotification {
+    /// The ID the server assigned
+    pub id: u32,
+    /// The sending application's name
+    pub app_name: String,
+    /// The icon name or URI
+    pub app_icon: String,
+    /// The summary line
+    pub summary: String,
+    /// The body text
+    pub body: String,
+    /// Action keys and labels, alternating
+    pub actions: Vec<String>,
+    /// The urgency hint, if set
+    pub urgency: Option<u8>,
+    /// The requested timeout in milliseconds
+    pub expire_timeout: i32,
+}
+
+#[derive(Default)]
+struct MockState {
+    next_id: u32,
+    notifications: Vec<ReceivedNotification>,
+    connection: Option<Connection>,
+}
+
+/// A notification server that records notifications instead of showing them.
+///
+/// Serve it on a private connection to test notification code without a
+/// desktop session. On a private `dbus-daemon`, also request the
+/// `org.freedesktop.Notifications` name so clients can find it.
+///
+/// # Example
+///
+/// ```ignore
+/// let mock = MockNotificationServer::new();
+/// mock.serve(&server_connection).await?;
+///
+/// let client = NotificationClient::with_connection(&client_connection, "Test").await?;
+/// let id = client.notify(&Notification::new("Hello")).await?;
+/// assert_eq!(mock.notifications()[0].summary, "Hello");
+///
+/// mock.invoke_action(id.0, "default").await?;
+/// ```
+#[derive(Clone, Default)]
+pub struct MockNotificationServer {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockNotificationServer {
+    /// Creates a server with no notifications.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serves the Notifications interface on `connection`.
+    pub async fn serve(&self, connection: &Connection) -> Result<()> {
+        self.state.lock().unwrap().connection = Some(connection.clone());
+        connection
+            .object_server()
+            .at(
+                PATH,
+                MockInterface {
+                    state: self.state.clone(),
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Returns the notifications that are currently open.
+    pub fn notifications(&self) -> Vec<ReceivedNotification> {
+        self.state.lock().unwrap().notifications.clone()
+    }
+
+    /// Simulates the user activating an action.
+    pub async fn invoke_action(&self, id: u32, action_key: &str) -> Result<()> {
+        let emitter = self.emitter()?;
+        MockInterface::action_invoked(&emitter, id, action_key).await?;
+        Ok(())
+    }
+
+    /// Simulates the server closing a notification, with a reason as in
+    /// the specification (1 expired, 2 dismissed, 3 closed).
+    pub async fn close(&self, id: u32, reason: u32) -> Result<()> {
+        self.state
+            .lock()
+            .unwrap()
+            .notifications
+            .retain(|n| n.id != id);
+        let emitter = self.emitter()?;
+        MockInterface::notification_closed(&emitter, id, reason).await?;
+        Ok(())
+    }
+
+    fn emitter(&self) -> Result<SignalEmitter<'static>> {
+        let state = self.state.lock().unwrap();
+        let connection = state
+            .connection
+            .as_ref()
+            .context("server is not being served")?;
+        Ok(SignalEmitter::new(connection, PATH)?.into_owned())
+    }
+}
+
+struct MockInterface {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[interface(name = "org.freedesktop.Notifications")]
+impl MockInterface {
+    #[allow(clippy::too_many_arguments)]
+    fn notify(
+        &self,
+        app_name: String,
+        replaces_id: u32,
+        app_icon: String,
+        summary: String,
+        body: String,
+        actions: Vec<String>,
+        hints: HashMap<String, OwnedValue>,
+        expire_timeout: i32,
+    ) -> u32 {
+        let mut state = self.state.lock().unwrap();
+        let id = if replaces_id != 0 {
+            state.notifications.retain(|n| n.id != replaces_id);
+            replaces_id
+        } else {
+            state.next_id += 1;
+            state.next_id
+        };
+        let urgency = hints
+            .get("urgency")
+            .and_then(|value| u8::try_from(value).ok());
+
+        state.notifications.push(ReceivedNotification {
+            id,
+            app_name,
+            app_icon,
+            summary,
+            body,
+            actions,
+            urgency,
+            expire_timeout,
+        });
+        id
+    }
+
+    async fn close_notification(
+        &self,
+        id: u32,
+        #[zbus(signal_emitter)] emitter: SignalEmitter<'_>,
+    ) -> zbus::fdo::Result<()> {
+        self.state
+            .lock()
+            .unwrap()
+            .notifications
+            .retain(|n| n.id != id);
+        Self::notification_closed(&emitter, id, 3).await?;
+        Ok(())
+    }
+
+    fn get_capabilities(&self) -> Vec<String> {
+        vec!["actions".to_string(), "body".to_string()]
+    }
+
+    fn get_server_information(&self) -> (String, String, String, String) {
+        (
+            "applib-mock".to_string(),
+            "applib".to_string(),
+            env!("CARGO_PKG_VERSION").to_string(),
+            "1.2".to_string(),
+        )
+    }
+
+    #[zbus(signal)]
+    async fn action_invoked(
+        emitter: &SignalEmitter<'_>,
+        id: u32,
+        action_key: &str,
+    ) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn notification_closed(
+        emitter: &SignalEmitter<'_>,
+        id: u32,
+        reason: u32,
+    ) -> zbus::Result<()>;
+}
@@ -0,0 +1,471 @@
+//! Desktop notifications through the freedesktop D-Bus Notifications service.
+//!
+//! This module talks to the `org.freedesktop.Notifications` service on the
+//! session bus, which is provided by every major Linux desktop. Use it for
+//! events the user should hear about while the application is in the
+//! background; use [`Toasts`](crate::Toasts) for feedback inside a window.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use applib::notifications::{Notification, NotificationClient, NotificationEvent, Urgency};
+//!
+//! cx.spawn(async move |cx| {
+//!     let client = NotificationClient::connect("Mail").await?;
+//!     let _events = client
+//!         .subscribe(cx, |event, _cx| {
+//!             if let NotificationEvent::ActionInvoked { action, .. } = event {
+//!                 println!("Clicked {}", action);
+//!             }
+//!         })
+//!         .await?;
+//!
+//!     client
+//!         .notify(
+//!             &Notification::new("New message")
+//!                 .body("Alice: Are we still on for lunch?")
+//!                 .icon("mail-unread")
+//!                 .urgency(Urgency::Normal)
+//!                 .action("reply", "Reply"),
+//!         )
+//!         .await?;
+//!     anyhow::Ok(())
+//! })
+//! .detach();
+//! ```
+
+#[cfg(any(test, feature = "test-support"))]
+mod mock;
+mod proxy;
+
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::stream::{self, Stream, StreamExt};
+use gpui::{App, AsyncApp, SharedString, Task};
+use zbus::zvariant::Value;
+use zbus::Connection;
+
+#[cfg(any(test, feature = "test-support"))]
+pub use mock::{MockNotificationServer, ReceivedNotification};
+use proxy::NotificationsProxy;
+
+/// Identifies a notification shown by the notification server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NotificationId(pub u32);
+
+/// How urgent a notification is.
+///
+/// Servers may show critical notifications until the user dismisses them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Urgency {
+    /// Low urgency, e.g. a finished download
+    Low,
+    /// Normal urgency
+    #[default]
+    Normal,
+    /// Critical urgency, e.g. a low battery
+    Critical,
+}
+
+impl Urgency {
+    fn as_byte(&self) -> u8 {
+        match self {
+            Urgency::Low => 0,
+            Urgency::Normal => 1,
+            Urgency::Critical => 2,
+        }
+    }
+}
+
+/// How long a notification stays on screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Timeout {
+    /// The server's default timeout
+    #[default]
+    Default,
+    /// The notification stays until the user dismisses it
+    Never,
+    /// The notification closes after the given time
+    After(Duration),
+}
+
+impl Timeout {
+    fn as_millis(&self) -> i32 {
+        match self {
+            Timeout::Default => -1,
+            Timeout::Never => 0,
+            Timeout::After(duration) => duration.as_millis().clamp(1, i32::MAX as u128) as i32,
+        }
+    }
+}
+
+/// Why a notification was closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloseReason {
+    /// The notification expired
+    Expired,
+    /// The user dismissed the notification
+    Dismissed,
+    /// The notification was closed with [`NotificationClient::close`]
+    Closed,
+    /// The server did not give a reason
+    Undefined,
+}
+
+impl From<u32> for CloseReason {
+    fn from(reason: u32) -> Self {
+        match reason {
+            1 => CloseReason::Expired,
+            2 => CloseReason::Dismissed,
+            3 => CloseReason::Closed,
+            _ => CloseReason::Undefined,
+        }
+    }
+}
+
+/// An event reported by the notification server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationEvent {
+    /// The user activated one of the notification's actions.
+    ActionInvoked {
+        /// The notification
+        id: NotificationId,
+        /// The key of the activated action; `"default"` for the notification itself
+        action: String,
+    },
+    /// The notification was closed.
+    Closed {
+        /// The notification
+        id: NotificationId,
+        /// Why it was closed
+        reason: CloseReason,
+    },
+}
+
+/// A stream of [`NotificationEvent`]s.
+pub type NotificationEvents = Pin<Box<dyn Stream<Item = NotificationEvent> + Send>>;
+
+/// A desktop notification.
+///
+/// # Example
+///
+/// ```ignore
+/// Notification::new("Download complete")
+///     .body("report.pdf was saved to Downloads")
+///     .icon("folder-download")
+///     .urgency(Urgency::Low)
+///     .action("default", "Open")
+///     .action("show", "Show in Folder")
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Notification {
+    summary: SharedString,
+    body: Option<SharedString>,
+    icon: Option<SharedString>,
+    actions: Vec<(SharedString, SharedString)>,
+    urgency: Urgency,
+    timeout: Timeout,
+    category: Option<SharedString>,
+    desktop_entry: Option<SharedString>,
+    transient: bool,
+    replaces: Option<NotificationId>,
+}
+
+impl Notification {
+    /// Creates a notification with the given summary line.
+    pub fn new(summary: impl Into<SharedString>) -> Self {
+        Self {
+            summary: summary.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the body text. Many servers support simple markup such as `<b>`.
+    pub fn body(mut self, body: impl Into<SharedString>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Sets the icon, either a themed icon name or a `file://` URI.
+    pub fn icon(mut self, icon: impl Into<SharedString>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Adds an action button.
+    ///
+    /// The key is reported in [`NotificationEvent::ActionInvoked`]. An action
+    /// with the key `"default"` is invoked when the notification itself is clicked.
+    pub fn action(mut self, key: impl Into<SharedString>, label: impl Into<SharedString>) -> Self {
+        self.actions.push((key.into(), label.into()));
+        self
+    }
+
+    /// Sets the urgency level (default: normal).
+    pub fn urgency(mut self, urgency: Urgency) -> Self {
+        self.urgency = urgency;
+        self
+    }
+
+    /// Sets how long the notification stays on screen.
+    pub fn timeout(mut self, timeout: Timeout) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the category, such as `"email.arrived"` or `"transfer.complete"`.
+    pub fn category(mut self, category: impl Into<SharedString>) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    /// Sets the desktop entry name of the application, without the `.desktop` suffix.
+    pub fn desktop_entry(mut self, desktop_entry: impl Into<SharedString>) -> Self {
+        self.desktop_entry = Some(desktop_entry.into());
+        self
+    }
+
+    /// Sets whether the server should skip keeping the notification in its history.
+    pub fn transient(mut self, transient: bool) -> Self {
+        self.transient = transient;
+        self
+    }
+
+    /// Replaces an earlier notification instead of showing a new one.
+    pub fn replaces(mut self, id: NotificationId) -> Self {
+        self.replaces = Some(id);
+        self
+    }
+
+    /// Returns the actions flattened into key, label pairs as the protocol expects.
+    fn action_list(&self) -> Vec<&str> {
+        self.actions
+            .iter()
+            .flat_map(|(key, label)| [key.as_ref(), label.as_ref()])
+            .collect()
+    }
+
+    fn hints(&self) -> HashMap<&str, Value<'_>> {
+        let mut hints = HashMap::new();
+        hints.insert("urgency", Value::U8(self.urgency.as_byte()));
+        if let Some(ref category) = self.category {
+            hints.insert("category", Value::from(category.as_ref()));
+        }
+        if let Some(ref desktop_entry) = self.desktop_entry {
+            hints.insert("desktop-entry", Value::from(desktop_entry.as_ref()));
+        }
+        if self.transient {
+            hints.insert("transient", Value::Bool(true));
+        }
+        hints
+    }
+}
+
+/// A connection to the desktop notification server.
+#[derive(Clone)]
+pub struct NotificationClient {
+    app_name: SharedString,
+    proxy: NotificationsProxy<'static>,
+}
+
+impl NotificationClient {
+    /// Connects to the notification server on the session bus.
+    pub async fn connect(app_name: impl Into<SharedString>) -> Result<Self> {
+        let connection = Connection::session().await?;
+        Self::with_connection(&connection, app_name).await
+    }
+
+    /// Uses an existing D-Bus connection, e.g. to a private bus in tests.
+    pub async fn with_connection(
+        connection: &Connection,
+        app_name: impl Into<SharedString>,
+    ) -> Result<Self> {
+        Ok(Self {
+            app_name: app_name.into(),
+            proxy: NotificationsProxy::new(connection).await?,
+        })
+    }
+
+    /// Shows a notification and returns the ID the server assigned to it.
+    pub async fn notify(&self, notification: &Notification) -> Result<NotificationId> {
+        let id = self
+            .proxy
+            .notify(
+                &self.app_name,
+                notification.replaces.map_or(0, |id| id.0),
+                notification.icon.as_ref().map_or("", |icon| icon.as_ref()),
+                &notification.summary,
+                notification.body.as_ref().map_or("", |body| body.as_ref()),
+                &notification.action_list(),
+                notification.hints(),
+                notification.timeout.as_millis(),
+            )
+            .await?;
+        Ok(NotificationId(id))
+    }
+
+    /// Closes a notification.
+    pub async fn close(&self, id: NotificationId) -> Result<()> {
+        self.proxy.close_notification(id.0).await?;
+        Ok(())
+    }
+
+    /// Returns the optional features the server supports, such as `"actions"` or `"body-markup"`.
+    pub async fn capabilities(&self) -> Result<Vec<String>> {
+        Ok(self.proxy.get_capabilities().await?)
+    }
+
+    /// Returns a stream of events for notifications shown by any client.
+    ///
+    /// Subscribe before showing a notification so no events are missed.
+    pub async fn events(&self) -> Result<NotificationEvents> {
+        let actions = self
+            .proxy
+            .receive_action_invoked()
+            .await?
+            .filter_map(|signal| async move {
+                let args = signal.args().ok()?;
+                Some(NotificationEvent::ActionInvoked {
+                    id: NotificationId(args.id),
+                    action: args.action_key,
+                })
+            });
+        let closed =
+            self.proxy
+                .receive_notification_closed()
+                .await?
+                .filter_map(|signal| async move {
+                    let args = signal.args().ok()?;
+                    Some(NotificationEvent::Closed {
+                        id: NotificationId(args.id),
+                        reason: CloseReason::from(args.reason),
+                    })
+                });
+        Ok(Box::pin(stream::select(actions, closed)))
+    }
+
+    /// Calls `handler` on the main thread for each event until the returned task is dropped.
+    ///
+    /// Fails if the server's signals can't be subscribed to. Events are
+    /// received from the time this returns, so await it before showing a
+    /// notification.
+    pub async fn subscribe(
+        &self,
+        cx: &AsyncApp,
+        handler: impl Fn(NotificationEvent, &mut App) + 'static,
+    ) -> Result<Task<()>> {
+        let mut events = self.events().await?;
+        Ok(cx.spawn(async move |cx| {
+            while let Some(event) = events.next().await {
+                if cx.update(|cx| handler(event, cx)).is_err() {
+                    break;
+                }
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::net::UnixStream;
+    use zbus::connection::Builder;
+    use zbus::Guid;
+
+    /// Connects a client to a mock server over a private socket pair.
+    async fn connect_mock() -> (NotificationClient, MockNotificationServer, Connection) {
+        let (server_stream, client_stream) = UnixStream::pair().unwrap();
+        let guid = Guid::generate();
+        let server = Builder::unix_stream(server_stream)
+            .server(guid)
+            .unwrap()
+            .p2p()
+            .build();
+        let client = Builder::unix_stream(client_stream).p2p().build();
+        let (server, client) = futures::join!(server, client);
+        let (server, client) = (server.unwrap(), client.unwrap());
+
+        let mock = MockNotificationServer::new();
+        mock.serve(&server).await.unwrap();
+        let notifications = NotificationClient::with_connection(&client, "Test")
+            .await
+            .unwrap();
+        (notifications, mock, client)
+    }
+
+    #[test]
+    fn test_notification_hints() {
+        let notification = Notification::new("Saved")
+            .urgency(Urgency::Critical)
+            .category("transfer.complete")
+            .action("default", "Open")
+            .action("show", "Show");
+        let hints = notification.hints();
+        assert_eq!(hints.get("urgency"), Some(&Value::U8(2)));
+        assert_eq!(
+            hints.get("category"),
+            Some(&Value::from("transfer.complete"))
+        );
+        assert!(!hints.contains_key("transient"));
+        assert_eq!(
+            notification.action_list(),
+            vec!["default", "Open", "show", "Show"]
+        );
+    }
+
+    #[test]
+    fn test_timeout_millis() {
+        assert_eq!(Timeout::Default.as_millis(), -1);
+        assert_eq!(Timeout::Never.as_millis(), 0);
+        assert_eq!(Timeout::After(Duration::from_secs(5)).as_millis(), 5000);
+    }
+
+    #[test]
+    fn test_notify_and_signals() {
+        zbus::block_on(async {
+            let (client, mock, _connection) = connect_mock().await;
+            let mut events = client.events().await.unwrap();
+
+            let id = client
+                .notify(
+                    &Notification::new("New message")
+                        .body("Hello")
+                        .icon("mail-unread")
+                        .urgency(Urgency::Low)
+                        .action("reply", "Reply"),
+                )
+                .await
+                .unwrap();
+
+            let received = mock.notifications();
+            assert_eq!(received.len(), 1);
+            assert_eq!(received[0].id, id.0);
+            assert_eq!(received[0].app_name, "Test");
+            assert_eq!(received[0].summary, "New message");
+            assert_eq!(received[0].actions, vec!["reply", "Reply"]);
+            assert_eq!(received[0].urgency, Some(0));
+
+            mock.invoke_action(id.0, "reply").await.unwrap();
+            assert_eq!(
+                events.next().await,
+                Some(NotificationEvent::ActionInvoked {
+                    id,
+                    action: "reply".to_string()
+                })
+            );
+
+            client.close(id).await.unwrap();
+            assert_eq!(
+                events.next().await,
+                Some(NotificationEvent::Closed {
+                    id,
+                    reason: CloseReason::Closed
+                })
+            );
+            assert!(mock.notifications().is_empty());
+        });
+    }
+}
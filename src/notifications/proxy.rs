@@ -0,0 +1,45 @@
+//! D-Bus proxy for the `org.freedesktop.Notifications` interface.
+
+use std::collections::HashMap;
+
+use zbus::proxy;
+use zbus::zvariant::Value;
+
+/// The object path the mock server is served at
+#[cfg(any(test, feature = "test-support"))]
+pub(crate) const PATH: &str = "/org/freedesktop/Notifications";
+
+#[proxy(
+    interface = "org.freedesktop.Notifications",
+    default_service = "org.freedesktop.Notifications",
+    default_path = "/org/freedesktop/Notifications"
+)]
+pub(crate) trait Notifications {
+    /// Shows or replaces a notification and returns its ID.
+    #[allow(clippy::too_many_arguments)]
+    fn notify(
+        &self,
+        app_name: &str,
+        replaces_id: u32,
+        app_icon: &str,
+        summary: &str,
+        body: &str,
+        actions: &[&str],
+        hints: HashMap<&str, Value<'_>>,
+        expire_timeout: i32,
+    ) -> zbus::Result<u32>;
+
+    /// Closes a notification.
+    fn close_notification(&self, id: u32) -> zbus::Result<()>;
+
+    /// Returns the optional features the server supports.
+    fn get_capabilities(&self) -> zbus::Result<Vec<String>>;
+
+    /// Emitted when the user activates one of a notification's actions.
+    #[zbus(signal)]
+    fn action_invoked(&self, id: u32, action_key: String) -> zbus::Result<()>;
+
+    /// Emitted when a notification is closed.
+    #[zbus(signal)]
+    fn notification_closed(&self, id: u32, reason: u32) -> zbus::Result<()>;
+}
@@ -0,0 +1,45 @@
+//! Saving and restoring application state across launches.
+//!
+//! - [`WindowStates`] - Window bounds, split positions and selections, keyed by window ID
+//! - [`PersistentState`] - Any serializable value, such as preferences, stored by key
+//!
+//! # Example
+//!
+//! ```ignore
+//! use applib::persistence::WindowStates;
+//!
+//! Application::new().run(|cx| {
+//!     // Reads ~/.config/mail/window-state.json
+//!     WindowStates::init("mail", cx);
+//!
+//!     Panel::new("main", |cx| MailView::new(cx))
+//!         .size(px(900.0), px(600.0))
+//!         .restore_state()
+//!         .open(cx)
+//!         .unwrap();
+//! });
+//! ```
+
+mod persistent_state;
+mod window_state;
+
+pub use persistent_state::{PersistentOptions, PersistentState, StorageLocation};
+pub use window_state::{WindowState, WindowStates};
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context as _, Result};
+
+/// Writes a file through a temporary file, so a crash never leaves it truncated.
+fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = Path::new(&tmp);
+    fs::write(tmp, contents).with_context(|| format!("failed to write {}", tmp.display()))?;
+    fs::rename(tmp, path)?;
+    Ok(())
+}
@@ -0,0 +1,524 @@
+//! Values saved to disk and restored on the next launch.
+//!
+//! `PersistentState<T>` works like [`State<T>`](crate::State) but stores its
+//! value as JSON under the XDG config or data directory. The file is read
+//! when the state is created, and every change schedules a debounced write,
+//! so a burst of changes only writes the file once. Files are written to a
+//! temporary file and renamed, so a crash never leaves a truncated file.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use applib::persistence::PersistentState;
+//!
+//! #[derive(Clone, Default, Serialize, Deserialize)]
+//! #[serde(default)]
+//! struct Preferences {
+//!     dark_mode: bool,
+//!     font_size: f64,
+//! }
+//!
+//! // Reads ~/.config/mail/preferences.json
+//! let prefs = cx.new(|cx| PersistentState::<Preferences>::new("mail", "preferences", cx));
+//!
+//! prefs.update(cx, |prefs, cx| prefs.update(|p| p.dark_mode = true, cx));
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context as _, Result};
+use gpui::{App, Context, Entity, SharedString, Subscription};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::write_atomic;
+use crate::state::Binding;
+use crate::utils::Debouncer;
+
+/// How long to wait after the last change before writing the file.
+const SAVE_DELAY: Duration = Duration::from_millis(500);
+
+type Migration = Box<dyn Fn(Value) -> Result<Value>>;
+
+/// The directory a persistent value is stored in.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StorageLocation {
+    /// `$XDG_CONFIG_HOME/<app-id>`, for preferences the user chose
+    Config,
+    /// `$XDG_DATA_HOME/<app-id>`, for data the application keeps, such as history
+    Data,
+    /// An exact file path
+    Path(PathBuf),
+    /// Nothing is read or written, e.g. for tests
+    Memory,
+}
+
+/// A file saved by a newer version of the application.
+#[derive(Debug)]
+struct NewerVersion {
+    found: u32,
+    supported: u32,
+}
+
+impl fmt::Display for NewerVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "the file has version {}, newer than the supported version {}",
+            self.found, self.supported
+        )
+    }
+}
+
+impl std::error::Error for NewerVersion {}
+
+/// The on-disk format: the value together with its schema version.
+#[derive(Serialize, Deserialize)]
+struct Envelope<V> {
+    version: u32,
+    value: V,
+}
+
+/// Where and how a [`PersistentState`] is stored.
+///
+/// # Example
+///
+/// ```ignore
+/// // Version 1 stored the font size in points as an integer,
+/// // version 2 stores it as a float in pixels.
+/// let options = PersistentOptions::new("mail", "preferences")
+///     .version(2)
+///     .migration(1, |mut value| {
+///         let points = value["font_size"].as_i64().unwrap_or(10);
+///         value["font_size"] = json!(points as f64 * 4.0 / 3.0);
+///         Ok(value)
+///     });
+///
+/// let prefs = cx.new(|cx| PersistentState::<Preferences>::with_options(options, cx));
+/// ```
+pub struct PersistentOptions {
+    app_id: String,
+    key: String,
+    location: StorageLocation,
+    version: u32,
+    migrations: BTreeMap<u32, Migration>,
+    save_delay: Duration,
+}
+
+impl PersistentOptions {
+    /// Stores the value in `$XDG_CONFIG_HOME/<app_id>/<key>.json`.
+    pub fn new(app_id: impl Into<String>, key: impl Into<String>) -> Self {
+        Self {
+            app_id: app_id.into(),
+            key: key.into(),
+            location: StorageLocation::Config,
+            version: 0,
+            migrations: BTreeMap::new(),
+            save_delay: SAVE_DELAY,
+        }
+    }
+
+    /// Sets the directory the file is stored in (default `Config`).
+    pub fn location(mut self, location: StorageLocation) -> Self {
+        self.location = location;
+        self
+    }
+
+    /// Sets the current schema version (default 0).
+    ///
+    /// Files saved with an older version are migrated when loaded.
+    pub fn version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Adds a migration from version `from` to version `from + 1`.
+    ///
+    /// The migration receives the stored JSON value and returns it in the
+    /// next version's shape. Versions without a migration keep the value as
+    /// is, which is enough when only fields with serde defaults were added.
+    pub fn migration(
+        mut self,
+        from: u32,
+        migrate: impl Fn(Value) -> Result<Value> + 'static,
+    ) -> Self {
+        self.migrations.insert(from, Box::new(migrate));
+        self
+    }
+
+    /// Sets how long to wait after the last change before writing (default 500 ms).
+    pub fn save_delay(mut self, delay: Duration) -> Self {
+        self.save_delay = delay;
+        self
+    }
+
+    /// Returns the file the value is stored in, or `None` if kept in memory.
+    pub fn path(&self) -> Option<PathBuf> {
+        let file = format!("{}.json", self.key);
+        match self.location {
+            StorageLocation::Config => {
+                dirs::config_dir().map(|dir| dir.join(&self.app_id).join(file))
+            }
+            StorageLocation::Data => dirs::data_dir().map(|dir| dir.join(&self.app_id).join(file)),
+            StorageLocation::Path(ref path) => Some(path.clone()),
+            StorageLocation::Memory => None,
+        }
+    }
+}
+
+/// An observable value saved across launches.
+///
+/// Use it like [`State<T>`](crate::State): read with `get`, and change with
+/// `set` or `update`, which notify observers and schedule a save. Pending
+/// changes are also written when the application quits or the entity is
+/// released.
+///
+/// A file that can't be read or migrated is moved aside to
+/// `<key>.json.corrupt` and the state starts with the default value. A file
+/// saved by a newer version stays in place, and is only replaced once the
+/// value changes. Load and save errors are available from
+/// [`last_error`](Self::last_error).
+pub struct PersistentState<T> {
+    value: T,
+    path: Option<PathBuf>,
+    version: u32,
+    last_error: Option<SharedString>,
+    /// Whether the value changed since it was last written
+    dirty: bool,
+    saver: Debouncer,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl<T: Serialize + DeserializeOwned + Default + 'static> PersistentState<T> {
+    /// Loads the value stored in `$XDG_CONFIG_HOME/<app_id>/<key>.json`.
+    pub fn new(app_id: &str, key: &str, cx: &mut Context<Self>) -> Self {
+        Self::with_options(PersistentOptions::new(app_id, key), cx)
+    }
+
+    /// Loads the value from the location described by `options`.
+    pub fn with_options(options: PersistentOptions, cx: &mut Context<Self>) -> Self {
+        let path = options.path();
+        let (value, last_error) = path
+            .as_deref()
+            .map(|path| load_or_default(path, options.version, &options.migrations))
+            .unwrap_or_default();
+
+        let subscriptions = vec![
+            cx.on_app_quit(|this, _cx| {
+                this.flush_and_record();
+                async {}
+            }),
+            cx.on_release(|this, _cx| this.flush_and_record()),
+        ];
+
+        Self {
+            value,
+            path,
+            version: options.version,
+            last_error,
+            dirty: false,
+            saver: Debouncer::new(options.save_delay),
+            _subscriptions: subscriptions,
+        }
+    }
+}
+
+impl<T: Serialize + 'static> PersistentState<T> {
+    /// Returns a reference to the current value.
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns the file the value is stored in, or `None` if kept in memory.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Returns why the stored file couldn't be loaded or the last save
+    /// failed, or `None` if there was no error.
+    ///
+    /// A successful save clears the error. Observers are notified when a
+    /// debounced save fails, so a view can show the error as it happens.
+    pub fn last_error(&self) -> Option<&SharedString> {
+        self.last_error.as_ref()
+    }
+
+    /// Sets a new value, notifies observers and schedules a save.
+    pub fn set(&mut self, value: T, cx: &mut Context<Self>) {
+        self.value = value;
+        self.changed(cx);
+    }
+
+    /// Updates the value using a closure, notifies observers and schedules a save.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// prefs.update(|p| p.font_size += 1.0, cx);
+    /// ```
+    pub fn update(&mut self, f: impl FnOnce(&mut T), cx: &mut Context<Self>) {
+        f(&mut self.value);
+        self.changed(cx);
+    }
+
+    /// Writes pending changes now instead of waiting for the debounced save.
+    pub fn flush(&mut self) -> Result<()> {
+        self.saver.cancel();
+        if !self.dirty {
+            return Ok(());
+        }
+        self.save()?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    fn changed(&mut self, cx: &mut Context<Self>) {
+        self.dirty = true;
+        cx.notify();
+        if self.path.is_none() {
+            return;
+        }
+        let this = cx.weak_entity();
+        self.saver.call(cx, move |cx| {
+            this.update(cx, |this, cx| {
+                let had_error = this.last_error.is_some();
+                this.flush_and_record();
+                if had_error || this.last_error.is_some() {
+                    cx.notify();
+                }
+            })
+            .ok();
+        });
+    }
+
+    /// Flushes pending changes, keeping any error for `last_error`.
+    fn flush_and_record(&mut self) {
+        match self.flush() {
+            Ok(()) => self.last_error = None,
+            Err(err) => {
+                let path = self.path.as_deref().unwrap_or(Path::new("")).display();
+                self.last_error = Some(format!("Failed to save {path}: {err:#}").into());
+            }
+        }
+    }
+
+    fn save(&self) -> Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        let json = serde_json::to_string_pretty(&Envelope {
+            version: self.version,
+            value: &self.value,
+        })?;
+        write_atomic(path, &json)
+    }
+}
+
+impl<T: Serialize + Clone + 'static> PersistentState<T> {
+    /// Creates a two-way binding to this state.
+    ///
+    /// Writes through the binding are saved like `set`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let dark_mode = PersistentState::binding(&self.prefs, cx)
+    ///     .map(|p| p.dark_mode, |p, on| p.dark_mode = on);
+    ///
+    /// Toggle::new("dark-mode", "Dark mode", false).is_on(dark_mode)
+    /// ```
+    pub fn binding(entity: &Entity<Self>, _cx: &App) -> Binding<T> {
+        let (reader, writer) = (entity.clone(), entity.clone());
+        Binding::from_entity(
+            entity,
+            move |cx| reader.read(cx).get().clone(),
+            move |value, cx| writer.update(cx, |this, cx| this.set(value, cx)),
+        )
+    }
+}
+
+/// Loads a stored value, falling back to the default if it can't be read.
+///
+/// Returns the error alongside the default value. Unreadable files are moved
+/// aside so the next save doesn't overwrite them. Files from a newer version
+/// are left alone: nothing is saved until the value changes, so running an
+/// older build doesn't lose them.
+fn load_or_default<T: DeserializeOwned + Default>(
+    path: &Path,
+    version: u32,
+    migrations: &BTreeMap<u32, Migration>,
+) -> (T, Option<SharedString>) {
+    let json = match fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return (T::default(), None),
+        Err(err) => {
+            let error = format!("Failed to read {}: {err}. Using defaults.", path.display());
+            return (T::default(), Some(error.into()));
+        }
+    };
+    match decode(&json, version, migrations) {
+        Ok(value) => (value, None),
+        Err(err) if err.is::<NewerVersion>() => {
+            let error = format!(
+                "Failed to load {}: {err}. Using defaults until the value changes.",
+                path.display()
+            );
+            (T::default(), Some(error.into()))
+        }
+        Err(err) => {
+            let backup = path.with_extension("json.corrupt");
+            let moved = fs::rename(path, &backup).is_ok();
+            let error = format!(
+                "Failed to load {}: {err:#}. Using defaults{}.",
+                path.display(),
+                if moved {
+                    format!("; the file was moved to {}", backup.display())
+                } else {
+                    String::new()
+                }
+            );
+            (T::default(), Some(error.into()))
+        }
+    }
+}
+
+/// Parses a stored file and migrates it to the current version.
+fn decode<T: DeserializeOwned>(
+    json: &str,
+    version: u32,
+    migrations: &BTreeMap<u32, Migration>,
+) -> Result<T> {
+    let envelope: Envelope<Value> = serde_json::from_str(json)?;
+    if envelope.version > version {
+        return Err(NewerVersion {
+            found: envelope.version,
+            supported: version,
+        }
+        .into());
+    }
+    let mut value = envelope.value;
+    for from in envelope.version..version {
+        if let Some(migrate) = migrations.get(&from) {
+            value =
+                migrate(value).with_context(|| format!("migration from version {from} failed"))?;
+        }
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{AppContext as _, TestAppContext};
+    use serde_json::json;
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    struct Prefs {
+        dark_mode: bool,
+        font_size: f64,
+    }
+
+    fn migrations() -> BTreeMap<u32, Migration> {
+        let mut migrations: BTreeMap<u32, Migration> = BTreeMap::new();
+        // Version 0 stored `dark` instead of `dark_mode`
+        migrations.insert(
+            0,
+            Box::new(|mut value| {
+                value["dark_mode"] = value["dark"].take();
+                Ok(value)
+            }),
+        );
+        migrations
+    }
+
+    #[test]
+    fn test_decode_migrates_old_versions() {
+        let json = r#"{"version":0,"value":{"dark":true}}"#;
+        let prefs: Prefs = decode(json, 2, &migrations()).unwrap();
+        assert_eq!(
+            prefs,
+            Prefs {
+                dark_mode: true,
+                font_size: 0.0
+            }
+        );
+
+        let json = json!({"version": 2, "value": {"font_size": 14.0}}).to_string();
+        let prefs: Prefs = decode(&json, 2, &migrations()).unwrap();
+        assert_eq!(prefs.font_size, 14.0);
+    }
+
+    #[test]
+    fn test_decode_rejects_newer_and_invalid_files() {
+        let json = r#"{"version":3,"value":{}}"#;
+        assert!(decode::<Prefs>(json, 2, &migrations())
+            .unwrap_err()
+            .is::<NewerVersion>());
+        assert!(decode::<Prefs>("{\"version\":", 2, &migrations()).is_err());
+        assert!(decode::<Prefs>(r#"{"dark_mode":true}"#, 2, &migrations()).is_err());
+    }
+
+    #[gpui::test]
+    fn test_newer_file_is_kept_until_the_value_changes(cx: &mut TestAppContext) {
+        let dir = std::env::temp_dir().join(format!("applib-newer-{}", std::process::id()));
+        let path = dir.join("prefs.json");
+        let json = r#"{"version":3,"value":{"dark_mode":true}}"#;
+        write_atomic(&path, json).unwrap();
+
+        let options = || {
+            PersistentOptions::new("test", "prefs")
+                .location(StorageLocation::Path(path.clone()))
+                .version(2)
+        };
+        let prefs = cx.new(|cx| PersistentState::<Prefs>::with_options(options(), cx));
+        prefs.read_with(cx, |prefs, _| {
+            assert_eq!(*prefs.get(), Prefs::default());
+            assert!(prefs.last_error().is_some());
+        });
+
+        // Releasing the state doesn't write the defaults over the file
+        drop(prefs);
+        cx.run_until_parked();
+        assert_eq!(fs::read_to_string(&path).unwrap(), json);
+        assert!(!dir.join("prefs.json.corrupt").exists());
+
+        // Changing the value replaces it
+        let prefs = cx.new(|cx| PersistentState::<Prefs>::with_options(options(), cx));
+        prefs.update(cx, |prefs, cx| {
+            prefs.update(|p| p.font_size = 12.0, cx);
+            prefs.flush().unwrap();
+        });
+        let saved: Envelope<Prefs> =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved.version, 2);
+        assert_eq!(saved.value.font_size, 12.0);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_corrupt_file_is_moved_aside() {
+        let dir = std::env::temp_dir().join(format!("applib-persistent-{}", std::process::id()));
+        let path = dir.join("prefs.json");
+        write_atomic(&path, "not json").unwrap();
+
+        let (prefs, error): (Prefs, _) = load_or_default(&path, 0, &BTreeMap::new());
+        assert_eq!(prefs, Prefs::default());
+        assert!(error.unwrap().contains("prefs.json.corrupt"));
+        assert!(!path.exists());
+        assert!(dir.join("prefs.json.corrupt").exists());
+
+        // A missing file isn't an error
+        let (_, error): (Prefs, _) = load_or_default(&path, 0, &BTreeMap::new());
+        assert!(error.is_none());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,421 @@
+//! Window state persistence.
+//!
+//! Remembers window bounds, the maximized state, split positions and selected
+//! tabs for each window, and writes them to a JSON file in the XDG config
+//! directory. Saves are debounced so dragging a window or a divider only
+//! writes the file once the user stops, and pending changes are written when
+//! the application quits.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::Result;
+use gpui::{
+    point, size, App, BorrowAppContext, Bounds, Global, Pixels, SharedString, Subscription, Window,
+    WindowBounds,
+};
+use serde::{Deserialize, Serialize};
+
+use super::write_atomic;
+use crate::utils::Debouncer;
+
+/// The file name used inside the application's config directory.
+const FILE_NAME: &str = "window-state.json";
+/// How long to wait after the last change before writing the file.
+const SAVE_DELAY: Duration = Duration::from_millis(500);
+
+/// The saved state of a single window.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowState {
+    /// The window bounds; for a maximized window, the bounds it restores to
+    pub bounds: Option<Bounds<Pixels>>,
+    /// Whether the window was maximized
+    pub maximized: bool,
+    /// Split view divider positions, keyed by split view name
+    pub split_sizes: BTreeMap<String, Pixels>,
+    /// Selected tab indices, keyed by tab view name
+    pub selected_tabs: BTreeMap<String, usize>,
+}
+
+impl WindowState {
+    /// Returns the saved bounds moved and shrunk to fit the given displays.
+    ///
+    /// The window is kept on the display it overlaps most. If it no longer
+    /// overlaps any display, e.g. because a monitor was unplugged, it is
+    /// centered on the first display.
+    pub fn fitted_bounds(&self, displays: &[Bounds<Pixels>]) -> Option<Bounds<Pixels>> {
+        let bounds = self.bounds?;
+        let Some(first) = displays.first() else {
+            return Some(bounds);
+        };
+
+        let display = displays
+            .iter()
+            .map(|display| (display, overlap_area(&bounds, display)))
+            .filter(|(_, area)| *area > 0.0)
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(display, _)| display);
+
+        let Some(display) = display else {
+            let size = size(
+                bounds.size.width.min(first.size.width),
+                bounds.size.height.min(first.size.height),
+            );
+            return Some(Bounds::centered_at(first.center(), size));
+        };
+
+        let width = bounds.size.width.min(display.size.width);
+        let height = bounds.size.height.min(display.size.height);
+        let x = bounds
+            .origin
+            .x
+            .clamp(display.left(), display.right() - width);
+        let y = bounds
+            .origin
+            .y
+            .clamp(display.top(), display.bottom() - height);
+        Some(Bounds::new(point(x, y), size(width, height)))
+    }
+}
+
+/// Returns the area two bounds have in common.
+fn overlap_area(a: &Bounds<Pixels>, b: &Bounds<Pixels>) -> f32 {
+    let width = f32::from(a.right().min(b.right()) - a.left().max(b.left()));
+    let height = f32::from(a.bottom().min(b.bottom()) - a.top().max(b.top()));
+    width.max(0.0) * height.max(0.0)
+}
+
+/// The saved state of all windows, stored in the `WindowStates` global.
+///
+/// Call [`WindowStates::init`] once at startup to load the state file.
+/// Until then, changes are kept in memory only. Load and save errors are
+/// available from [`WindowStates::last_error`].
+///
+/// # Example
+///
+/// ```ignore
+/// WindowStates::init("mail", cx);
+///
+/// // Restore the selected tab and save it when it changes
+/// TabView::new("inspector", 0)
+///     .selection(State::binding(&self.selected_tab, cx))
+///     .autosave("main", "inspector")
+/// ```
+pub struct WindowStates {
+    path: Option<PathBuf>,
+    windows: HashMap<String, WindowState>,
+    last_error: Option<SharedString>,
+    saver: Debouncer,
+    _on_quit: Option<Subscription>,
+}
+
+impl Default for WindowStates {
+    fn default() -> Self {
+        Self {
+            path: None,
+            windows: HashMap::new(),
+            last_error: None,
+            saver: Debouncer::new(SAVE_DELAY),
+            _on_quit: None,
+        }
+    }
+}
+
+impl Global for WindowStates {}
+
+impl WindowStates {
+    /// Loads the state file from `$XDG_CONFIG_HOME/<app_id>/window-state.json`.
+    pub fn init(app_id: &str, cx: &mut App) {
+        let path = dirs::config_dir().map(|dir| dir.join(app_id).join(FILE_NAME));
+        Self::init_with_path(path, cx);
+    }
+
+    /// Loads the state file from the given path, or keeps state in memory if `None`.
+    ///
+    /// A missing or unreadable file starts with no saved state. Pending
+    /// changes are written when the application quits.
+    pub fn init_with_path(path: Option<PathBuf>, cx: &mut App) {
+        let (windows, last_error) = match path.as_deref().map(load) {
+            Some(Ok(windows)) => (windows, None),
+            Some(Err(err)) => {
+                let not_found = err
+                    .downcast_ref::<io::Error>()
+                    .is_some_and(|err| err.kind() == io::ErrorKind::NotFound);
+                let error = (!not_found).then(|| {
+                    let path = path.as_deref().unwrap_or(Path::new("")).display();
+                    format!("Failed to load {path}: {err:#}").into()
+                });
+                (HashMap::new(), error)
+            }
+            None => (HashMap::new(), None),
+        };
+        let on_quit = cx.on_app_quit(|cx| {
+            Self::flush(cx).ok();
+            async {}
+        });
+        cx.set_global(Self {
+            path,
+            windows,
+            last_error,
+            _on_quit: Some(on_quit),
+            ..Default::default()
+        });
+    }
+
+    /// Returns why the state file couldn't be loaded or the last save
+    /// failed, or `None` if there was no error.
+    ///
+    /// A successful save clears the error. The global is updated when the
+    /// error changes, so views can watch it with `cx.observe_global`.
+    pub fn last_error(cx: &App) -> Option<SharedString> {
+        cx.try_global::<Self>()?.last_error.clone()
+    }
+
+    /// Returns the saved state of a window.
+    pub fn get(window_id: &str, cx: &App) -> Option<WindowState> {
+        cx.try_global::<Self>()?.windows.get(window_id).cloned()
+    }
+
+    /// Changes the saved state of a window and schedules a save.
+    pub fn update(window_id: &str, cx: &mut App, f: impl FnOnce(&mut WindowState)) {
+        cx.update_default_global(|states: &mut Self, cx| {
+            let state = states.windows.entry(window_id.to_string()).or_default();
+            let old = state.clone();
+            f(state);
+            if *state != old {
+                states.schedule_save(cx);
+            }
+        });
+    }
+
+    /// Returns the bounds to open a window with, fitted to the current displays.
+    pub fn window_bounds(window_id: &str, cx: &App) -> Option<WindowBounds> {
+        let state = Self::get(window_id, cx)?;
+        let displays: Vec<_> = cx
+            .displays()
+            .iter()
+            .map(|display| display.bounds())
+            .collect();
+        let bounds = state.fitted_bounds(&displays)?;
+        Some(if state.maximized {
+            WindowBounds::Maximized(bounds)
+        } else {
+            WindowBounds::Windowed(bounds)
+        })
+    }
+
+    /// Records a window's current bounds and maximized state.
+    ///
+    /// Fullscreen windows are saved with the bounds they restore to.
+    pub fn save_window_bounds(window_id: &str, window: &Window, cx: &mut App) {
+        let (bounds, maximized) = match window.window_bounds() {
+            WindowBounds::Windowed(bounds) => (bounds, false),
+            WindowBounds::Maximized(bounds) => (bounds, true),
+            WindowBounds::Fullscreen(bounds) => (bounds, false),
+        };
+        Self::update(window_id, cx, |state| {
+            state.bounds = Some(bounds);
+            state.maximized = maximized;
+        });
+    }
+
+    /// Returns the saved divider position of a split view.
+    pub fn split_size(window_id: &str, key: &str, cx: &App) -> Option<Pixels> {
+        Self::get(window_id, cx)?.split_sizes.get(key).copied()
+    }
+
+    /// Saves the divider position of a split view.
+    pub fn set_split_size(window_id: &str, key: &str, size: Pixels, cx: &mut App) {
+        Self::update(window_id, cx, |state| {
+            state.split_sizes.insert(key.to_string(), size);
+        });
+    }
+
+    /// Returns the saved selected index of a tab view.
+    pub fn selected_tab(window_id: &str, key: &str, cx: &App) -> Option<usize> {
+        Self::get(window_id, cx)?.selected_tabs.get(key).copied()
+    }
+
+    /// Saves the selected index of a tab view.
+    pub fn set_selected_tab(window_id: &str, key: &str, index: usize, cx: &mut App) {
+        Self::update(window_id, cx, |state| {
+            state.selected_tabs.insert(key.to_string(), index);
+        });
+    }
+
+    /// Writes the state file now instead of waiting for the debounced save.
+    ///
+    /// Pending changes are also written when the application quits, so
+    /// this is only needed to save sooner.
+    pub fn flush(cx: &mut App) -> Result<()> {
+        let Some(states) = cx.try_global::<Self>() else {
+            return Ok(());
+        };
+        let result = states.save();
+        cx.update_default_global(|states: &mut Self, _cx| states.saver.cancel());
+        Self::record(&result, cx);
+        result
+    }
+
+    fn schedule_save(&mut self, cx: &mut App) {
+        if self.path.is_none() {
+            return;
+        }
+        self.saver.call(cx, |cx| {
+            if let Some(states) = cx.try_global::<Self>() {
+                let result = states.save();
+                Self::record(&result, cx);
+            }
+        });
+    }
+
+    /// Keeps the error of a save for `last_error`, updating the global only
+    /// when it changes.
+    fn record(result: &Result<()>, cx: &mut App) {
+        let Some(states) = cx.try_global::<Self>() else {
+            return;
+        };
+        let error = result.as_ref().err().map(|err| {
+            let path = states.path.as_deref().unwrap_or(Path::new("")).display();
+            SharedString::from(format!("Failed to save {path}: {err:#}"))
+        });
+        if states.last_error != error {
+            cx.update_global(|states: &mut Self, _cx| states.last_error = error);
+        }
+    }
+
+    fn save(&self) -> Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        let windows: BTreeMap<_, _> = self.windows.iter().collect();
+        let json = serde_json::to_string_pretty(&windows)?;
+        write_atomic(path, &json)
+    }
+}
+
+fn load(path: &Path) -> Result<HashMap<String, WindowState>> {
+    let json = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{px, TestAppContext};
+
+    fn display(x: f32, y: f32, width: f32, height: f32) -> Bounds<Pixels> {
+        Bounds::new(point(px(x), px(y)), size(px(width), px(height)))
+    }
+
+    fn state_with_bounds(bounds: Bounds<Pixels>) -> WindowState {
+        WindowState {
+            bounds: Some(bounds),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_fitted_bounds_unchanged_when_visible() {
+        let bounds = display(100.0, 100.0, 800.0, 600.0);
+        let state = state_with_bounds(bounds);
+        let displays = [display(0.0, 0.0, 1920.0, 1080.0)];
+        assert_eq!(state.fitted_bounds(&displays), Some(bounds));
+    }
+
+    #[test]
+    fn test_fitted_bounds_moves_partially_offscreen_window() {
+        let state = state_with_bounds(display(1500.0, 900.0, 800.0, 600.0));
+        let displays = [display(0.0, 0.0, 1920.0, 1080.0)];
+        assert_eq!(
+            state.fitted_bounds(&displays),
+            Some(display(1120.0, 480.0, 800.0, 600.0))
+        );
+    }
+
+    #[test]
+    fn test_fitted_bounds_shrinks_to_display() {
+        let state = state_with_bounds(display(0.0, 0.0, 2560.0, 1440.0));
+        let displays = [display(0.0, 0.0, 1920.0, 1080.0)];
+        assert_eq!(
+            state.fitted_bounds(&displays),
+            Some(display(0.0, 0.0, 1920.0, 1080.0))
+        );
+    }
+
+    #[test]
+    fn test_fitted_bounds_keeps_second_display() {
+        let bounds = display(2000.0, 100.0, 800.0, 600.0);
+        let state = state_with_bounds(bounds);
+        let displays = [
+            display(0.0, 0.0, 1920.0, 1080.0),
+            display(1920.0, 0.0, 1920.0, 1080.0),
+        ];
+        assert_eq!(state.fitted_bounds(&displays), Some(bounds));
+    }
+
+    #[test]
+    fn test_fitted_bounds_centers_on_removed_display() {
+        let state = state_with_bounds(display(2000.0, 100.0, 800.0, 600.0));
+        let displays = [display(0.0, 0.0, 1920.0, 1080.0)];
+        assert_eq!(
+            state.fitted_bounds(&displays),
+            Some(display(560.0, 240.0, 800.0, 600.0))
+        );
+    }
+
+    #[test]
+    fn test_window_state_round_trip() {
+        let mut state = state_with_bounds(display(10.0, 20.0, 800.0, 600.0));
+        state.maximized = true;
+        state.split_sizes.insert("sidebar".to_string(), px(240.0));
+        state.selected_tabs.insert("inspector".to_string(), 2);
+
+        let json = serde_json::to_string(&state).unwrap();
+        let loaded: WindowState = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, state);
+
+        // Fields missing from older files fall back to their defaults
+        let loaded: WindowState = serde_json::from_str(r#"{"maximized":true}"#).unwrap();
+        assert!(loaded.maximized);
+        assert!(loaded.bounds.is_none());
+    }
+
+    #[gpui::test]
+    fn test_load_errors_are_kept(cx: &mut TestAppContext) {
+        let dir = std::env::temp_dir().join(format!("applib-window-state-{}", std::process::id()));
+        let path = dir.join(FILE_NAME);
+
+        // A missing file isn't an error
+        cx.update(|cx| WindowStates::init_with_path(Some(path.clone()), cx));
+        assert!(cx.read(WindowStates::last_error).is_none());
+
+        write_atomic(&path, "not json").unwrap();
+        cx.update(|cx| WindowStates::init_with_path(Some(path.clone()), cx));
+        assert!(cx.read(WindowStates::last_error).is_some());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[gpui::test]
+    fn test_pending_changes_saved_on_quit(cx: &mut TestAppContext) {
+        let dir = std::env::temp_dir().join(format!("applib-window-quit-{}", std::process::id()));
+        let path = dir.join(FILE_NAME);
+
+        cx.update(|cx| {
+            WindowStates::init_with_path(Some(path.clone()), cx);
+            WindowStates::set_selected_tab("main", "inspector", 2, cx);
+        });
+        assert!(!path.exists(), "the save is still debounced");
+
+        cx.quit();
+        let windows = load(&path).unwrap();
+        assert_eq!(windows["main"].selected_tabs["inspector"], 2);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
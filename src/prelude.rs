@@ -12,7 +12,7 @@
 pub use crate::components::*;
 
 // Re-export state management primitives
-pub use crate::state::{Binding, State};
+pub use crate::state::{Binding, Computed, ObservableVec, State, UndoManager, VecChange};
 
 // Re-export gpui prelude for convenience
 pub use gpui::prelude::*;
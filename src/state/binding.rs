@@ -20,18 +20,56 @@
 //! }
 //! ```
 
-use gpui::{App, Entity};
+use gpui::{App, Entity, Subscription};
+use std::rc::Rc;
 
 use super::State;
 
+type Getter<T> = Rc<dyn Fn(&App) -> T>;
+type Setter<T> = Rc<dyn Fn(T, &mut App)>;
+type OnChange = Rc<dyn Fn(&mut App)>;
+type Observer = Rc<dyn Fn(OnChange, &mut App) -> Option<Subscription>>;
+
+/// Where a binding reads and writes its value.
+enum Source<T: Clone + 'static> {
+    /// A whole `State<T>`
+    State(Entity<State<T>>),
+    /// A value projected from another binding
+    Derived {
+        get: Getter<T>,
+        set: Setter<T>,
+        observe: Observer,
+    },
+    /// A fixed value that ignores writes
+    Constant(T),
+}
+
+impl<T: Clone + 'static> Clone for Source<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Source::State(entity) => Source::State(entity.clone()),
+            Source::Derived { get, set, observe } => Source::Derived {
+                get: get.clone(),
+                set: set.clone(),
+                observe: observe.clone(),
+            },
+            Source::Constant(value) => Source::Constant(value.clone()),
+        }
+    }
+}
+
 /// A two-way binding to a `State<T>` value.
 ///
 /// Bindings allow components to both read and write state without
 /// requiring manual callback wiring. When a component updates a binding,
 /// the underlying state is updated and observers are notified automatically.
+///
+/// Besides pointing at a whole state, a binding can project part of another
+/// binding's value with `map`, `index` or `unwrap_or`, or hold a constant
+/// for previews and tests.
 #[derive(Clone)]
 pub struct Binding<T: Clone + 'static> {
-    entity: Entity<State<T>>,
+    source: Source<T>,
 }
 
 impl<T: Clone + 'static> Binding<T> {
@@ -39,12 +77,28 @@ impl<T: Clone + 'static> Binding<T> {
     ///
     /// Typically you'll use `State::binding(&entity, cx)` instead of calling this directly.
     pub fn new(entity: Entity<State<T>>, _cx: &App) -> Self {
-        Self { entity }
+        Self {
+            source: Source::State(entity),
+        }
+    }
+
+    /// Creates a binding to a fixed value.
+    ///
+    /// Reads return the value and writes are ignored, so components can be
+    /// shown in previews and tests without creating state.
+    pub fn constant(value: T) -> Self {
+        Self {
+            source: Source::Constant(value),
+        }
     }
 
     /// Returns a clone of the current value.
     pub fn get(&self, cx: &App) -> T {
-        self.entity.read(cx).get().clone()
+        match &self.source {
+            Source::State(entity) => entity.read(cx).get().clone(),
+            Source::Derived { get, .. } => get(cx),
+            Source::Constant(value) => value.clone(),
+        }
     }
 
     /// Sets a new value, notifying observers.
@@ -52,24 +106,143 @@ impl<T: Clone + 'static> Binding<T> {
     /// This updates the underlying `State<T>` and triggers re-renders
     /// for any views observing the state.
     pub fn set(&self, value: T, cx: &mut App) {
-        self.entity.update(cx, |state, cx| {
-            state.set(value, cx);
-        });
+        match &self.source {
+            Source::State(entity) => entity.update(cx, |state, cx| state.set(value, cx)),
+            Source::Derived { set, .. } => set(value, cx),
+            Source::Constant(_) => {}
+        }
     }
 
     /// Updates the value using a closure, notifying observers.
     pub fn update(&self, f: impl FnOnce(&mut T), cx: &mut App) {
-        self.entity.update(cx, |state, cx| {
-            state.update(f, cx);
-        });
+        match &self.source {
+            Source::State(entity) => entity.update(cx, |state, cx| state.update(f, cx)),
+            Source::Derived { .. } => {
+                let mut value = self.get(cx);
+                f(&mut value);
+                self.set(value, cx);
+            }
+            Source::Constant(_) => {}
+        }
     }
 
     /// Returns a reference to the underlying entity.
     ///
     /// This is useful when you need direct access to the entity for
     /// observation or other GPUI operations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the binding wasn't created from a `State<T>`. Use
+    /// [`try_entity`](Self::try_entity) for derived and constant bindings.
     pub fn entity(&self) -> &Entity<State<T>> {
-        &self.entity
+        self.try_entity()
+            .expect("Binding::entity called on a derived or constant binding")
+    }
+
+    /// Returns the underlying entity of a binding created from a `State<T>`.
+    ///
+    /// Derived and constant bindings return `None`.
+    pub fn try_entity(&self) -> Option<&Entity<State<T>>> {
+        match &self.source {
+            Source::State(entity) => Some(entity),
+            _ => None,
+        }
+    }
+
+    /// Returns true if this is a constant binding.
+    pub fn is_constant(&self) -> bool {
+        matches!(self.source, Source::Constant(_))
+    }
+
+    /// Creates a binding that reads and writes `entity` through the given functions.
+    pub(crate) fn from_entity<E: 'static>(
+        entity: &Entity<E>,
+        get: impl Fn(&App) -> T + 'static,
+        set: impl Fn(T, &mut App) + 'static,
+    ) -> Self {
+        let entity = entity.clone();
+        Self {
+            source: Source::Derived {
+                get: Rc::new(get),
+                set: Rc::new(set),
+                observe: Rc::new(move |on_change: OnChange, cx: &mut App| {
+                    Some(cx.observe(&entity, move |_, cx| on_change(cx)))
+                }),
+            },
+        }
+    }
+
+    /// Calls `on_change` whenever the state behind this binding notifies.
+    ///
+    /// Returns `None` for constant bindings, which never change.
+    pub(crate) fn observe(
+        &self,
+        on_change: impl Fn(&mut App) + 'static,
+        cx: &mut App,
+    ) -> Option<Subscription> {
+        self.observe_with(Rc::new(on_change), cx)
+    }
+
+    fn observe_with(&self, on_change: OnChange, cx: &mut App) -> Option<Subscription> {
+        match &self.source {
+            Source::State(entity) => Some(cx.observe(entity, move |_, cx| on_change(cx))),
+            Source::Derived { observe, .. } => observe(on_change, cx),
+            Source::Constant(_) => None,
+        }
+    }
+
+    /// Calls `f` with a reference to the current value.
+    fn read<R>(&self, cx: &App, f: impl FnOnce(&T) -> R) -> R {
+        match &self.source {
+            Source::State(entity) => f(entity.read(cx).get()),
+            Source::Derived { get, .. } => f(&get(cx)),
+            Source::Constant(value) => f(value),
+        }
+    }
+
+    /// Creates a binding to a part of this binding's value.
+    ///
+    /// `get` extracts the part; `set` writes a new part into the value.
+    /// Writing through the derived binding updates this one, so observers
+    /// of the underlying state are notified.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let settings: Binding<Settings> = State::binding(&self.settings, cx);
+    /// let dark_mode = settings.map(|s| s.dark_mode, |s, on| s.dark_mode = on);
+    ///
+    /// Toggle::new("dark-mode", "Dark mode", false).is_on(dark_mode)
+    /// ```
+    pub fn map<U: Clone + 'static>(
+        &self,
+        get: impl Fn(&T) -> U + 'static,
+        set: impl Fn(&mut T, U) + 'static,
+    ) -> Binding<U> {
+        let (parent, setter_parent, observed_parent) = (self.clone(), self.clone(), self.clone());
+        Binding {
+            source: Source::Derived {
+                get: Rc::new(move |cx| parent.read(cx, &get)),
+                set: Rc::new(move |part, cx| setter_parent.update(|value| set(value, part), cx)),
+                observe: Rc::new(move |on_change, cx| observed_parent.observe_with(on_change, cx)),
+            },
+        }
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> Binding<T> {
+    /// Sets a new value if it differs from the current one.
+    ///
+    /// Observers are only notified when the value changes. Components use
+    /// this to write back user edits, so echoing the bound value doesn't
+    /// trigger another render. Returns true if the value changed.
+    pub fn set_if_changed(&self, value: T, cx: &mut App) -> bool {
+        if self.read(cx, |current| current == &value) {
+            return false;
+        }
+        self.set(value, cx);
+        true
     }
 }
 
@@ -80,12 +253,42 @@ impl<T: Clone + Default + 'static> Binding<T> {
     }
 }
 
+impl<T: Clone + Default + 'static> Binding<Vec<T>> {
+    /// Creates a binding to the element at `index`.
+    ///
+    /// Reading returns `T::default()` while the vector has no element at
+    /// `index`, so a binding held by a row doesn't panic when the vector
+    /// shrinks. Writing to a missing element is ignored.
+    pub fn index(&self, index: usize) -> Binding<T> {
+        self.map(
+            move |items| items.get(index).cloned().unwrap_or_default(),
+            move |items, item| {
+                if let Some(slot) = items.get_mut(index) {
+                    *slot = item;
+                }
+            },
+        )
+    }
+}
+
+impl<T: Clone + 'static> Binding<Option<T>> {
+    /// Creates a binding that reads `default` while the value is `None`.
+    ///
+    /// Writing through it always stores `Some`.
+    pub fn unwrap_or(&self, default: T) -> Binding<T> {
+        self.map(
+            move |value| value.clone().unwrap_or_else(|| default.clone()),
+            |value, item| *value = Some(item),
+        )
+    }
+}
+
 // Convenience implementations for common types
 
 impl Binding<String> {
     /// Returns true if the string is empty.
     pub fn is_empty(&self, cx: &App) -> bool {
-        self.entity.read(cx).get().is_empty()
+        self.read(cx, String::is_empty)
     }
 
     /// Clears the string value.
@@ -104,12 +307,12 @@ impl Binding<bool> {
 impl<T: Clone + 'static> Binding<Option<T>> {
     /// Returns true if the option is None.
     pub fn is_none(&self, cx: &App) -> bool {
-        self.entity.read(cx).get().is_none()
+        self.read(cx, Option::is_none)
     }
 
     /// Returns true if the option is Some.
     pub fn is_some(&self, cx: &App) -> bool {
-        self.entity.read(cx).get().is_some()
+        self.read(cx, Option::is_some)
     }
 
     /// Clears the option to None.
@@ -117,3 +320,84 @@ impl<T: Clone + 'static> Binding<Option<T>> {
         self.set(None, cx);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{AppContext as _, TestAppContext};
+
+    #[derive(Clone, Default)]
+    struct Settings {
+        dark_mode: bool,
+        recent: Vec<String>,
+    }
+
+    #[test]
+    fn test_binding_sources() {
+        let settings = Binding::constant(Settings::default());
+        assert!(settings.is_constant());
+        assert!(settings.try_entity().is_none());
+
+        // Projections are derived, even from a constant
+        let dark_mode = settings.map(|s| s.dark_mode, |s, on| s.dark_mode = on);
+        assert!(!dark_mode.is_constant());
+        assert!(dark_mode.try_entity().is_none());
+
+        // Indexing is lazy and reads a default when the element is missing
+        let recent = settings.map(|s| s.recent.clone(), |s, recent| s.recent = recent);
+        assert!(!recent.index(0).is_constant());
+    }
+
+    #[gpui::test]
+    fn test_map_round_trip(cx: &mut TestAppContext) {
+        let settings = cx.new(|_| State::new(Settings::default()));
+        let dark_mode = cx.update(|cx| {
+            State::binding(&settings, cx).map(|s| s.dark_mode, |s, on| s.dark_mode = on)
+        });
+        cx.update(|cx| {
+            assert!(!dark_mode.get(cx));
+            dark_mode.set(true, cx);
+            assert!(dark_mode.get(cx));
+            dark_mode.toggle(cx);
+        });
+        assert!(!settings.read_with(cx, |settings, _| settings.get().dark_mode));
+        settings.update(cx, |settings, cx| {
+            settings.update(|s| s.dark_mode = true, cx)
+        });
+        assert!(cx.update(|cx| dark_mode.get(cx)));
+    }
+
+    #[gpui::test]
+    fn test_index_round_trip(cx: &mut TestAppContext) {
+        let recent = cx.new(|_| State::new(vec!["a".to_string(), "b".to_string()]));
+        let (second, missing) = cx.update(|cx| {
+            let binding = State::binding(&recent, cx);
+            (binding.index(1), binding.index(5))
+        });
+        cx.update(|cx| {
+            assert_eq!(second.get(cx), "b");
+            second.set("c".to_string(), cx);
+            // An index past the end reads the default and ignores writes
+            assert_eq!(missing.get(cx), "");
+            missing.set("d".to_string(), cx);
+        });
+        assert_eq!(
+            recent.read_with(cx, |recent, _| recent.get().clone()),
+            ["a", "c"]
+        );
+    }
+
+    #[gpui::test]
+    fn test_unwrap_or_round_trip(cx: &mut TestAppContext) {
+        let limit = cx.new(|_| State::new(None::<u32>));
+        let binding = cx.update(|cx| State::binding(&limit, cx).unwrap_or(10));
+        cx.update(|cx| {
+            assert_eq!(binding.get(cx), 10);
+            binding.set(20, cx);
+            assert_eq!(binding.get(cx), 20);
+        });
+        assert_eq!(limit.read_with(cx, |limit, _| *limit.get()), Some(20));
+        limit.update(cx, |limit, cx| limit.set(None, cx));
+        assert_eq!(cx.update(|cx| binding.get(cx)), 10);
+    }
+}
@@ -0,0 +1,315 @@
+//! Derived state that recomputes when its dependencies change.
+//!
+//! `Computed<T>` holds a value calculated from other entities, such as a
+//! filtered list derived from items and a search query. It observes the
+//! entities it depends on, recomputes when they notify, and only notifies
+//! its own observers when the result actually changed.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use applib::prelude::*;
+//!
+//! let items: Entity<State<Vec<String>>> = cx.new(|_| State::new(names));
+//! let query: Entity<State<String>> = cx.new(|_| State::new(String::new()));
+//!
+//! let visible = cx.new(|cx| {
+//!     let (items_source, query_source) = (items.clone(), query.clone());
+//!     Computed::new(
+//!         move |cx| {
+//!             let query = query_source.read(cx).get().to_lowercase();
+//!             items_source
+//!                 .read(cx)
+//!                 .get()
+//!                 .iter()
+//!                 .filter(|item| item.to_lowercase().contains(&query))
+//!                 .cloned()
+//!                 .collect::<Vec<_>>()
+//!         },
+//!         cx,
+//!     )
+//!     .depends_on(&items, cx)
+//!     .depends_on(&query, cx)
+//! });
+//! ```
+
+use gpui::{App, Context, Entity, Subscription, Task};
+use std::cell::{Cell, Ref, RefCell};
+use std::rc::Rc;
+
+type SyncCompute<T> = Rc<dyn Fn(&App) -> T>;
+type AsyncCompute<T> = Rc<dyn Fn(&mut App) -> Task<T>>;
+
+/// How a computed value is produced.
+enum Compute<T> {
+    Sync(SyncCompute<T>),
+    Async(AsyncCompute<T>),
+}
+
+/// A value derived from other entities.
+///
+/// Synchronous computations are lazy: a dependency change only marks the
+/// value stale, and it is recomputed by the next `get`, or at the end of
+/// the current update to find out whether observers need notifying.
+/// Several dependency changes in one update cause a single recomputation.
+///
+/// Asynchronous computations start a task on every change and keep showing
+/// the previous value until it finishes. Starting a new computation drops,
+/// and so cancels, the one still running.
+///
+/// Observers are only notified when the new value differs from the old one.
+pub struct Computed<T: PartialEq + 'static> {
+    compute: Compute<T>,
+    value: RefCell<T>,
+    /// Whether a dependency changed since the value was computed
+    dirty: Cell<bool>,
+    /// Whether `get` recomputed a different value observers haven't seen
+    unnotified: Cell<bool>,
+    flush_scheduled: bool,
+    pending: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl<T: PartialEq + 'static> Computed<T> {
+    /// Creates a computed value from a synchronous computation.
+    ///
+    /// The computation runs once immediately. Add the entities it reads
+    /// with `depends_on`.
+    pub fn new(compute: impl Fn(&App) -> T + 'static, cx: &mut Context<Self>) -> Self {
+        let value = compute(cx);
+        Self {
+            compute: Compute::Sync(Rc::new(compute)),
+            value: RefCell::new(value),
+            dirty: Cell::new(false),
+            unnotified: Cell::new(false),
+            flush_scheduled: false,
+            pending: None,
+            _subscriptions: Vec::new(),
+        }
+    }
+
+    /// Creates a computed value from an asynchronous computation.
+    ///
+    /// `initial` is shown until the first computation, which starts
+    /// immediately, finishes.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let results = cx.new(|cx| {
+    ///     let query = query.clone();
+    ///     Computed::new_async(Vec::new(), move |cx| {
+    ///         let query = query.read(cx).get().clone();
+    ///         cx.background_spawn(async move { search_index(&query) })
+    ///     }, cx)
+    ///     .depends_on(&query, cx)
+    /// });
+    /// ```
+    pub fn new_async(
+        initial: T,
+        compute: impl Fn(&mut App) -> Task<T> + 'static,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut this = Self {
+            compute: Compute::Async(Rc::new(compute)),
+            value: RefCell::new(initial),
+            dirty: Cell::new(true),
+            unnotified: Cell::new(false),
+            flush_scheduled: false,
+            pending: None,
+            _subscriptions: Vec::new(),
+        };
+        this.flush(cx);
+        this
+    }
+
+    /// Recomputes the value whenever `entity` notifies.
+    pub fn depends_on<E: 'static>(mut self, entity: &Entity<E>, cx: &mut Context<Self>) -> Self {
+        self._subscriptions
+            .push(cx.observe(entity, |this, _, cx| this.invalidate(cx)));
+        self
+    }
+
+    /// Returns the current value, recomputing it first if it's stale.
+    ///
+    /// Asynchronous values return the last finished result.
+    pub fn get(&self, cx: &App) -> Ref<'_, T> {
+        if let Compute::Sync(ref compute) = self.compute {
+            if self.dirty.get() {
+                self.store(compute(cx));
+            }
+        }
+        self.value.borrow()
+    }
+
+    /// Returns true while an asynchronous computation is running.
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Marks the value stale, as if a dependency had changed.
+    pub fn invalidate(&mut self, cx: &mut Context<Self>) {
+        self.dirty.set(true);
+        if self.flush_scheduled {
+            return;
+        }
+        self.flush_scheduled = true;
+        let this = cx.weak_entity();
+        cx.defer(move |cx| {
+            this.update(cx, |this, cx| this.flush(cx)).ok();
+        });
+    }
+
+    /// Stores a recomputed value, remembering whether it changed.
+    fn store(&self, value: T) {
+        self.dirty.set(false);
+        if *self.value.borrow() != value {
+            *self.value.borrow_mut() = value;
+            self.unnotified.set(true);
+        }
+    }
+
+    /// Brings a stale value up to date and notifies observers if it changed.
+    fn flush(&mut self, cx: &mut Context<Self>) {
+        self.flush_scheduled = false;
+        match self.compute {
+            Compute::Sync(ref compute) => {
+                if self.dirty.get() {
+                    let compute = compute.clone();
+                    self.store(compute(cx));
+                }
+                if self.unnotified.take() {
+                    cx.notify();
+                }
+            }
+            Compute::Async(ref compute) => {
+                if !self.dirty.replace(false) {
+                    return;
+                }
+                let task = compute.clone()(cx);
+                // Replacing the previous task cancels it
+                self.pending = Some(cx.spawn(async move |this, cx| {
+                    let value = task.await;
+                    this.update(cx, |this, cx| {
+                        this.pending = None;
+                        if *this.value.borrow() != value {
+                            *this.value.get_mut() = value;
+                            cx.notify();
+                        }
+                    })
+                    .ok();
+                }));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::State;
+    use futures::channel::oneshot;
+    use gpui::{AppContext as _, TestAppContext};
+
+    fn computed(value: i32) -> Computed<i32> {
+        Computed {
+            compute: Compute::Sync(Rc::new(|_| 0)),
+            value: RefCell::new(value),
+            dirty: Cell::new(true),
+            unnotified: Cell::new(false),
+            flush_scheduled: false,
+            pending: None,
+            _subscriptions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_store() {
+        let computed = computed(1);
+        computed.store(1);
+        assert!(!computed.dirty.get());
+        assert!(
+            !computed.unnotified.get(),
+            "an equal value needs no notification"
+        );
+
+        computed.dirty.set(true);
+        computed.store(2);
+        assert!(!computed.dirty.get());
+        assert!(computed.unnotified.get());
+        assert_eq!(*computed.value.borrow(), 2);
+    }
+
+    #[gpui::test]
+    fn test_sync_recomputes_once_per_update(cx: &mut TestAppContext) {
+        let source = cx.new(|_| State::new(1));
+        let runs = Rc::new(Cell::new(0));
+        let computed = cx.new(|cx| {
+            let (read_source, runs) = (source.clone(), runs.clone());
+            Computed::new(
+                move |cx| {
+                    runs.set(runs.get() + 1);
+                    read_source.read(cx).get() % 2
+                },
+                cx,
+            )
+            .depends_on(&source, cx)
+        });
+        let notified = Rc::new(Cell::new(0));
+        cx.update(|cx| {
+            let notified = notified.clone();
+            cx.observe(&computed, move |_, _| notified.set(notified.get() + 1))
+                .detach();
+        });
+
+        // Two changes in one update recompute once, to the same parity
+        cx.update(|cx| {
+            source.update(cx, |source, cx| source.set(2, cx));
+            source.update(cx, |source, cx| source.set(3, cx));
+        });
+        cx.run_until_parked();
+        assert_eq!(runs.get(), 2);
+        assert_eq!(notified.get(), 0);
+
+        source.update(cx, |source, cx| source.set(4, cx));
+        cx.run_until_parked();
+        assert_eq!(runs.get(), 3);
+        assert_eq!(notified.get(), 1);
+        computed.read_with(cx, |computed, cx| assert_eq!(*computed.get(cx), 0));
+    }
+
+    #[gpui::test]
+    fn test_async_replace_cancels_previous(cx: &mut TestAppContext) {
+        let source = cx.new(|_| State::new(0));
+        let senders = Rc::new(RefCell::new(Vec::new()));
+        let computed = cx.new(|cx| {
+            let senders = senders.clone();
+            Computed::new_async(
+                0,
+                move |cx| {
+                    let (sender, receiver) = oneshot::channel::<i32>();
+                    senders.borrow_mut().push(sender);
+                    cx.background_spawn(async move { receiver.await.unwrap_or(-1) })
+                },
+                cx,
+            )
+            .depends_on(&source, cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(senders.borrow().len(), 1);
+
+        source.update(cx, |source, cx| source.set(1, cx));
+        cx.run_until_parked();
+        assert_eq!(senders.borrow().len(), 2);
+        assert!(computed.read_with(cx, |computed, _| computed.is_pending()));
+
+        let first = senders.borrow_mut().remove(0);
+        assert!(first.send(10).is_err(), "the first computation was dropped");
+        senders.borrow_mut().remove(0).send(20).unwrap();
+        cx.run_until_parked();
+        computed.read_with(cx, |computed, cx| {
+            assert!(!computed.is_pending());
+            assert_eq!(*computed.get(cx), 20);
+        });
+    }
+}
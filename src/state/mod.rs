@@ -4,6 +4,9 @@
 //!
 //! - [`State<T>`] - Observable value container with automatic change notification
 //! - [`Binding<T>`] - Two-way reference for component bindings
+//! - [`Computed<T>`] - Value derived from other state, recomputed on change
+//! - [`ObservableVec<T>`] - List that reports inserts, removals and moves
+//! - [`UndoManager`] - Application-wide undo history of state changes
 //!
 //! # Example
 //!
@@ -41,7 +44,13 @@
 //! ```
 
 mod binding;
+mod computed;
+mod observable_vec;
 mod state;
+mod undo;
 
 pub use binding::Binding;
+pub use computed::Computed;
+pub use observable_vec::{ObservableVec, VecChange};
 pub use state::State;
+pub use undo::{Redo, Undo, UndoManager};
@@ -0,0 +1,483 @@
+//! Observable collection with fine-grained change events.
+//!
+//! `ObservableVec<T>` is a list of items that reports each change as a
+//! [`VecChange`] event instead of just "something changed". It uses the
+//! changes to keep selection bindings pointing at the same items and to keep
+//! virtualized lists from jumping when rows are added or removed above the
+//! visible area.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use applib::prelude::*;
+//!
+//! let messages = cx.new(|_| ObservableVec::keyed(load_messages(), |m: &Message| m.id));
+//! let selected = cx.new(|_| State::new(None::<usize>));
+//! let scroll_handle = UniformListScrollHandle::new();
+//!
+//! let selection = State::binding(&selected, cx);
+//! messages.update(cx, |messages, _cx| {
+//!     messages.remap_selection(selection);
+//!     messages.keep_scroll_position(scroll_handle.clone());
+//! });
+//!
+//! // The selected message stays selected and visible rows stay in place
+//! messages.update(cx, |messages, cx| messages.insert(0, new_message, cx));
+//! ```
+
+use std::any::Any;
+use std::ops::Range;
+use std::rc::Rc;
+
+use gpui::{point, px, Context, EventEmitter, UniformListScrollHandle};
+
+use super::Binding;
+
+type KeyFn<T> = Rc<dyn Fn(&T) -> Box<dyn ItemKey>>;
+
+/// The key of an item in a keyed collection, compared by value.
+trait ItemKey {
+    fn as_any(&self) -> &dyn Any;
+
+    /// Returns whether `other` is a key of the same type with the same value.
+    fn eq_key(&self, other: &dyn Any) -> bool;
+}
+
+impl<K: Eq + 'static> ItemKey for K {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn eq_key(&self, other: &dyn Any) -> bool {
+        other.downcast_ref::<K>() == Some(self)
+    }
+}
+
+/// A change made to an [`ObservableVec`].
+///
+/// Indices refer to the collection as it was before the change, except for
+/// `Insert`, whose index is where the new items now start.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VecChange {
+    /// `count` items were inserted at `index`.
+    Insert { index: usize, count: usize },
+    /// `count` items were removed starting at `index`.
+    Remove { index: usize, count: usize },
+    /// The item at `from` was moved so it is now at `to`.
+    Move { from: usize, to: usize },
+    /// The item at `index` was changed in place.
+    Update { index: usize },
+    /// All items were replaced.
+    Reset,
+}
+
+impl VecChange {
+    /// Returns where the item at `index` is after this change.
+    ///
+    /// Returns `None` if the item was removed, or for `Reset`, which
+    /// doesn't say where items went.
+    pub fn remap(&self, index: usize) -> Option<usize> {
+        match *self {
+            VecChange::Insert { index: at, count } => {
+                Some(if index >= at { index + count } else { index })
+            }
+            VecChange::Remove { index: at, count } => {
+                if index < at {
+                    Some(index)
+                } else if index < at + count {
+                    None
+                } else {
+                    Some(index - count)
+                }
+            }
+            VecChange::Move { from, to } => Some(if index == from {
+                to
+            } else if from < index && index <= to {
+                index - 1
+            } else if to <= index && index < from {
+                index + 1
+            } else {
+                index
+            }),
+            VecChange::Update { .. } => Some(index),
+            VecChange::Reset => None,
+        }
+    }
+
+    /// Returns the row that should be first in view after this change, given
+    /// the first row in view before it.
+    ///
+    /// Rows inserted or removed above the first visible row move it, so the
+    /// same row stays at the top. If that row itself was removed, the row
+    /// that took its place is used.
+    fn anchor(&self, top: usize) -> usize {
+        match *self {
+            VecChange::Remove { index, count } if index <= top && top < index + count => index,
+            VecChange::Move { from, .. } if from == top => top,
+            _ => self.remap(top).unwrap_or(top),
+        }
+    }
+}
+
+/// An observable list that emits a [`VecChange`] for every change.
+///
+/// Observers are notified as with `State<T>`, and subscribers receive the
+/// change itself, e.g. to update a cache for just the affected rows.
+///
+/// A keyed collection identifies items by a key such as a database ID.
+/// When all items are replaced with `set`, selections and the scroll
+/// position follow their items by key.
+pub struct ObservableVec<T> {
+    items: Vec<T>,
+    key: Option<KeyFn<T>>,
+    selections: Vec<Binding<Option<usize>>>,
+    scroll_handles: Vec<UniformListScrollHandle>,
+}
+
+impl<T: 'static> EventEmitter<VecChange> for ObservableVec<T> {}
+
+impl<T: 'static> ObservableVec<T> {
+    /// Creates a collection with the given items.
+    pub fn new(items: Vec<T>) -> Self {
+        Self {
+            items,
+            key: None,
+            selections: Vec::new(),
+            scroll_handles: Vec::new(),
+        }
+    }
+
+    /// Creates a collection whose items are identified by `key`.
+    ///
+    /// Keys should be unique within the collection.
+    pub fn keyed<K: Eq + 'static>(items: Vec<T>, key: impl Fn(&T) -> K + 'static) -> Self {
+        Self {
+            key: Some(Rc::new(move |item| Box::new(key(item)))),
+            ..Self::new(items)
+        }
+    }
+
+    /// Returns the items.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Returns the item at `index`.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.items.get(index)
+    }
+
+    /// Returns the number of items.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if there are no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the index of the item with the given key in a keyed collection.
+    ///
+    /// `key` must have the type returned by the collection's key function;
+    /// a key of another type matches no item.
+    pub fn position_of_key<K: Eq + 'static>(&self, key: &K) -> Option<usize> {
+        let key_fn = self.key.as_ref()?;
+        self.items.iter().position(|item| key_fn(item).eq_key(key))
+    }
+
+    /// Keeps a selection binding pointing at the same item as items change.
+    ///
+    /// Removing the selected item clears the selection.
+    pub fn remap_selection(&mut self, selection: Binding<Option<usize>>) {
+        self.selections.push(selection);
+    }
+
+    /// Keeps the rows in view in place when rows are added or removed above them.
+    ///
+    /// Pass the handle given to the `List`, `Table` or `LazyVStack` showing
+    /// the items with `track_scroll`.
+    pub fn keep_scroll_position(&mut self, handle: UniformListScrollHandle) {
+        self.scroll_handles.push(handle);
+    }
+
+    /// Adds an item at the end.
+    pub fn push(&mut self, item: T, cx: &mut Context<Self>) {
+        let index = self.items.len();
+        self.insert(index, item, cx);
+    }
+
+    /// Adds items at the end.
+    pub fn extend(&mut self, items: impl IntoIterator<Item = T>, cx: &mut Context<Self>) {
+        let index = self.items.len();
+        self.insert_many(index, items, cx);
+    }
+
+    /// Inserts an item at `index`, shifting later items down.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, item: T, cx: &mut Context<Self>) {
+        self.insert_many(index, [item], cx);
+    }
+
+    /// Inserts items starting at `index`, shifting later items down.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert_many(
+        &mut self,
+        index: usize,
+        items: impl IntoIterator<Item = T>,
+        cx: &mut Context<Self>,
+    ) {
+        let len = self.items.len();
+        self.items.splice(index..index, items);
+        let count = self.items.len() - len;
+        if count > 0 {
+            self.changed(VecChange::Insert { index, count }, cx);
+        }
+    }
+
+    /// Removes and returns the item at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize, cx: &mut Context<Self>) -> T {
+        let item = self.items.remove(index);
+        self.changed(VecChange::Remove { index, count: 1 }, cx);
+        item
+    }
+
+    /// Removes and returns the items in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn remove_range(&mut self, range: Range<usize>, cx: &mut Context<Self>) -> Vec<T> {
+        let index = range.start;
+        let removed: Vec<T> = self.items.drain(range).collect();
+        if !removed.is_empty() {
+            let count = removed.len();
+            self.changed(VecChange::Remove { index, count }, cx);
+        }
+        removed
+    }
+
+    /// Removes the items for which `keep` returns false.
+    ///
+    /// Each run of removed items is reported as its own `Remove`, from the
+    /// end of the collection to the start.
+    pub fn retain(&mut self, mut keep: impl FnMut(&T) -> bool, cx: &mut Context<Self>) {
+        let mut index = self.items.len();
+        while index > 0 {
+            let end = index;
+            while index > 0 && !keep(&self.items[index - 1]) {
+                index -= 1;
+            }
+            if index < end {
+                self.remove_range(index..end, cx);
+            } else {
+                index -= 1;
+            }
+        }
+    }
+
+    /// Removes all items.
+    pub fn clear(&mut self, cx: &mut Context<Self>) {
+        let len = self.items.len();
+        self.remove_range(0..len, cx);
+    }
+
+    /// Moves the item at `from` so it ends up at `to`.
+    ///
+    /// This matches the indices `List::on_reorder` reports.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of bounds.
+    pub fn move_item(&mut self, from: usize, to: usize, cx: &mut Context<Self>) {
+        if from == to {
+            return;
+        }
+        let item = self.items.remove(from);
+        self.items.insert(to, item);
+        self.changed(VecChange::Move { from, to }, cx);
+    }
+
+    /// Changes the item at `index` in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn update(&mut self, index: usize, f: impl FnOnce(&mut T), cx: &mut Context<Self>) {
+        f(&mut self.items[index]);
+        self.changed(VecChange::Update { index }, cx);
+    }
+
+    /// Replaces all items.
+    ///
+    /// In a keyed collection, selections and the scroll position follow
+    /// their items by key; otherwise the selection is cleared.
+    pub fn set(&mut self, items: Vec<T>, cx: &mut Context<Self>) {
+        let selected_keys: Vec<_> = self
+            .selections
+            .iter()
+            .map(|selection| selection.get(cx).and_then(|index| self.key_at(index)))
+            .collect();
+        let top_keys: Vec<_> = self
+            .scroll_handles
+            .iter()
+            .map(|handle| scroll_top(handle).and_then(|(top, _)| Some((top, self.key_at(top)?))))
+            .collect();
+
+        self.items = items;
+
+        for (selection, key) in self.selections.iter().zip(selected_keys) {
+            let index = key.and_then(|key| self.index_of_key(key.as_ref()));
+            selection.set_if_changed(index, cx);
+        }
+        for (handle, top) in self.scroll_handles.iter().zip(top_keys) {
+            if let Some((top, key)) = top {
+                if let Some(new_top) = self.index_of_key(key.as_ref()) {
+                    shift_scroll(handle, top, new_top);
+                }
+            }
+        }
+
+        cx.emit(VecChange::Reset);
+        cx.notify();
+    }
+
+    /// Applies a change to the bound selections and scroll positions, then reports it.
+    fn changed(&mut self, change: VecChange, cx: &mut Context<Self>) {
+        for selection in &self.selections {
+            if let Some(index) = selection.get(cx) {
+                selection.set_if_changed(change.remap(index), cx);
+            }
+        }
+        for handle in &self.scroll_handles {
+            // At the very top, rows inserted at the start come into view
+            if let Some((top, true)) = scroll_top(handle) {
+                shift_scroll(handle, top, change.anchor(top));
+            }
+        }
+        cx.emit(change);
+        cx.notify();
+    }
+
+    fn key_at(&self, index: usize) -> Option<Box<dyn ItemKey>> {
+        let key = self.key.as_ref()?;
+        self.items.get(index).map(|item| key(item))
+    }
+
+    fn index_of_key(&self, key: &dyn ItemKey) -> Option<usize> {
+        let key_fn = self.key.as_ref()?;
+        self.items
+            .iter()
+            .position(|item| key_fn(item).eq_key(key.as_any()))
+    }
+}
+
+impl<T: Clone + 'static> ObservableVec<T> {
+    /// Returns a copy of the items.
+    pub fn to_vec(&self) -> Vec<T> {
+        self.items.clone()
+    }
+}
+
+/// Returns the first visible row of a list and whether it's scrolled at all.
+///
+/// Returns `None` until the list has been laid out.
+fn scroll_top(handle: &UniformListScrollHandle) -> Option<(usize, bool)> {
+    let state = handle.0.borrow();
+    let row_height = state.last_item_size?.item.height;
+    if row_height <= px(0.0) {
+        return None;
+    }
+    let offset = -state.base_handle.offset().y;
+    let top = (offset / row_height).floor().max(0.0) as usize;
+    Some((top, offset > px(0.0)))
+}
+
+/// Scrolls a list by the height of the rows between `top` and `new_top`.
+fn shift_scroll(handle: &UniformListScrollHandle, top: usize, new_top: usize) {
+    if top == new_top {
+        return;
+    }
+    let state = handle.0.borrow();
+    let Some(size) = state.last_item_size else {
+        return;
+    };
+    let delta = size.item.height * (new_top as f32 - top as f32);
+    let offset = state.base_handle.offset();
+    // The list clamps offsets past the end when it's laid out
+    let y = (offset.y - delta).min(px(0.0));
+    state.base_handle.set_offset(point(offset.x, y));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remap() {
+        let insert = VecChange::Insert { index: 2, count: 3 };
+        assert_eq!(insert.remap(1), Some(1));
+        assert_eq!(insert.remap(2), Some(5));
+
+        let remove = VecChange::Remove { index: 2, count: 2 };
+        assert_eq!(remove.remap(1), Some(1));
+        assert_eq!(remove.remap(3), None);
+        assert_eq!(remove.remap(4), Some(2));
+
+        let down = VecChange::Move { from: 1, to: 3 };
+        assert_eq!(
+            (0..5).map(|i| down.remap(i).unwrap()).collect::<Vec<_>>(),
+            [0, 3, 1, 2, 4]
+        );
+        let up = VecChange::Move { from: 3, to: 1 };
+        assert_eq!(
+            (0..5).map(|i| up.remap(i).unwrap()).collect::<Vec<_>>(),
+            [0, 2, 3, 1, 4]
+        );
+
+        assert_eq!(VecChange::Update { index: 0 }.remap(4), Some(4));
+        assert_eq!(VecChange::Reset.remap(4), None);
+    }
+
+    #[test]
+    fn test_anchor() {
+        // Rows added or removed above the top row keep it in view
+        assert_eq!(VecChange::Insert { index: 0, count: 2 }.anchor(10), 12);
+        assert_eq!(
+            VecChange::Insert {
+                index: 20,
+                count: 2
+            }
+            .anchor(10),
+            10
+        );
+        assert_eq!(VecChange::Remove { index: 0, count: 3 }.anchor(10), 7);
+        // A removed top row is replaced by the row that takes its place
+        assert_eq!(VecChange::Remove { index: 8, count: 5 }.anchor(10), 8);
+        assert_eq!(VecChange::Move { from: 2, to: 20 }.anchor(10), 9);
+        assert_eq!(VecChange::Move { from: 10, to: 0 }.anchor(10), 10);
+    }
+
+    #[test]
+    fn test_keys_compare_by_value() {
+        let names = ObservableVec::keyed(vec!["a".to_string(), "b".to_string()], |s| s.clone());
+        assert_eq!(names.position_of_key(&"b".to_string()), Some(1));
+        assert_eq!(names.position_of_key(&"c".to_string()), None);
+        // A key of another type matches nothing
+        assert_eq!(names.position_of_key(&"b"), None);
+
+        let key = names.key_at(0).unwrap();
+        assert_eq!(names.index_of_key(key.as_ref()), Some(0));
+        assert!(ObservableVec::new(vec![1]).position_of_key(&1).is_none());
+    }
+}
@@ -27,9 +27,9 @@
 //! }
 //! ```
 
-use gpui::{App, Context, Entity};
+use gpui::{App, Context, Entity, SharedString};
 
-use super::Binding;
+use super::{Binding, UndoManager};
 
 /// Observable state container that auto-notifies on changes.
 ///
@@ -39,12 +39,25 @@ use super::Binding;
 #[derive(Debug)]
 pub struct State<T> {
     value: T,
+    undoable: Option<Undoable<T>>,
+}
+
+/// How an undoable state records its changes.
+#[derive(Debug, Clone)]
+struct Undoable<T> {
+    /// The action name shown in the Edit menu
+    name: SharedString,
+    /// Copies the value before and after a change
+    snapshot: fn(&T) -> T,
 }
 
 impl<T> State<T> {
     /// Creates a new state with the given initial value.
     pub fn new(value: T) -> Self {
-        Self { value }
+        Self {
+            value,
+            undoable: None,
+        }
     }
 
     /// Returns a reference to the current value.
@@ -54,8 +67,9 @@ impl<T> State<T> {
 
     /// Returns a mutable reference to the current value.
     ///
-    /// Note: This does NOT trigger a notification. Use `set()` or `update()`
-    /// if you want automatic re-renders.
+    /// Note: This does NOT trigger a notification, and changes made through
+    /// it are not undoable. Use `set()` or `update()` if you want automatic
+    /// re-renders.
     pub fn get_mut(&mut self) -> &mut T {
         &mut self.value
     }
@@ -66,7 +80,9 @@ impl<T: 'static> State<T> {
     ///
     /// This automatically calls `cx.notify()` to trigger re-renders.
     pub fn set(&mut self, value: T, cx: &mut Context<Self>) {
+        let old = self.snapshot(cx);
         self.value = value;
+        self.record_change(old, cx);
         cx.notify();
     }
 
@@ -80,12 +96,69 @@ impl<T: 'static> State<T> {
     /// state.update(|value| *value += 1, cx);
     /// ```
     pub fn update(&mut self, f: impl FnOnce(&mut T), cx: &mut Context<Self>) {
+        let old = self.snapshot(cx);
         f(&mut self.value);
+        self.record_change(old, cx);
         cx.notify();
     }
+
+    /// Copies the value before a change that should be recorded.
+    fn snapshot(&self, cx: &App) -> Option<T> {
+        let undoable = self.undoable.as_ref()?;
+        if UndoManager::is_applying(cx) {
+            return None;
+        }
+        Some((undoable.snapshot)(&self.value))
+    }
+
+    /// Records a change from `old` to the current value with the `UndoManager`.
+    fn record_change(&self, old: Option<T>, cx: &mut Context<Self>) {
+        let (Some(undoable), Some(old)) = (&self.undoable, old) else {
+            return;
+        };
+        let snapshot = undoable.snapshot;
+        let new = snapshot(&self.value);
+        let (undo_state, redo_state) = (cx.weak_entity(), cx.weak_entity());
+        UndoManager::register_state_change(
+            undoable.name.clone(),
+            cx.entity_id(),
+            move |cx| {
+                undo_state
+                    .update(cx, |state, cx| state.set(snapshot(&old), cx))
+                    .ok();
+            },
+            move |cx| {
+                redo_state
+                    .update(cx, |state, cx| state.set(snapshot(&new), cx))
+                    .ok();
+            },
+            cx,
+        );
+    }
 }
 
 impl<T: Clone + 'static> State<T> {
+    /// Records changes with the [`UndoManager`] under the given action name.
+    ///
+    /// Every `set` and `update`, including writes through bindings, becomes
+    /// an undoable action such as "Undo Rename". Quick successive changes,
+    /// such as dragging a slider, are merged into one action.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let volume = cx.new(|_| State::new(0.5).undoable("Change Volume"));
+    ///
+    /// Slider::new("volume", 0.5).value(State::binding(&volume, cx))
+    /// ```
+    pub fn undoable(mut self, name: impl Into<SharedString>) -> Self {
+        self.undoable = Some(Undoable {
+            name: name.into(),
+            snapshot: T::clone,
+        });
+        self
+    }
+
     /// Creates a two-way binding to this state.
     ///
     /// The binding can be passed to components that support it,
@@ -114,6 +187,7 @@ impl<T: Clone> Clone for State<T> {
     fn clone(&self) -> Self {
         Self {
             value: self.value.clone(),
+            undoable: self.undoable.clone(),
         }
     }
 }
@@ -0,0 +1,560 @@
+//! Application-wide undo and redo.
+//!
+//! `UndoManager` keeps a history of named, reversible changes. States
+//! created with [`State::undoable`](super::State::undoable) record every
+//! `set` and `update`, including writes through bindings, so controls bound
+//! to them become undoable without extra code. Other changes can be
+//! registered by hand with [`UndoManager::register`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! use applib::prelude::*;
+//!
+//! UndoManager::init(cx);
+//!
+//! let name = cx.new(|_| State::new(String::from("Untitled")).undoable("Rename"));
+//!
+//! TextField::new("name").text(State::binding(&name, cx));
+//!
+//! // Later, e.g. from the Edit menu
+//! if UndoManager::can_undo(cx) {
+//!     UndoManager::undo(cx); // "Undo Rename"
+//! }
+//! ```
+
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use gpui::{actions, App, BorrowAppContext, EntityId, Global, KeyBinding, SharedString};
+
+actions!(
+    applib,
+    [
+        /// Reverts the most recent change.
+        Undo,
+        /// Reapplies the most recently undone change.
+        Redo,
+    ]
+);
+
+/// How many actions are kept by default.
+const MAX_DEPTH: usize = 100;
+/// Changes to the same state within this interval are merged into one action.
+const COALESCE_INTERVAL: Duration = Duration::from_millis(1000);
+
+type ChangeFn = Rc<dyn Fn(&mut App)>;
+
+/// A single reversible change.
+struct Change {
+    /// The state that changed, for coalescing repeated changes
+    source: Option<EntityId>,
+    undo: ChangeFn,
+    redo: ChangeFn,
+}
+
+/// A named entry in the history, undone and redone as a unit.
+struct Action {
+    name: SharedString,
+    changes: Vec<Change>,
+    /// When the last change was added, for coalescing
+    updated_at: Instant,
+}
+
+impl Action {
+    /// Returns whether a change to `source` named `name` continues this action.
+    fn coalesces(&self, name: &SharedString, source: Option<EntityId>, now: Instant) -> bool {
+        source.is_some()
+            && self.name == *name
+            && matches!(self.changes.as_slice(), [change] if change.source == source)
+            && now.duration_since(self.updated_at) < COALESCE_INTERVAL
+    }
+}
+
+/// A group that is still collecting changes.
+struct OpenGroup {
+    name: SharedString,
+    changes: Vec<Change>,
+}
+
+/// The application's undo history, stored in the `UndoManager` global.
+///
+/// Each action has a name used for the Edit menu, such as "Rename" in
+/// "Undo Rename". Consecutive changes to the same state with the same name
+/// are merged into one action while they arrive less than a second apart,
+/// so dragging a slider or typing a word undoes in one step. The history
+/// keeps the last 100 actions unless changed with [`UndoManager::set_max_depth`].
+///
+/// Changes made while undoing or redoing are not recorded.
+///
+/// # Example
+///
+/// ```ignore
+/// // Edit menu items that stay up to date
+/// Menu::new("edit", "Edit")
+///     .item(
+///         MenuItem::new("undo", UndoManager::undo_title(cx))
+///             .shortcut("Ctrl+Z")
+///             .disabled(!UndoManager::can_undo(cx))
+///             .on_select(|_window, cx| UndoManager::undo(cx)),
+///     )
+///     .item(
+///         MenuItem::new("redo", UndoManager::redo_title(cx))
+///             .shortcut("Ctrl+Shift+Z")
+///             .disabled(!UndoManager::can_redo(cx))
+///             .on_select(|_window, cx| UndoManager::redo(cx)),
+///     )
+/// ```
+pub struct UndoManager {
+    undo_stack: VecDeque<Action>,
+    redo_stack: Vec<Action>,
+    groups: Vec<OpenGroup>,
+    max_depth: usize,
+    /// Whether an undo or redo is running, so its changes aren't recorded
+    applying: bool,
+    /// Whether `init` has registered the actions and key bindings
+    initialized: bool,
+}
+
+impl Default for UndoManager {
+    fn default() -> Self {
+        Self {
+            undo_stack: VecDeque::new(),
+            redo_stack: Vec::new(),
+            groups: Vec::new(),
+            max_depth: MAX_DEPTH,
+            applying: false,
+            initialized: false,
+        }
+    }
+}
+
+impl Global for UndoManager {}
+
+impl UndoManager {
+    /// Binds Ctrl+Z, Ctrl+Shift+Z and Ctrl+Y and registers the [`Undo`] and [`Redo`] handlers.
+    ///
+    /// Calling it again does nothing, so each undo runs once.
+    pub fn init(cx: &mut App) {
+        if cx
+            .try_global::<Self>()
+            .is_some_and(|manager| manager.initialized)
+        {
+            return;
+        }
+        cx.bind_keys([
+            KeyBinding::new("ctrl-z", Undo, None),
+            KeyBinding::new("ctrl-shift-z", Redo, None),
+            KeyBinding::new("ctrl-y", Redo, None),
+        ]);
+        cx.on_action(|_: &Undo, cx| Self::undo(cx));
+        cx.on_action(|_: &Redo, cx| Self::redo(cx));
+        cx.update_default_global(|manager: &mut Self, _cx| manager.initialized = true);
+    }
+
+    /// Sets how many actions the history keeps (default: 100).
+    pub fn set_max_depth(max_depth: usize, cx: &mut App) {
+        cx.update_default_global(|manager: &mut Self, _cx| {
+            manager.max_depth = max_depth;
+            manager.trim();
+        });
+    }
+
+    /// Records a reversible change made by the caller.
+    ///
+    /// The change has already happened: `undo` reverts it and `redo` applies
+    /// it again. Recording a change clears the redo history.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let (a, b) = (items.clone(), items.clone());
+    /// items.update(cx, |items, cx| items.remove(index, cx));
+    /// UndoManager::register(
+    ///     "Delete",
+    ///     move |cx| a.update(cx, |items, cx| items.insert(index, item.clone(), cx)),
+    ///     move |cx| b.update(cx, |items, cx| items.remove(index, cx)),
+    ///     cx,
+    /// );
+    /// ```
+    pub fn register(
+        name: impl Into<SharedString>,
+        undo: impl Fn(&mut App) + 'static,
+        redo: impl Fn(&mut App) + 'static,
+        cx: &mut App,
+    ) {
+        let change = Change {
+            source: None,
+            undo: Rc::new(undo),
+            redo: Rc::new(redo),
+        };
+        Self::record(name.into(), change, cx);
+    }
+
+    /// Records a change to an undoable state.
+    pub(crate) fn register_state_change(
+        name: SharedString,
+        source: EntityId,
+        undo: impl Fn(&mut App) + 'static,
+        redo: impl Fn(&mut App) + 'static,
+        cx: &mut App,
+    ) {
+        let change = Change {
+            source: Some(source),
+            undo: Rc::new(undo),
+            redo: Rc::new(redo),
+        };
+        Self::record(name, change, cx);
+    }
+
+    /// Runs `f` and records every change it makes as a single action.
+    ///
+    /// Groups can be nested; the changes of inner groups become part of the
+    /// outermost one, which gives the action its name.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// UndoManager::group("Apply Style", cx, |cx| {
+    ///     bold.update(cx, |state, cx| state.set(true, cx));
+    ///     size.update(cx, |state, cx| state.set(18.0, cx));
+    /// });
+    /// ```
+    pub fn group<R>(
+        name: impl Into<SharedString>,
+        cx: &mut App,
+        f: impl FnOnce(&mut App) -> R,
+    ) -> R {
+        Self::begin_group(name, cx);
+        let result = f(cx);
+        Self::end_group(cx);
+        result
+    }
+
+    /// Starts collecting changes into a single action, e.g. across an async operation.
+    ///
+    /// Every call must be balanced by [`UndoManager::end_group`].
+    pub fn begin_group(name: impl Into<SharedString>, cx: &mut App) {
+        let name = name.into();
+        cx.update_default_global(|manager: &mut Self, _cx| {
+            manager.groups.push(OpenGroup {
+                name,
+                changes: Vec::new(),
+            });
+        });
+    }
+
+    /// Finishes the group started by the matching [`UndoManager::begin_group`].
+    pub fn end_group(cx: &mut App) {
+        cx.update_default_global(|manager: &mut Self, _cx| {
+            let Some(group) = manager.groups.pop() else {
+                return;
+            };
+            if let Some(parent) = manager.groups.last_mut() {
+                parent.changes.extend(group.changes);
+            } else if !group.changes.is_empty() {
+                manager.push(Action {
+                    name: group.name,
+                    changes: group.changes,
+                    updated_at: Instant::now(),
+                });
+            }
+        });
+    }
+
+    /// Runs `f` as a group, and reverts its changes if it fails.
+    ///
+    /// On success the changes are recorded as a single action. On failure
+    /// they are undone in reverse order and leave no trace in the history.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// UndoManager::transaction("Import", cx, |cx| {
+    ///     for row in rows {
+    ///         let contact = parse_contact(&row)?;
+    ///         contacts.update(cx, |list, cx| list.update(|l| l.push(contact), cx));
+    ///     }
+    ///     Ok::<_, anyhow::Error>(())
+    /// })?;
+    /// ```
+    pub fn transaction<R, E>(
+        name: impl Into<SharedString>,
+        cx: &mut App,
+        f: impl FnOnce(&mut App) -> Result<R, E>,
+    ) -> Result<R, E> {
+        Self::begin_group(name, cx);
+        let result = f(cx);
+        if result.is_ok() {
+            Self::end_group(cx);
+        } else {
+            let changes = cx
+                .update_default_global(|manager: &mut Self, _cx| manager.groups.pop())
+                .map(|group| group.changes)
+                .unwrap_or_default();
+            Self::apply(changes.iter().rev().map(|change| &change.undo), cx);
+        }
+        result
+    }
+
+    /// Reverts the most recent action and moves it to the redo history.
+    pub fn undo(cx: &mut App) {
+        let Some(action) =
+            cx.update_default_global(|manager: &mut Self, _cx| manager.undo_stack.pop_back())
+        else {
+            return;
+        };
+        Self::apply(action.changes.iter().rev().map(|change| &change.undo), cx);
+        cx.update_global(|manager: &mut Self, _cx| manager.redo_stack.push(action));
+    }
+
+    /// Reapplies the most recently undone action.
+    pub fn redo(cx: &mut App) {
+        let Some(action) =
+            cx.update_default_global(|manager: &mut Self, _cx| manager.redo_stack.pop())
+        else {
+            return;
+        };
+        Self::apply(action.changes.iter().map(|change| &change.redo), cx);
+        cx.update_global(|manager: &mut Self, _cx| manager.undo_stack.push_back(action));
+    }
+
+    /// Returns true if there is an action to undo.
+    pub fn can_undo(cx: &App) -> bool {
+        cx.try_global::<Self>()
+            .is_some_and(|manager| !manager.undo_stack.is_empty())
+    }
+
+    /// Returns true if there is an action to redo.
+    pub fn can_redo(cx: &App) -> bool {
+        cx.try_global::<Self>()
+            .is_some_and(|manager| !manager.redo_stack.is_empty())
+    }
+
+    /// Returns the title for an Undo menu item, such as "Undo Rename".
+    ///
+    /// Returns "Undo" when there is nothing to undo.
+    pub fn undo_title(cx: &App) -> SharedString {
+        let name = cx
+            .try_global::<Self>()
+            .and_then(|manager| manager.undo_stack.back())
+            .map(|action| &action.name);
+        menu_title("Undo", name)
+    }
+
+    /// Returns the title for a Redo menu item, such as "Redo Rename".
+    ///
+    /// Returns "Redo" when there is nothing to redo.
+    pub fn redo_title(cx: &App) -> SharedString {
+        let name = cx
+            .try_global::<Self>()
+            .and_then(|manager| manager.redo_stack.last())
+            .map(|action| &action.name);
+        menu_title("Redo", name)
+    }
+
+    /// Removes all actions from the history, e.g. after saving a document.
+    pub fn clear(cx: &mut App) {
+        cx.update_default_global(|manager: &mut Self, _cx| {
+            manager.undo_stack.clear();
+            manager.redo_stack.clear();
+        });
+    }
+
+    /// Returns true while an undo, redo or rollback is applying changes.
+    pub(crate) fn is_applying(cx: &App) -> bool {
+        cx.try_global::<Self>()
+            .is_some_and(|manager| manager.applying)
+    }
+
+    fn record(name: SharedString, change: Change, cx: &mut App) {
+        if Self::is_applying(cx) {
+            return;
+        }
+        cx.update_default_global(|manager: &mut Self, _cx| {
+            if let Some(group) = manager.groups.last_mut() {
+                group.changes.push(change);
+                return;
+            }
+
+            let now = Instant::now();
+            manager.redo_stack.clear();
+            match manager.undo_stack.back_mut() {
+                Some(action) if action.coalesces(&name, change.source, now) => {
+                    // Keep the original undo so the whole run reverts at once
+                    action.changes[0].redo = change.redo;
+                    action.updated_at = now;
+                }
+                _ => manager.push(Action {
+                    name,
+                    changes: vec![change],
+                    updated_at: now,
+                }),
+            }
+        });
+    }
+
+    /// Runs changes without recording the state updates they cause.
+    ///
+    /// The global is released while the changes run, since they update
+    /// entities and may read the history.
+    fn apply<'a>(changes: impl Iterator<Item = &'a ChangeFn>, cx: &mut App) {
+        cx.update_default_global(|manager: &mut Self, _cx| manager.applying = true);
+        for change in changes {
+            change(cx);
+        }
+        cx.update_global(|manager: &mut Self, _cx| manager.applying = false);
+    }
+
+    fn push(&mut self, action: Action) {
+        self.redo_stack.clear();
+        self.undo_stack.push_back(action);
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        while self.undo_stack.len() > self.max_depth {
+            self.undo_stack.pop_front();
+        }
+    }
+}
+
+/// Builds an Edit menu title from a verb and an optional action name.
+fn menu_title(verb: &str, name: Option<&SharedString>) -> SharedString {
+    match name {
+        Some(name) if !name.is_empty() => format!("{verb} {name}").into(),
+        _ => verb.to_string().into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::State;
+    use gpui::{AppContext as _, Entity, TestAppContext};
+    use std::cell::Cell;
+
+    fn action(name: &str, changes: Vec<Change>, updated_at: Instant) -> Action {
+        Action {
+            name: name.to_string().into(),
+            changes,
+            updated_at,
+        }
+    }
+
+    fn change(source: Option<EntityId>) -> Change {
+        Change {
+            source,
+            undo: Rc::new(|_| {}),
+            redo: Rc::new(|_| {}),
+        }
+    }
+
+    #[test]
+    fn test_menu_title() {
+        assert_eq!(menu_title("Undo", None), "Undo");
+        assert_eq!(menu_title("Undo", Some(&"".into())), "Undo");
+        assert_eq!(menu_title("Redo", Some(&"Rename".into())), "Redo Rename");
+    }
+
+    #[test]
+    fn test_action_coalesces() {
+        let source = Some(EntityId::from(1u64));
+        let other = Some(EntityId::from(2u64));
+        let now = Instant::now();
+        let slider = action("Volume", vec![change(source)], now);
+
+        assert!(slider.coalesces(&"Volume".into(), source, now));
+        assert!(!slider.coalesces(&"Volume".into(), other, now));
+        assert!(!slider.coalesces(&"Balance".into(), source, now));
+        assert!(!slider.coalesces(&"Volume".into(), source, now + COALESCE_INTERVAL));
+
+        // Registered changes and groups never coalesce
+        let registered = action("Delete", vec![change(None)], now);
+        assert!(!registered.coalesces(&"Delete".into(), None, now));
+        let group = action("Volume", vec![change(source), change(source)], now);
+        assert!(!group.coalesces(&"Volume".into(), source, now));
+    }
+
+    #[test]
+    fn test_history_is_trimmed() {
+        let mut manager = UndoManager {
+            max_depth: 2,
+            ..Default::default()
+        };
+        let now = Instant::now();
+        for name in ["a", "b", "c"] {
+            manager.push(action(name, vec![change(None)], now));
+        }
+        let names: Vec<_> = manager.undo_stack.iter().map(|a| a.name.clone()).collect();
+        assert_eq!(names, ["b", "c"]);
+    }
+
+    fn value(state: &Entity<State<i32>>, cx: &TestAppContext) -> i32 {
+        state.read_with(cx, |state, _| *state.get())
+    }
+
+    #[gpui::test]
+    fn test_undoable_state_undo_and_redo(cx: &mut TestAppContext) {
+        let size = cx.new(|_| State::new(12).undoable("Font Size"));
+        size.update(cx, |size, cx| size.set(14, cx));
+        cx.update(|cx| {
+            assert!(UndoManager::can_undo(cx));
+            assert_eq!(UndoManager::undo_title(cx), "Undo Font Size");
+            UndoManager::undo(cx);
+        });
+        assert_eq!(value(&size, cx), 12);
+        cx.update(|cx| {
+            assert!(!UndoManager::can_undo(cx));
+            UndoManager::redo(cx);
+        });
+        assert_eq!(value(&size, cx), 14);
+        assert!(cx.update(|cx| !UndoManager::can_redo(cx)));
+    }
+
+    #[gpui::test]
+    fn test_repeated_updates_undo_in_one_step(cx: &mut TestAppContext) {
+        let volume = cx.new(|_| State::new(0).undoable("Volume"));
+        for level in 1..=5 {
+            volume.update(cx, |volume, cx| volume.set(level * 10, cx));
+        }
+        cx.update(UndoManager::undo);
+        assert_eq!(value(&volume, cx), 0);
+        assert!(cx.update(|cx| !UndoManager::can_undo(cx)));
+        cx.update(UndoManager::redo);
+        assert_eq!(value(&volume, cx), 50);
+    }
+
+    #[gpui::test]
+    fn test_failed_transaction_rolls_back(cx: &mut TestAppContext) {
+        let first = cx.new(|_| State::new(1).undoable("First"));
+        let second = cx.new(|_| State::new(2).undoable("Second"));
+        let result = cx.update(|cx| {
+            UndoManager::transaction("Import", cx, |cx| {
+                first.update(cx, |first, cx| first.set(10, cx));
+                second.update(cx, |second, cx| second.set(20, cx));
+                Err::<(), _>("invalid row")
+            })
+        });
+        assert_eq!(result, Err("invalid row"));
+        assert_eq!(value(&first, cx), 1);
+        assert_eq!(value(&second, cx), 2);
+        assert!(cx.update(|cx| !UndoManager::can_undo(cx)));
+    }
+
+    #[gpui::test]
+    fn test_init_twice_undoes_once(cx: &mut TestAppContext) {
+        let undone = Rc::new(Cell::new(0));
+        cx.update(|cx| {
+            UndoManager::init(cx);
+            UndoManager::init(cx);
+            for name in ["a", "b"] {
+                let undone = undone.clone();
+                UndoManager::register(name, move |_| undone.set(undone.get() + 1), |_| {}, cx);
+            }
+            cx.dispatch_action(&Undo);
+        });
+        assert_eq!(undone.get(), 1);
+    }
+}
@@ -0,0 +1,62 @@
+//! Conversion between paths and `file://` URIs.
+//!
+//! Desktop services such as the file chooser portal, GTK bookmarks and
+//! drag-and-drop exchange file locations as URIs rather than paths.
+
+use std::ffi::OsStr;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+
+/// Converts a `file://` URI to a path, decoding percent escapes.
+pub fn path_from_uri(uri: &str) -> Option<PathBuf> {
+    let rest = uri.strip_prefix("file://")?;
+    // Skip an optional host such as "localhost"
+    let path = &rest[rest.find('/')?..];
+
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut input = path.bytes();
+    while let Some(byte) = input.next() {
+        if byte == b'%' {
+            let hex = [input.next()?, input.next()?];
+            let hex = std::str::from_utf8(&hex).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    Some(PathBuf::from(OsStr::from_bytes(&bytes)))
+}
+
+/// Converts a path to a `file://` URI, percent-encoding reserved bytes.
+pub fn uri_from_path(path: &Path) -> String {
+    let mut uri = String::from("file://");
+    for &byte in path.as_os_str().as_bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-._~".contains(&byte) {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    uri
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uri_round_trip() {
+        let path = Path::new("/home/ada/My Notes/größe #1.txt");
+        let uri = uri_from_path(path);
+        assert_eq!(
+            uri,
+            "file:///home/ada/My%20Notes/gr%C3%B6%C3%9Fe%20%231.txt"
+        );
+        assert_eq!(path_from_uri(&uri).as_deref(), Some(path));
+        assert_eq!(
+            path_from_uri("file://localhost/tmp/a.txt"),
+            Some(PathBuf::from("/tmp/a.txt"))
+        );
+        assert_eq!(path_from_uri("https://example.com/a.txt"), None);
+    }
+}
@@ -3,5 +3,7 @@
 //! This module provides common utilities that are useful across components.
 
 mod debounce;
+mod file_uri;
 
 pub use debounce::Debouncer;
+pub use file_uri::{path_from_uri, uri_from_path};